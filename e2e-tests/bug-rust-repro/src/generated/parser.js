@@ -13,6 +13,7 @@ import {
   BigIntRuntype,
   StringWithFormatRuntype,
   NumberWithFormatRuntype,
+  NumberRangeRuntype,
   AnyOfConstsRuntype,
   TupleRuntype,
   AllOfRuntype,
@@ -21,6 +22,7 @@ import {
   AnyOfDiscriminatedRuntype,
   ObjectRuntype,
   OptionalFieldRuntype,
+  ConstrainedRuntype,
   BaseRefRuntype,
   registerStringFormatter,
   registerNumberFormatter,
@@ -29,6 +31,9 @@ import {
   TypedArrayRuntype,
   MapRuntype,
   SetRuntype,
+  InstanceOfRuntype,
+  ScopedRefRuntype,
+  runtypeOfParser,
 } from "@beff/client/codegen-v2";
 
 class RefRuntype extends BaseRefRuntype  {
@@ -58,10 +63,21 @@ const buildParsers = (args) => {
     const v = numberFormats[k];
     registerNumberFormatter(k, v);
   });
+  const classes = args?.classes ?? {};
+  for (const k of RequiredClasses) {
+    if (classes[k] == null) {
+      throw new Error(`Missing class ${k}`);
+    }
+  }
   let acc = {};
   for (const k of Object.keys(buildParsersInput)) {
-    const it = buildParserFromRuntype(buildParsersInput[k], k, false);
-    acc[k] = it;
+    const input = buildParsersInput[k];
+    if (typeof input === "function") {
+      acc[k] = (...parsers) =>
+        buildParserFromRuntype(input(...parsers.map(runtypeOfParser)), k, false, classes);
+      continue;
+    }
+    acc[k] = buildParserFromRuntype(input, k, false, classes);
   }
   return acc;
 };
@@ -74,6 +90,7 @@ const direct_hoist_2 = new TypeofRuntype(undefined, "string");
 const direct_hoist_3 = new ObjectRuntype(undefined, {
     "value": direct_hoist_2
 }, []);
+const RequiredClasses = [];
 const namedRuntypes = {
     "A": direct_hoist_2,
     "T": direct_hoist_3
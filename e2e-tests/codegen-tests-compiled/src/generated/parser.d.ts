@@ -0,0 +1,8 @@
+import { BuildCompiledParserFunction, BuildRoutesFunction } from "@beff/client";
+
+declare const _exports: {
+  buildParsers: BuildCompiledParserFunction;
+  buildRoutes: BuildRoutesFunction;
+};
+
+export default _exports;
@@ -0,0 +1,2015 @@
+//@ts-nocheck
+
+"use strict";
+
+
+
+import { deepmerge, unionDecodeError, printErrors } from "@beff/client/codegen-v2";
+
+const stringFormatters = {};
+const numberFormatters = {};
+
+const hasOwn = (obj, key) => Object.prototype.hasOwnProperty.call(obj, key);
+
+function registeredClass(ctx, name) {
+  const ctor = ctx.classes[name];
+  if (ctor == null) {
+    throw new Error(`Class ${name} was not provided to buildParsers`);
+  }
+  return ctor;
+}
+
+function validateStringFormat(name, input) {
+  const validator = stringFormatters[name]?.validator;
+  return validator != null && validator(input);
+}
+
+function validateNumberFormat(name, input) {
+  const validator = numberFormatters[name]?.validator;
+  return validator != null && validator(input);
+}
+
+function formatErrorMessage(formatters, typeName, formats, input) {
+  if (typeof input === typeName) {
+    for (const f of [...formats].reverse()) {
+      const errorMessage = formatters[f]?.errorMessage;
+      if (errorMessage != null) {
+        return errorMessage(input);
+      }
+    }
+  }
+  return `expected ${typeName} with format "${formats.join(" and ")}"`;
+}
+
+const stringFormatError = (formats, input) => formatErrorMessage(stringFormatters, "string", formats, input);
+const numberFormatError = (formats, input) => formatErrorMessage(numberFormatters, "number", formats, input);
+
+function buildError(ctx, message, received) {
+  return [{ message, path: [...ctx.path], received }];
+}
+
+class CompiledParser {
+  constructor(codec, name, classes) {
+    this._codec = codec;
+    this.name = name;
+    this._classes = classes;
+  }
+  validate(input, options) {
+    const disallowExtraProperties = options?.disallowExtraProperties ?? false;
+    return this._codec.validate({ disallowExtraProperties, classes: this._classes }, input);
+  }
+  safeParse(input, options) {
+    const disallowExtraProperties = options?.disallowExtraProperties ?? false;
+    const objectKeyOrder = options?.objectKeyOrder ?? "input";
+    const classes = this._classes;
+    if (this._codec.validate({ disallowExtraProperties, classes }, input)) {
+      return {
+        success: true,
+        data: this._codec.parse({ disallowExtraProperties, objectKeyOrder, classes }, input),
+      };
+    }
+    return {
+      success: false,
+      errors: this._codec.report({ path: [], disallowExtraProperties, classes }, input).slice(0, 10),
+    };
+  }
+  parse(input, options) {
+    const safe = this.safeParse(input, options);
+    if (safe.success) {
+      return safe.data;
+    }
+    throw new Error(`Failed to parse ${this.name} - ${printErrors(safe.errors)}`);
+  }
+}
+
+
+function codecOfParser(parser) {
+  if (parser instanceof CompiledParser) {
+    return parser._codec;
+  }
+  return {
+    validate: (ctx, input) => parser.validate(input, ctx),
+    parse: (ctx, input) => parser.parse(input, ctx),
+    report: (ctx, input) => {
+      const res = parser.safeParse(input, ctx);
+      return res.success ? [] : res.errors.map((err) => ({ ...err, path: [...ctx.path, ...err.path] }));
+    },
+  };
+}
+
+function registerFormats(formatters, required, provided) {
+  for (const k of required) {
+    if (provided[k] == null) {
+      throw new Error(`Missing custom format ${k}`);
+    }
+  }
+  for (const k of Object.keys(provided)) {
+    const v = provided[k];
+    formatters[k] = typeof v === "function" ? { validator: v } : v;
+  }
+}
+
+const buildParsers = (args) => {
+  registerFormats(stringFormatters, RequiredStringFormats, args?.stringFormats ?? {});
+  registerFormats(numberFormatters, RequiredNumberFormats, args?.numberFormats ?? {});
+  const classes = args?.classes ?? {};
+  for (const k of RequiredClasses) {
+    if (classes[k] == null) {
+      throw new Error(`Missing class ${k}`);
+    }
+  }
+  let acc = {};
+  for (const k of Object.keys(buildParsersInput)) {
+    const input = buildParsersInput[k];
+    if (typeof input === "function") {
+      acc[k] = (...parsers) => new CompiledParser(input(...parsers.map(codecOfParser)), k, classes);
+      continue;
+    }
+    acc[k] = new CompiledParser(input, k, classes);
+  }
+  return acc;
+};
+
+const RequiredStringFormats = [];
+const RequiredNumberFormats = [];
+const pattern_0 = new RegExp("^[a-z_]+$", "u");
+const RequiredClasses = [
+    "Point"
+];
+function validate_0(ctx, input) {
+    return typeof input === "string";
+}
+function parse_0(ctx, input) {
+    return input;
+}
+function report_0(ctx, input) {
+    return buildError(ctx, "expected string", input);
+}
+function validate_1(ctx, input) {
+    return typeof input === "number";
+}
+function parse_1(ctx, input) {
+    return input;
+}
+function report_1(ctx, input) {
+    return buildError(ctx, "expected number", input);
+}
+function validate_2(ctx, input) {
+    return typeof input === "boolean";
+}
+function parse_2(ctx, input) {
+    return input;
+}
+function report_2(ctx, input) {
+    return buildError(ctx, "expected boolean", input);
+}
+function validate_3(ctx, input) {
+    return input == null;
+}
+function parse_3(ctx, input) {
+    return input;
+}
+function report_3(ctx, input) {
+    return buildError(ctx, "expected nullish value", input);
+}
+function validate_4(ctx, input) {
+    return true;
+}
+function parse_4(ctx, input) {
+    return input;
+}
+function report_4(ctx, input) {
+    return buildError(ctx, "expected any", input);
+}
+function validate_5(ctx, input) {
+    return input === "constant value";
+}
+function parse_5(ctx, input) {
+    return input;
+}
+function report_5(ctx, input) {
+    return buildError(ctx, 'expected "constant value"', input);
+}
+function validate_6(ctx, input) {
+    if (typeof input !== "object" || input === null || Array.isArray(input)) {
+        return false;
+    }
+    if (!validate_7(ctx, input.a)) {
+        return false;
+    }
+    if (!validate_7(ctx, input.b)) {
+        return false;
+    }
+    if (ctx.disallowExtraProperties) {
+        for (const k of Object.keys(input)){
+            switch(k){
+                case "a":
+                case "b":
+                    continue;
+            }
+            return false;
+        }
+    }
+    return true;
+}
+function parse_6(ctx, input) {
+    const acc = {};
+    if (ctx.objectKeyOrder === "input") {
+        for (const k of Object.keys(input)){
+            switch(k){
+                case "a":
+                    acc.a = parse_7(ctx, input.a);
+                    continue;
+                case "b":
+                    acc.b = parse_7(ctx, input.b);
+                    continue;
+            }
+        }
+    } else {
+        if (hasOwn(input, "a")) {
+            acc.a = parse_7(ctx, input.a);
+        }
+        if (hasOwn(input, "b")) {
+            acc.b = parse_7(ctx, input.b);
+        }
+    }
+    return acc;
+}
+function report_6(ctx, input) {
+    if (typeof input !== "object" || Array.isArray(input) || input === null) {
+        return buildError(ctx, "expected object", input);
+    }
+    const acc = [];
+    if (!validate_7(ctx, input.a)) {
+        ctx.path.push("a");
+        acc.push(...report_7(ctx, input.a));
+        ctx.path.pop();
+    }
+    if (!validate_7(ctx, input.b)) {
+        ctx.path.push("b");
+        acc.push(...report_7(ctx, input.b));
+        ctx.path.pop();
+    }
+    if (ctx.disallowExtraProperties) {
+        const extra = [];
+        for (const k of Object.keys(input)){
+            switch(k){
+                case "a":
+                case "b":
+                    continue;
+            }
+            ctx.path.push(k);
+            extra.push(...buildError(ctx, "extra property", input[k]));
+            ctx.path.pop();
+        }
+        if (extra.length > 0) {
+            return extra;
+        }
+    }
+    return acc;
+}
+function validate_7(ctx, input) {
+    if (!Array.isArray(input)) {
+        return false;
+    }
+    for(let i = 0; i < input.length; i++){
+        if (!(typeof input[i] === "string")) {
+            return false;
+        }
+    }
+    return true;
+}
+function parse_7(ctx, input) {
+    return input.slice();
+}
+function report_7(ctx, input) {
+    if (!Array.isArray(input)) {
+        return buildError(ctx, "expected array", input);
+    }
+    const acc = [];
+    for(let i = 0; i < input.length; i++){
+        if (!(typeof input[i] === "string")) {
+            ctx.path.push(`[${i}]`);
+            acc.push(...buildError(ctx, "expected string", input[i]));
+            ctx.path.pop();
+        }
+    }
+    return acc;
+}
+function validate_8(ctx, input) {
+    if (typeof input !== "object" || input === null || Array.isArray(input)) {
+        return false;
+    }
+    if (!(typeof input.label === "string")) {
+        return false;
+    }
+    if (!validate_9(ctx, input.outer)) {
+        return false;
+    }
+    if (ctx.disallowExtraProperties) {
+        for (const k of Object.keys(input)){
+            switch(k){
+                case "label":
+                case "outer":
+                    continue;
+            }
+            return false;
+        }
+    }
+    return true;
+}
+function parse_8(ctx, input) {
+    const acc = {};
+    if (ctx.objectKeyOrder === "input") {
+        for (const k of Object.keys(input)){
+            switch(k){
+                case "label":
+                    acc.label = input.label;
+                    continue;
+                case "outer":
+                    acc.outer = parse_9(ctx, input.outer);
+                    continue;
+            }
+        }
+    } else {
+        if (hasOwn(input, "label")) {
+            acc.label = input.label;
+        }
+        if (hasOwn(input, "outer")) {
+            acc.outer = parse_9(ctx, input.outer);
+        }
+    }
+    return acc;
+}
+function report_8(ctx, input) {
+    if (typeof input !== "object" || Array.isArray(input) || input === null) {
+        return buildError(ctx, "expected object", input);
+    }
+    const acc = [];
+    if (!(typeof input.label === "string")) {
+        ctx.path.push("label");
+        acc.push(...buildError(ctx, "expected string", input.label));
+        ctx.path.pop();
+    }
+    if (!validate_9(ctx, input.outer)) {
+        ctx.path.push("outer");
+        acc.push(...report_9(ctx, input.outer));
+        ctx.path.pop();
+    }
+    if (ctx.disallowExtraProperties) {
+        const extra = [];
+        for (const k of Object.keys(input)){
+            switch(k){
+                case "label":
+                case "outer":
+                    continue;
+            }
+            ctx.path.push(k);
+            extra.push(...buildError(ctx, "extra property", input[k]));
+            ctx.path.pop();
+        }
+        if (extra.length > 0) {
+            return extra;
+        }
+    }
+    return acc;
+}
+function validate_9(ctx, input) {
+    if (typeof input !== "object" || input === null || Array.isArray(input)) {
+        return false;
+    }
+    if (!(typeof input.a === "number")) {
+        return false;
+    }
+    if (!(typeof input.b === "number")) {
+        return false;
+    }
+    if (ctx.disallowExtraProperties) {
+        for (const k of Object.keys(input)){
+            switch(k){
+                case "a":
+                case "b":
+                    continue;
+            }
+            return false;
+        }
+    }
+    return true;
+}
+function parse_9(ctx, input) {
+    const acc = {};
+    if (ctx.objectKeyOrder === "input") {
+        for (const k of Object.keys(input)){
+            switch(k){
+                case "a":
+                    acc.a = input.a;
+                    continue;
+                case "b":
+                    acc.b = input.b;
+                    continue;
+            }
+        }
+    } else {
+        if (hasOwn(input, "a")) {
+            acc.a = input.a;
+        }
+        if (hasOwn(input, "b")) {
+            acc.b = input.b;
+        }
+    }
+    return acc;
+}
+function report_9(ctx, input) {
+    if (typeof input !== "object" || Array.isArray(input) || input === null) {
+        return buildError(ctx, "expected object", input);
+    }
+    const acc = [];
+    if (!(typeof input.a === "number")) {
+        ctx.path.push("a");
+        acc.push(...buildError(ctx, "expected number", input.a));
+        ctx.path.pop();
+    }
+    if (!(typeof input.b === "number")) {
+        ctx.path.push("b");
+        acc.push(...buildError(ctx, "expected number", input.b));
+        ctx.path.pop();
+    }
+    if (ctx.disallowExtraProperties) {
+        const extra = [];
+        for (const k of Object.keys(input)){
+            switch(k){
+                case "a":
+                case "b":
+                    continue;
+            }
+            ctx.path.push(k);
+            extra.push(...buildError(ctx, "extra property", input[k]));
+            ctx.path.pop();
+        }
+        if (extra.length > 0) {
+            return extra;
+        }
+    }
+    return acc;
+}
+function validate_10(ctx, input) {
+    if (typeof input !== "object" || input === null || Array.isArray(input)) {
+        return false;
+    }
+    if (!(typeof input.a === "string")) {
+        return false;
+    }
+    if (!validate_11(ctx, input.b)) {
+        return false;
+    }
+    if (!validate_12(ctx, input.c)) {
+        return false;
+    }
+    if (!validate_13(ctx, input.d)) {
+        return false;
+    }
+    if (!(input.e == null || typeof input.e === "string")) {
+        return false;
+    }
+    if (ctx.disallowExtraProperties) {
+        for (const k of Object.keys(input)){
+            switch(k){
+                case "a":
+                case "b":
+                case "c":
+                case "d":
+                case "e":
+                    continue;
+            }
+            return false;
+        }
+    }
+    return true;
+}
+function parse_10(ctx, input) {
+    const acc = {};
+    if (ctx.objectKeyOrder === "input") {
+        for (const k of Object.keys(input)){
+            switch(k){
+                case "a":
+                    acc.a = input.a;
+                    continue;
+                case "b":
+                    acc.b = input.b;
+                    continue;
+                case "c":
+                    acc.c = input.c;
+                    continue;
+                case "d":
+                    acc.d = input.d;
+                    continue;
+                case "e":
+                    acc.e = input.e;
+                    continue;
+            }
+        }
+    } else {
+        if (hasOwn(input, "a")) {
+            acc.a = input.a;
+        }
+        if (hasOwn(input, "b")) {
+            acc.b = input.b;
+        }
+        if (hasOwn(input, "c")) {
+            acc.c = input.c;
+        }
+        if (hasOwn(input, "d")) {
+            acc.d = input.d;
+        }
+        if (hasOwn(input, "e")) {
+            acc.e = input.e;
+        }
+    }
+    return acc;
+}
+function report_10(ctx, input) {
+    if (typeof input !== "object" || Array.isArray(input) || input === null) {
+        return buildError(ctx, "expected object", input);
+    }
+    const acc = [];
+    if (!(typeof input.a === "string")) {
+        ctx.path.push("a");
+        acc.push(...buildError(ctx, "expected string", input.a));
+        ctx.path.pop();
+    }
+    if (!validate_11(ctx, input.b)) {
+        ctx.path.push("b");
+        acc.push(...report_11(ctx, input.b));
+        ctx.path.pop();
+    }
+    if (!validate_12(ctx, input.c)) {
+        ctx.path.push("c");
+        acc.push(...report_12(ctx, input.c));
+        ctx.path.pop();
+    }
+    if (!validate_13(ctx, input.d)) {
+        ctx.path.push("d");
+        acc.push(...report_13(ctx, input.d));
+        ctx.path.pop();
+    }
+    if (!(input.e == null || typeof input.e === "string")) {
+        ctx.path.push("e");
+        acc.push(...buildError(ctx, "expected string", input.e));
+        ctx.path.pop();
+    }
+    if (ctx.disallowExtraProperties) {
+        const extra = [];
+        for (const k of Object.keys(input)){
+            switch(k){
+                case "a":
+                case "b":
+                case "c":
+                case "d":
+                case "e":
+                    continue;
+            }
+            ctx.path.push(k);
+            extra.push(...buildError(ctx, "extra property", input[k]));
+            ctx.path.pop();
+        }
+        if (extra.length > 0) {
+            return extra;
+        }
+    }
+    return acc;
+}
+function validate_11(ctx, input) {
+    return input == null || typeof input === "string";
+}
+function parse_11(ctx, input) {
+    return input;
+}
+function report_11(ctx, input) {
+    const path = ctx.path;
+    ctx.path = [];
+    const branchErrors = [
+        buildError(ctx, "expected nullish value", input),
+        buildError(ctx, "expected string", input)
+    ];
+    ctx.path = path;
+    return unionDecodeError(ctx, branchErrors, input);
+}
+function validate_12(ctx, input) {
+    return input == null || typeof input === "string";
+}
+function parse_12(ctx, input) {
+    return input;
+}
+function report_12(ctx, input) {
+    const path = ctx.path;
+    ctx.path = [];
+    const branchErrors = [
+        buildError(ctx, "expected nullish value", input),
+        buildError(ctx, "expected string", input)
+    ];
+    ctx.path = path;
+    return unionDecodeError(ctx, branchErrors, input);
+}
+function validate_13(ctx, input) {
+    return input == null || typeof input === "string";
+}
+function parse_13(ctx, input) {
+    return input;
+}
+function report_13(ctx, input) {
+    const path = ctx.path;
+    ctx.path = [];
+    const branchErrors = [
+        buildError(ctx, "expected nullish value", input),
+        buildError(ctx, "expected string", input)
+    ];
+    ctx.path = path;
+    return unionDecodeError(ctx, branchErrors, input);
+}
+function validate_14(ctx, input) {
+    if (typeof input !== "object" || input === null || Array.isArray(input)) {
+        return false;
+    }
+    if (!(typeof input.a === "string")) {
+        return false;
+    }
+    if (!validate_11(ctx, input.b)) {
+        return false;
+    }
+    if (!validate_12(ctx, input.c)) {
+        return false;
+    }
+    if (!validate_13(ctx, input.d)) {
+        return false;
+    }
+    if (!(typeof input.e === "string")) {
+        return false;
+    }
+    if (ctx.disallowExtraProperties) {
+        for (const k of Object.keys(input)){
+            switch(k){
+                case "a":
+                case "b":
+                case "c":
+                case "d":
+                case "e":
+                    continue;
+            }
+            return false;
+        }
+    }
+    return true;
+}
+function parse_14(ctx, input) {
+    const acc = {};
+    if (ctx.objectKeyOrder === "input") {
+        for (const k of Object.keys(input)){
+            switch(k){
+                case "a":
+                    acc.a = input.a;
+                    continue;
+                case "b":
+                    acc.b = input.b;
+                    continue;
+                case "c":
+                    acc.c = input.c;
+                    continue;
+                case "d":
+                    acc.d = input.d;
+                    continue;
+                case "e":
+                    acc.e = input.e;
+                    continue;
+            }
+        }
+    } else {
+        if (hasOwn(input, "a")) {
+            acc.a = input.a;
+        }
+        if (hasOwn(input, "b")) {
+            acc.b = input.b;
+        }
+        if (hasOwn(input, "c")) {
+            acc.c = input.c;
+        }
+        if (hasOwn(input, "d")) {
+            acc.d = input.d;
+        }
+        if (hasOwn(input, "e")) {
+            acc.e = input.e;
+        }
+    }
+    return acc;
+}
+function report_14(ctx, input) {
+    if (typeof input !== "object" || Array.isArray(input) || input === null) {
+        return buildError(ctx, "expected object", input);
+    }
+    const acc = [];
+    if (!(typeof input.a === "string")) {
+        ctx.path.push("a");
+        acc.push(...buildError(ctx, "expected string", input.a));
+        ctx.path.pop();
+    }
+    if (!validate_11(ctx, input.b)) {
+        ctx.path.push("b");
+        acc.push(...report_11(ctx, input.b));
+        ctx.path.pop();
+    }
+    if (!validate_12(ctx, input.c)) {
+        ctx.path.push("c");
+        acc.push(...report_12(ctx, input.c));
+        ctx.path.pop();
+    }
+    if (!validate_13(ctx, input.d)) {
+        ctx.path.push("d");
+        acc.push(...report_13(ctx, input.d));
+        ctx.path.pop();
+    }
+    if (!(typeof input.e === "string")) {
+        ctx.path.push("e");
+        acc.push(...buildError(ctx, "expected string", input.e));
+        ctx.path.pop();
+    }
+    if (ctx.disallowExtraProperties) {
+        const extra = [];
+        for (const k of Object.keys(input)){
+            switch(k){
+                case "a":
+                case "b":
+                case "c":
+                case "d":
+                case "e":
+                    continue;
+            }
+            ctx.path.push(k);
+            extra.push(...buildError(ctx, "extra property", input[k]));
+            ctx.path.pop();
+        }
+        if (extra.length > 0) {
+            return extra;
+        }
+    }
+    return acc;
+}
+function validate_15(ctx, input) {
+    if (typeof input !== "object" || input === null || Array.isArray(input)) {
+        return false;
+    }
+    if (!(typeof input.a === "number")) {
+        return false;
+    }
+    if (!(typeof input.b === "number")) {
+        return false;
+    }
+    for (const k of Object.keys(input)){
+        switch(k){
+            case "a":
+            case "b":
+                continue;
+        }
+        let matched = false;
+        if (typeof k === "string" && /^(x_)(.*)$/.test(k)) {
+            matched = true;
+            if (!(typeof input[k] === "number")) {
+                return false;
+            }
+        }
+        if (!matched) {
+            return false;
+        }
+    }
+    return true;
+}
+function parse_15(ctx, input) {
+    const acc = {};
+    if (ctx.objectKeyOrder === "input") {
+        for (const k of Object.keys(input)){
+            switch(k){
+                case "a":
+                    acc.a = input.a;
+                    continue;
+                case "b":
+                    acc.b = input.b;
+                    continue;
+            }
+            if (typeof k === "string" && /^(x_)(.*)$/.test(k)) {
+                acc[k] = input[k];
+                continue;
+            }
+        }
+    } else {
+        if (hasOwn(input, "a")) {
+            acc.a = input.a;
+        }
+        if (hasOwn(input, "b")) {
+            acc.b = input.b;
+        }
+        const extraKeys = [];
+        for (const k of Object.keys(input)){
+            switch(k){
+                case "a":
+                case "b":
+                    continue;
+            }
+            extraKeys.push(k);
+        }
+        extraKeys.sort();
+        for (const k of extraKeys){
+            if (typeof k === "string" && /^(x_)(.*)$/.test(k)) {
+                acc[k] = input[k];
+                continue;
+            }
+        }
+    }
+    return acc;
+}
+function report_15(ctx, input) {
+    if (typeof input !== "object" || Array.isArray(input) || input === null) {
+        return buildError(ctx, "expected object", input);
+    }
+    const acc = [];
+    if (!(typeof input.a === "number")) {
+        ctx.path.push("a");
+        acc.push(...buildError(ctx, "expected number", input.a));
+        ctx.path.pop();
+    }
+    if (!(typeof input.b === "number")) {
+        ctx.path.push("b");
+        acc.push(...buildError(ctx, "expected number", input.b));
+        ctx.path.pop();
+    }
+    for (const k of Object.keys(input)){
+        switch(k){
+            case "a":
+            case "b":
+                continue;
+        }
+        const matches0 = typeof k === "string" && /^(x_)(.*)$/.test(k);
+        ctx.path.push(k);
+        if (!matches0) {
+            acc.push(...buildError(ctx, "expected string matching `x_${string}`", k));
+        }
+        if (matches0 && !(typeof input[k] === "number")) {
+            acc.push(...buildError(ctx, "expected number", input[k]));
+        }
+        ctx.path.pop();
+    }
+    return acc;
+}
+function validate_16(ctx, input) {
+    if (typeof input !== "object" || input === null || Array.isArray(input)) {
+        return false;
+    }
+    if (!(input.a == null || typeof input.a === "number")) {
+        return false;
+    }
+    if (!(input.b == null || typeof input.b === "number")) {
+        return false;
+    }
+    if (ctx.disallowExtraProperties) {
+        for (const k of Object.keys(input)){
+            switch(k){
+                case "a":
+                case "b":
+                    continue;
+            }
+            return false;
+        }
+    }
+    return true;
+}
+function parse_16(ctx, input) {
+    const acc = {};
+    if (ctx.objectKeyOrder === "input") {
+        for (const k of Object.keys(input)){
+            switch(k){
+                case "a":
+                    acc.a = input.a;
+                    continue;
+                case "b":
+                    acc.b = input.b;
+                    continue;
+            }
+        }
+    } else {
+        if (hasOwn(input, "a")) {
+            acc.a = input.a;
+        }
+        if (hasOwn(input, "b")) {
+            acc.b = input.b;
+        }
+    }
+    return acc;
+}
+function report_16(ctx, input) {
+    if (typeof input !== "object" || Array.isArray(input) || input === null) {
+        return buildError(ctx, "expected object", input);
+    }
+    const acc = [];
+    if (!(input.a == null || typeof input.a === "number")) {
+        ctx.path.push("a");
+        acc.push(...buildError(ctx, "expected number", input.a));
+        ctx.path.pop();
+    }
+    if (!(input.b == null || typeof input.b === "number")) {
+        ctx.path.push("b");
+        acc.push(...buildError(ctx, "expected number", input.b));
+        ctx.path.pop();
+    }
+    if (ctx.disallowExtraProperties) {
+        const extra = [];
+        for (const k of Object.keys(input)){
+            switch(k){
+                case "a":
+                case "b":
+                    continue;
+            }
+            ctx.path.push(k);
+            extra.push(...buildError(ctx, "extra property", input[k]));
+            ctx.path.pop();
+        }
+        if (extra.length > 0) {
+            return extra;
+        }
+    }
+    return acc;
+}
+function validate_17(ctx, input) {
+    if (typeof input !== "object" || input === null || Array.isArray(input)) {
+        return false;
+    }
+    for (const k of Object.keys(input)){
+        let matched = false;
+        if (typeof k === "string" && /^(x_)(.*)$/.test(k)) {
+            matched = true;
+            if (!(typeof input[k] === "number")) {
+                return false;
+            }
+        }
+        if (!matched) {
+            return false;
+        }
+    }
+    return true;
+}
+function parse_17(ctx, input) {
+    const acc = {};
+    if (ctx.objectKeyOrder === "input") {
+        for (const k of Object.keys(input)){
+            if (typeof k === "string" && /^(x_)(.*)$/.test(k)) {
+                acc[k] = input[k];
+                continue;
+            }
+        }
+    } else {
+        const extraKeys = [];
+        for (const k of Object.keys(input)){
+            extraKeys.push(k);
+        }
+        extraKeys.sort();
+        for (const k of extraKeys){
+            if (typeof k === "string" && /^(x_)(.*)$/.test(k)) {
+                acc[k] = input[k];
+                continue;
+            }
+        }
+    }
+    return acc;
+}
+function report_17(ctx, input) {
+    if (typeof input !== "object" || Array.isArray(input) || input === null) {
+        return buildError(ctx, "expected object", input);
+    }
+    const acc = [];
+    for (const k of Object.keys(input)){
+        const matches0 = typeof k === "string" && /^(x_)(.*)$/.test(k);
+        ctx.path.push(k);
+        if (!matches0) {
+            acc.push(...buildError(ctx, "expected string matching `x_${string}`", k));
+        }
+        if (matches0 && !(typeof input[k] === "number")) {
+            acc.push(...buildError(ctx, "expected number", input[k]));
+        }
+        ctx.path.pop();
+    }
+    return acc;
+}
+function validate_18(ctx, input) {
+    if (typeof input !== "object" || input === null || Array.isArray(input)) {
+        return false;
+    }
+    for (const k of Object.keys(input)){
+        let matched = false;
+        if (typeof k === "string" && /^(x_)(.*)$/.test(k)) {
+            matched = true;
+            if (!(input[k] == null || typeof input[k] === "number")) {
+                return false;
+            }
+        }
+        if (!matched) {
+            return false;
+        }
+    }
+    return true;
+}
+function parse_18(ctx, input) {
+    const acc = {};
+    if (ctx.objectKeyOrder === "input") {
+        for (const k of Object.keys(input)){
+            if (typeof k === "string" && /^(x_)(.*)$/.test(k)) {
+                acc[k] = input[k];
+                continue;
+            }
+        }
+    } else {
+        const extraKeys = [];
+        for (const k of Object.keys(input)){
+            extraKeys.push(k);
+        }
+        extraKeys.sort();
+        for (const k of extraKeys){
+            if (typeof k === "string" && /^(x_)(.*)$/.test(k)) {
+                acc[k] = input[k];
+                continue;
+            }
+        }
+    }
+    return acc;
+}
+function report_18(ctx, input) {
+    if (typeof input !== "object" || Array.isArray(input) || input === null) {
+        return buildError(ctx, "expected object", input);
+    }
+    const acc = [];
+    for (const k of Object.keys(input)){
+        const matches0 = typeof k === "string" && /^(x_)(.*)$/.test(k);
+        ctx.path.push(k);
+        if (!matches0) {
+            acc.push(...buildError(ctx, "expected string matching `x_${string}`", k));
+        }
+        if (matches0 && !(input[k] == null || typeof input[k] === "number")) {
+            acc.push(...buildError(ctx, "expected number", input[k]));
+        }
+        ctx.path.pop();
+    }
+    return acc;
+}
+function validate_19(ctx, input) {
+    if (typeof input !== "object" || input === null || Array.isArray(input)) {
+        return false;
+    }
+    if (!(input.alpha == null || typeof input.alpha === "string")) {
+        return false;
+    }
+    if (!(input.beta == null || typeof input.beta === "string")) {
+        return false;
+    }
+    for (const k of Object.keys(input)){
+        switch(k){
+            case "alpha":
+            case "beta":
+                continue;
+        }
+        let matched = false;
+        if (validate_20(ctx, k)) {
+            matched = true;
+            if (!(input[k] == null || typeof input[k] === "string")) {
+                return false;
+            }
+        }
+        if (!matched) {
+            return false;
+        }
+    }
+    return true;
+}
+function parse_19(ctx, input) {
+    const acc = {};
+    if (ctx.objectKeyOrder === "input") {
+        for (const k of Object.keys(input)){
+            switch(k){
+                case "alpha":
+                    acc.alpha = input.alpha;
+                    continue;
+                case "beta":
+                    acc.beta = input.beta;
+                    continue;
+            }
+            if (validate_20(ctx, k)) {
+                acc[k] = input[k];
+                continue;
+            }
+        }
+    } else {
+        if (hasOwn(input, "alpha")) {
+            acc.alpha = input.alpha;
+        }
+        if (hasOwn(input, "beta")) {
+            acc.beta = input.beta;
+        }
+        const extraKeys = [];
+        for (const k of Object.keys(input)){
+            switch(k){
+                case "alpha":
+                case "beta":
+                    continue;
+            }
+            extraKeys.push(k);
+        }
+        extraKeys.sort();
+        for (const k of extraKeys){
+            if (validate_20(ctx, k)) {
+                acc[k] = input[k];
+                continue;
+            }
+        }
+    }
+    return acc;
+}
+function report_19(ctx, input) {
+    if (typeof input !== "object" || Array.isArray(input) || input === null) {
+        return buildError(ctx, "expected object", input);
+    }
+    const acc = [];
+    if (!(input.alpha == null || typeof input.alpha === "string")) {
+        ctx.path.push("alpha");
+        acc.push(...buildError(ctx, "expected string", input.alpha));
+        ctx.path.pop();
+    }
+    if (!(input.beta == null || typeof input.beta === "string")) {
+        ctx.path.push("beta");
+        acc.push(...buildError(ctx, "expected string", input.beta));
+        ctx.path.pop();
+    }
+    for (const k of Object.keys(input)){
+        switch(k){
+            case "alpha":
+            case "beta":
+                continue;
+        }
+        const matches0 = validate_20(ctx, k);
+        ctx.path.push(k);
+        if (!matches0) {
+            acc.push(...report_20(ctx, k));
+        }
+        if (matches0 && !(input[k] == null || typeof input[k] === "string")) {
+            acc.push(...buildError(ctx, "expected string", input[k]));
+        }
+        ctx.path.pop();
+    }
+    return acc;
+}
+function validate_20(ctx, input) {
+    return typeof input === "string" && /^(alpha_entity_)(.*)$/.test(input) || typeof input === "string" && /^(beta-entity-)(.*)$/.test(input);
+}
+function parse_20(ctx, input) {
+    return input;
+}
+function report_20(ctx, input) {
+    const path = ctx.path;
+    ctx.path = [];
+    const branchErrors = [
+        buildError(ctx, "expected string matching `alpha_entity_${string}`", input),
+        buildError(ctx, "expected string matching `beta-entity-${string}`", input)
+    ];
+    ctx.path = path;
+    return unionDecodeError(ctx, branchErrors, input);
+}
+function validate_21(ctx, input) {
+    if (typeof input !== "object" || input === null || Array.isArray(input)) {
+        return false;
+    }
+    if (!(typeof input.alpha === "string")) {
+        return false;
+    }
+    if (!(typeof input.beta === "string")) {
+        return false;
+    }
+    for (const k of Object.keys(input)){
+        switch(k){
+            case "alpha":
+            case "beta":
+                continue;
+        }
+        let matched = false;
+        if (validate_20(ctx, k)) {
+            matched = true;
+            if (!(typeof input[k] === "string")) {
+                return false;
+            }
+        }
+        if (!matched) {
+            return false;
+        }
+    }
+    return true;
+}
+function parse_21(ctx, input) {
+    const acc = {};
+    if (ctx.objectKeyOrder === "input") {
+        for (const k of Object.keys(input)){
+            switch(k){
+                case "alpha":
+                    acc.alpha = input.alpha;
+                    continue;
+                case "beta":
+                    acc.beta = input.beta;
+                    continue;
+            }
+            if (validate_20(ctx, k)) {
+                acc[k] = input[k];
+                continue;
+            }
+        }
+    } else {
+        if (hasOwn(input, "alpha")) {
+            acc.alpha = input.alpha;
+        }
+        if (hasOwn(input, "beta")) {
+            acc.beta = input.beta;
+        }
+        const extraKeys = [];
+        for (const k of Object.keys(input)){
+            switch(k){
+                case "alpha":
+                case "beta":
+                    continue;
+            }
+            extraKeys.push(k);
+        }
+        extraKeys.sort();
+        for (const k of extraKeys){
+            if (validate_20(ctx, k)) {
+                acc[k] = input[k];
+                continue;
+            }
+        }
+    }
+    return acc;
+}
+function report_21(ctx, input) {
+    if (typeof input !== "object" || Array.isArray(input) || input === null) {
+        return buildError(ctx, "expected object", input);
+    }
+    const acc = [];
+    if (!(typeof input.alpha === "string")) {
+        ctx.path.push("alpha");
+        acc.push(...buildError(ctx, "expected string", input.alpha));
+        ctx.path.pop();
+    }
+    if (!(typeof input.beta === "string")) {
+        ctx.path.push("beta");
+        acc.push(...buildError(ctx, "expected string", input.beta));
+        ctx.path.pop();
+    }
+    for (const k of Object.keys(input)){
+        switch(k){
+            case "alpha":
+            case "beta":
+                continue;
+        }
+        const matches0 = validate_20(ctx, k);
+        ctx.path.push(k);
+        if (!matches0) {
+            acc.push(...report_20(ctx, k));
+        }
+        if (matches0 && !(typeof input[k] === "string")) {
+            acc.push(...buildError(ctx, "expected string", input[k]));
+        }
+        ctx.path.pop();
+    }
+    return acc;
+}
+function validate_22(ctx, input) {
+    if (typeof input !== "object" || input === null || Array.isArray(input)) {
+        return false;
+    }
+    if (!validate_23(ctx, input.BAR_OPTION)) {
+        return false;
+    }
+    if (!validate_7(ctx, input.BAZ_VALUES)) {
+        return false;
+    }
+    if (!(typeof input.FOO_VALUE === "string")) {
+        return false;
+    }
+    if (ctx.disallowExtraProperties) {
+        for (const k of Object.keys(input)){
+            switch(k){
+                case "BAR_OPTION":
+                case "BAZ_VALUES":
+                case "FOO_VALUE":
+                    continue;
+            }
+            return false;
+        }
+    }
+    return true;
+}
+function parse_22(ctx, input) {
+    const acc = {};
+    if (ctx.objectKeyOrder === "input") {
+        for (const k of Object.keys(input)){
+            switch(k){
+                case "BAR_OPTION":
+                    acc.BAR_OPTION = input.BAR_OPTION;
+                    continue;
+                case "BAZ_VALUES":
+                    acc.BAZ_VALUES = parse_7(ctx, input.BAZ_VALUES);
+                    continue;
+                case "FOO_VALUE":
+                    acc.FOO_VALUE = input.FOO_VALUE;
+                    continue;
+            }
+        }
+    } else {
+        if (hasOwn(input, "BAR_OPTION")) {
+            acc.BAR_OPTION = input.BAR_OPTION;
+        }
+        if (hasOwn(input, "BAZ_VALUES")) {
+            acc.BAZ_VALUES = parse_7(ctx, input.BAZ_VALUES);
+        }
+        if (hasOwn(input, "FOO_VALUE")) {
+            acc.FOO_VALUE = input.FOO_VALUE;
+        }
+    }
+    return acc;
+}
+function report_22(ctx, input) {
+    if (typeof input !== "object" || Array.isArray(input) || input === null) {
+        return buildError(ctx, "expected object", input);
+    }
+    const acc = [];
+    if (!validate_23(ctx, input.BAR_OPTION)) {
+        ctx.path.push("BAR_OPTION");
+        acc.push(...report_23(ctx, input.BAR_OPTION));
+        ctx.path.pop();
+    }
+    if (!validate_7(ctx, input.BAZ_VALUES)) {
+        ctx.path.push("BAZ_VALUES");
+        acc.push(...report_7(ctx, input.BAZ_VALUES));
+        ctx.path.pop();
+    }
+    if (!(typeof input.FOO_VALUE === "string")) {
+        ctx.path.push("FOO_VALUE");
+        acc.push(...buildError(ctx, "expected string", input.FOO_VALUE));
+        ctx.path.pop();
+    }
+    if (ctx.disallowExtraProperties) {
+        const extra = [];
+        for (const k of Object.keys(input)){
+            switch(k){
+                case "BAR_OPTION":
+                case "BAZ_VALUES":
+                case "FOO_VALUE":
+                    continue;
+            }
+            ctx.path.push(k);
+            extra.push(...buildError(ctx, "extra property", input[k]));
+            ctx.path.pop();
+        }
+        if (extra.length > 0) {
+            return extra;
+        }
+    }
+    return acc;
+}
+function validate_23(ctx, input) {
+    switch(input){
+        case "blue":
+        case "red":
+            return true;
+        default:
+            return false;
+    }
+}
+function parse_23(ctx, input) {
+    return input;
+}
+function report_23(ctx, input) {
+    return buildError(ctx, 'expected one of "blue", "red"', input);
+}
+function validate_24(ctx, input) {
+    return typeof input === "string" && /^([^a-z]*)$/.test(input);
+}
+function parse_24(ctx, input) {
+    return input;
+}
+function report_24(ctx, input) {
+    return buildError(ctx, "expected string matching `${Uppercase<string>}`", input);
+}
+function validate_25(ctx, input) {
+    return typeof input === "string" && /^([^a-z].*)?(x)$/.test(input);
+}
+function parse_25(ctx, input) {
+    return input;
+}
+function report_25(ctx, input) {
+    return buildError(ctx, "expected string matching `${Capitalize<string>}x`", input);
+}
+function validate_26(ctx, input) {
+    return typeof input === "string" && /^([+-]?(\d+(\.\d*)?|\.\d+)([eE][+-]?\d+)?|[+-]?Infinity|0[xX][0-9a-fA-F]+|0[oO][0-7]+|0[bB][01]+)(px)$/.test(input);
+}
+function parse_26(ctx, input) {
+    return input;
+}
+function report_26(ctx, input) {
+    return buildError(ctx, "expected string matching `${number}px`", input);
+}
+function validate_27(ctx, input) {
+    if (!Array.isArray(input)) {
+        return false;
+    }
+    if (input.length > 2) {
+        return false;
+    }
+    const len = Math.max(input.length, 1);
+    const optionalCount = Math.min(1, len - 1);
+    if (!(typeof input[0] === "string")) {
+        return false;
+    }
+    if (optionalCount > 0) {
+        if (!(input[1] == null || validate_28(ctx, input[1]))) {
+            return false;
+        }
+    }
+    return true;
+}
+function parse_27(ctx, input) {
+    const len = Math.max(input.length, 1);
+    const optionalCount = Math.min(1, len - 1);
+    const acc = [];
+    acc.push(input[0]);
+    if (optionalCount > 0) {
+        acc.push(input[1]);
+    }
+    return acc;
+}
+function report_27(ctx, input) {
+    if (!Array.isArray(input)) {
+        return buildError(ctx, "expected tuple", input);
+    }
+    if (input.length > 2) {
+        return buildError(ctx, "expected tuple with at most 2 items", input);
+    }
+    const len = Math.max(input.length, 1);
+    const optionalCount = Math.min(1, len - 1);
+    const acc = [];
+    if (!(typeof input[0] === "string")) {
+        ctx.path.push("[0]");
+        acc.push(...buildError(ctx, "expected string", input[0]));
+        ctx.path.pop();
+    }
+    if (optionalCount > 0) {
+        if (!(input[1] == null || validate_28(ctx, input[1]))) {
+            ctx.path.push("[1]");
+            acc.push(...report_28(ctx, input[1]));
+            ctx.path.pop();
+        }
+    }
+    return acc;
+}
+function validate_28(ctx, input) {
+    return input == null || typeof input === "number";
+}
+function parse_28(ctx, input) {
+    return input;
+}
+function report_28(ctx, input) {
+    const path = ctx.path;
+    ctx.path = [];
+    const branchErrors = [
+        buildError(ctx, "expected nullish value", input),
+        buildError(ctx, "expected number", input)
+    ];
+    ctx.path = path;
+    return unionDecodeError(ctx, branchErrors, input);
+}
+function validate_29(ctx, input) {
+    if (!Array.isArray(input)) {
+        return false;
+    }
+    if (input.length > 2) {
+        return false;
+    }
+    const len = Math.max(input.length, 1);
+    const optionalCount = Math.min(1, len - 1);
+    if (!(typeof input[0] === "number")) {
+        return false;
+    }
+    if (optionalCount > 0) {
+        if (!(input[1] == null || validate_28(ctx, input[1]))) {
+            return false;
+        }
+    }
+    return true;
+}
+function parse_29(ctx, input) {
+    const len = Math.max(input.length, 1);
+    const optionalCount = Math.min(1, len - 1);
+    const acc = [];
+    acc.push(input[0]);
+    if (optionalCount > 0) {
+        acc.push(input[1]);
+    }
+    return acc;
+}
+function report_29(ctx, input) {
+    if (!Array.isArray(input)) {
+        return buildError(ctx, "expected tuple", input);
+    }
+    if (input.length > 2) {
+        return buildError(ctx, "expected tuple with at most 2 items", input);
+    }
+    const len = Math.max(input.length, 1);
+    const optionalCount = Math.min(1, len - 1);
+    const acc = [];
+    if (!(typeof input[0] === "number")) {
+        ctx.path.push("[0]");
+        acc.push(...buildError(ctx, "expected number", input[0]));
+        ctx.path.pop();
+    }
+    if (optionalCount > 0) {
+        if (!(input[1] == null || validate_28(ctx, input[1]))) {
+            ctx.path.push("[1]");
+            acc.push(...report_28(ctx, input[1]));
+            ctx.path.pop();
+        }
+    }
+    return acc;
+}
+function validate_30(ctx, input) {
+    if (!Array.isArray(input)) {
+        return false;
+    }
+    if (input.length > 3) {
+        return false;
+    }
+    const len = Math.max(input.length, 2);
+    const optionalCount = Math.min(1, len - 2);
+    if (!(typeof input[0] === "string")) {
+        return false;
+    }
+    if (!(typeof input[1] === "number")) {
+        return false;
+    }
+    if (optionalCount > 0) {
+        if (!(input[2] == null || validate_31(ctx, input[2]))) {
+            return false;
+        }
+    }
+    return true;
+}
+function parse_30(ctx, input) {
+    const len = Math.max(input.length, 2);
+    const optionalCount = Math.min(1, len - 2);
+    const acc = [];
+    acc.push(input[0]);
+    acc.push(input[1]);
+    if (optionalCount > 0) {
+        acc.push(input[2]);
+    }
+    return acc;
+}
+function report_30(ctx, input) {
+    if (!Array.isArray(input)) {
+        return buildError(ctx, "expected tuple", input);
+    }
+    if (input.length > 3) {
+        return buildError(ctx, "expected tuple with at most 3 items", input);
+    }
+    const len = Math.max(input.length, 2);
+    const optionalCount = Math.min(1, len - 2);
+    const acc = [];
+    if (!(typeof input[0] === "string")) {
+        ctx.path.push("[0]");
+        acc.push(...buildError(ctx, "expected string", input[0]));
+        ctx.path.pop();
+    }
+    if (!(typeof input[1] === "number")) {
+        ctx.path.push("[1]");
+        acc.push(...buildError(ctx, "expected number", input[1]));
+        ctx.path.pop();
+    }
+    if (optionalCount > 0) {
+        if (!(input[2] == null || validate_31(ctx, input[2]))) {
+            ctx.path.push("[2]");
+            acc.push(...report_31(ctx, input[2]));
+            ctx.path.pop();
+        }
+    }
+    return acc;
+}
+function validate_31(ctx, input) {
+    return input == null || typeof input === "boolean";
+}
+function parse_31(ctx, input) {
+    return input;
+}
+function report_31(ctx, input) {
+    const path = ctx.path;
+    ctx.path = [];
+    const branchErrors = [
+        buildError(ctx, "expected nullish value", input),
+        buildError(ctx, "expected boolean", input)
+    ];
+    ctx.path = path;
+    return unionDecodeError(ctx, branchErrors, input);
+}
+function validate_32(ctx, input) {
+    return input instanceof registeredClass(ctx, "Point");
+}
+function parse_32(ctx, input) {
+    return input;
+}
+function report_32(ctx, input) {
+    return buildError(ctx, "expected instance of Point", input);
+}
+function validate_33(ctx, input) {
+    return typeof input === "number" && !Number.isNaN(input) && Number.isInteger(input) && input >= 0;
+}
+function parse_33(ctx, input) {
+    return input;
+}
+function report_33(ctx, input) {
+    return buildError(ctx, "expected Int & Range<0, number>", input);
+}
+function validate_34(ctx, input) {
+    return typeof input === "number" && !Number.isNaN(input) && input >= 0 && input <= 100;
+}
+function parse_34(ctx, input) {
+    return input;
+}
+function report_34(ctx, input) {
+    return buildError(ctx, "expected Range<0, 100>", input);
+}
+function validate_35(ctx, input) {
+    return typeof input === "number" && !Number.isNaN(input) && Number.isInteger(input) && input <= -1 || typeof input === "number" && !Number.isNaN(input) && Number.isInteger(input) && input >= 1;
+}
+function parse_35(ctx, input) {
+    return input;
+}
+function report_35(ctx, input) {
+    const path = ctx.path;
+    ctx.path = [];
+    const branchErrors = [
+        buildError(ctx, "expected Int & Range<number, -1>", input),
+        buildError(ctx, "expected Int & Range<1, number>", input)
+    ];
+    ctx.path = path;
+    return unionDecodeError(ctx, branchErrors, input);
+}
+function validate_36(ctx, input) {
+    return typeof input === "number" && !Number.isNaN(input) && input > 0;
+}
+function parse_36(ctx, input) {
+    return input;
+}
+function report_36(ctx, input) {
+    return buildError(ctx, "expected Exclude<NonNegative, 0>", input);
+}
+function validate_37(ctx, input) {
+    return typeof input === "number" && !Number.isNaN(input) && input > 0 && input < 1;
+}
+function parse_37(ctx, input) {
+    return input;
+}
+function report_37(ctx, input) {
+    return buildError(ctx, "expected Exclude<Exclude<Range<0, 1>, 0>, 1>", input);
+}
+function validate_38(ctx, input) {
+    if (typeof input !== "object" || input === null || Array.isArray(input)) {
+        return false;
+    }
+    if (!validate_39(ctx, input.age)) {
+        return false;
+    }
+    if (!validate_40(ctx, input.handle)) {
+        return false;
+    }
+    if (!validate_41(ctx, input.tags)) {
+        return false;
+    }
+    if (ctx.disallowExtraProperties) {
+        for (const k of Object.keys(input)){
+            switch(k){
+                case "age":
+                case "handle":
+                case "tags":
+                    continue;
+            }
+            return false;
+        }
+    }
+    return true;
+}
+function parse_38(ctx, input) {
+    const acc = {};
+    if (ctx.objectKeyOrder === "input") {
+        for (const k of Object.keys(input)){
+            switch(k){
+                case "age":
+                    acc.age = input.age;
+                    continue;
+                case "handle":
+                    acc.handle = input.handle;
+                    continue;
+                case "tags":
+                    acc.tags = parse_41(ctx, input.tags);
+                    continue;
+            }
+        }
+    } else {
+        if (hasOwn(input, "age")) {
+            acc.age = input.age;
+        }
+        if (hasOwn(input, "handle")) {
+            acc.handle = input.handle;
+        }
+        if (hasOwn(input, "tags")) {
+            acc.tags = parse_41(ctx, input.tags);
+        }
+    }
+    return acc;
+}
+function report_38(ctx, input) {
+    if (typeof input !== "object" || Array.isArray(input) || input === null) {
+        return buildError(ctx, "expected object", input);
+    }
+    const acc = [];
+    if (!validate_39(ctx, input.age)) {
+        ctx.path.push("age");
+        acc.push(...report_39(ctx, input.age));
+        ctx.path.pop();
+    }
+    if (!validate_40(ctx, input.handle)) {
+        ctx.path.push("handle");
+        acc.push(...report_40(ctx, input.handle));
+        ctx.path.pop();
+    }
+    if (!validate_41(ctx, input.tags)) {
+        ctx.path.push("tags");
+        acc.push(...report_41(ctx, input.tags));
+        ctx.path.pop();
+    }
+    if (ctx.disallowExtraProperties) {
+        const extra = [];
+        for (const k of Object.keys(input)){
+            switch(k){
+                case "age":
+                case "handle":
+                case "tags":
+                    continue;
+            }
+            ctx.path.push(k);
+            extra.push(...buildError(ctx, "extra property", input[k]));
+            ctx.path.pop();
+        }
+        if (extra.length > 0) {
+            return extra;
+        }
+    }
+    return acc;
+}
+function constraints_39(input) {
+    if (typeof input === "number") {
+        if (input < 0) {
+            return "expected number greater than or equal to 0";
+        }
+        if (input > 130) {
+            return "expected number less than or equal to 130";
+        }
+    }
+    return undefined;
+}
+function validate_39(ctx, input) {
+    return typeof input === "number" && constraints_39(input) === undefined;
+}
+function parse_39(ctx, input) {
+    return input;
+}
+function report_39(ctx, input) {
+    const message = constraints_39(input);
+    if (message === undefined || !(typeof input === "number")) {
+        return buildError(ctx, "expected number", input);
+    }
+    return buildError(ctx, message, input);
+}
+function constraints_40(input) {
+    if (typeof input === "string") {
+        const length = Array.from(input).length;
+        if (length < 3) {
+            return "expected string with at least 3 characters";
+        }
+        if (length > 5) {
+            return "expected string with at most 5 characters";
+        }
+        if (!pattern_0.test(input)) {
+            return "expected string matching ^[a-z_]+$";
+        }
+    }
+    return undefined;
+}
+function validate_40(ctx, input) {
+    return typeof input === "string" && constraints_40(input) === undefined;
+}
+function parse_40(ctx, input) {
+    return input;
+}
+function report_40(ctx, input) {
+    const message = constraints_40(input);
+    if (message === undefined || !(typeof input === "string")) {
+        return buildError(ctx, "expected string", input);
+    }
+    return buildError(ctx, message, input);
+}
+function constraints_41(input) {
+    if (Array.isArray(input)) {
+        if (input.length < 1) {
+            return "expected array with at least 1 items";
+        }
+        if (input.length > 2) {
+            return "expected array with at most 2 items";
+        }
+    }
+    return undefined;
+}
+function validate_41(ctx, input) {
+    return validate_7(ctx, input) && constraints_41(input) === undefined;
+}
+function parse_41(ctx, input) {
+    return parse_7(ctx, input);
+}
+function report_41(ctx, input) {
+    const message = constraints_41(input);
+    if (message === undefined || !validate_7(ctx, input)) {
+        return report_7(ctx, input);
+    }
+    return buildError(ctx, message, input);
+}
+const buildParsersInput = {
+    "Dec": {
+        validate: validate_0,
+        parse: parse_0,
+        report: report_0
+    },
+    "AliasToString": {
+        validate: validate_0,
+        parse: parse_0,
+        report: report_0
+    },
+    "AliasToNumber": {
+        validate: validate_1,
+        parse: parse_1,
+        report: report_1
+    },
+    "AliasToBoolean": {
+        validate: validate_2,
+        parse: parse_2,
+        report: report_2
+    },
+    "AliasToNull": {
+        validate: validate_3,
+        parse: parse_3,
+        report: report_3
+    },
+    "AliasToAny": {
+        validate: validate_4,
+        parse: parse_4,
+        report: report_4
+    },
+    "AliasToConst": {
+        validate: validate_5,
+        parse: parse_5,
+        report: report_5
+    },
+    "TestHoist": {
+        validate: validate_6,
+        parse: parse_6,
+        report: report_6
+    },
+    "NestedOrder": {
+        validate: validate_8,
+        parse: parse_8,
+        report: report_8
+    },
+    "BeforeRequired": {
+        validate: validate_10,
+        parse: parse_10,
+        report: report_10
+    },
+    "AfterRequired": {
+        validate: validate_14,
+        parse: parse_14,
+        report: report_14
+    },
+    "R": {
+        validate: validate_15,
+        parse: parse_15,
+        report: report_15
+    },
+    "R2": {
+        validate: validate_9,
+        parse: parse_9,
+        report: report_9
+    },
+    "R3": {
+        validate: validate_16,
+        parse: parse_16,
+        report: report_16
+    },
+    "R4": {
+        validate: validate_17,
+        parse: parse_17,
+        report: report_17
+    },
+    "R5": {
+        validate: validate_18,
+        parse: parse_18,
+        report: report_18
+    },
+    "Meta": {
+        validate: validate_19,
+        parse: parse_19,
+        report: report_19
+    },
+    "Meta2": {
+        validate: validate_21,
+        parse: parse_21,
+        report: report_21
+    },
+    "KnownConstants": {
+        validate: validate_22,
+        parse: parse_22,
+        report: report_22
+    },
+    "UpperName": {
+        validate: validate_24,
+        parse: parse_24,
+        report: report_24
+    },
+    "EventSuffix": {
+        validate: validate_25,
+        parse: parse_25,
+        report: report_25
+    },
+    "Pixels": {
+        validate: validate_26,
+        parse: parse_26,
+        report: report_26
+    },
+    "OptionalParams": {
+        validate: validate_27,
+        parse: parse_27,
+        report: report_27
+    },
+    "OptionalElements": {
+        validate: validate_29,
+        parse: parse_29,
+        report: report_29
+    },
+    "SpreadParams": {
+        validate: validate_30,
+        parse: parse_30,
+        report: report_30
+    },
+    "Point": {
+        validate: validate_32,
+        parse: parse_32,
+        report: report_32
+    },
+    "Age": {
+        validate: validate_33,
+        parse: parse_33,
+        report: report_33
+    },
+    "Percent": {
+        validate: validate_34,
+        parse: parse_34,
+        report: report_34
+    },
+    "NonZero": {
+        validate: validate_35,
+        parse: parse_35,
+        report: report_35
+    },
+    "Positive": {
+        validate: validate_36,
+        parse: parse_36,
+        report: report_36
+    },
+    "UnitOpen": {
+        validate: validate_37,
+        parse: parse_37,
+        report: report_37
+    },
+    "Constrained": {
+        validate: validate_38,
+        parse: parse_38,
+        report: report_38
+    }
+};
+
+const buildRoutes = () => ({"components":{"schemas":{"Constrained":{"additionalProperties":false,"properties":{"age":{"maximum":130,"minimum":0,"type":"number"},"handle":{"maxLength":5,"minLength":3,"pattern":"^[a-z_]+$","type":"string"},"tags":{"items":{"type":"string"},"maxItems":2,"minItems":1,"type":"array"}},"required":["age","handle","tags"],"type":"object"}}},"info":{"title":"Codegen","version":"1.0.0"},"openapi":"3.1.0","paths":{"/constrained/{handle}":{"get":{"parameters":[{"in":"path","name":"handle","required":true,"schema":{"type":"string"}}],"responses":{"200":{"content":{"application/json":{"schema":{"$ref":"#/components/schemas/Constrained"}}},"description":"OK"}}},"put":{"parameters":[{"in":"path","name":"handle","required":true,"schema":{"type":"string"}}],"requestBody":{"content":{"application/json":{"schema":{"$ref":"#/components/schemas/Constrained"}}},"required":true},"responses":{"204":{"description":"No Content"}}}}}});
+export default { buildParsers, buildRoutes };
\ No newline at end of file
@@ -0,0 +1,24 @@
+import path from "path";
+import { defineConfig } from "vitest/config";
+
+// the codegen-tests suite, run against the output of the compiled backend
+const runtimeOutput = path.resolve(__dirname, "../codegen-tests/src/generated/parser");
+const compiledOutput = path.resolve(__dirname, "./src/generated/parser.js");
+
+export default defineConfig({
+  plugins: [
+    {
+      name: "compiled-parser",
+      enforce: "pre",
+      resolveId(source, importer) {
+        if (importer != null && path.resolve(path.dirname(importer), source) === runtimeOutput) {
+          return compiledOutput;
+        }
+        return null;
+      },
+    },
+  ],
+  test: {
+    dir: "../codegen-tests/tests",
+  },
+});
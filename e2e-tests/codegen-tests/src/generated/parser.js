@@ -13,6 +13,7 @@ import {
   BigIntRuntype,
   StringWithFormatRuntype,
   NumberWithFormatRuntype,
+  NumberRangeRuntype,
   AnyOfConstsRuntype,
   TupleRuntype,
   AllOfRuntype,
@@ -21,6 +22,7 @@ import {
   AnyOfDiscriminatedRuntype,
   ObjectRuntype,
   OptionalFieldRuntype,
+  ConstrainedRuntype,
   BaseRefRuntype,
   registerStringFormatter,
   registerNumberFormatter,
@@ -29,6 +31,9 @@ import {
   TypedArrayRuntype,
   MapRuntype,
   SetRuntype,
+  InstanceOfRuntype,
+  ScopedRefRuntype,
+  runtypeOfParser,
 } from "@beff/client/codegen-v2";
 
 class RefRuntype extends BaseRefRuntype  {
@@ -58,10 +63,21 @@ const buildParsers = (args) => {
     const v = numberFormats[k];
     registerNumberFormatter(k, v);
   });
+  const classes = args?.classes ?? {};
+  for (const k of RequiredClasses) {
+    if (classes[k] == null) {
+      throw new Error(`Missing class ${k}`);
+    }
+  }
   let acc = {};
   for (const k of Object.keys(buildParsersInput)) {
-    const it = buildParserFromRuntype(buildParsersInput[k], k, false);
-    acc[k] = it;
+    const input = buildParsersInput[k];
+    if (typeof input === "function") {
+      acc[k] = (...parsers) =>
+        buildParserFromRuntype(input(...parsers.map(runtypeOfParser)), k, false, classes);
+      continue;
+    }
+    acc[k] = buildParserFromRuntype(input, k, false, classes);
   }
   return acc;
 };
@@ -87,139 +103,260 @@ const direct_hoist_15 = new RefRuntype(undefined, "R5");
 const direct_hoist_16 = new RefRuntype(undefined, "Meta");
 const direct_hoist_17 = new RefRuntype(undefined, "Meta2");
 const direct_hoist_18 = new RefRuntype(undefined, "KnownConstants");
-const direct_hoist_19 = new NullishRuntype(undefined, "undefined");
-const direct_hoist_20 = new AnyOfRuntype(undefined, [
-    direct_hoist_19,
+const direct_hoist_19 = new RefRuntype(undefined, "UpperName");
+const direct_hoist_20 = new RefRuntype(undefined, "EventSuffix");
+const direct_hoist_21 = new RefRuntype(undefined, "Pixels");
+const direct_hoist_22 = new RefRuntype(undefined, "OptionalParams");
+const direct_hoist_23 = new RefRuntype(undefined, "OptionalElements");
+const direct_hoist_24 = new RefRuntype(undefined, "SpreadParams");
+const direct_hoist_25 = new InstanceOfRuntype(undefined, "Point");
+const direct_hoist_26 = new RefRuntype(undefined, "Age");
+const direct_hoist_27 = new RefRuntype(undefined, "Percent");
+const direct_hoist_28 = new RefRuntype(undefined, "NonZero");
+const direct_hoist_29 = new RefRuntype(undefined, "Positive");
+const direct_hoist_30 = new RefRuntype(undefined, "UnitOpen");
+const direct_hoist_31 = new RefRuntype(undefined, "Constrained");
+const direct_hoist_32 = new NullishRuntype(undefined, "undefined");
+const direct_hoist_33 = new AnyOfRuntype(undefined, [
+    direct_hoist_32,
     direct_hoist_0
 ]);
-const direct_hoist_21 = new NullishRuntype(undefined, "void");
-const direct_hoist_22 = new AnyOfRuntype(undefined, [
-    direct_hoist_21,
+const direct_hoist_34 = new NullishRuntype(undefined, "void");
+const direct_hoist_35 = new AnyOfRuntype(undefined, [
+    direct_hoist_34,
     direct_hoist_0
 ]);
-const direct_hoist_23 = new NullishRuntype(undefined, "null");
-const direct_hoist_24 = new AnyOfRuntype(undefined, [
-    direct_hoist_23,
+const direct_hoist_36 = new NullishRuntype(undefined, "null");
+const direct_hoist_37 = new AnyOfRuntype(undefined, [
+    direct_hoist_36,
     direct_hoist_0
 ]);
-const direct_hoist_25 = new ObjectRuntype(undefined, {
+const direct_hoist_38 = new ObjectRuntype(undefined, {
     "a": direct_hoist_0,
-    "b": direct_hoist_20,
-    "c": direct_hoist_22,
-    "d": direct_hoist_24,
+    "b": direct_hoist_33,
+    "c": direct_hoist_35,
+    "d": direct_hoist_37,
     "e": direct_hoist_0
 }, []);
-const direct_hoist_26 = new AnyRuntype(undefined);
-const direct_hoist_27 = new TypeofRuntype(undefined, "boolean");
-const direct_hoist_28 = new ConstRuntype(undefined, "constant value");
-const direct_hoist_29 = new TypeofRuntype(undefined, "number");
-const direct_hoist_30 = new ObjectRuntype(undefined, {
+const direct_hoist_39 = new NumberRangeRuntype(undefined, {
+    "minimum": 0,
+    "multipleOf": 1
+});
+const direct_hoist_40 = new AnyRuntype(undefined);
+const direct_hoist_41 = new TypeofRuntype(undefined, "boolean");
+const direct_hoist_42 = new ConstRuntype(undefined, "constant value");
+const direct_hoist_43 = new TypeofRuntype(undefined, "number");
+const direct_hoist_44 = new ObjectRuntype(undefined, {
     "a": direct_hoist_0,
-    "b": direct_hoist_20,
-    "c": direct_hoist_22,
-    "d": direct_hoist_24,
+    "b": direct_hoist_33,
+    "c": direct_hoist_35,
+    "d": direct_hoist_37,
     "e": new OptionalFieldRuntype(direct_hoist_0)
 }, []);
-const direct_hoist_31 = new AnyOfConstsRuntype(undefined, [
+const direct_hoist_45 = new ConstrainedRuntype(new TypeofRuntype(undefined, "number"), {
+    "maximum": 130,
+    "minimum": 0
+});
+const direct_hoist_46 = new ConstrainedRuntype(new TypeofRuntype(undefined, "string"), {
+    "maxLength": 5,
+    "minLength": 3,
+    "pattern": "^[a-z_]+$"
+});
+const direct_hoist_47 = new ConstrainedRuntype(new ArrayRuntype(undefined, direct_hoist_0), {
+    "maxItems": 2,
+    "minItems": 1
+});
+const direct_hoist_48 = new ObjectRuntype(undefined, {
+    "age": direct_hoist_45,
+    "handle": direct_hoist_46,
+    "tags": direct_hoist_47
+}, []);
+const direct_hoist_49 = new RegexRuntype(undefined, /^([^a-z].*)?(x)$/, "`${Capitalize<string>}x`");
+const direct_hoist_50 = new AnyOfConstsRuntype(undefined, [
     "blue",
     "red"
 ]);
-const direct_hoist_32 = new ArrayRuntype(undefined, direct_hoist_0);
-const direct_hoist_33 = new ObjectRuntype(undefined, {
-    "BAR_OPTION": direct_hoist_31,
-    "BAZ_VALUES": direct_hoist_32,
+const direct_hoist_51 = new ArrayRuntype(undefined, direct_hoist_0);
+const direct_hoist_52 = new ObjectRuntype(undefined, {
+    "BAR_OPTION": direct_hoist_50,
+    "BAZ_VALUES": direct_hoist_51,
     "FOO_VALUE": direct_hoist_0
 }, []);
-const direct_hoist_34 = new RegexRuntype(undefined, /(alpha_entity_)(.*)/, "`alpha_entity_${string}`");
-const direct_hoist_35 = new RegexRuntype(undefined, /(beta-entity-)(.*)/, "`beta-entity-${string}`");
-const direct_hoist_36 = new AnyOfRuntype(undefined, [
-    direct_hoist_34,
-    direct_hoist_35
+const direct_hoist_53 = new RegexRuntype(undefined, /^(alpha_entity_)(.*)$/, "`alpha_entity_${string}`");
+const direct_hoist_54 = new RegexRuntype(undefined, /^(beta-entity-)(.*)$/, "`beta-entity-${string}`");
+const direct_hoist_55 = new AnyOfRuntype(undefined, [
+    direct_hoist_53,
+    direct_hoist_54
 ]);
-const direct_hoist_37 = new ObjectRuntype(undefined, {
+const direct_hoist_56 = new ObjectRuntype(undefined, {
     "alpha": new OptionalFieldRuntype(direct_hoist_0),
     "beta": new OptionalFieldRuntype(direct_hoist_0)
 }, [
     {
-        "key": direct_hoist_36,
+        "key": direct_hoist_55,
         "value": new OptionalFieldRuntype(direct_hoist_0)
     }
 ]);
-const direct_hoist_38 = new ObjectRuntype(undefined, {
+const direct_hoist_57 = new ObjectRuntype(undefined, {
     "alpha": direct_hoist_0,
     "beta": direct_hoist_0
 }, [
     {
-        "key": direct_hoist_36,
+        "key": direct_hoist_55,
         "value": direct_hoist_0
     }
 ]);
-const direct_hoist_39 = new ConstRuntype(undefined, "alpha");
-const direct_hoist_40 = new ConstRuntype(undefined, "beta");
-const direct_hoist_41 = new AnyOfRuntype(undefined, [
-    direct_hoist_39,
-    direct_hoist_34,
-    direct_hoist_40,
-    direct_hoist_35
+const direct_hoist_58 = new ConstRuntype(undefined, "alpha");
+const direct_hoist_59 = new ConstRuntype(undefined, "beta");
+const direct_hoist_60 = new AnyOfRuntype(undefined, [
+    direct_hoist_58,
+    direct_hoist_53,
+    direct_hoist_59,
+    direct_hoist_54
 ]);
-const direct_hoist_42 = new ObjectRuntype(undefined, {
-    "a": direct_hoist_29,
-    "b": direct_hoist_29
+const direct_hoist_61 = new ObjectRuntype(undefined, {
+    "a": direct_hoist_43,
+    "b": direct_hoist_43
 }, []);
-const direct_hoist_43 = new ObjectRuntype(undefined, {
+const direct_hoist_62 = new ObjectRuntype(undefined, {
     "label": direct_hoist_0,
-    "outer": direct_hoist_42
+    "outer": direct_hoist_61
 }, []);
-const direct_hoist_44 = new RegexRuntype(undefined, /(x_)(.*)/, "`x_${string}`");
-const direct_hoist_45 = new ObjectRuntype(undefined, {
-    "a": direct_hoist_29,
-    "b": direct_hoist_29
+const direct_hoist_63 = new NumberRangeRuntype(undefined, {
+    "maximum": -1,
+    "multipleOf": 1
+});
+const direct_hoist_64 = new NumberRangeRuntype(undefined, {
+    "minimum": 1,
+    "multipleOf": 1
+});
+const direct_hoist_65 = new AnyOfRuntype(undefined, [
+    direct_hoist_63,
+    direct_hoist_64
+]);
+const direct_hoist_66 = new AnyOfRuntype(undefined, [
+    direct_hoist_32,
+    direct_hoist_43
+]);
+const direct_hoist_67 = new TupleRuntype(undefined, [
+    direct_hoist_43
+], null, [
+    new OptionalFieldRuntype(direct_hoist_66)
+], []);
+const direct_hoist_68 = new TupleRuntype(undefined, [
+    direct_hoist_0
+], null, [
+    new OptionalFieldRuntype(direct_hoist_66)
+], []);
+const direct_hoist_69 = new NumberRangeRuntype(undefined, {
+    "maximum": 100,
+    "minimum": 0
+});
+const direct_hoist_70 = new RegexRuntype(undefined, /^([+-]?(\d+(\.\d*)?|\.\d+)([eE][+-]?\d+)?|[+-]?Infinity|0[xX][0-9a-fA-F]+|0[oO][0-7]+|0[bB][01]+)(px)$/, "`${number}px`");
+const direct_hoist_71 = new ObjectRuntype(undefined, {
+    "x": direct_hoist_43,
+    "y": direct_hoist_43
+}, []);
+const direct_hoist_72 = new NumberRangeRuntype(undefined, {
+    "exclusiveMinimum": 0
+});
+const direct_hoist_73 = new RegexRuntype(undefined, /^(x_)(.*)$/, "`x_${string}`");
+const direct_hoist_74 = new ObjectRuntype(undefined, {
+    "a": direct_hoist_43,
+    "b": direct_hoist_43
 }, [
     {
-        "key": direct_hoist_44,
-        "value": direct_hoist_29
+        "key": direct_hoist_73,
+        "value": direct_hoist_43
     }
 ]);
-const direct_hoist_46 = new ObjectRuntype(undefined, {
-    "a": new OptionalFieldRuntype(direct_hoist_29),
-    "b": new OptionalFieldRuntype(direct_hoist_29)
+const direct_hoist_75 = new ObjectRuntype(undefined, {
+    "a": new OptionalFieldRuntype(direct_hoist_43),
+    "b": new OptionalFieldRuntype(direct_hoist_43)
 }, []);
-const direct_hoist_47 = new ObjectRuntype(undefined, {}, [
+const direct_hoist_76 = new ObjectRuntype(undefined, {}, [
     {
-        "key": direct_hoist_44,
-        "value": direct_hoist_29
+        "key": direct_hoist_73,
+        "value": direct_hoist_43
     }
 ]);
-const direct_hoist_48 = new ObjectRuntype(undefined, {}, [
+const direct_hoist_77 = new ObjectRuntype(undefined, {}, [
     {
-        "key": direct_hoist_44,
-        "value": new OptionalFieldRuntype(direct_hoist_29)
+        "key": direct_hoist_73,
+        "value": new OptionalFieldRuntype(direct_hoist_43)
     }
 ]);
-const direct_hoist_49 = new ObjectRuntype(undefined, {
-    "a": direct_hoist_32,
-    "b": direct_hoist_32
+const direct_hoist_78 = new AnyOfRuntype(undefined, [
+    direct_hoist_32,
+    direct_hoist_41
+]);
+const direct_hoist_79 = new TupleRuntype(undefined, [
+    direct_hoist_0,
+    direct_hoist_43
+], null, [
+    new OptionalFieldRuntype(direct_hoist_78)
+], []);
+const direct_hoist_80 = new ObjectRuntype(undefined, {
+    "a": direct_hoist_51,
+    "b": direct_hoist_51
+}, []);
+const direct_hoist_81 = new NumberRangeRuntype(undefined, {
+    "exclusiveMaximum": 1,
+    "exclusiveMinimum": 0
+});
+const direct_hoist_82 = new RegexRuntype(undefined, /^([^a-z]*)$/, "`${Uppercase<string>}`");
+const direct_hoist_83 = new ObjectRuntype(undefined, {
+    "handle": direct_hoist_0
+}, []);
+const direct_hoist_84 = new ObjectRuntype(undefined, {
+    "params": direct_hoist_83,
+    "response": direct_hoist_31
+}, []);
+const direct_hoist_85 = new ObjectRuntype(undefined, {
+    "body": direct_hoist_31,
+    "params": direct_hoist_83
+}, []);
+const direct_hoist_86 = new ObjectRuntype(undefined, {
+    "GET /constrained/{handle}": direct_hoist_84,
+    "PUT /constrained/{handle}": direct_hoist_85
 }, []);
+const RequiredClasses = [
+    "Point"
+];
 const namedRuntypes = {
-    "AfterRequired": direct_hoist_25,
-    "AliasToAny": direct_hoist_26,
-    "AliasToBoolean": direct_hoist_27,
-    "AliasToConst": direct_hoist_28,
-    "AliasToNull": direct_hoist_23,
-    "AliasToNumber": direct_hoist_29,
+    "AfterRequired": direct_hoist_38,
+    "Age": direct_hoist_39,
+    "AliasToAny": direct_hoist_40,
+    "AliasToBoolean": direct_hoist_41,
+    "AliasToConst": direct_hoist_42,
+    "AliasToNull": direct_hoist_36,
+    "AliasToNumber": direct_hoist_43,
     "AliasToString": direct_hoist_0,
-    "BeforeRequired": direct_hoist_30,
-    "KnownConstants": direct_hoist_33,
-    "Meta": direct_hoist_37,
-    "Meta2": direct_hoist_38,
-    "MetaKey": direct_hoist_41,
-    "NestedOrder": direct_hoist_43,
-    "R": direct_hoist_45,
-    "R2": direct_hoist_42,
-    "R3": direct_hoist_46,
-    "R4": direct_hoist_47,
-    "R5": direct_hoist_48,
-    "TestHoist": direct_hoist_49
+    "BeforeRequired": direct_hoist_44,
+    "Constrained": direct_hoist_48,
+    "EventSuffix": direct_hoist_49,
+    "KnownConstants": direct_hoist_52,
+    "Meta": direct_hoist_56,
+    "Meta2": direct_hoist_57,
+    "MetaKey": direct_hoist_60,
+    "NestedOrder": direct_hoist_62,
+    "NonZero": direct_hoist_65,
+    "OptionalElements": direct_hoist_67,
+    "OptionalParams": direct_hoist_68,
+    "Percent": direct_hoist_69,
+    "Pixels": direct_hoist_70,
+    "Point": direct_hoist_71,
+    "Positive": direct_hoist_72,
+    "R": direct_hoist_74,
+    "R2": direct_hoist_61,
+    "R3": direct_hoist_75,
+    "R4": direct_hoist_76,
+    "R5": direct_hoist_77,
+    "SpreadParams": direct_hoist_79,
+    "TestHoist": direct_hoist_80,
+    "UnitOpen": direct_hoist_81,
+    "UpperName": direct_hoist_82,
+    "Routes": direct_hoist_86
 };
 const buildParsersInput = {
     "Dec": direct_hoist_0,
@@ -240,7 +377,21 @@ const buildParsersInput = {
     "R5": direct_hoist_15,
     "Meta": direct_hoist_16,
     "Meta2": direct_hoist_17,
-    "KnownConstants": direct_hoist_18
+    "KnownConstants": direct_hoist_18,
+    "UpperName": direct_hoist_19,
+    "EventSuffix": direct_hoist_20,
+    "Pixels": direct_hoist_21,
+    "OptionalParams": direct_hoist_22,
+    "OptionalElements": direct_hoist_23,
+    "SpreadParams": direct_hoist_24,
+    "Point": direct_hoist_25,
+    "Age": direct_hoist_26,
+    "Percent": direct_hoist_27,
+    "NonZero": direct_hoist_28,
+    "Positive": direct_hoist_29,
+    "UnitOpen": direct_hoist_30,
+    "Constrained": direct_hoist_31
 };
 
-export default { buildParsers };
\ No newline at end of file
+const buildRoutes = () => ({"components":{"schemas":{"Constrained":{"additionalProperties":false,"properties":{"age":{"maximum":130,"minimum":0,"type":"number"},"handle":{"maxLength":5,"minLength":3,"pattern":"^[a-z_]+$","type":"string"},"tags":{"items":{"type":"string"},"maxItems":2,"minItems":1,"type":"array"}},"required":["age","handle","tags"],"type":"object"}}},"info":{"title":"Codegen","version":"1.0.0"},"openapi":"3.1.0","paths":{"/constrained/{handle}":{"get":{"parameters":[{"in":"path","name":"handle","required":true,"schema":{"type":"string"}}],"responses":{"200":{"content":{"application/json":{"schema":{"$ref":"#/components/schemas/Constrained"}}},"description":"OK"}}},"put":{"parameters":[{"in":"path","name":"handle","required":true,"schema":{"type":"string"}}],"requestBody":{"content":{"application/json":{"schema":{"$ref":"#/components/schemas/Constrained"}}},"required":true},"responses":{"204":{"description":"No Content"}}}}}});
+export default { buildParsers, buildRoutes };
\ No newline at end of file
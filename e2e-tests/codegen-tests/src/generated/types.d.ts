@@ -0,0 +1,70 @@
+import type * as beff from "@beff/client";
+
+export type AfterRequired = { a: string; b: undefined | string; c: void | string; d: null | string; e: string };
+export type Age = beff.Int & beff.Range<0, number>;
+export type AliasToAny = any;
+export type AliasToBoolean = boolean;
+export type AliasToConst = "constant value";
+export type AliasToNull = null;
+export type AliasToNumber = number;
+export type AliasToString = string;
+export type BeforeRequired = { a: string; b: undefined | string; c: void | string; d: null | string; e?: string };
+export type Constrained = { age: number; handle: string; tags: Array<string> };
+export type EventSuffix = `${Capitalize<string>}x`;
+export type KnownConstants = { BAR_OPTION: "blue" | "red"; BAZ_VALUES: Array<string>; FOO_VALUE: string };
+export type Meta = { alpha?: string; beta?: string } & { [K in `alpha_entity_${string}` | `beta-entity-${string}`]?: string };
+export type Meta2 = { alpha: string; beta: string } & { [K in `alpha_entity_${string}` | `beta-entity-${string}`]: string };
+export type MetaKey = "alpha" | `alpha_entity_${string}` | "beta" | `beta-entity-${string}`;
+export type NestedOrder = { label: string; outer: { a: number; b: number } };
+export type NonZero = (beff.Int & beff.Range<number, -1>) | (beff.Int & beff.Range<1, number>);
+export type OptionalElements = [x: number, y?: (undefined | number)];
+export type OptionalParams = [string, (undefined | number)?];
+export type Percent = beff.Range<0, 100>;
+export type Pixels = `${number}px`;
+export type Point = { x: number; y: number };
+export type Positive = beff.NonNegative;
+export type R = { a: number; b: number } & { [K in `x_${string}`]: number };
+export type R2 = { a: number; b: number };
+export type R3 = { a?: number; b?: number };
+export type R4 = { [K in `x_${string}`]: number };
+export type R5 = { [K in `x_${string}`]?: number };
+export type SpreadParams = [string, number, (undefined | boolean)?];
+export type TestHoist = { a: Array<string>; b: Array<string> };
+export type UnitOpen = beff.Range<0, 1>;
+export type UpperName = `${Uppercase<string>}`;
+export type Routes = { "GET /constrained/{handle}": { params: { handle: string }; response: Constrained }; "PUT /constrained/{handle}": { body: Constrained; params: { handle: string } } };
+
+export type BuiltParsers = {
+  Dec: beff.BeffParser<string>;
+  AliasToString: beff.BeffParser<AliasToString>;
+  AliasToNumber: beff.BeffParser<AliasToNumber>;
+  AliasToBoolean: beff.BeffParser<AliasToBoolean>;
+  AliasToNull: beff.BeffParser<AliasToNull>;
+  AliasToAny: beff.BeffParser<AliasToAny>;
+  AliasToConst: beff.BeffParser<AliasToConst>;
+  TestHoist: beff.BeffParser<TestHoist>;
+  NestedOrder: beff.BeffParser<NestedOrder>;
+  BeforeRequired: beff.BeffParser<BeforeRequired>;
+  AfterRequired: beff.BeffParser<AfterRequired>;
+  R: beff.BeffParser<R>;
+  R2: beff.BeffParser<R2>;
+  R3: beff.BeffParser<R3>;
+  R4: beff.BeffParser<R4>;
+  R5: beff.BeffParser<R5>;
+  Meta: beff.BeffParser<Meta>;
+  Meta2: beff.BeffParser<Meta2>;
+  KnownConstants: beff.BeffParser<KnownConstants>;
+  UpperName: beff.BeffParser<UpperName>;
+  EventSuffix: beff.BeffParser<EventSuffix>;
+  Pixels: beff.BeffParser<Pixels>;
+  OptionalParams: beff.BeffParser<OptionalParams>;
+  OptionalElements: beff.BeffParser<OptionalElements>;
+  SpreadParams: beff.BeffParser<SpreadParams>;
+  Point: beff.BeffParser<import("../parser").Point>;
+  Age: beff.BeffParser<Age>;
+  Percent: beff.BeffParser<Percent>;
+  NonZero: beff.BeffParser<NonZero>;
+  Positive: beff.BeffParser<Positive>;
+  UnitOpen: beff.BeffParser<UnitOpen>;
+  Constrained: beff.BeffParser<Constrained>;
+};
@@ -1,4 +1,5 @@
 import parse from "./generated/parser";
+import { InstanceOf, Int, NonNegative, Range } from "@beff/client";
 import * as constants from "./constants";
 
 type AliasToString = string;
@@ -42,6 +43,48 @@ export type Meta = Partial<Record<MetaKey, string>>;
 export type Meta2 = Record<MetaKey, string>;
 type KnownConstants = typeof constants;
 
+export type UpperName = Uppercase<string>;
+export type EventSuffix = Capitalize<`${string}x`>;
+export type Pixels = `${number}px`;
+
+export type OptionalParams = Parameters<(a: string, b?: number) => void>;
+export type OptionalElements = [x: number, y?: number];
+export type SpreadParams = Parameters<(a: string, ...rest: [number, boolean?]) => void>;
+
+export type Age = Int & NonNegative;
+export type Percent = Range<0, 100>;
+export type NonZero = Exclude<Int, 0>;
+export type Positive = Exclude<NonNegative, 0>;
+export type UnitOpen = Exclude<Range<0, 1>, 0 | 1>;
+
+export type Constrained = {
+  /**
+   * @minLength 3
+   * @maxLength 5
+   * @pattern ^[a-z_]+$
+   */
+  handle: string;
+  /**
+   * @minimum 0
+   * @maximum 130
+   */
+  age: number;
+  /**
+   * @minItems 1
+   * @maxItems 2
+   */
+  tags: string[];
+};
+
+export class Point {
+  x: number;
+  y: number;
+  constructor(x: number, y: number) {
+    this.x = x;
+    this.y = y;
+  }
+}
+
 export const Codecs = parse.buildParsers<{
   Dec: string;
   AliasToString: AliasToString;
@@ -62,4 +105,26 @@ export const Codecs = parse.buildParsers<{
   Meta: Meta;
   Meta2: Meta2;
   KnownConstants: KnownConstants;
-}>();
+  UpperName: UpperName;
+  EventSuffix: EventSuffix;
+  Pixels: Pixels;
+  OptionalParams: OptionalParams;
+  OptionalElements: OptionalElements;
+  SpreadParams: SpreadParams;
+  Point: InstanceOf<Point>;
+  Age: Age;
+  Percent: Percent;
+  NonZero: NonZero;
+  Positive: Positive;
+  UnitOpen: UnitOpen;
+  Constrained: Constrained;
+}>({
+  classes: { Point },
+});
+
+type Routes = {
+  "GET /constrained/{handle}": { params: { handle: string }; response: Constrained };
+  "PUT /constrained/{handle}": { params: { handle: string }; body: Constrained };
+};
+
+export const Routes = parse.buildRoutes<Routes>({ title: "Codegen", version: "1.0.0" });
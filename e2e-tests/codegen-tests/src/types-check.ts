@@ -0,0 +1,12 @@
+// type-checked by `tsc`, the generated declarations must agree with the types they are built from
+import type { TypeOf } from "@beff/client";
+import type { BuiltParsers, Constrained as DeclaredConstrained } from "./generated/types";
+import { Codecs, Point } from "./parser";
+
+type Parsed<K extends keyof BuiltParsers> = TypeOf<BuiltParsers[K]>;
+
+export const point: Parsed<"Point"> = new Point(1, 2);
+export const asPoint = (it: Parsed<"Point">): Point => it;
+
+export const constrained = (it: TypeOf<typeof Codecs.Constrained>): DeclaredConstrained => it;
+export const parsers: BuiltParsers = Codecs;
@@ -0,0 +1,7 @@
+import { it } from "vitest";
+
+// codegen-tests-compiled runs this suite with `--mode compiled`
+export const compiled = import.meta.env.MODE === "compiled";
+
+// `describe`, `schema` and `hash` are only available on parsers of the runtime backend
+export const itRuntime = it.skipIf(compiled);
@@ -0,0 +1,23 @@
+import { expect, it } from "vitest";
+import parse from "../src/generated/parser";
+import { Codecs, Point } from "../src/parser";
+
+it("checks instances of the classes given to buildParsers", () => {
+  expect(Codecs.Point.safeParse(new Point(1, 2)).success).toBe(true);
+  expect(Codecs.Point.safeParse({ x: 1, y: 2 }).success).toBe(false);
+  expect(() => Codecs.Point.parse({ x: 1, y: 2 })).toThrowErrorMatchingInlineSnapshot(
+    '"Failed to parse Point - expected instance of Point, received: Object"',
+  );
+});
+
+it("requires every class", () => {
+  expect(() => parse.buildParsers()).toThrowErrorMatchingInlineSnapshot('"Missing class Point"');
+});
+
+it("keeps the classes of each build", () => {
+  class Other {}
+  const other = parse.buildParsers({ classes: { Point: Other } });
+  expect(other.Point.safeParse(new Other()).success).toBe(true);
+  expect(Codecs.Point.safeParse(new Other()).success).toBe(false);
+  expect(Codecs.Point.safeParse(new Point(1, 2)).success).toBe(true);
+});
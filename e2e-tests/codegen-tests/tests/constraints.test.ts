@@ -0,0 +1,162 @@
+import { expect, it } from "vitest";
+import { Codecs } from "../src/parser";
+
+const valid = { handle: "abc", age: 30, tags: ["a"] };
+
+it("accepts values within the constraints", () => {
+  expect(Codecs.Constrained.safeParse(valid).success).toBe(true);
+  expect(Codecs.Constrained.safeParse({ handle: "a_b_c", age: 0, tags: ["a", "b"] }).success).toBe(true);
+  expect(Codecs.Constrained.safeParse({ ...valid, age: 130 }).success).toBe(true);
+});
+
+it("minLength and maxLength", () => {
+  expect(Codecs.Constrained.safeParse({ ...valid, handle: "ab" })).toMatchInlineSnapshot(`
+    {
+      "errors": [
+        {
+          "message": "expected string with at least 3 characters",
+          "path": [
+            "handle",
+          ],
+          "received": "ab",
+        },
+      ],
+      "success": false,
+    }
+  `);
+  expect(Codecs.Constrained.safeParse({ ...valid, handle: "abcdef" })).toMatchInlineSnapshot(`
+    {
+      "errors": [
+        {
+          "message": "expected string with at most 5 characters",
+          "path": [
+            "handle",
+          ],
+          "received": "abcdef",
+        },
+      ],
+      "success": false,
+    }
+  `);
+});
+
+it("pattern", () => {
+  expect(Codecs.Constrained.safeParse({ ...valid, handle: "ABC" })).toMatchInlineSnapshot(`
+    {
+      "errors": [
+        {
+          "message": "expected string matching ^[a-z_]+$",
+          "path": [
+            "handle",
+          ],
+          "received": "ABC",
+        },
+      ],
+      "success": false,
+    }
+  `);
+});
+
+it("minimum and maximum", () => {
+  expect(Codecs.Constrained.safeParse({ ...valid, age: -1 })).toMatchInlineSnapshot(`
+    {
+      "errors": [
+        {
+          "message": "expected number greater than or equal to 0",
+          "path": [
+            "age",
+          ],
+          "received": -1,
+        },
+      ],
+      "success": false,
+    }
+  `);
+  expect(Codecs.Constrained.safeParse({ ...valid, age: 131 })).toMatchInlineSnapshot(`
+    {
+      "errors": [
+        {
+          "message": "expected number less than or equal to 130",
+          "path": [
+            "age",
+          ],
+          "received": 131,
+        },
+      ],
+      "success": false,
+    }
+  `);
+});
+
+it("minItems and maxItems", () => {
+  expect(Codecs.Constrained.safeParse({ ...valid, tags: [] })).toMatchInlineSnapshot(`
+    {
+      "errors": [
+        {
+          "message": "expected array with at least 1 items",
+          "path": [
+            "tags",
+          ],
+          "received": [],
+        },
+      ],
+      "success": false,
+    }
+  `);
+  expect(Codecs.Constrained.safeParse({ ...valid, tags: ["a", "b", "c"] })).toMatchInlineSnapshot(`
+    {
+      "errors": [
+        {
+          "message": "expected array with at most 2 items",
+          "path": [
+            "tags",
+          ],
+          "received": [
+            "a",
+            "b",
+            "c",
+          ],
+        },
+      ],
+      "success": false,
+    }
+  `);
+});
+
+it("reports the type before the constraints", () => {
+  expect(Codecs.Constrained.safeParse({ ...valid, handle: 1 })).toMatchInlineSnapshot(`
+    {
+      "errors": [
+        {
+          "message": "expected string",
+          "path": [
+            "handle",
+          ],
+          "received": 1,
+        },
+      ],
+      "success": false,
+    }
+  `);
+  expect(Codecs.Constrained.safeParse({ ...valid, tags: [1] })).toMatchInlineSnapshot(`
+    {
+      "errors": [
+        {
+          "message": "expected string",
+          "path": [
+            "tags",
+            "[0]",
+          ],
+          "received": 1,
+        },
+      ],
+      "success": false,
+    }
+  `);
+});
+
+it("throws with the constraint message", () => {
+  expect(() => Codecs.Constrained.parse({ ...valid, handle: "ab" })).toThrowErrorMatchingInlineSnapshot(
+    '"Failed to parse Constrained - (handle) expected string with at least 3 characters, received: \\"ab\\""',
+  );
+});
@@ -1,7 +1,8 @@
 import { it, expect } from "vitest";
 import { Codecs } from "../src/parser";
+import { itRuntime } from "./backend";
 
-it("R: regular syntax with infinite indexed key", () => {
+itRuntime("R: regular syntax with infinite indexed key: describe and schema", () => {
   expect(Codecs.R.describe()).toMatchInlineSnapshot(
     '"type CodecR = { a: number, b: number, [K in `x_${string}`]: number };"',
   );
@@ -36,7 +37,9 @@ it("R: regular syntax with infinite indexed key", () => {
       ],
     }
   `);
+});
 
+it("R: regular syntax with infinite indexed key", () => {
   expect(Codecs.R.safeParse({ a: 1, b: 2 })).toMatchInlineSnapshot(`
     {
       "data": {
@@ -100,7 +103,7 @@ it("R: regular syntax with infinite indexed key", () => {
   `);
 });
 
-it("R2: mapped finite required", () => {
+itRuntime("R2: mapped finite required: describe and schema", () => {
   expect(Codecs.R2.describe()).toMatchInlineSnapshot('"type CodecR2 = { a: number, b: number };"');
   expect(Codecs.R2.schema()).toMatchInlineSnapshot(`
     {
@@ -120,7 +123,9 @@ it("R2: mapped finite required", () => {
       "type": "object",
     }
   `);
+});
 
+it("R2: mapped finite required", () => {
   expect(Codecs.R2.safeParse({ a: 1, b: 2 })).toMatchInlineSnapshot(`
     {
       "data": {
@@ -146,7 +151,7 @@ it("R2: mapped finite required", () => {
   `);
 });
 
-it("R3: mapped finite optional", () => {
+itRuntime("R3: mapped finite optional: describe and schema", () => {
   expect(Codecs.R3.describe()).toMatchInlineSnapshot('"type CodecR3 = { a?: number, b?: number };"');
   expect(Codecs.R3.schema()).toMatchInlineSnapshot(`
     {
@@ -162,7 +167,9 @@ it("R3: mapped finite optional", () => {
       "type": "object",
     }
   `);
+});
 
+it("R3: mapped finite optional", () => {
   expect(Codecs.R3.safeParse({})).toMatchInlineSnapshot(`
     {
       "data": {},
@@ -202,7 +209,7 @@ it("R3: mapped finite optional", () => {
   `);
 });
 
-it("R4: mapped infinite required value", () => {
+itRuntime("R4: mapped infinite required value: describe and schema", () => {
   expect(Codecs.R4.describe()).toMatchInlineSnapshot('"type CodecR4 = { [K in `x_${string}`]: number };"');
   expect(Codecs.R4.schema()).toMatchInlineSnapshot(`
     {
@@ -216,7 +223,9 @@ it("R4: mapped infinite required value", () => {
       "type": "object",
     }
   `);
+});
 
+it("R4: mapped infinite required value", () => {
   expect(Codecs.R4.safeParse({})).toMatchInlineSnapshot(`
     {
       "data": {},
@@ -288,7 +297,7 @@ it("R: input order interleaves known and indexed properties by default", () => {
   expect(Object.keys(Codecs.R.parse(input, { objectKeyOrder: "sorted" }))).toEqual(["a", "b", "x_a", "x_b"]);
 });
 
-it("R5: mapped infinite optional value", () => {
+itRuntime("R5: mapped infinite optional value: describe and schema", () => {
   expect(Codecs.R5.describe()).toMatchInlineSnapshot('"type CodecR5 = { [K in `x_${string}`]?: number };"');
   expect(Codecs.R5.schema()).toMatchInlineSnapshot(`
     {
@@ -309,7 +318,9 @@ it("R5: mapped infinite optional value", () => {
       "type": "object",
     }
   `);
+});
 
+it("R5: mapped infinite optional value", () => {
   expect(Codecs.R5.safeParse({})).toMatchInlineSnapshot(`
     {
       "data": {},
@@ -362,7 +373,7 @@ it("R5: mapped infinite optional value", () => {
   `);
 });
 
-it("Meta: Partial<Record<MixedUnion, V>>", () => {
+itRuntime("Meta: Partial<Record<MixedUnion, V>>: describe and schema", () => {
   expect(Codecs.Meta.describe()).toMatchInlineSnapshot(
     '"type CodecMeta = { alpha?: string, beta?: string, [K in (`alpha_entity_${string}` | `beta-entity-${string}`)]?: string };"',
   );
@@ -408,7 +419,9 @@ it("Meta: Partial<Record<MixedUnion, V>>", () => {
       ],
     }
   `);
+});
 
+it("Meta: Partial<Record<MixedUnion, V>>", () => {
   expect(Codecs.Meta.safeParse({})).toMatchInlineSnapshot(`
     {
       "data": {},
@@ -521,7 +534,7 @@ it("Meta: Partial<Record<MixedUnion, V>>", () => {
   `);
 });
 
-it("Meta2: Record<MixedUnion, V>", () => {
+itRuntime("Meta2: Record<MixedUnion, V>: describe and schema", () => {
   expect(Codecs.Meta2.describe()).toMatchInlineSnapshot(
     '"type CodecMeta2 = { alpha: string, beta: string, [K in (`alpha_entity_${string}` | `beta-entity-${string}`)]: string };"',
   );
@@ -564,7 +577,9 @@ it("Meta2: Record<MixedUnion, V>", () => {
       ],
     }
   `);
+});
 
+it("Meta2: Record<MixedUnion, V>", () => {
   expect(Codecs.Meta2.safeParse({ alpha: "a", beta: "b" })).toMatchInlineSnapshot(`
     {
       "data": {
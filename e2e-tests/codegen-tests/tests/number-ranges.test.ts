@@ -0,0 +1,99 @@
+import { expect, it } from "vitest";
+import { Codecs } from "../src/parser";
+import { itRuntime } from "./backend";
+
+it("inclusive bounds", () => {
+  expect(Codecs.Percent.safeParse(0).success).toBe(true);
+  expect(Codecs.Percent.safeParse(100).success).toBe(true);
+  expect(Codecs.Percent.safeParse(50.5).success).toBe(true);
+  expect(Codecs.Percent.safeParse(-0.1).success).toBe(false);
+  expect(Codecs.Percent.safeParse(100.1).success).toBe(false);
+  expect(Codecs.Percent.safeParse("50").success).toBe(false);
+  expect(Codecs.Percent.safeParse(101)).toMatchInlineSnapshot(`
+    {
+      "errors": [
+        {
+          "message": "expected Range<0, 100>",
+          "path": [],
+          "received": 101,
+        },
+      ],
+      "success": false,
+    }
+  `);
+});
+
+it("exclusive bounds", () => {
+  expect(Codecs.UnitOpen.safeParse(0.5).success).toBe(true);
+  expect(Codecs.UnitOpen.safeParse(0).success).toBe(false);
+  expect(Codecs.UnitOpen.safeParse(1).success).toBe(false);
+  expect(Codecs.Positive.safeParse(Number.MIN_VALUE).success).toBe(true);
+  expect(Codecs.Positive.safeParse(0).success).toBe(false);
+  expect(Codecs.UnitOpen.safeParse(1)).toMatchInlineSnapshot(`
+    {
+      "errors": [
+        {
+          "message": "expected Exclude<Exclude<Range<0, 1>, 0>, 1>",
+          "path": [],
+          "received": 1,
+        },
+      ],
+      "success": false,
+    }
+  `);
+  expect(Codecs.Positive.safeParse(0)).toMatchInlineSnapshot(`
+    {
+      "errors": [
+        {
+          "message": "expected Exclude<NonNegative, 0>",
+          "path": [],
+          "received": 0,
+        },
+      ],
+      "success": false,
+    }
+  `);
+});
+
+it("Int and NonNegative", () => {
+  expect(Codecs.Age.safeParse(0).success).toBe(true);
+  expect(Codecs.Age.safeParse(42).success).toBe(true);
+  expect(Codecs.Age.safeParse(1.5).success).toBe(false);
+  expect(Codecs.Age.safeParse(-1).success).toBe(false);
+  expect(Codecs.NonZero.safeParse(-1).success).toBe(true);
+  expect(Codecs.NonZero.safeParse(1).success).toBe(true);
+  expect(Codecs.NonZero.safeParse(0).success).toBe(false);
+  expect(Codecs.NonZero.safeParse(0.5).success).toBe(false);
+  expect(Codecs.Age.safeParse(1.5)).toMatchInlineSnapshot(`
+    {
+      "errors": [
+        {
+          "message": "expected Int & Range<0, number>",
+          "path": [],
+          "received": 1.5,
+        },
+      ],
+      "success": false,
+    }
+  `);
+});
+
+it("NaN and Infinity", () => {
+  expect(Codecs.Percent.safeParse(NaN).success).toBe(false);
+  expect(Codecs.Age.safeParse(NaN).success).toBe(false);
+  expect(Codecs.Positive.safeParse(NaN).success).toBe(false);
+  // Infinity is a number, it is only rejected by a bound or by Int
+  expect(Codecs.Positive.safeParse(Infinity).success).toBe(true);
+  expect(Codecs.Percent.safeParse(Infinity).success).toBe(false);
+  expect(Codecs.Percent.safeParse(-Infinity).success).toBe(false);
+  expect(Codecs.Age.safeParse(Infinity).success).toBe(false);
+  expect(Codecs.NonZero.safeParse(-Infinity).success).toBe(false);
+});
+
+itRuntime("describe", () => {
+  expect(Codecs.Age.describe()).toMatchInlineSnapshot('"type CodecAge = Int & Range<0, number>;"');
+  expect(Codecs.Percent.describe()).toMatchInlineSnapshot('"type CodecPercent = Range<0, 100>;"');
+  expect(Codecs.NonZero.describe()).toMatchInlineSnapshot('"type CodecNonZero = (Int & Range<number, -1> | Int & Range<1, number>);"');
+  expect(Codecs.Positive.describe()).toMatchInlineSnapshot('"type CodecPositive = Exclude<NonNegative, 0>;"');
+  expect(Codecs.UnitOpen.describe()).toMatchInlineSnapshot('"type CodecUnitOpen = Exclude<Exclude<Range<0, 1>, 0>, 1>;"');
+});
@@ -0,0 +1,12 @@
+import { expect, it } from "vitest";
+import { Routes } from "../src/parser";
+import openapi from "../src/generated/openapi.json";
+
+it("returns the document written to openapi.json", () => {
+  expect(Routes).toEqual(openapi);
+  expect(Object.keys(Routes.paths)).toMatchInlineSnapshot(`
+    [
+      "/constrained/{handle}",
+    ]
+  `);
+});
@@ -0,0 +1,15 @@
+import { expect, it } from "vitest";
+import { Codecs } from "../src/parser";
+
+it("template literal validators match the whole string", () => {
+  expect(Codecs.UpperName.safeParse("ABC").success).toBe(true);
+  expect(Codecs.UpperName.safeParse("aBC").success).toBe(false);
+
+  expect(Codecs.EventSuffix.safeParse("Abcx").success).toBe(true);
+  expect(Codecs.EventSuffix.safeParse("abcx").success).toBe(false);
+  expect(Codecs.EventSuffix.safeParse("Abc").success).toBe(false);
+
+  expect(Codecs.Pixels.safeParse("-1.5e3px").success).toBe(true);
+  expect(Codecs.Pixels.safeParse("x1px").success).toBe(false);
+  expect(Codecs.Pixels.safeParse("1px2").success).toBe(false);
+});
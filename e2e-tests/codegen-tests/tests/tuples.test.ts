@@ -0,0 +1,27 @@
+import { expect, it } from "vitest";
+import { Codecs } from "../src/parser";
+
+it("optional parameters may be omitted", () => {
+  expect(Codecs.OptionalParams.safeParse(["x"]).success).toBe(true);
+  expect(Codecs.OptionalParams.safeParse(["x", 1]).success).toBe(true);
+  expect(Codecs.OptionalParams.safeParse(["x", undefined]).success).toBe(true);
+  expect(Codecs.OptionalParams.safeParse([]).success).toBe(false);
+  expect(Codecs.OptionalParams.safeParse(["x", "y"]).success).toBe(false);
+  expect(Codecs.OptionalParams.safeParse(["x", 1, 2]).success).toBe(false);
+});
+
+it("optional tuple elements accept undefined", () => {
+  expect(Codecs.OptionalElements.safeParse([1]).success).toBe(true);
+  expect(Codecs.OptionalElements.safeParse([1, 2]).success).toBe(true);
+  expect(Codecs.OptionalElements.safeParse([1, undefined]).success).toBe(true);
+  expect(Codecs.OptionalElements.safeParse([1, "2"]).success).toBe(false);
+  expect(Codecs.OptionalElements.safeParse([]).success).toBe(false);
+});
+
+it("spread rest parameters keep their optional elements", () => {
+  expect(Codecs.SpreadParams.safeParse(["x", 1]).success).toBe(true);
+  expect(Codecs.SpreadParams.safeParse(["x", 1, true]).success).toBe(true);
+  expect(Codecs.SpreadParams.safeParse(["x", 1, undefined]).success).toBe(true);
+  expect(Codecs.SpreadParams.safeParse(["x"]).success).toBe(false);
+  expect(Codecs.SpreadParams.safeParse(["x", 1, true, 2]).success).toBe(false);
+});
@@ -17,6 +17,7 @@ const {
   BigIntRuntype,
   StringWithFormatRuntype,
   NumberWithFormatRuntype,
+  NumberRangeRuntype,
   AnyOfConstsRuntype,
   TupleRuntype,
   AllOfRuntype,
@@ -25,6 +26,7 @@ const {
   AnyOfDiscriminatedRuntype,
   ObjectRuntype,
   OptionalFieldRuntype,
+  ConstrainedRuntype,
   BaseRefRuntype,
   registerStringFormatter,
   registerNumberFormatter,
@@ -33,6 +35,9 @@ const {
   TypedArrayRuntype,
   MapRuntype,
   SetRuntype,
+  InstanceOfRuntype,
+  ScopedRefRuntype,
+  runtypeOfParser,
 } = require("@beff/client/codegen-v2");
 
 class RefRuntype extends BaseRefRuntype  {
@@ -62,10 +67,21 @@ const buildParsers = (args) => {
     const v = numberFormats[k];
     registerNumberFormatter(k, v);
   });
+  const classes = args?.classes ?? {};
+  for (const k of RequiredClasses) {
+    if (classes[k] == null) {
+      throw new Error(`Missing class ${k}`);
+    }
+  }
   let acc = {};
   for (const k of Object.keys(buildParsersInput)) {
-    const it = buildParserFromRuntype(buildParsersInput[k], k, false);
-    acc[k] = it;
+    const input = buildParsersInput[k];
+    if (typeof input === "function") {
+      acc[k] = (...parsers) =>
+        buildParserFromRuntype(input(...parsers.map(runtypeOfParser)), k, false, classes);
+      continue;
+    }
+    acc[k] = buildParserFromRuntype(input, k, false, classes);
   }
   return acc;
 };
@@ -192,7 +208,7 @@ const direct_hoist_46 = new ObjectRuntype(undefined, {
     "children": direct_hoist_45,
     "value": direct_hoist_1
 }, []);
-const direct_hoist_47 = new RegexRuntype(undefined, /(\d+(\.\d+)?)(\.)(\d+(\.\d+)?)(\.)(\d+(\.\d+)?)/, "`${number}.${number}.${number}`");
+const direct_hoist_47 = new RegexRuntype(undefined, /^([+-]?(\d+(\.\d*)?|\.\d+)([eE][+-]?\d+)?|[+-]?Infinity|0[xX][0-9a-fA-F]+|0[oO][0-7]+|0[bB][01]+)(\.)([+-]?(\d+(\.\d*)?|\.\d+)([eE][+-]?\d+)?|[+-]?Infinity|0[xX][0-9a-fA-F]+|0[oO][0-7]+|0[bB][01]+)(\.)([+-]?(\d+(\.\d*)?|\.\d+)([eE][+-]?\d+)?|[+-]?Infinity|0[xX][0-9a-fA-F]+|0[oO][0-7]+|0[bB][01]+)$/, "`${number}.${number}.${number}`");
 const direct_hoist_48 = new ObjectRuntype(undefined, {
     "a": direct_hoist_0,
     "b": direct_hoist_1
@@ -223,6 +239,7 @@ const direct_hoist_58 = new ObjectRuntype(undefined, {
     "optionalNote": new OptionalFieldRuntype(direct_hoist_0),
     "trainingEndDate": direct_hoist_0
 }, []);
+const RequiredClasses = [];
 const namedRuntypes = {
     "DiscriminatedUnion": direct_hoist_31,
     "DocumentedPayload": direct_hoist_34,
@@ -13,6 +13,7 @@ import {
   BigIntRuntype,
   StringWithFormatRuntype,
   NumberWithFormatRuntype,
+  NumberRangeRuntype,
   AnyOfConstsRuntype,
   TupleRuntype,
   AllOfRuntype,
@@ -21,6 +22,7 @@ import {
   AnyOfDiscriminatedRuntype,
   ObjectRuntype,
   OptionalFieldRuntype,
+  ConstrainedRuntype,
   BaseRefRuntype,
   registerStringFormatter,
   registerNumberFormatter,
@@ -29,6 +31,9 @@ import {
   TypedArrayRuntype,
   MapRuntype,
   SetRuntype,
+  InstanceOfRuntype,
+  ScopedRefRuntype,
+  runtypeOfParser,
 } from "@beff/client/codegen-v2";
 
 class RefRuntype extends BaseRefRuntype  {
@@ -58,10 +63,21 @@ const buildParsers = (args) => {
     const v = numberFormats[k];
     registerNumberFormatter(k, v);
   });
+  const classes = args?.classes ?? {};
+  for (const k of RequiredClasses) {
+    if (classes[k] == null) {
+      throw new Error(`Missing class ${k}`);
+    }
+  }
   let acc = {};
   for (const k of Object.keys(buildParsersInput)) {
-    const it = buildParserFromRuntype(buildParsersInput[k], k, false);
-    acc[k] = it;
+    const input = buildParsersInput[k];
+    if (typeof input === "function") {
+      acc[k] = (...parsers) =>
+        buildParserFromRuntype(input(...parsers.map(runtypeOfParser)), k, false, classes);
+      continue;
+    }
+    acc[k] = buildParserFromRuntype(input, k, false, classes);
   }
   return acc;
 };
@@ -183,15 +199,15 @@ const direct_hoist_77 = new AnyOfConstsRuntype(undefined, [
     "ADMIN Admin",
     "USER User"
 ]);
-const direct_hoist_78 = new RegexRuntype(undefined, /((ADMIN)|(USER))/, '`("ADMIN" | "USER")`');
-const direct_hoist_79 = new RegexRuntype(undefined, /((ADMIN Admin)|(USER User))/, '`("ADMIN Admin" | "USER User")`');
+const direct_hoist_78 = new RegexRuntype(undefined, /^((ADMIN)|(USER))$/, '`("ADMIN" | "USER")`');
+const direct_hoist_79 = new RegexRuntype(undefined, /^((ADMIN Admin)|(USER User))$/, '`("ADMIN Admin" | "USER User")`');
 const direct_hoist_80 = new AnyOfConstsRuntype(undefined, [
     "LevelAndDSettings",
     "OmitSettings",
     "PartialSettings",
     "RequiredPartialObject"
 ]);
-const direct_hoist_81 = new RegexRuntype(undefined, /(\d+(\.\d+)?)(x)(\d+(\.\d+)?)/, "`${number}x${number}`");
+const direct_hoist_81 = new RegexRuntype(undefined, /^([+-]?(\d+(\.\d*)?|\.\d+)([eE][+-]?\d+)?|[+-]?Infinity|0[xX][0-9a-fA-F]+|0[oO][0-7]+|0[bB][01]+)(x)([+-]?(\d+(\.\d*)?|\.\d+)([eE][+-]?\d+)?|[+-]?Infinity|0[xX][0-9a-fA-F]+|0[oO][0-7]+|0[bB][01]+)$/, "`${number}x${number}`");
 const direct_hoist_82 = new ConstRuntype(undefined, "b");
 const direct_hoist_83 = new ObjectRuntype(undefined, {
     "tag": direct_hoist_82
@@ -484,8 +500,8 @@ const direct_hoist_156 = new ObjectRuntype(undefined, {
     "friends": direct_hoist_155,
     "name": direct_hoist_7
 }, []);
-const direct_hoist_157 = new RegexRuntype(undefined, /(\d+(\.\d+)?)(\.)(\d+(\.\d+)?)(\.)(\d+(\.\d+)?)/, "`${number}.${number}.${number}`");
-const direct_hoist_158 = new RegexRuntype(undefined, /(v)(\d+(\.\d+)?)(\.)(\d+(\.\d+)?)(\.)(\d+(\.\d+)?)/, "`v${number}.${number}.${number}`");
+const direct_hoist_157 = new RegexRuntype(undefined, /^([+-]?(\d+(\.\d*)?|\.\d+)([eE][+-]?\d+)?|[+-]?Infinity|0[xX][0-9a-fA-F]+|0[oO][0-7]+|0[bB][01]+)(\.)([+-]?(\d+(\.\d*)?|\.\d+)([eE][+-]?\d+)?|[+-]?Infinity|0[xX][0-9a-fA-F]+|0[oO][0-7]+|0[bB][01]+)(\.)([+-]?(\d+(\.\d*)?|\.\d+)([eE][+-]?\d+)?|[+-]?Infinity|0[xX][0-9a-fA-F]+|0[oO][0-7]+|0[bB][01]+)$/, "`${number}.${number}.${number}`");
+const direct_hoist_158 = new RegexRuntype(undefined, /^(v)([+-]?(\d+(\.\d*)?|\.\d+)([eE][+-]?\d+)?|[+-]?Infinity|0[xX][0-9a-fA-F]+|0[oO][0-7]+|0[bB][01]+)(\.)([+-]?(\d+(\.\d*)?|\.\d+)([eE][+-]?\d+)?|[+-]?Infinity|0[xX][0-9a-fA-F]+|0[oO][0-7]+|0[bB][01]+)(\.)([+-]?(\d+(\.\d*)?|\.\d+)([eE][+-]?\d+)?|[+-]?Infinity|0[xX][0-9a-fA-F]+|0[oO][0-7]+|0[bB][01]+)$/, "`v${number}.${number}.${number}`");
 const direct_hoist_159 = new ObjectRuntype(undefined, {
     "optional": new OptionalFieldRuntype(direct_hoist_7)
 }, []);
@@ -551,6 +567,7 @@ const direct_hoist_175 = new StringWithFormatRuntype(undefined, [
     "WriteAuthorizedUserId"
 ]);
 const direct_hoist_176 = new ConstRuntype(undefined, "c");
+const RequiredClasses = [];
 const namedRuntypes = {
     "DeploymentState": direct_hoist_67,
     "Query": direct_hoist_73,
@@ -0,0 +1,74 @@
+import { it, expect } from "vitest";
+import * as fs from "fs";
+import * as path from "path";
+
+const root = path.join(__dirname, "..");
+const generated = path.join(root, "src/generated");
+
+const BASE64 = "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+const decodeSegment = (segment: string): number[] => {
+  const fields: number[] = [];
+  let value = 0;
+  let shift = 0;
+  for (const char of segment) {
+    const digit = BASE64.indexOf(char);
+    value += (digit & 31) << shift;
+    if (digit & 32) {
+      shift += 5;
+    } else {
+      fields.push(value & 1 ? -(value >> 1) : value >> 1);
+      value = 0;
+      shift = 0;
+    }
+  }
+  return fields;
+};
+
+// the zero-based source and line the first segment of each generated line points to
+const originalLines = (mappings: string) => {
+  const out: ({ source: number; line: number } | undefined)[] = [];
+  let source = 0;
+  let line = 0;
+  for (const segments of mappings.split(";")) {
+    let first: { source: number; line: number } | undefined;
+    for (const segment of segments.split(",").filter((it) => it.length > 0)) {
+      const [, sourceDelta, lineDelta] = decodeSegment(segment);
+      source += sourceDelta;
+      line += lineDelta;
+      first ??= { source, line };
+    }
+    out.push(first);
+  }
+  return out;
+};
+
+const mapLinesContaining = (needle: string) => {
+  const code = fs.readFileSync(path.join(generated, "parser.js"), "utf-8").split("\n");
+  const map = JSON.parse(fs.readFileSync(path.join(generated, "parser.js.map"), "utf-8"));
+  const lines = originalLines(map.mappings);
+  return code.flatMap((it, idx) => {
+    const original = lines[idx];
+    if (!it.includes(needle) || original == null) {
+      return [];
+    }
+    const file = path.join(generated, map.sources[original.source]);
+    const declaration = fs.readFileSync(file, "utf-8").split("\n")[original.line].trim();
+    return [`${path.relative(root, file)}:${original.line + 1} ${declaration}`];
+  });
+};
+
+it("maps the validators back to their declarations", () => {
+  expect(mapLinesContaining('"BeforeRequired":')).toMatchInlineSnapshot(`
+    [
+      "tests/partial.test.ts:4 export type BeforeRequired = {",
+      "src/parser.ts:11 BeforeRequired: BeforeRequired;",
+    ]
+  `);
+  expect(mapLinesContaining('"SomeChar":')).toMatchInlineSnapshot(`
+    [
+      "tests/string-indexed.test.ts:5 export type SomeChar = SomeString[number];",
+      "src/parser.ts:17 SomeChar: SomeChar;",
+    ]
+  `);
+});
@@ -1,4 +1,4 @@
-import { type BeffParser, type DecodeError, type ParseOptions, type RegularDecodeError } from "./types.js";
+import { type BeffParser, type CompiledParser, type DecodeError, type ParseOptions, type RegularDecodeError } from "./types.js";
 import { z } from "zod";
 import {
   generateHashFromString,
@@ -16,6 +16,8 @@ import {
   bigintHash,
   stringWithFormatHash,
   numberWithFormatHash,
+  numberRangeHash,
+  constrainedHash,
   anyOfConstsHash,
   tupleHash,
   allOfHash,
@@ -24,9 +26,10 @@ import {
   mapHash,
   setHash,
 } from "./hash.js";
-import { JSONSchema7, JSONSchema7Definition } from "./json-schema.js";
+import { JSONSchema7, JSONSchema7Definition, JSONSchema7Type } from "./json-schema.js";
 import { removeNullUnionBranch } from "./openapi-pp.js";
 import { printErrors } from "./err.js";
+export { printErrors } from "./err.js";
 export { generateHashFromString, generateHashFromNumbers } from "./hash.js";
 
 const JSON_PROTO = Object.getPrototypeOf({});
@@ -228,7 +231,7 @@ function deepmergeArray(options: any) {
   };
 }
 
-const deepmerge = deepmergeConstructor({ mergeArray: deepmergeArray }) as (...args: unknown[]) => unknown;
+export const deepmerge = deepmergeConstructor({ mergeArray: deepmergeArray }) as (...args: unknown[]) => unknown;
 
 function maxErrorDepth(errors: DecodeError[]): number {
   let max = 0;
@@ -271,6 +274,19 @@ function buildUnionError(ctx: { path: string[] }, errors: DecodeError[], receive
     },
   ];
 }
+
+// keeps the errors of the branches that got the furthest, the branch errors are relative to the union
+export function unionDecodeError(
+  ctx: { path: string[] },
+  branchErrors: DecodeError[][],
+  received: unknown,
+): DecodeError[] {
+  const depths = branchErrors.map((errors) => maxErrorDepth(errors));
+  const bestDepth = Math.max(...depths);
+  const filtered =
+    bestDepth > 0 ? branchErrors.filter((_, i) => depths[i] === bestDepth).flat() : branchErrors.flat();
+  return buildUnionError(ctx, filtered, received);
+}
 function buildError(ctx: { path: string[] }, message: string, received: unknown): RegularDecodeError[] {
   return [
     {
@@ -354,6 +370,10 @@ export function registerNumberFormatter(name: string, value: UserProvidedNumberF
   numberFormatters[name] = value;
 }
 
+type UserProvidedClass = new (...args: any[]) => unknown;
+// the classes given to a `buildParsers` call, its parsers check `InstanceOf` against them
+type ProvidedClasses = Record<string, UserProvidedClass>;
+
 function resolveStringFormatErrorMessage(formats: string[], input: string): string | undefined {
   for (const f of [...formats].reverse()) {
     const formatter = stringFormatters[f];
@@ -467,16 +487,19 @@ type SchemaContext = {
 
 type ValidateContext = {
   disallowExtraProperties: boolean;
+  classes: ProvidedClasses;
 };
 
 type ParseContext = {
   disallowExtraProperties: boolean;
   objectKeyOrder: "input" | "sorted";
+  classes: ProvidedClasses;
 };
 
 type ReportContext = {
   disallowExtraProperties: boolean;
   path: string[];
+  classes: ProvidedClasses;
 };
 
 type HashContext = {
@@ -502,15 +525,19 @@ export interface Runtype {
 
 export type RuntypeMetadata = {
   description?: string;
+  format?: string;
+  default?: JSONSchema7Type;
+  deprecated?: boolean;
+  examples?: JSONSchema7Type[];
 };
 
 function annotateSchema<T extends JSONSchema7>(metadata: RuntypeMetadata | undefined, schema: T): T {
-  if (metadata?.description == null) {
+  if (metadata == null) {
     return schema;
   }
   return {
     ...schema,
-    description: metadata.description,
+    ...metadata,
   };
 }
 
@@ -970,6 +997,46 @@ export class TypedArrayRuntype extends BaseRuntype {
   }
 }
 
+export class InstanceOfRuntype extends BaseRuntype {
+  private className: string;
+  private hashValue: number;
+
+  constructor(metadata: RuntypeMetadata | undefined, className: string) {
+    super(metadata);
+    this.className = className;
+    this.hashValue = generateHashFromString("instanceof" + className);
+  }
+  private getCtor(ctx: ValidateContext): UserProvidedClass {
+    const ctor = ctx.classes[this.className];
+    if (ctor == null) {
+      throw new Error(`Class ${this.className} was not provided to buildParsers`);
+    }
+    return ctor;
+  }
+  protected describeTypeExpr(_ctx: DescribeContext): string {
+    return this.className;
+  }
+  schema(ctx: SchemaContext): JSONSchema7 {
+    throw new Error(buildSchemaErrorMessage(ctx, `Cannot generate JSON Schema for class ${this.className}`));
+  }
+  validate(ctx: ValidateContext, input: unknown): boolean {
+    return input instanceof this.getCtor(ctx);
+  }
+  parseAfterValidation(_ctx: ParseContext, input: unknown): unknown {
+    return input;
+  }
+  reportDecodeError(ctx: ReportContext, input: unknown): DecodeError[] {
+    return buildError(ctx, `expected instance of ${this.className}`, input);
+  }
+  hash(_ctx: HashContext): number {
+    return this.hashValue;
+  }
+  hash256(ctx: Hash256Context): void {
+    ctx.writer.updateTag("instanceOf");
+    ctx.writer.updateString(this.className);
+  }
+}
+
 export class StringWithFormatRuntype extends BaseRuntype {
   private formats: string[];
 
@@ -1119,6 +1186,97 @@ export class NumberWithFormatRuntype extends BaseRuntype {
   }
 }
 
+type NumberRangeKeywords = {
+  multipleOf?: number;
+  minimum?: number;
+  maximum?: number;
+  exclusiveMinimum?: number;
+  exclusiveMaximum?: number;
+};
+
+export class NumberRangeRuntype extends BaseRuntype {
+  private keywords: NumberRangeKeywords;
+  constructor(metadata: RuntypeMetadata | undefined, keywords: NumberRangeKeywords) {
+    super(metadata);
+    this.keywords = keywords;
+  }
+  protected describeTypeExpr(_ctx: DescribeContext): string {
+    return this.describeRange();
+  }
+  private describeRange(): string {
+    const { multipleOf, minimum, maximum, exclusiveMinimum, exclusiveMaximum } = this.keywords;
+    const min = minimum ?? exclusiveMinimum;
+    const max = maximum ?? exclusiveMaximum;
+    const range = `Range<${min ?? "number"}, ${max ?? "number"}>`;
+    let acc: string;
+    if (min == null && max == null) {
+      acc = multipleOf != null ? "Int" : "number";
+    } else if (multipleOf != null) {
+      acc = `Int & ${range}`;
+    } else if (min === 0 && max == null) {
+      acc = "NonNegative";
+    } else {
+      acc = range;
+    }
+    // an exclusive bound is the inclusive one without its value
+    for (const it of [exclusiveMinimum, exclusiveMaximum]) {
+      if (it != null) {
+        acc = `Exclude<${acc}, ${it}>`;
+      }
+    }
+    return acc;
+  }
+  schema(_ctx: SchemaContext): JSONSchema7 {
+    return annotateSchema(this.metadata, { type: "number", ...this.keywords });
+  }
+  validate(_ctx: ValidateContext, input: unknown): boolean {
+    if (typeof input !== "number" || Number.isNaN(input)) {
+      return false;
+    }
+    const { multipleOf, minimum, maximum, exclusiveMinimum, exclusiveMaximum } = this.keywords;
+    if (multipleOf != null && !Number.isInteger(input)) {
+      return false;
+    }
+    if (minimum != null && input < minimum) {
+      return false;
+    }
+    if (maximum != null && input > maximum) {
+      return false;
+    }
+    if (exclusiveMinimum != null && input <= exclusiveMinimum) {
+      return false;
+    }
+    if (exclusiveMaximum != null && input >= exclusiveMaximum) {
+      return false;
+    }
+    return true;
+  }
+  parseAfterValidation(_ctx: ParseContext, input: unknown): unknown {
+    return input;
+  }
+  reportDecodeError(ctx: ReportContext, input: unknown): DecodeError[] {
+    return buildError(ctx, `expected ${this.describeRange()}`, input);
+  }
+  hash(_ctx: HashContext): number {
+    let acc: number[] = [numberRangeHash];
+    for (const [key, value] of Object.entries(this.keywords).sort(([a], [b]) => a.localeCompare(b))) {
+      acc.push(generateHashFromString(key), value);
+    }
+    return generateHashFromNumbers(acc);
+  }
+  hash256(ctx: Hash256Context): void {
+    ctx.writer.updateTag("numberRange");
+    for (const key of ["multipleOf", "minimum", "maximum", "exclusiveMinimum", "exclusiveMaximum"] as const) {
+      const value = this.keywords[key];
+      if (value == null) {
+        ctx.writer.updateNull();
+      } else {
+        ctx.writer.updateNumber(value);
+      }
+    }
+  }
+}
+
 export class AnyOfConstsRuntype extends BaseRuntype {
   private values: Const[];
   constructor(metadata: RuntypeMetadata | undefined, values: Const[]) {
@@ -1195,49 +1353,77 @@ export class AnyOfConstsRuntype extends BaseRuntype {
 export class TupleRuntype extends BaseRuntype {
   private prefix: Runtype[];
   private rest: Runtype | null;
-  constructor(metadata: RuntypeMetadata | undefined, prefix: Runtype[], rest: Runtype | null) {
+  private optional: Runtype[];
+  private suffix: Runtype[];
+  constructor(
+    metadata: RuntypeMetadata | undefined,
+    prefix: Runtype[],
+    rest: Runtype | null,
+    optional: Runtype[] = [],
+    suffix: Runtype[] = [],
+  ) {
     super(metadata);
     this.prefix = prefix;
     this.rest = rest;
+    this.optional = optional;
+    this.suffix = suffix;
+  }
+  // The runtype of each element of an array with `length` elements, or null if the tuple cannot
+  // have that length. Missing required elements are checked against `undefined`.
+  private elementsFor(length: number): Runtype[] | null {
+    const required = this.prefix.length + this.suffix.length;
+    const len = Math.max(length, required);
+    const optionalCount = Math.min(this.optional.length, len - required);
+    const restCount = len - required - optionalCount;
+    if (restCount > 0 && this.rest == null) {
+      return null;
+    }
+    const rest = this.rest;
+    return [
+      ...this.prefix,
+      ...this.optional.slice(0, optionalCount),
+      ...Array.from({ length: restCount }, () => rest as Runtype),
+      ...this.suffix,
+    ];
   }
   protected describeTypeExpr(ctx: DescribeContext): string {
-    const prefix = this.prefix.map((it) => describeTypeExpr(ctx, it)).join(", ");
-    const rest = this.rest != null ? `...Array<${describeTypeExpr(ctx, this.rest)}>` : null;
+    const prefix = this.prefix.map((it) => describeTypeExpr(ctx, it));
+    const optional = this.optional.map((it) => `${describeTypeExpr(ctx, it)}?`);
+    const rest = this.rest != null ? [`...Array<${describeTypeExpr(ctx, this.rest)}>`] : [];
+    const suffix = this.suffix.map((it) => describeTypeExpr(ctx, it));
 
-    const inner = [prefix, rest].filter((it) => it != null && it.length > 0).join(", ");
-    return `[${inner}]`;
+    return `[${[...prefix, ...optional, ...rest, ...suffix].join(", ")}]`;
   }
   override describeChildren(): Runtype[] {
-    return [...this.prefix, ...(this.rest == null ? [] : [this.rest])];
+    return [...this.prefix, ...this.optional, ...(this.rest == null ? [] : [this.rest]), ...this.suffix];
   }
   schema(ctx: SchemaContext): JSONSchema7 {
     pushPath(ctx, "[]");
-    const prefixItems = this.prefix.map((it) => it.schema(ctx));
-    const items = this.rest != null ? this.rest.schema(ctx) : false;
+    const prefixItems = [...this.prefix, ...this.optional].map((it) => it.schema(ctx));
+    const rest = this.rest != null ? this.rest.schema(ctx) : false;
+    // JSON Schema cannot place items after the rest, they are allowed anywhere after the prefix
+    const suffix = this.suffix.map((it) => it.schema(ctx));
     popPath(ctx);
+    const items = suffix.length === 0 || rest === false ? rest : { anyOf: [rest, ...suffix] };
+    const lengthKeywords =
+      this.optional.length === 0 && this.suffix.length === 0
+        ? {}
+        : { minItems: this.prefix.length + this.suffix.length };
     return annotateSchema(this.metadata, {
       type: "array",
       prefixItems,
       items,
+      ...lengthKeywords,
     } as any);
   }
   validate(ctx: ValidateContext, input: unknown): boolean {
     if (Array.isArray(input)) {
-      let idx = 0;
-      for (const prefixItem of this.prefix) {
-        if (!prefixItem.validate(ctx, input[idx])) {
-          return false;
-        }
-        idx++;
+      const elements = this.elementsFor(input.length);
+      if (elements == null) {
+        return false;
       }
-      if (this.rest != null) {
-        for (let i = idx; i < input.length; i++) {
-          if (!this.rest.validate(ctx, input[i])) {
-            return false;
-          }
-        }
-      } else {
-        if (input.length > idx) {
+      for (let i = 0; i < elements.length; i++) {
+        if (!elements[i].validate(ctx, input[i])) {
           return false;
         }
       }
@@ -1246,49 +1432,30 @@ export class TupleRuntype extends BaseRuntype {
     return false;
   }
   parseAfterValidation(ctx: ParseContext, input: any): unknown {
-    let idx = 0;
-    let acc = [];
-    for (const prefixItem of this.prefix) {
-      acc.push(prefixItem.parseAfterValidation(ctx, input[idx]));
-      idx++;
-    }
-    if (this.rest != null) {
-      for (let i = idx; i < input.length; i++) {
-        acc.push(this.rest.parseAfterValidation(ctx, input[i]));
-      }
-    }
-    return acc;
+    const elements = this.elementsFor(input.length) ?? [];
+    return elements.map((it, i) => it.parseAfterValidation(ctx, input[i]));
   }
   reportDecodeError(ctx: ReportContext, input: unknown): DecodeError[] {
     if (!Array.isArray(input)) {
       return buildError(ctx, "expected tuple", input);
     }
 
-    let idx = 0;
+    const elements = this.elementsFor(input.length);
+    if (elements == null) {
+      const max = this.prefix.length + this.optional.length + this.suffix.length;
+      return buildError(ctx, `expected tuple with at most ${max} items`, input);
+    }
 
     let acc = [];
 
-    for (const prefixItem of this.prefix) {
-      const ok = prefixItem.validate(ctx, input[idx]);
+    for (let i = 0; i < elements.length; i++) {
+      const ok = elements[i].validate(ctx, input[i]);
       if (!ok) {
-        pushPath(ctx, `[${idx}]`);
-        const errors = prefixItem.reportDecodeError(ctx, input[idx]);
+        pushPath(ctx, `[${i}]`);
+        const errors = elements[i].reportDecodeError(ctx, input[i]);
         acc.push(...errors);
         popPath(ctx);
       }
-      idx++;
-    }
-
-    if (this.rest != null) {
-      for (let i = idx; i < input.length; i++) {
-        const ok = this.rest.validate(ctx, input[i]);
-        if (!ok) {
-          pushPath(ctx, `[${i}]`);
-          const errors = this.rest.reportDecodeError(ctx, input[i]);
-          acc.push(...errors);
-          popPath(ctx);
-        }
-      }
     }
 
     return acc;
@@ -1303,6 +1470,12 @@ export class TupleRuntype extends BaseRuntype {
     } else {
       acc.push(0);
     }
+    for (const p of [...this.optional, ...this.suffix]) {
+      acc.push(p.hash(ctx));
+    }
+    if (this.optional.length > 0 || this.suffix.length > 0) {
+      acc.push(this.optional.length, this.suffix.length);
+    }
     return generateHashFromNumbers(acc);
   }
   hash256(ctx: Hash256Context): void {
@@ -1317,6 +1490,16 @@ export class TupleRuntype extends BaseRuntype {
       ctx.writer.updateTag("rest");
       this.rest.hash256(ctx);
     }
+    if (this.optional.length > 0 || this.suffix.length > 0) {
+      ctx.writer.updateNumber(this.optional.length);
+      for (const p of this.optional) {
+        p.hash256(ctx);
+      }
+      ctx.writer.updateNumber(this.suffix.length);
+      for (const p of this.suffix) {
+        p.hash256(ctx);
+      }
+    }
   }
 }
 
@@ -1498,14 +1681,7 @@ export class AnyOfRuntype extends BaseRuntype {
       branchErrors.push(v.reportDecodeError(ctx, input));
     }
     ctx.path = oldPaths;
-
-    // Filter to closest-matching branches by max error depth
-    const depths = branchErrors.map((errors) => maxErrorDepth(errors));
-    const bestDepth = Math.max(...depths);
-    const filtered =
-      bestDepth > 0 ? branchErrors.filter((_, i) => depths[i] === bestDepth).flat() : branchErrors.flat();
-
-    return buildUnionError(ctx, filtered, input);
+    return unionDecodeError(ctx, branchErrors, input);
   }
   protected describeTypeExpr(ctx: DescribeContext): string {
     return `(${this.schemas.map((it) => describeTypeExpr(ctx, it)).join(" | ")})`;
@@ -1958,6 +2134,100 @@ export class OptionalFieldRuntype implements Runtype {
   }
 }
 
+type ConstraintKeywords = {
+  minLength?: number;
+  maxLength?: number;
+  pattern?: string;
+  minimum?: number;
+  maximum?: number;
+  minItems?: number;
+  maxItems?: number;
+};
+
+// Keywords only constrain values of their own type, as in JSON Schema.
+function constraintErrorMessage(keywords: ConstraintKeywords, input: unknown): string | undefined {
+  const { minLength, maxLength, minimum, maximum, minItems, maxItems } = keywords;
+  if (typeof input === "string") {
+    const length = Array.from(input).length;
+    if (minLength != null && length < minLength) {
+      return `expected string with at least ${minLength} characters`;
+    }
+    if (maxLength != null && length > maxLength) {
+      return `expected string with at most ${maxLength} characters`;
+    }
+    if (keywords.pattern != null && !new RegExp(keywords.pattern, "u").test(input)) {
+      return `expected string matching ${keywords.pattern}`;
+    }
+  }
+  if (typeof input === "number") {
+    if (minimum != null && input < minimum) {
+      return `expected number greater than or equal to ${minimum}`;
+    }
+    if (maximum != null && input > maximum) {
+      return `expected number less than or equal to ${maximum}`;
+    }
+  }
+  if (Array.isArray(input)) {
+    if (minItems != null && input.length < minItems) {
+      return `expected array with at least ${minItems} items`;
+    }
+    if (maxItems != null && input.length > maxItems) {
+      return `expected array with at most ${maxItems} items`;
+    }
+  }
+  return undefined;
+}
+
+export class ConstrainedRuntype implements Runtype {
+  readonly t: Runtype;
+  private keywords: ConstraintKeywords;
+
+  constructor(t: Runtype, keywords: ConstraintKeywords) {
+    this.t = t;
+    this.keywords = keywords;
+  }
+
+  schema(ctx: SchemaContext): JSONSchema7 {
+    return { ...this.t.schema(ctx), ...this.keywords };
+  }
+  validate(ctx: ValidateContext, input: unknown): boolean {
+    return this.t.validate(ctx, input) && constraintErrorMessage(this.keywords, input) == null;
+  }
+  parseAfterValidation(ctx: ParseContext, input: any): unknown {
+    return this.t.parseAfterValidation(ctx, input);
+  }
+  reportDecodeError(ctx: ReportContext, input: unknown): DecodeError[] {
+    const message = constraintErrorMessage(this.keywords, input);
+    if (message == null || !this.t.validate(ctx, input)) {
+      return this.t.reportDecodeError(ctx, input);
+    }
+    return buildError(ctx, message, input);
+  }
+  describe(ctx: DescribeContext): TypeDescription {
+    return this.t.describe(ctx);
+  }
+  describeChildren(): Runtype[] {
+    return [this.t];
+  }
+  hash(ctx: HashContext): number {
+    let acc = [constrainedHash, this.t.hash(ctx)];
+    for (const [key, value] of Object.entries(this.keywords).sort(([a], [b]) => a.localeCompare(b))) {
+      acc.push(generateHashFromString(key), generateHashFromString(String(value)));
+    }
+    return generateHashFromNumbers(acc);
+  }
+  hash256(ctx: Hash256Context): void {
+    ctx.writer.updateTag("constrained");
+    const entries = Object.entries(this.keywords).sort(([a], [b]) => a.localeCompare(b));
+    ctx.writer.updateNumber(entries.length);
+    for (const [key, value] of entries) {
+      ctx.writer.updateString(key);
+      ctx.writer.updateString(String(value));
+    }
+    this.t.hash256(ctx);
+  }
+}
+
 export class ObjectRuntype extends BaseRuntype {
   private properties: Record<string, Runtype>;
   private indexedPropertiesParser: Array<{
@@ -2084,19 +2354,18 @@ export class ObjectRuntype extends BaseRuntype {
         const inputKeys = Object.keys(input);
         const extraKeys = inputKeys.filter((k) => !configKeys.includes(k));
         for (const k of extraKeys) {
-          let isValid = false;
+          // a key must satisfy every index signature it matches
+          let isMatched = false;
           for (const p of this.indexedPropertiesParser) {
             if (!p.key.validate(ctx, k)) {
               continue;
             }
-            const v = input[k];
-            if (!p.value.validate(ctx, v)) {
-              continue;
+            isMatched = true;
+            if (!p.value.validate(ctx, input[k])) {
+              return false;
             }
-            isValid = true;
-            break;
           }
-          if (!isValid) {
+          if (!isMatched) {
             return false;
           }
         }
@@ -2130,11 +2399,11 @@ export class ObjectRuntype extends BaseRuntype {
 
         for (const p of this.indexedPropertiesParser) {
           const v = input[k];
-          const isValid = p.key.validate(ctx, k) && p.value.validate(ctx, v);
-          if (isValid) {
+          if (p.key.validate(ctx, k)) {
             const itemParsed = p.value.parseAfterValidation(ctx, v);
             const keyParsed = p.key.parseAfterValidation(ctx, k);
             acc[keyParsed as any] = itemParsed;
+            break;
           }
         }
       }
@@ -2155,11 +2424,11 @@ export class ObjectRuntype extends BaseRuntype {
         for (const k of extraKeys) {
           const v = input[k];
           for (const p of this.indexedPropertiesParser) {
-            const isValid = p.key.validate(ctx, k) && p.value.validate(ctx, v);
-            if (isValid) {
+            if (p.key.validate(ctx, k)) {
               const itemParsed = p.value.parseAfterValidation(ctx, v);
               const keyParsed = p.key.parseAfterValidation(ctx, k);
               acc[keyParsed as any] = itemParsed;
+              break;
             }
           }
         }
@@ -2191,23 +2460,19 @@ export class ObjectRuntype extends BaseRuntype {
       const inputKeys = Object.keys(input);
       const extraKeys = inputKeys.filter((k) => !configKeys.includes(k));
       for (const k of extraKeys) {
-        for (const p of this.indexedPropertiesParser) {
-          const keyOk = p.key.validate(ctx, k);
-          const valueOk = p.value.validate(ctx, input[k]);
-          const ok = keyOk && valueOk;
-          if (!ok) {
-            pushPath(ctx, k);
-            if (!keyOk) {
-              const keyReported = p.key.reportDecodeError(ctx, k);
-              acc.push(...keyReported);
-            }
-            if (!valueOk) {
-              const valueReported = p.value.reportDecodeError(ctx, input[k]);
-              acc.push(...valueReported);
-            }
-            popPath(ctx);
+        const matching = this.indexedPropertiesParser.filter((p) => p.key.validate(ctx, k));
+        pushPath(ctx, k);
+        if (matching.length === 0) {
+          for (const p of this.indexedPropertiesParser) {
+            acc.push(...p.key.reportDecodeError(ctx, k));
+          }
+        }
+        for (const p of matching) {
+          if (!p.value.validate(ctx, input[k])) {
+            acc.push(...p.value.reportDecodeError(ctx, input[k]));
           }
         }
+        popPath(ctx);
       }
     } else {
       if (ctx.disallowExtraProperties) {
@@ -2369,6 +2634,20 @@ export abstract class BaseRefRuntype extends BaseRuntype {
   }
 }
 
+/**
+ * Reference to a type instantiated by a generic parser factory, it lives in the scope of a single factory call.
+ */
+export class ScopedRefRuntype extends BaseRefRuntype {
+  private scope: Record<string, Runtype>;
+  constructor(metadata: RuntypeMetadata | undefined, refName: string, scope: Record<string, Runtype>) {
+    super(metadata, refName);
+    this.scope = scope;
+  }
+  getNamedRuntypes(): Record<string, Runtype> {
+    return this.scope;
+  }
+}
+
 const namedRuntypes: Record<string, Runtype> = {};
 
 class RuntimeRefRuntype extends BaseRefRuntype {
@@ -2397,10 +2676,12 @@ class ParserFromRuntype implements BeffParser<any> {
   _runtype: Runtype;
   name: string;
   private hideTypeNameInDescribe: boolean;
-  constructor(runtype: Runtype, name: string, hideTypeNameInDescribe: boolean) {
+  private classes: ProvidedClasses;
+  constructor(runtype: Runtype, name: string, hideTypeNameInDescribe: boolean, classes: ProvidedClasses) {
     this._runtype = runtype;
     this.name = name;
     this.hideTypeNameInDescribe = hideTypeNameInDescribe;
+    this.classes = classes;
   }
   parse(input: any, options?: ParseOptions): any {
     const safe = this.safeParse(input, options);
@@ -2418,11 +2699,11 @@ class ParserFromRuntype implements BeffParser<any> {
     const objectKeyOrder = options?.objectKeyOrder ?? "input";
     const ok = this.validate(input, options);
     if (ok) {
-      let ctx = { disallowExtraProperties, objectKeyOrder };
+      let ctx = { disallowExtraProperties, objectKeyOrder, classes: this.classes };
       const parsed = this._runtype.parseAfterValidation(ctx, input);
       return { success: true, data: parsed };
     }
-    let ctx = { path: [], disallowExtraProperties };
+    let ctx = { path: [], disallowExtraProperties, classes: this.classes };
     return {
       success: false,
       errors: this._runtype.reportDecodeError(ctx, input).slice(0, 10),
@@ -2434,14 +2715,17 @@ class ParserFromRuntype implements BeffParser<any> {
       (data: any) => this.validate(data),
       //@ts-ignore
       (val: any) => {
-        const errors = this._runtype.reportDecodeError({ path: [], disallowExtraProperties: false }, val);
+        const errors = this._runtype.reportDecodeError(
+          { path: [], disallowExtraProperties: false, classes: this.classes },
+          val,
+        );
         return printErrors(errors, []);
       },
     );
   }
   validate(input: any, options?: ParseOptions): input is any {
     const disallowExtraProperties = options?.disallowExtraProperties ?? false;
-    const ctx = { disallowExtraProperties };
+    const ctx = { disallowExtraProperties, classes: this.classes };
     const ok = this._runtype.validate(ctx, input);
     if (typeof ok !== "boolean") {
       throw new Error("INTERNAL ERROR: Expected boolean");
@@ -2504,8 +2788,52 @@ class ParserFromRuntype implements BeffParser<any> {
   }
 }
 
+/**
+ * A parser that was not built from runtypes, like a compiled one, passed to a parser factory.
+ */
+class ParserRuntype extends BaseRuntype {
+  private parser: CompiledParser<any>;
+  constructor(parser: CompiledParser<any>) {
+    super(undefined);
+    this.parser = parser;
+  }
+  // compiled parsers only validate and parse
+  private fullParser(method: keyof BeffParser<any>): BeffParser<any> {
+    if (!(method in this.parser)) {
+      throw new Error(`${method} is not available on compiled parsers`);
+    }
+    return this.parser as BeffParser<any>;
+  }
+  protected describeTypeExpr(_ctx: DescribeContext): string {
+    return this.parser.name;
+  }
+  schema(_ctx: SchemaContext): JSONSchema7 {
+    return this.fullParser("schema").schema();
+  }
+  validate(ctx: ValidateContext, input: unknown): boolean {
+    return this.parser.validate(input, ctx);
+  }
+  parseAfterValidation(ctx: ParseContext, input: unknown): unknown {
+    return this.parser.parse(input, ctx);
+  }
+  reportDecodeError(ctx: ReportContext, input: unknown): DecodeError[] {
+    const res = this.parser.safeParse(input, ctx);
+    return res.success ? [] : res.errors.map((err) => ({ ...err, path: [...ctx.path, ...err.path] }));
+  }
+  hash(_ctx: HashContext): number {
+    return this.fullParser("hash").hash();
+  }
+  hash256(ctx: Hash256Context): void {
+    ctx.writer.updateString(this.fullParser("hash256").hash256());
+  }
+}
+
+export const runtypeOfParser = (parser: CompiledParser<any>): Runtype =>
+  parser instanceof ParserFromRuntype ? parser._runtype : new ParserRuntype(parser);
+
 export const buildParserFromRuntype = (
   runtype: Runtype,
   name: string,
   hideTypeNameInDescribe: boolean,
-): BeffParser<any> => new ParserFromRuntype(runtype, name, hideTypeNameInDescribe);
+  classes: ProvidedClasses = {},
+): BeffParser<any> => new ParserFromRuntype(runtype, name, hideTypeNameInDescribe, classes);
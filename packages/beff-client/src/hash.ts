@@ -40,11 +40,13 @@ export const dateHash = generateHashFromString("date");
 export const bigintHash = generateHashFromString("bigint");
 export const stringWithFormatHash = generateHashFromString("StringWithFormat");
 export const numberWithFormatHash = generateHashFromString("NumberWithFormat");
+export const numberRangeHash = generateHashFromString("NumberRange");
 export const anyOfConstsHash = generateHashFromString("AnyOfConsts");
 export const tupleHash = generateHashFromString("Tuple");
 export const allOfHash = generateHashFromString("AllOf");
 export const anyOfHash = generateHashFromString("AnyOf");
 export const optionalFieldHash = generateHashFromString("OptionalField");
+export const constrainedHash = generateHashFromString("Constrained");
 export const mapHash = generateHashFromString("Map");
 export const setHash = generateHashFromString("Set");
 
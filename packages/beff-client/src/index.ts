@@ -5,12 +5,21 @@ export {
   StringFormatExtends,
   NumberFormat,
   NumberFormatExtends,
+  Int,
+  NonNegative,
+  Range,
+  InstanceOf,
   RegularDecodeError,
   UnionDecodeError,
   DecodeError,
   ParseOptions,
   BeffParser,
+  CompiledParser,
   BuildParserFunction,
+  BuildCompiledParserFunction,
+  BuildRoutesFunction,
+  OpenApiInfo,
+  OpenApiDocument,
   TypeOf,
 } from "./types.js";
 export {
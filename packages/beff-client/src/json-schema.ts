@@ -181,5 +181,6 @@ export interface JSONSchema7 {
   readOnly?: boolean | undefined;
   writeOnly?: boolean | undefined;
   examples?: JSONSchema7Type | undefined;
+  deprecated?: boolean | undefined;
   discriminator?: OpenAPIDiscriminator | undefined;
 }
@@ -14,6 +14,23 @@ export type NumberFormatExtends<Base, TagNext extends string> = Base & {
   [k in TagNext]: TagNext;
 };
 
+/** An integer number. */
+export type Int = number & { Int: "Int" };
+
+/** A number greater than or equal to zero. */
+export type NonNegative = number & { NonNegative: "NonNegative" };
+
+/**
+ * A number between `Min` and `Max`, inclusive. Pass `number` to leave a side unbounded.
+ */
+export type Range<Min extends number, Max extends number> = number & { Range: [Min, Max] };
+
+/**
+ * Opts a class into `instanceof` validation, instead of checking its public fields.
+ * The constructor must be passed to `buildParsers` under `classes`.
+ */
+export type InstanceOf<T> = T;
+
 export type RegularDecodeError = {
   message: string;
   path: string[];
@@ -47,8 +64,31 @@ export type BeffParser<T> = {
   hash: () => number;
   hash256: () => string;
 };
+/** A parser of the compiled backend, it can only validate and parse. */
+export type CompiledParser<T> = Pick<BeffParser<T>, "parse" | "safeParse" | "validate" | "name">;
+type IsAny<T> = 0 extends 1 & T ? true : false;
+// generic entries are declared as functions from parsers to a parser, other functions are validated as values
+type ParserEntry<V> = V extends (...args: BeffParser<any>[]) => infer R
+  ? IsAny<R> extends true
+    ? BeffParser<V>
+    : R extends BeffParser<any>
+      ? V
+      : BeffParser<V>
+  : BeffParser<V>;
 type Parsers<T> = {
-  [K in keyof T]: BeffParser<T[K]>;
+  [K in keyof T]: ParserEntry<T[K]>;
+};
+
+// factories keep their declared signature, they can be declared with `CompiledParser`s
+type CompiledParserEntry<V> = V extends (...args: BeffParser<any>[]) => infer R
+  ? IsAny<R> extends true
+    ? CompiledParser<V>
+    : R extends CompiledParser<any>
+      ? V
+      : CompiledParser<V>
+  : CompiledParser<V>;
+type CompiledParsers<T> = {
+  [K in keyof T]: CompiledParserEntry<T[K]>;
 };
 
 export type UserProvidedStringFormat =
@@ -70,6 +110,30 @@ export type UserProvidedNumberFormat =
 export type BuildParserFunction = <T>(args?: {
   stringFormats?: { [key: string]: UserProvidedStringFormat };
   numberFormats?: { [key: string]: UserProvidedNumberFormat };
+  classes?: { [key: string]: new (...args: any[]) => unknown };
 }) => Parsers<T>;
 
+export type BuildCompiledParserFunction = <T>(args?: {
+  stringFormats?: { [key: string]: UserProvidedStringFormat };
+  numberFormats?: { [key: string]: UserProvidedNumberFormat };
+  classes?: { [key: string]: new (...args: any[]) => unknown };
+}) => CompiledParsers<T>;
+
+export type OpenApiInfo = {
+  title?: string;
+  version?: string;
+  summary?: string;
+  description?: string;
+};
+
+export type OpenApiDocument = {
+  openapi: "3.1.0";
+  info: { title: string; version: string; summary?: string; description?: string };
+  paths: Record<string, unknown>;
+  components?: { schemas: Record<string, JSONSchema7> };
+};
+
+/** The routes are only read at build time, the info must be an object of string literals. */
+export type BuildRoutesFunction = <T>(info?: OpenApiInfo) => OpenApiDocument;
+
 export type TypeOf<T> = T extends BeffParser<infer U> ? U : never;
@@ -0,0 +1,111 @@
+import { it, expect } from "vitest";
+import {
+  AnyOfRuntype,
+  ArrayRuntype,
+  NullishRuntype,
+  ObjectRuntype,
+  Runtype,
+  ScopedRefRuntype,
+  TypeofRuntype,
+  buildParserFromRuntype,
+  runtypeOfParser,
+} from "../src/codegen-v2";
+import { CompiledParser } from "../src/types";
+
+// what is generated for `Paginated<T>(item: BeffParser<T>): BeffParser<Paginated<T>>`,
+// with `type Paginated<T> = { items: T[]; next: Paginated<T> | null }`
+const paginatedInput = (T: Runtype) => {
+  const scopedRuntypes: Record<string, Runtype> = {};
+  const self = new ScopedRefRuntype(undefined, "Paginated_instance_0", scopedRuntypes);
+  scopedRuntypes["Paginated_instance_0"] = new ObjectRuntype(
+    undefined,
+    {
+      items: new ArrayRuntype(undefined, T),
+      next: new AnyOfRuntype(undefined, [new NullishRuntype(undefined, "null"), self]),
+    },
+    [],
+  );
+  return self;
+};
+const Paginated = (item: CompiledParser<any>) =>
+  buildParserFromRuntype(paginatedInput(runtypeOfParser(item)), "Paginated", false);
+
+// stands in for a parser of the compiled backend, it is not built from runtypes
+const compiledString: CompiledParser<string> = {
+  name: "CompiledString",
+  validate: (input: any): input is string => typeof input === "string",
+  safeParse: (input: any) =>
+    typeof input === "string"
+      ? { success: true, data: input }
+      : { success: false, errors: [{ message: "expected string", path: [], received: input }] },
+  parse: (input: any) => {
+    if (typeof input !== "string") {
+      throw new Error("Failed to parse CompiledString");
+    }
+    return input;
+  },
+};
+
+const runtimeString = buildParserFromRuntype(new TypeofRuntype(undefined, "string"), "String", true);
+
+it("parser factories take runtime parsers", () => {
+  const parser = Paginated(runtimeString);
+  expect(parser.parse({ items: ["a"], next: { items: ["b"], next: null } })).toMatchInlineSnapshot(`
+    {
+      "items": [
+        "a",
+      ],
+      "next": {
+        "items": [
+          "b",
+        ],
+        "next": null,
+      },
+    }
+  `);
+  expect(parser.safeParse({ items: ["a"], next: { items: [1], next: null } })).toMatchInlineSnapshot(`
+    {
+      "errors": [
+        {
+          "message": "expected string",
+          "path": [
+            "next",
+            "items",
+            "[0]",
+          ],
+          "received": 1,
+        },
+      ],
+      "success": false,
+    }
+  `);
+  expect(parser.describe()).toMatchInlineSnapshot(`
+    "type Paginated_instance_0 = { items: Array<string>, next: (null | Paginated_instance_0) };
+
+    type CodecPaginated = Paginated_instance_0;"
+  `);
+});
+
+it("parser factories take compiled parsers", () => {
+  const parser = Paginated(compiledString);
+  expect(parser.validate({ items: ["a"], next: { items: ["b"], next: null } })).toBe(true);
+  expect(parser.safeParse({ items: ["a"], next: { items: [1], next: null } })).toMatchInlineSnapshot(`
+    {
+      "errors": [
+        {
+          "message": "expected string",
+          "path": [
+            "next",
+            "items",
+            "[0]",
+          ],
+          "received": 1,
+        },
+      ],
+      "success": false,
+    }
+  `);
+  expect(() => parser.schema()).toThrowErrorMatchingInlineSnapshot(
+    '"schema is not available on compiled parsers"',
+  );
+});
@@ -44,7 +44,7 @@ impl N {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum Json {
     Null,
     Bool(bool),
@@ -191,6 +191,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::approx_constant)]
     fn test_number_conversion() {
         let num1 = N::parse_f64(42.0);
         assert_eq!(num1.integral, 42);
@@ -1,6 +1,7 @@
 use super::json::N;
 use crate::NamedSchema;
 use crate::RuntypeUUID;
+use crate::TypeAddress;
 use crate::ast::json::Json;
 use crate::subtyping::ToSemType;
 use crate::subtyping::semtype::SemTypeContext;
@@ -87,13 +88,63 @@ impl RuntypeConst {
     }
 }
 
+#[derive(PartialEq, Eq, Hash, Debug, Ord, PartialOrd, Clone, Copy)]
+pub enum StringIntrinsic {
+    Uppercase,
+    Lowercase,
+    Capitalize,
+    Uncapitalize,
+}
+
+impl StringIntrinsic {
+    pub fn name(&self) -> &'static str {
+        match self {
+            StringIntrinsic::Uppercase => "Uppercase",
+            StringIntrinsic::Lowercase => "Lowercase",
+            StringIntrinsic::Capitalize => "Capitalize",
+            StringIntrinsic::Uncapitalize => "Uncapitalize",
+        }
+    }
+
+    pub fn apply(&self, s: &str) -> String {
+        match self {
+            StringIntrinsic::Uppercase => s.to_uppercase(),
+            StringIntrinsic::Lowercase => s.to_lowercase(),
+            StringIntrinsic::Capitalize | StringIntrinsic::Uncapitalize => {
+                let mut chars = s.chars();
+                match chars.next() {
+                    Some(first) => {
+                        let first: String = if *self == StringIntrinsic::Capitalize {
+                            first.to_uppercase().collect()
+                        } else {
+                            first.to_lowercase().collect()
+                        };
+                        first + chars.as_str()
+                    }
+                    None => String::new(),
+                }
+            }
+        }
+    }
+
+    fn applies_to_whole_string(&self) -> bool {
+        matches!(
+            self,
+            StringIntrinsic::Uppercase | StringIntrinsic::Lowercase
+        )
+    }
+}
+
 #[derive(PartialEq, Eq, Hash, Debug, Ord, PartialOrd, Clone)]
 pub enum TplLitTypeItem {
     String,
     Number,
+    BigInt,
     Boolean,
     StringConst(String),
     OneOf(BTreeSet<TplLitTypeItem>),
+    /// Any string `s` such that applying the intrinsic to `s` returns `s`, e.g. `Uppercase<string>`.
+    IntrinsicString(StringIntrinsic),
 }
 
 fn escape_regex(lit: &str) -> String {
@@ -126,10 +177,66 @@ impl TplLitTypeItem {
         }
     }
 
+    pub fn finite_strings(&self) -> Option<Vec<String>> {
+        match self {
+            TplLitTypeItem::StringConst(s) => Some(vec![s.clone()]),
+            TplLitTypeItem::OneOf(vs) => {
+                let mut acc = vec![];
+                for v in vs {
+                    acc.extend(v.finite_strings()?);
+                }
+                Some(acc)
+            }
+            TplLitTypeItem::String
+            | TplLitTypeItem::Number
+            | TplLitTypeItem::BigInt
+            | TplLitTypeItem::Boolean
+            | TplLitTypeItem::IntrinsicString(_) => None,
+        }
+    }
+
+    /// Applies the intrinsic to the strings matched by this item.
+    /// Capitalize and Uncapitalize only look at the first character, see `TplLitType::apply_intrinsic`.
+    pub fn apply_intrinsic(&self, intrinsic: StringIntrinsic) -> TplLitTypeItem {
+        match self {
+            TplLitTypeItem::StringConst(s) => TplLitTypeItem::StringConst(intrinsic.apply(s)),
+            TplLitTypeItem::OneOf(vs) => {
+                TplLitTypeItem::one_of(vs.iter().map(|it| it.apply_intrinsic(intrinsic)).collect())
+            }
+            TplLitTypeItem::Boolean => TplLitTypeItem::one_of(vec![
+                TplLitTypeItem::StringConst(intrinsic.apply("true")),
+                TplLitTypeItem::StringConst(intrinsic.apply("false")),
+            ]),
+            // like tsc, numbers and bigints are kept as they are
+            TplLitTypeItem::Number => TplLitTypeItem::Number,
+            TplLitTypeItem::BigInt => TplLitTypeItem::BigInt,
+            TplLitTypeItem::String => TplLitTypeItem::IntrinsicString(intrinsic),
+            TplLitTypeItem::IntrinsicString(prev) => match (prev, intrinsic) {
+                // an uppercase string is already capitalized, a lowercase one is already uncapitalized
+                (StringIntrinsic::Uppercase, StringIntrinsic::Capitalize)
+                | (StringIntrinsic::Lowercase, StringIntrinsic::Uncapitalize) => self.clone(),
+                _ => TplLitTypeItem::IntrinsicString(intrinsic),
+            },
+        }
+    }
+
     pub fn regex_expr(&self) -> String {
         match self {
             TplLitTypeItem::String => "(.*)".to_string(),
-            TplLitTypeItem::Number => r"(\d+(\.\d+)?)".to_string(),
+            // only ASCII letters are checked at runtime, while tsc and `matches` use the
+            // Unicode case mappings, so `Uppercase<string>` accepts "é"
+            TplLitTypeItem::IntrinsicString(intrinsic) => match intrinsic {
+                StringIntrinsic::Uppercase => "([^a-z]*)".to_string(),
+                StringIntrinsic::Lowercase => "([^A-Z]*)".to_string(),
+                StringIntrinsic::Capitalize => "([^a-z].*)?".to_string(),
+                StringIntrinsic::Uncapitalize => "([^A-Z].*)?".to_string(),
+            },
+            // the strings `${number}` accepts in tsc, e.g. `-1.5e3`, `.5`, `Infinity` or `0x1f`
+            TplLitTypeItem::Number => {
+                r"([+-]?(\d+(\.\d*)?|\.\d+)([eE][+-]?\d+)?|[+-]?Infinity|0[xX][0-9a-fA-F]+|0[oO][0-7]+|0[bB][01]+)"
+                    .to_string()
+            }
+            TplLitTypeItem::BigInt => r"(-?\d+|0[xX][0-9a-fA-F]+|0[oO][0-7]+|0[bB][01]+)".to_string(),
             TplLitTypeItem::Boolean => "(true|false)".to_string(),
             TplLitTypeItem::OneOf(vs) => {
                 let mut vs = vs.iter().collect::<Vec<_>>();
@@ -156,6 +263,101 @@ impl TplLitTypeItem {
 #[derive(PartialEq, Eq, Hash, Debug, Ord, PartialOrd, Clone)]
 pub struct CustomFormat(pub String, pub Vec<String>);
 
+#[derive(PartialEq, Eq, Hash, Debug, Ord, PartialOrd, Clone)]
+pub struct NumberBound {
+    pub value: N,
+    pub exclusive: bool,
+}
+
+/// The numbers between two optional bounds, only the integers when `integer` is set.
+/// Written `Int`, `NonNegative` or `Range<Min, Max>`, where `number` leaves a side unbounded.
+#[derive(PartialEq, Eq, Hash, Debug, Ord, PartialOrd, Clone)]
+pub struct NumberRange {
+    pub integer: bool,
+    pub minimum: Option<NumberBound>,
+    pub maximum: Option<NumberBound>,
+}
+
+impl NumberRange {
+    /// The numbers in both ranges, `None` when there are none.
+    pub fn intersect(&self, other: &NumberRange) -> Option<NumberRange> {
+        // the tighter bound, or the exclusive one when they have the same value
+        fn tighter(
+            a: &Option<NumberBound>,
+            b: &Option<NumberBound>,
+            is_tighter: fn(f64, f64) -> bool,
+        ) -> Option<NumberBound> {
+            match (a, b) {
+                (Some(a), Some(b)) => {
+                    let (x, y) = (a.value.to_f64(), b.value.to_f64());
+                    let keep_a = is_tighter(x, y) || (x == y && a.exclusive);
+                    Some(if keep_a { a.clone() } else { b.clone() })
+                }
+                (Some(it), None) | (None, Some(it)) => Some(it.clone()),
+                (None, None) => None,
+            }
+        }
+        let minimum = tighter(&self.minimum, &other.minimum, |a, b| a > b);
+        let maximum = tighter(&self.maximum, &other.maximum, |a, b| a < b);
+        if let (Some(min), Some(max)) = (&minimum, &maximum) {
+            let (lo, hi) = (min.value.to_f64(), max.value.to_f64());
+            if lo > hi || (lo == hi && (min.exclusive || max.exclusive)) {
+                return None;
+            }
+        }
+        Some(NumberRange {
+            integer: self.integer || other.integer,
+            minimum,
+            maximum,
+        })
+    }
+
+    pub fn contains(&self, n: f64) -> bool {
+        if n.is_nan() || (self.integer && !(n.is_finite() && n.fract() == 0.0)) {
+            return false;
+        }
+        let above_minimum = self.minimum.as_ref().is_none_or(|it| {
+            let min = it.value.to_f64();
+            if it.exclusive { n > min } else { n >= min }
+        });
+        let below_maximum = self.maximum.as_ref().is_none_or(|it| {
+            let max = it.value.to_f64();
+            if it.exclusive { n < max } else { n <= max }
+        });
+        above_minimum && below_maximum
+    }
+
+    pub fn describe(&self) -> String {
+        let bound = |it: &Option<NumberBound>| match it {
+            Some(it) => it.value.to_f64().to_string(),
+            None => "number".to_string(),
+        };
+        let is_non_negative = self.maximum.is_none()
+            && self
+                .minimum
+                .as_ref()
+                .is_some_and(|it| it.value.to_f64() == 0.0);
+        let range = format!("Range<{}, {}>", bound(&self.minimum), bound(&self.maximum));
+        let mut acc = match (
+            self.integer,
+            self.minimum.is_some() || self.maximum.is_some(),
+        ) {
+            (true, false) => "Int".to_string(),
+            (false, false) => "number".to_string(),
+            (false, true) if is_non_negative => "NonNegative".to_string(),
+            (false, true) => range,
+            (true, true) => format!("Int & {}", range),
+        };
+        // an exclusive bound is the inclusive one without its value
+        for it in [&self.minimum, &self.maximum].into_iter().flatten() {
+            if it.exclusive {
+                acc = format!("Exclude<{}, {}>", acc, it.value.to_f64());
+            }
+        }
+        acc
+    }
+}
+
 #[derive(PartialEq, Eq, Hash, Debug, Ord, PartialOrd, Clone)]
 pub struct TplLitType(pub Vec<TplLitTypeItem>);
 
@@ -173,7 +375,11 @@ impl TplLitType {
                     .map(|it| match it {
                         TplLitTypeItem::String => "${string}".to_string(),
                         TplLitTypeItem::Number => "${number}".to_string(),
+                        TplLitTypeItem::BigInt => "${bigint}".to_string(),
                         TplLitTypeItem::Boolean => "${boolean}".to_string(),
+                        TplLitTypeItem::IntrinsicString(intrinsic) => {
+                            format!("${{{}<string>}}", intrinsic.name())
+                        }
                         TplLitTypeItem::StringConst(v) => v.clone(),
                         TplLitTypeItem::OneOf(values) => {
                             let mut values = values.iter().collect::<Vec<_>>();
@@ -191,6 +397,114 @@ impl TplLitType {
             }
         }
     }
+    pub fn apply_intrinsic(&self, intrinsic: StringIntrinsic) -> TplLitType {
+        if intrinsic.applies_to_whole_string() {
+            return TplLitType(
+                self.0
+                    .iter()
+                    .map(|it| it.apply_intrinsic(intrinsic))
+                    .collect(),
+            );
+        }
+        // like tsc, only the first item that is not an empty string is changed
+        let mut items = self.0.clone();
+        if let Some(first) = items
+            .iter_mut()
+            .find(|it| !matches!(it, TplLitTypeItem::StringConst(s) if s.is_empty()))
+        {
+            *first = first.apply_intrinsic(intrinsic);
+        }
+        TplLitType(items)
+    }
+
+    /// Whether the template matches finitely many strings.
+    pub fn is_finite(&self) -> bool {
+        self.0.iter().all(|it| it.finite_strings().is_some())
+    }
+    /// The strings matched by the template, if there are finitely many and at most `limit`.
+    pub fn finite_strings_up_to(&self, limit: usize) -> Option<Vec<String>> {
+        let mut acc = vec![String::new()];
+        for item in &self.0 {
+            let suffixes = item.finite_strings()?;
+            if acc.len() * suffixes.len() > limit {
+                return None;
+            }
+            acc = acc
+                .iter()
+                .flat_map(|prefix| suffixes.iter().map(move |s| format!("{}{}", prefix, s)))
+                .collect();
+        }
+        Some(acc)
+    }
+    /// Whether the string is matched by the template, agreeing with `regex_expr`.
+    pub fn matches(&self, s: &str) -> bool {
+        fn is_digits(s: &str, radix: u32) -> bool {
+            !s.is_empty() && s.chars().all(|c| c.is_digit(radix))
+        }
+        fn is_radix_integer(s: &str) -> bool {
+            [
+                ("0x", 16),
+                ("0X", 16),
+                ("0o", 8),
+                ("0O", 8),
+                ("0b", 2),
+                ("0B", 2),
+            ]
+            .iter()
+            .any(|(prefix, radix)| {
+                s.strip_prefix(prefix)
+                    .is_some_and(|it| is_digits(it, *radix))
+            })
+        }
+        fn is_number(s: &str) -> bool {
+            if is_radix_integer(s) {
+                return true;
+            }
+            let unsigned = s.strip_prefix(['+', '-']).unwrap_or(s);
+            if unsigned == "Infinity" {
+                return true;
+            }
+            let (mantissa, exponent) = match unsigned.split_once(['e', 'E']) {
+                Some((mantissa, exponent)) => (mantissa, Some(exponent)),
+                None => (unsigned, None),
+            };
+            let exponent_ok =
+                exponent.is_none_or(|it| is_digits(it.strip_prefix(['+', '-']).unwrap_or(it), 10));
+            let mantissa_ok = match mantissa.split_once('.') {
+                None => is_digits(mantissa, 10),
+                Some((int, frac)) => {
+                    (is_digits(int, 10) && frac.chars().all(|c| c.is_ascii_digit()))
+                        || (int.is_empty() && is_digits(frac, 10))
+                }
+            };
+            mantissa_ok && exponent_ok
+        }
+        fn is_bigint(s: &str) -> bool {
+            is_radix_integer(s) || is_digits(s.strip_prefix('-').unwrap_or(s), 10)
+        }
+        fn item_matches(item: &TplLitTypeItem, s: &str) -> bool {
+            match item {
+                TplLitTypeItem::String => true,
+                TplLitTypeItem::Number => is_number(s),
+                TplLitTypeItem::BigInt => is_bigint(s),
+                TplLitTypeItem::Boolean => s == "true" || s == "false",
+                TplLitTypeItem::StringConst(c) => s == c,
+                TplLitTypeItem::OneOf(vs) => vs.iter().any(|it| item_matches(it, s)),
+                TplLitTypeItem::IntrinsicString(intrinsic) => intrinsic.apply(s) == s,
+            }
+        }
+        fn go(items: &[TplLitTypeItem], s: &str) -> bool {
+            match items.split_first() {
+                None => s.is_empty(),
+                Some((first, rest)) => s
+                    .char_indices()
+                    .map(|(i, _)| i)
+                    .chain(std::iter::once(s.len()))
+                    .any(|i| item_matches(first, &s[..i]) && go(rest, &s[i..])),
+            }
+        }
+        go(&self.0, s)
+    }
     pub fn regex_expr(&self) -> String {
         let mut regex_exp = String::new();
 
@@ -199,6 +513,10 @@ impl TplLitType {
         }
         regex_exp
     }
+    /// `regex_expr` matching the whole string, for validators that `test` it.
+    pub fn anchored_regex_expr(&self) -> String {
+        format!("^{}$", self.regex_expr())
+    }
 }
 
 #[derive(PartialEq, Eq, Hash, Debug, Ord, PartialOrd, Clone, Copy)]
@@ -253,6 +571,39 @@ impl TypedArrayKind {
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
 pub struct RuntypeMetadata {
     pub description: Option<String>,
+    // name of a tuple element, `x` in `[x: number]`
+    pub label: Option<String>,
+    // boxed, most runtypes have no keywords
+    pub keywords: Option<Box<SchemaKeywords>>,
+}
+
+/// JSON Schema keywords read from the JSDoc tags of a declaration.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct SchemaKeywords {
+    pub min_length: Option<usize>,
+    pub max_length: Option<usize>,
+    pub pattern: Option<String>,
+    pub minimum: Option<N>,
+    pub maximum: Option<N>,
+    pub min_items: Option<usize>,
+    pub max_items: Option<usize>,
+    pub format: Option<String>,
+    pub default: Option<Json>,
+    pub deprecated: bool,
+    pub examples: Vec<Json>,
+}
+
+impl SchemaKeywords {
+    /// Whether validation has to check any keyword, on top of the type itself.
+    pub fn has_constraints(&self) -> bool {
+        self.min_length.is_some()
+            || self.max_length.is_some()
+            || self.pattern.is_some()
+            || self.minimum.is_some()
+            || self.maximum.is_some()
+            || self.min_items.is_some()
+            || self.max_items.is_some()
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -305,15 +656,19 @@ pub enum RuntypeKind {
     AnyArrayLike,
     StringWithFormat(CustomFormat),
     NumberWithFormat(CustomFormat),
+    NumberRange(NumberRange),
     TplLitType(TplLitType),
     Object {
         vs: BTreeMap<String, Optionality<Runtype>>,
-        indexed_properties: Option<Box<IndexedProperty>>,
+        indexed_properties: Vec<IndexedProperty>,
     },
     Array(Box<Runtype>),
+    // `[...prefix_items, ...optional_items?, ...items[], ...suffix_items]`
     Tuple {
         prefix_items: Vec<Runtype>,
+        optional_items: Vec<Runtype>,
         items: Option<Box<Runtype>>,
+        suffix_items: Vec<Runtype>,
     },
     Ref(RuntypeUUID),
 
@@ -323,19 +678,32 @@ pub enum RuntypeKind {
     // semantic types
     Never,
     StNot(Box<Runtype>),
-    Function,
+    Function {
+        params: Box<Runtype>,
+        return_type: Box<Runtype>,
+        is_constructor: bool,
+    },
+    Promise(Box<Runtype>),
     Date,
     BigInt,
     TypedArray(TypedArrayKind),
+    // instances of a user-defined class, checked with `instanceof`
+    InstanceOf(TypeAddress),
     Map(Box<Runtype>, Box<Runtype>),
     Set(Box<Runtype>),
+    // type parameter of a generic parser factory, bound when the factory is called
+    TypeParameter(String),
 }
 
 impl Runtype {
     pub const fn new(kind: RuntypeKind) -> Self {
         Self {
             kind,
-            metadata: RuntypeMetadata { description: None },
+            metadata: RuntypeMetadata {
+                description: None,
+                label: None,
+                keywords: None,
+            },
         }
     }
 
@@ -344,6 +712,11 @@ impl Runtype {
         self
     }
 
+    pub fn with_metadata(mut self, metadata: RuntypeMetadata) -> Self {
+        self.metadata = metadata;
+        self
+    }
+
     pub const fn null() -> Self {
         Self::new(RuntypeKind::Null)
     }
@@ -371,8 +744,22 @@ impl Runtype {
     pub const fn never() -> Self {
         Self::new(RuntypeKind::Never)
     }
-    pub const fn function() -> Self {
-        Self::new(RuntypeKind::Function)
+    pub fn function() -> Self {
+        Self::function_signature(
+            Runtype::tuple(vec![], Some(Runtype::any().into())),
+            Runtype::any(),
+            false,
+        )
+    }
+    pub fn function_signature(params: Runtype, return_type: Runtype, is_constructor: bool) -> Self {
+        Self::new(RuntypeKind::Function {
+            params: params.into(),
+            return_type: return_type.into(),
+            is_constructor,
+        })
+    }
+    pub fn promise(value: Box<Runtype>) -> Self {
+        Self::new(RuntypeKind::Promise(value))
     }
     pub const fn date() -> Self {
         Self::new(RuntypeKind::Date)
@@ -386,6 +773,9 @@ impl Runtype {
     pub fn number_with_format(format: CustomFormat) -> Self {
         Self::new(RuntypeKind::NumberWithFormat(format))
     }
+    pub fn number_range(range: NumberRange) -> Self {
+        Self::new(RuntypeKind::NumberRange(range))
+    }
     pub fn tpl_lit_type(tpl: TplLitType) -> Self {
         Self::new(RuntypeKind::TplLitType(tpl))
     }
@@ -393,11 +783,27 @@ impl Runtype {
         Self::new(RuntypeKind::Array(item))
     }
     pub fn tuple(prefix_items: Vec<Runtype>, items: Option<Box<Runtype>>) -> Self {
+        Self::variadic_tuple(prefix_items, vec![], items, vec![])
+    }
+    /// Elements after the rest are only allowed when there is a rest.
+    pub fn variadic_tuple(
+        prefix_items: Vec<Runtype>,
+        optional_items: Vec<Runtype>,
+        items: Option<Box<Runtype>>,
+        suffix_items: Vec<Runtype>,
+    ) -> Self {
+        debug_assert!(items.is_some() || suffix_items.is_empty());
         Self::new(RuntypeKind::Tuple {
             prefix_items,
+            optional_items,
             items,
+            suffix_items,
         })
     }
+    pub fn with_label(mut self, label: String) -> Self {
+        self.metadata.label = Some(label);
+        self
+    }
     pub fn ref_(name: RuntypeUUID) -> Self {
         Self::new(RuntypeKind::Ref(name))
     }
@@ -410,29 +816,17 @@ impl Runtype {
     pub fn typed_array(kind: TypedArrayKind) -> Self {
         Self::new(RuntypeKind::TypedArray(kind))
     }
+    pub fn instance_of(class: TypeAddress) -> Self {
+        Self::new(RuntypeKind::InstanceOf(class))
+    }
     pub fn map(key: Box<Runtype>, value: Box<Runtype>) -> Self {
         Self::new(RuntypeKind::Map(key, value))
     }
     pub fn set(value: Box<Runtype>) -> Self {
         Self::new(RuntypeKind::Set(value))
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn runtype_metadata_does_not_affect_semantic_identity() {
-        let left = Runtype::string().with_description("left".to_string());
-        let right = Runtype::string().with_description("right".to_string());
-
-        assert_eq!(left, right);
-
-        let mut set = BTreeSet::new();
-        set.insert(left);
-        set.insert(right);
-        assert_eq!(set.len(), 1);
+    pub fn type_parameter(name: String) -> Self {
+        Self::new(RuntypeKind::TypeParameter(name))
     }
 }
 
@@ -467,6 +861,66 @@ pub struct DebugPrintCtx<'a> {
     pub type_with_args_names: &'a mut BTreeMap<RuntypeUUID, String>,
 }
 impl Runtype {
+    /// The runtypes directly nested in this one, references are not followed.
+    pub fn children(&self) -> Vec<&Runtype> {
+        match &self.kind {
+            RuntypeKind::Null
+            | RuntypeKind::Undefined
+            | RuntypeKind::Void
+            | RuntypeKind::Boolean
+            | RuntypeKind::String
+            | RuntypeKind::Number
+            | RuntypeKind::Any
+            | RuntypeKind::AnyArrayLike
+            | RuntypeKind::StringWithFormat(_)
+            | RuntypeKind::NumberWithFormat(_)
+            | RuntypeKind::NumberRange(_)
+            | RuntypeKind::TplLitType(_)
+            | RuntypeKind::Ref(_)
+            | RuntypeKind::Const(_)
+            | RuntypeKind::Never
+            | RuntypeKind::Date
+            | RuntypeKind::BigInt
+            | RuntypeKind::TypedArray(_)
+            | RuntypeKind::InstanceOf(_)
+            | RuntypeKind::TypeParameter(_) => vec![],
+            RuntypeKind::Object {
+                vs,
+                indexed_properties,
+            } => vs
+                .values()
+                .map(|it| it.inner())
+                .chain(
+                    indexed_properties
+                        .iter()
+                        .flat_map(|it| [&it.key, it.value.inner()]),
+                )
+                .collect(),
+            RuntypeKind::Array(it)
+            | RuntypeKind::StNot(it)
+            | RuntypeKind::Promise(it)
+            | RuntypeKind::Set(it) => vec![it],
+            RuntypeKind::Tuple {
+                prefix_items,
+                optional_items,
+                items,
+                suffix_items,
+            } => prefix_items
+                .iter()
+                .chain(optional_items)
+                .chain(items.as_deref())
+                .chain(suffix_items)
+                .collect(),
+            RuntypeKind::AnyOf(vs) | RuntypeKind::AllOf(vs) => vs.iter().collect(),
+            RuntypeKind::Function {
+                params,
+                return_type,
+                ..
+            } => vec![params, return_type],
+            RuntypeKind::Map(k, v) => vec![k, v],
+        }
+    }
+
     pub fn as_string_const(&self) -> Option<&str> {
         match &self.kind {
             RuntypeKind::TplLitType(TplLitType(items)) => match items.as_slice() {
@@ -479,13 +933,13 @@ impl Runtype {
     pub fn object(vs: Vec<(String, Optionality<Runtype>)>) -> Self {
         Self::new(RuntypeKind::Object {
             vs: vs.into_iter().collect(),
-            indexed_properties: None,
+            indexed_properties: vec![],
         })
     }
     pub fn record(key: Runtype, value: Optionality<Runtype>) -> Self {
         Self::new(RuntypeKind::Object {
             vs: BTreeMap::new(),
-            indexed_properties: Some(Box::new(IndexedProperty { key, value })),
+            indexed_properties: vec![IndexedProperty { key, value }],
         })
     }
     pub fn any_object() -> Runtype {
@@ -533,6 +987,25 @@ impl Runtype {
                 .into_iter()
                 .next()
                 .expect("we just checked len"),
+            _ if all_of_items
+                .iter()
+                .all(|it| matches!(it.kind, RuntypeKind::NumberRange(_))) =>
+            {
+                // `Int & NonNegative` is a single range
+                let mut acc: Option<NumberRange> = None;
+                for it in &all_of_items {
+                    if let RuntypeKind::NumberRange(range) = &it.kind {
+                        acc = match acc {
+                            None => Some(range.clone()),
+                            Some(acc) => match acc.intersect(range) {
+                                Some(it) => Some(it),
+                                None => return Runtype::never(),
+                            },
+                        };
+                    }
+                }
+                Runtype::number_range(acc.expect("there are at least two ranges"))
+            }
             _ => {
                 let mut obj_kvs: Vec<(String, Optionality<Runtype>)> = vec![];
                 let mut all_objects = true;
@@ -544,7 +1017,7 @@ impl Runtype {
                             vs,
                             indexed_properties,
                         } => {
-                            if !indexed_properties.is_none() {
+                            if !indexed_properties.is_empty() {
                                 rest_is_empty = false;
                                 break;
                             }
@@ -656,17 +1129,33 @@ impl Runtype {
 
                 acc
             }
+            RuntypeKind::NumberRange(range) => range.describe(),
             RuntypeKind::TplLitType(tpl_lit_type_items) => tpl_lit_type_items.describe(),
             RuntypeKind::Const(runtype_const) => runtype_const.clone().to_json().debug_print(),
             RuntypeKind::Date => "Date".to_string(),
             RuntypeKind::BigInt => "bigint".to_string(),
             RuntypeKind::TypedArray(kind) => kind.js_name().to_string(),
+            RuntypeKind::InstanceOf(class) => format!("InstanceOf<{}>", class.name),
+            RuntypeKind::TypeParameter(name) => name.clone(),
             RuntypeKind::Never => "never".to_string(),
             RuntypeKind::StNot(runtype) => {
                 let inner = runtype.debug_print(ctx);
                 format!("Not<{}>", inner)
             }
-            RuntypeKind::Function => "Function".to_string(),
+            RuntypeKind::Function {
+                params,
+                return_type,
+                is_constructor,
+            } => {
+                let params = params.debug_print(ctx);
+                let return_type = return_type.debug_print(ctx);
+                let new = if *is_constructor { "new " } else { "" };
+                format!("({}(...args: {}) => {})", new, params, return_type)
+            }
+            RuntypeKind::Promise(runtype) => {
+                let inner = runtype.debug_print(ctx);
+                format!("Promise<{}>", inner)
+            }
             RuntypeKind::Ref(r) => r.debug_print(ctx),
             RuntypeKind::Array(runtype) => {
                 let inner = runtype.debug_print(ctx);
@@ -683,15 +1172,27 @@ impl Runtype {
             }
             RuntypeKind::Tuple {
                 prefix_items,
+                optional_items,
                 items,
+                suffix_items,
             } => {
+                let element = |it: &Runtype, optional: &str| match &it.metadata.label {
+                    Some(label) => format!("{label}{optional}: {}", it.debug_print(ctx)),
+                    None => format!("{}{optional}", it.debug_print(ctx)),
+                };
                 let mut acc = vec![];
 
                 for it in prefix_items.iter() {
-                    acc.push(it.debug_print(ctx));
+                    acc.push(element(it, ""));
+                }
+                for it in optional_items.iter() {
+                    acc.push(element(it, "?"));
                 }
                 if let Some(items) = items.as_ref() {
-                    acc.push(format!("...{}", items.debug_print(ctx)));
+                    acc.push(format!("...{}", element(items, "")));
+                }
+                for it in suffix_items.iter() {
+                    acc.push(element(it, ""));
                 }
 
                 let args = acc.join(", ");
@@ -743,3 +1244,52 @@ impl Runtype {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn runtype_metadata_does_not_affect_semantic_identity() {
+        let left = Runtype::string().with_description("left".to_string());
+        let right = Runtype::string().with_description("right".to_string());
+
+        assert_eq!(left, right);
+
+        let mut set = BTreeSet::new();
+        set.insert(left);
+        set.insert(right);
+        assert_eq!(set.len(), 1);
+    }
+
+    #[test]
+    fn tpl_lit_number_and_bigint_matches() {
+        let number = TplLitType(vec![TplLitTypeItem::Number]);
+        for ok in [
+            "1",
+            "-1.5",
+            "+2",
+            ".5",
+            "1.",
+            "1e10",
+            "2.5E-3",
+            "Infinity",
+            "-Infinity",
+            "0x1F",
+            "0o17",
+            "0b10",
+        ] {
+            assert!(number.matches(ok), "{ok}");
+        }
+        for bad in ["", ".", "e5", "1e", "NaN", "0x", "1_000", "--1"] {
+            assert!(!number.matches(bad), "{bad}");
+        }
+        let bigint = TplLitType(vec![TplLitTypeItem::BigInt]);
+        for ok in ["0", "-12", "0xff", "0b1"] {
+            assert!(bigint.matches(ok), "{ok}");
+        }
+        for bad in ["1.5", "+1", "1e3", "Infinity"] {
+            assert!(!bigint.matches(bad), "{bad}");
+        }
+    }
+}
@@ -11,6 +11,11 @@ pub enum DiagnosticInfoMessage {
     CannotUseInterfaceInValuePosition,
     ExpressionIsNotAType,
     TupleRestTypeMustBeArray,
+    TupleRequiredElementAfterOptional,
+    TupleOptionalElementAfterRest,
+    InterfaceDeclarationsTypeParamsMismatch(String),
+    InterfacePropertyRedeclaredWithDifferentType(String),
+    InterfacePropertyNotAssignableToBase(String),
     CannotUseStarImportInValuePosition,
     CannotUseStarImportInTypePosition,
     CannotUseTypeInQualifiedTypePosition,
@@ -46,8 +51,27 @@ pub enum DiagnosticInfoMessage {
     TypeOfTsBuiltinNotSupported,
     TypeofTsEnumNotSupported,
     TplLitTypeNonStringNonNumberNonBoolean,
-    NestedTplLitToTplLit,
+    TplLitTypeTooComplex,
     ExcludeShouldHaveTwoTypeArguments,
+    ExtractShouldHaveTwoTypeArguments,
+    NonNullableShouldHaveOneTypeArgument,
+    StringIntrinsicShouldHaveOneTypeArgument(String),
+    StringIntrinsicOnNonString(String),
+    FunctionUtilityShouldHaveOneTypeArgument(String),
+    FunctionUtilityOnNonFunction(String),
+    PromiseShouldHaveOneTypeArgument,
+    AwaitedShouldHaveOneTypeArgument,
+    RestParameterShouldBeArray,
+    CannotUseClassInQualifiedTypePosition,
+    InstanceOfShouldHaveOneTypeArgument,
+    InstanceOfShouldReferToClass,
+    InstanceOfClassNameCollision(String),
+    RangeShouldHaveTwoTypeArguments,
+    RangeBoundShouldBeNumberLiteral,
+    RangeMinimumAboveMaximum,
+    JsdocTagShouldBeNonNegativeInteger(String),
+    JsdocTagShouldBeNumber(String),
+    JsdocTagShouldHaveValue(String),
     MissingArgumentsOnExclude,
     PartialShouldHaveOneTypeArgument,
     CannotUseExprDeclAsQualified,
@@ -78,7 +102,6 @@ pub enum DiagnosticInfoMessage {
     OmitShouldHaveStringAsTypeArgument,
     OmitShouldHaveObjectAsTypeArgument,
     IndexSignatureNonSerializable,
-    MultipleIndexSignaturesNotSupported,
     AnyhowError(String),
     CannotResolveKey(String),
     CannotNotFindSomethingOfOtherFile(String),
@@ -124,6 +147,14 @@ pub enum DiagnosticInfoMessage {
     GenericDecoderIsNotSupported,
     InvalidDecoderKey,
     InvalidDecoderProperty,
+    TwoCallsToBuildRoutes,
+    RoutesShouldBeObjectWithRouteKeys,
+    InvalidRouteKey(String),
+    InvalidRouteProperty,
+    InvalidRoutesInfo,
+    ParserFactoryParamShouldBeParserOfTypeParam,
+    ParserFactoryShouldReturnParser,
+    ParserFactoryTypeParamIsNotAParam(String),
     KeywordNonSerializable,
     PropertyNonSerializable,
     MissingArgumentsOnRecord,
@@ -134,6 +165,7 @@ pub enum DiagnosticInfoMessage {
     ThisTypeNonSerializable,
     TsConditionalTypeNonSerializable,
     TsInferTypeNonSerializable,
+    InferTypeInUnsupportedPosition,
     TsTypePredicateNonSerializable,
     TsImportTypeNonSerializable,
     OptionalTypeIsNotSupported,
@@ -148,7 +180,6 @@ pub enum DiagnosticInfoMessage {
     NonStringKeyInMappedType,
     NoTypeAnnotationInMappedType,
     CannotConvertExpr,
-    MappedTypeMinusNotSupported,
     CannotNotResolveType(ModuleItemAddress),
     CannotNotResolveValue(ModuleItemAddress),
     CannotNotFindFile(BffFileName),
@@ -170,7 +201,7 @@ impl DiagnosticInfoMessage {
                 "This property cannot be extracted".to_string()
             }
             DiagnosticInfoMessage::DuplicatedRestNonSerializable => {
-                "This rest parameter cannot be extracted".to_string()
+                "A tuple cannot have more than one rest element".to_string()
             }
             DiagnosticInfoMessage::ThisRefersToSomethingThatCannotBeSerialized(this) => {
                 format!("`{this}` cannot be extracted")
@@ -192,7 +223,7 @@ impl DiagnosticInfoMessage {
                 format!("Failed to resolve type reference '{name}' when extracting")
             }
             DiagnosticInfoMessage::TwoDifferentTypesWithTheSameName(name) => {
-                let name= name.diag_print();
+                let name = name.diag_print();
                 format!("This includes two different types with the same name '{name}'")
             }
             DiagnosticInfoMessage::InvalidIdentifierInPatternNoExplodeAllowed => {
@@ -252,6 +283,31 @@ impl DiagnosticInfoMessage {
             }
             DiagnosticInfoMessage::InvalidDecoderKey => "Invalid decoder key".to_string(),
             DiagnosticInfoMessage::InvalidDecoderProperty => "Invalid decoder property".to_string(),
+            DiagnosticInfoMessage::TwoCallsToBuildRoutes => {
+                "buildRoutes can only be called once".to_string()
+            }
+            DiagnosticInfoMessage::RoutesShouldBeObjectWithRouteKeys => {
+                "Routes should be an object type with routes as keys".to_string()
+            }
+            DiagnosticInfoMessage::InvalidRouteKey(key) => {
+                format!(
+                    "Route key '{key}' should be a method and a path, like \"GET /users/{{id}}\""
+                )
+            }
+            DiagnosticInfoMessage::InvalidRouteProperty => "Invalid route property".to_string(),
+            DiagnosticInfoMessage::InvalidRoutesInfo => {
+                "The argument of buildRoutes should be an object of string literals".to_string()
+            }
+            DiagnosticInfoMessage::ParserFactoryParamShouldBeParserOfTypeParam => {
+                "Parser factory parameters should be a BeffParser of one of its type parameters"
+                    .to_string()
+            }
+            DiagnosticInfoMessage::ParserFactoryShouldReturnParser => {
+                "Parser factory should return a BeffParser".to_string()
+            }
+            DiagnosticInfoMessage::ParserFactoryTypeParamIsNotAParam(name) => {
+                format!("Type parameter {name} should be the type of a parser factory parameter")
+            }
             DiagnosticInfoMessage::GetMustNotHaveBody => {
                 "GET methods must not have a body".to_string()
             }
@@ -268,7 +324,7 @@ impl DiagnosticInfoMessage {
                 "Found value, expected type".to_string()
             }
             DiagnosticInfoMessage::TypeQueryArgsNotSupported => {
-                "Type query args are not supported".to_string()
+                "Type arguments can only instantiate a generic function".to_string()
             }
             DiagnosticInfoMessage::InvalidIndexedAccess => "Invalid indexed access".to_string(),
             DiagnosticInfoMessage::CannotGetQualifiedTypeFromFileRec(s) => {
@@ -289,7 +345,9 @@ impl DiagnosticInfoMessage {
             DiagnosticInfoMessage::CannotResolveKey(key) => {
                 format!("Cannot resolve key '{key}' of non-object")
             }
-            DiagnosticInfoMessage::EnumMemberNoInit => "Enum must have initializer".to_string(),
+            DiagnosticInfoMessage::EnumMemberNoInit => {
+                "Enum member must have an initializer".to_string()
+            }
             DiagnosticInfoMessage::CannotUseTsEnumAsQualified => {
                 "Cannot use TS enum as qualified".to_string()
             }
@@ -330,7 +388,7 @@ impl DiagnosticInfoMessage {
                 "Type arguments in extends are not supported".to_string()
             }
             DiagnosticInfoMessage::ExtendsShouldBeIdent => {
-                "Extends should be an identifier".to_string()
+                "Extends should be an identifier or a qualified name".to_string()
             }
             DiagnosticInfoMessage::MissingArgumentsOnOmit => {
                 "Missing arguments on omit".to_string()
@@ -371,6 +429,9 @@ impl DiagnosticInfoMessage {
             DiagnosticInfoMessage::TsInferTypeNonSerializable => {
                 "Infer type cannot be extracted".to_string()
             }
+            DiagnosticInfoMessage::InferTypeInUnsupportedPosition => {
+                "Cannot infer a type from this position of the extends clause".to_string()
+            }
             DiagnosticInfoMessage::TsTypePredicateNonSerializable => {
                 "Type predicate cannot be extracted".to_string()
             }
@@ -386,14 +447,9 @@ impl DiagnosticInfoMessage {
             DiagnosticInfoMessage::NoTypeAnnotationInMappedType => {
                 "No type annotation in mapped type".to_string()
             }
-            DiagnosticInfoMessage::CannotConvertExpr => {
-                "Cannot convert expression".to_string()
-            }
-            DiagnosticInfoMessage::MappedTypeMinusNotSupported => {
-                "Mapped type minus is not supported".to_string()
-            }
+            DiagnosticInfoMessage::CannotConvertExpr => "Cannot convert expression".to_string(),
             DiagnosticInfoMessage::CannotResolveRefInExtractUnion(r) => {
-                let name =r.diag_print();
+                let name = r.diag_print();
                 format!("Cannot resolve ref '{name}' in extract union")
             }
             DiagnosticInfoMessage::ShouldHaveObjectAsTypeArgument => {
@@ -429,8 +485,72 @@ impl DiagnosticInfoMessage {
             DiagnosticInfoMessage::ExcludeShouldHaveTwoTypeArguments => {
                 "Exclude should have two type arguments".to_string()
             }
+            DiagnosticInfoMessage::ExtractShouldHaveTwoTypeArguments => {
+                "Extract should have two type arguments".to_string()
+            }
+            DiagnosticInfoMessage::NonNullableShouldHaveOneTypeArgument => {
+                "NonNullable should have one type argument".to_string()
+            }
+            DiagnosticInfoMessage::StringIntrinsicShouldHaveOneTypeArgument(name) => {
+                format!("{name} should have one type argument")
+            }
+            DiagnosticInfoMessage::StringIntrinsicOnNonString(name) => {
+                format!("{name} can only be applied to string types")
+            }
+            DiagnosticInfoMessage::FunctionUtilityShouldHaveOneTypeArgument(name) => {
+                format!("{name} should have one type argument")
+            }
+            DiagnosticInfoMessage::FunctionUtilityOnNonFunction(name) => {
+                format!("{name} can only be applied to function types")
+            }
+            DiagnosticInfoMessage::PromiseShouldHaveOneTypeArgument => {
+                "Promise should have one type argument".to_string()
+            }
+            DiagnosticInfoMessage::AwaitedShouldHaveOneTypeArgument => {
+                "Awaited should have one type argument".to_string()
+            }
+            DiagnosticInfoMessage::CannotUseClassInQualifiedTypePosition => {
+                "Cannot use class in qualified type position".to_string()
+            }
+            DiagnosticInfoMessage::InstanceOfShouldHaveOneTypeArgument => {
+                "InstanceOf should have one type argument".to_string()
+            }
+            DiagnosticInfoMessage::InstanceOfShouldReferToClass => {
+                "InstanceOf can only be applied to a class".to_string()
+            }
+            DiagnosticInfoMessage::InstanceOfClassNameCollision(name) => {
+                format!(
+                    "Another class named '{name}' is used with InstanceOf, classes are passed to buildParsers by name"
+                )
+            }
+            DiagnosticInfoMessage::RangeShouldHaveTwoTypeArguments => {
+                "Range should have two type arguments".to_string()
+            }
+            DiagnosticInfoMessage::RangeBoundShouldBeNumberLiteral => {
+                "Range bounds must be number literals, or number for no bound".to_string()
+            }
+            DiagnosticInfoMessage::RangeMinimumAboveMaximum => {
+                "Range minimum must not be greater than its maximum".to_string()
+            }
+            DiagnosticInfoMessage::JsdocTagShouldBeNonNegativeInteger(tag) => {
+                format!("JSDoc tag @{tag} must be a non-negative integer")
+            }
+            DiagnosticInfoMessage::JsdocTagShouldBeNumber(tag) => {
+                format!("JSDoc tag @{tag} must be a number")
+            }
+            DiagnosticInfoMessage::JsdocTagShouldHaveValue(tag) => {
+                format!("JSDoc tag @{tag} must have a value")
+            }
+            DiagnosticInfoMessage::RestParameterShouldBeArray => {
+                "Rest parameter type must be an array or tuple".to_string()
+            }
             DiagnosticInfoMessage::TplLitTypeNonStringNonNumberNonBoolean => {
-                "Template literal type must be a string, number, or boolean".to_string()
+                "Template literal type must be a string, number, bigint, boolean, null or undefined"
+                    .to_string()
+            }
+            DiagnosticInfoMessage::TplLitTypeTooComplex => {
+                "Template literal type produces a union type that is too complex to represent"
+                    .to_string()
             }
             DiagnosticInfoMessage::TypeofTsEnumNotSupported => {
                 "typeof on TS enum is not supported".to_string()
@@ -513,10 +633,8 @@ impl DiagnosticInfoMessage {
                 "Record key union should only contain strings".to_string()
             }
             DiagnosticInfoMessage::CannotHaveRecursiveGenericTypes => {
-                "Cannot have recursive generic types".to_string()
-            }
-            DiagnosticInfoMessage::NestedTplLitToTplLit => {
-                "Nested template literal types are not supported when converting to template literal".to_string()
+                "Recursive generic types must refer to themselves with the same type arguments"
+                    .to_string()
             }
             DiagnosticInfoMessage::CannotNotResolveValue(module_item_address) => {
                 let name = module_item_address.diag_print();
@@ -525,7 +643,7 @@ impl DiagnosticInfoMessage {
             DiagnosticInfoMessage::CannotNotResolveType(module_item_address) => {
                 let name = module_item_address.diag_print();
                 format!("Cannot resolve type '{name}'")
-            },
+            }
             DiagnosticInfoMessage::CannotNotFindFile(file) => {
                 format!("Cannot find file '{file}'")
             }
@@ -559,9 +677,22 @@ impl DiagnosticInfoMessage {
             DiagnosticInfoMessage::TupleRestTypeMustBeArray => {
                 "Rest type in tuple must be an array type".to_string()
             }
-            DiagnosticInfoMessage::ExpressionIsNotAType => {
-                "Expression is not a type".to_string()
+            DiagnosticInfoMessage::TupleRequiredElementAfterOptional => {
+                "A required tuple element cannot follow an optional element".to_string()
+            }
+            DiagnosticInfoMessage::TupleOptionalElementAfterRest => {
+                "An optional tuple element cannot follow a rest element".to_string()
             }
+            DiagnosticInfoMessage::InterfaceDeclarationsTypeParamsMismatch(name) => {
+                format!("All declarations of '{name}' must have identical type parameters")
+            }
+            DiagnosticInfoMessage::InterfacePropertyRedeclaredWithDifferentType(key) => {
+                format!("Subsequent declarations of property '{key}' must have the same type")
+            }
+            DiagnosticInfoMessage::InterfacePropertyNotAssignableToBase(key) => {
+                format!("Property '{key}' is not assignable to the same property in the base type")
+            }
+            DiagnosticInfoMessage::ExpressionIsNotAType => "Expression is not a type".to_string(),
             DiagnosticInfoMessage::CannotUseTypeInValuePosition => {
                 "Cannot use type in value position".to_string()
             }
@@ -577,10 +708,7 @@ impl DiagnosticInfoMessage {
             DiagnosticInfoMessage::TypeArgumentCountMismatch => {
                 "Type argument count mismatch".to_string()
             }
-            DiagnosticInfoMessage::MultipleIndexSignaturesNotSupported => {
-                "Multiple index signatures are not supported".to_string()
-            }
-            DiagnosticInfoMessage::SetShouldHaveOneTypeArgument =>{
+            DiagnosticInfoMessage::SetShouldHaveOneTypeArgument => {
                 "Set should have one type argument".to_string()
             }
             DiagnosticInfoMessage::MapShouldHaveTwoTypeArguments => {
@@ -3,16 +3,21 @@ use crate::ast::runtype::TypedArrayKind;
 use crate::subtyping::ToSemType;
 use crate::subtyping::semtype::{SemType, SemTypeContext, SemTypeOps};
 use crate::subtyping::to_schema::semtype_to_runtypes;
+use crate::swc_tools::bind_locals::class_decl_to_expr;
 use crate::swc_tools::{SymbolExport, SymbolExportDefault};
 use crate::{Anchor, NamedSchema, RuntypeUUID, TsBuiltIn, TypeAddress};
 use crate::{
     BeffUserSettings, BffFileName, FileManager, ImportReference, ModuleItemAddress, ParsedModule,
-    RuntypeName, Visibility,
-    ast::runtype::{
-        CustomFormat, Optionality, Runtype, RuntypeConst, RuntypeKind, TplLitType, TplLitTypeItem,
+    RuntypeName, SourcePosition, Visibility,
+    ast::{
+        json::{Json, N},
+        runtype::{
+            CustomFormat, NumberBound, NumberRange, Optionality, Runtype, RuntypeConst,
+            RuntypeKind, SchemaKeywords, StringIntrinsic, TplLitType, TplLitTypeItem,
+        },
     },
     diag::{DiagnosticInfoMessage, DiagnosticInformation, Location},
-    parser_extractor::BuiltDecoder,
+    parser_extractor::{BuiltDecoder, BuiltRoute},
 };
 use anyhow::{Result, anyhow};
 use std::collections::{BTreeMap, BTreeSet};
@@ -20,19 +25,296 @@ use std::rc::Rc;
 use swc_common::comments::CommentKind;
 use swc_common::{Span, Spanned};
 use swc_ecma_ast::{
-    Expr, Lit, MemberProp, Prop, PropName, PropOrSpread, TruePlusMinus, TsArrayType,
-    TsCallSignatureDecl, TsConditionalType, TsConstructSignatureDecl, TsConstructorType,
-    TsEntityName, TsEnumDecl, TsEnumMemberId, TsExprWithTypeArgs, TsFnOrConstructorType, TsFnParam,
-    TsFnType, TsGetterSignature, TsImportType, TsIndexSignature, TsIndexedAccessType, TsInferType,
-    TsInterfaceDecl, TsIntersectionType, TsKeywordType, TsKeywordTypeKind, TsLit, TsLitType,
-    TsMappedType, TsMethodSignature, TsOptionalType, TsParenthesizedType, TsPropertySignature,
-    TsQualifiedName, TsRestType, TsSetterSignature, TsThisType, TsTplLitType, TsTupleType, TsType,
-    TsTypeAliasDecl, TsTypeElement, TsTypeLit, TsTypeOperator, TsTypeOperatorOp,
+    Accessibility, BinaryOp, Class, ClassDecl, ClassExpr, ClassMember, Expr, FnExpr, Lit,
+    MemberExpr, MemberProp, ParamOrTsParamProp, Pat, Prop, PropName, PropOrSpread, TruePlusMinus,
+    TsArrayType, TsCallSignatureDecl, TsConditionalType, TsConstructSignatureDecl,
+    TsConstructorType, TsEntityName, TsEnumDecl, TsEnumMemberId, TsExprWithTypeArgs,
+    TsFnOrConstructorType, TsFnParam, TsFnType, TsGetterSignature, TsImportType, TsIndexSignature,
+    TsIndexedAccessType, TsInferType, TsInterfaceDecl, TsIntersectionType, TsKeywordType,
+    TsKeywordTypeKind, TsLit, TsLitType, TsMappedType, TsMethodSignature, TsOptionalType,
+    TsParamPropParam, TsParenthesizedType, TsPropertySignature, TsQualifiedName, TsRestType,
+    TsSetterSignature, TsThisType, TsTplLitType, TsTupleType, TsType, TsTypeAliasDecl, TsTypeAnn,
+    TsTypeElement, TsTypeLit, TsTypeOperator, TsTypeOperatorOp, TsTypeParam, TsTypeParamDecl,
     TsTypeParamInstantiation, TsTypePredicate, TsTypeQuery, TsTypeQueryExpr, TsTypeRef,
-    TsUnionOrIntersectionType, TsUnionType,
+    TsUnionOrIntersectionType, TsUnionType, UnaryOp,
 };
+use swc_ecma_visit::{Visit, VisitWith};
 type Res<T> = Result<T, Box<DiagnosticInformation>>;
 
+fn enum_member_name(id: &TsEnumMemberId) -> String {
+    match id {
+        TsEnumMemberId::Ident(i) => i.sym.to_string(),
+        TsEnumMemberId::Str(s) => s.value.to_string_lossy().to_string(),
+    }
+}
+
+// value of an enum member that could be computed at compile time
+#[derive(Debug, Clone)]
+enum EnumConst {
+    Number(f64),
+    String(String),
+}
+
+impl EnumConst {
+    fn from_runtype(it: &Runtype) -> Option<Self> {
+        match &it.kind {
+            RuntypeKind::Const(RuntypeConst::Number(n)) => Some(EnumConst::Number(n.to_f64())),
+            _ => it.extract_single_string_const().map(EnumConst::String),
+        }
+    }
+    fn to_runtype(&self) -> Runtype {
+        match self {
+            EnumConst::Number(n) => Runtype::const_(RuntypeConst::parse_f64(*n)),
+            EnumConst::String(s) => Runtype::single_string_const(s),
+        }
+    }
+    fn to_js_string(&self) -> String {
+        match self {
+            EnumConst::Number(n) => n.to_string(),
+            EnumConst::String(s) => s.clone(),
+        }
+    }
+}
+
+// ToInt32 of the ECMAScript spec
+fn to_int32(n: f64) -> i32 {
+    if !n.is_finite() {
+        return 0;
+    }
+    (n.trunc().rem_euclid(4294967296.0) as u32) as i32
+}
+
+fn fold_enum_unary(op: UnaryOp, arg: EnumConst) -> Option<EnumConst> {
+    let EnumConst::Number(n) = arg else {
+        return None;
+    };
+    match op {
+        UnaryOp::Minus => Some(EnumConst::Number(-n)),
+        UnaryOp::Plus => Some(EnumConst::Number(n)),
+        UnaryOp::Tilde => Some(EnumConst::Number(!to_int32(n) as f64)),
+        _ => None,
+    }
+}
+
+fn fold_enum_binary(op: BinaryOp, left: EnumConst, right: EnumConst) -> Option<EnumConst> {
+    let (l, r) = match (left, right) {
+        (EnumConst::Number(l), EnumConst::Number(r)) => (l, r),
+        (l, r) if op == BinaryOp::Add => {
+            return Some(EnumConst::String(format!(
+                "{}{}",
+                l.to_js_string(),
+                r.to_js_string()
+            )));
+        }
+        _ => return None,
+    };
+    let shift = (to_int32(r) as u32) & 31;
+    let value = match op {
+        BinaryOp::Add => l + r,
+        BinaryOp::Sub => l - r,
+        BinaryOp::Mul => l * r,
+        BinaryOp::Div => l / r,
+        BinaryOp::Mod => l % r,
+        BinaryOp::Exp => l.powf(r),
+        BinaryOp::LShift => to_int32(l).wrapping_shl(shift) as f64,
+        BinaryOp::RShift => to_int32(l).wrapping_shr(shift) as f64,
+        BinaryOp::ZeroFillRShift => (to_int32(l) as u32).wrapping_shr(shift) as f64,
+        BinaryOp::BitAnd => (to_int32(l) & to_int32(r)) as f64,
+        BinaryOp::BitOr => (to_int32(l) | to_int32(r)) as f64,
+        BinaryOp::BitXor => (to_int32(l) ^ to_int32(r)) as f64,
+        _ => return None,
+    };
+    Some(EnumConst::Number(value))
+}
+
+// the `T` of `BeffParser<T>`, or of `CompiledParser<T>`
+fn parser_type_argument(ty: &TsType) -> Option<&TsType> {
+    match ty {
+        TsType::TsTypeRef(TsTypeRef {
+            type_name: TsEntityName::Ident(ident),
+            type_params: Some(type_params),
+            ..
+        }) if ident.sym == "BeffParser" || ident.sym == "CompiledParser" => {
+            match type_params.params.as_slice() {
+                [single] => Some(single),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+fn ts_fn_param_to_pat(param: &TsFnParam) -> Pat {
+    match param {
+        TsFnParam::Ident(it) => Pat::Ident(it.clone()),
+        TsFnParam::Array(it) => Pat::Array(it.clone()),
+        TsFnParam::Rest(it) => Pat::Rest(it.clone()),
+        TsFnParam::Object(it) => Pat::Object(it.clone()),
+    }
+}
+
+struct FnSignature<'a> {
+    type_params: Option<&'a TsTypeParamDecl>,
+    params: &'a [&'a Pat],
+    return_type: Option<&'a TsTypeAnn>,
+    is_async: bool,
+    is_constructor: bool,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum FunctionUtility {
+    Parameters,
+    ConstructorParameters,
+    ReturnType,
+}
+
+impl FunctionUtility {
+    fn name(&self) -> &'static str {
+        match self {
+            FunctionUtility::Parameters => "Parameters",
+            FunctionUtility::ConstructorParameters => "ConstructorParameters",
+            FunctionUtility::ReturnType => "ReturnType",
+        }
+    }
+}
+
+fn remove_union_members(ty: Runtype, should_remove: impl Fn(&RuntypeKind) -> bool) -> Runtype {
+    match ty.kind {
+        RuntypeKind::AnyOf(vs) => Runtype::any_of(
+            vs.into_iter()
+                .filter(|it| !should_remove(&it.kind))
+                .collect(),
+        ),
+        _ => ty,
+    }
+}
+
+fn rest_type_ann(ty: &TsType) -> Option<&TsType> {
+    match ty {
+        TsType::TsRestType(TsRestType { type_ann, .. }) => Some(type_ann),
+        _ => None,
+    }
+}
+
+/// The elements of a tuple type, in the order they are written.
+#[derive(Default)]
+struct TupleParts {
+    prefix_items: Vec<Runtype>,
+    optional_items: Vec<Runtype>,
+    items: Option<Box<Runtype>>,
+    suffix_items: Vec<Runtype>,
+    // the optional elements so far all come from spread tuples
+    spread_optional: bool,
+}
+
+impl TupleParts {
+    fn push_required(&mut self, it: Runtype) -> Result<(), DiagnosticInfoMessage> {
+        if !self.optional_items.is_empty() {
+            if !self.spread_optional {
+                return Err(DiagnosticInfoMessage::TupleRequiredElementAfterOptional);
+            }
+            // `[...[A, B?], C]` is `[A, B | undefined, C]`
+            self.require_optional();
+        }
+        if self.items.is_some() {
+            self.suffix_items.push(it);
+        } else {
+            self.prefix_items.push(it);
+        }
+        Ok(())
+    }
+
+    /// Optional elements also accept `undefined`, like optional parameters.
+    fn push_optional(&mut self, it: Runtype) -> Result<(), DiagnosticInfoMessage> {
+        if self.items.is_some() {
+            return Err(DiagnosticInfoMessage::TupleOptionalElementAfterRest);
+        }
+        self.spread_optional = false;
+        let label = it.metadata.label.clone();
+        let it = Runtype::any_of(vec![it, Runtype::undefined()]);
+        self.optional_items.push(match label {
+            Some(label) => it.with_label(label),
+            None => it,
+        });
+        Ok(())
+    }
+
+    /// A required parameter after optional ones makes them required, as in
+    /// `(a = 1, b: string) => void`.
+    fn require_optional(&mut self) {
+        self.prefix_items.append(&mut self.optional_items);
+    }
+
+    fn push_rest(&mut self, it: Runtype) -> Result<(), DiagnosticInfoMessage> {
+        if self.items.is_some() {
+            return Err(DiagnosticInfoMessage::DuplicatedRestNonSerializable);
+        }
+        self.items = Some(it.into());
+        Ok(())
+    }
+
+    /// Spreads the elements of another tuple, as in `[A, ...[B, C?]]`.
+    fn push_spread(
+        &mut self,
+        prefix_items: Vec<Runtype>,
+        optional_items: Vec<Runtype>,
+        items: Option<Box<Runtype>>,
+        suffix_items: Vec<Runtype>,
+    ) -> Result<(), DiagnosticInfoMessage> {
+        for it in prefix_items {
+            self.push_required(it)?;
+        }
+        let spread_optional = self.optional_items.is_empty() || self.spread_optional;
+        for it in optional_items {
+            self.push_optional(it)?;
+        }
+        self.spread_optional = spread_optional;
+        if let Some(it) = items {
+            self.push_rest(*it)?;
+        }
+        for it in suffix_items {
+            self.push_required(it)?;
+        }
+        Ok(())
+    }
+
+    fn into_runtype(self) -> Runtype {
+        Runtype::variadic_tuple(
+            self.prefix_items,
+            self.optional_items,
+            self.items,
+            self.suffix_items,
+        )
+    }
+}
+
+#[derive(Clone, Copy)]
+enum SemTypeFilter {
+    Exclude,
+    Extract,
+}
+
+#[derive(Default)]
+struct InferNameCollector {
+    names: Vec<String>,
+}
+
+impl Visit for InferNameCollector {
+    fn visit_ts_infer_type(&mut self, n: &TsInferType) {
+        let name = n.type_param.name.sym.to_string();
+        if !self.names.contains(&name) {
+            self.names.push(name);
+        }
+        n.visit_children_with(self);
+    }
+}
+
+fn contains_infer<N: VisitWith<InferNameCollector>>(node: &N) -> bool {
+    let mut names = InferNameCollector::default();
+    node.visit_with(&mut names);
+    !names.names.is_empty()
+}
+
 fn clean_jsdoc_comment(text: &str) -> Option<String> {
     let text = text.trim_start();
     let text = text.strip_prefix('*').unwrap_or(text);
@@ -63,11 +345,98 @@ fn clean_jsdoc_comment(text: &str) -> Option<String> {
     }
 }
 
+/// JSDoc tags read as JSON Schema keywords, other tags are left in the description.
+const JSDOC_SCHEMA_TAGS: &[&str] = &[
+    "minLength",
+    "maxLength",
+    "pattern",
+    "minimum",
+    "maximum",
+    "minItems",
+    "maxItems",
+    "format",
+    "default",
+    "deprecated",
+    "example",
+];
+
+#[derive(Debug, PartialEq)]
+struct JsdocTag {
+    name: String,
+    value: String,
+}
+
+/// Splits a line that starts with a tag before each of the tags that follow it on the same line,
+/// as in `@minItems 1 @maxItems 3`.
+fn split_inline_jsdoc_tags(line: &str) -> Vec<&str> {
+    if !line.trim_start().starts_with('@') {
+        return vec![line];
+    }
+    let mut parts = vec![];
+    let mut start = 0;
+    let mut prev = None;
+    for (idx, c) in line.char_indices() {
+        if c == '@'
+            && prev.is_some_and(char::is_whitespace)
+            && line[idx + 1..].starts_with(|it: char| it.is_ascii_alphabetic())
+        {
+            parts.push(&line[start..idx]);
+            start = idx;
+        }
+        prev = Some(c);
+    }
+    parts.push(&line[start..]);
+    parts
+}
+
+/// Splits a cleaned JSDoc comment into its description and the tags of `JSDOC_SCHEMA_TAGS`. A tag
+/// value runs until the next tag, so examples can span many lines.
+fn split_jsdoc_tags(text: &str) -> (Option<String>, Vec<JsdocTag>) {
+    let mut description: Vec<&str> = vec![];
+    let mut tags: Vec<JsdocTag> = vec![];
+    let mut in_schema_tag = false;
+    for line in text.lines().flat_map(split_inline_jsdoc_tags) {
+        if let Some(rest) = line.trim_start().strip_prefix('@') {
+            let (name, value) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+            in_schema_tag = JSDOC_SCHEMA_TAGS.contains(&name);
+            if in_schema_tag {
+                tags.push(JsdocTag {
+                    name: name.to_string(),
+                    value: value.to_string(),
+                });
+                continue;
+            }
+        }
+        match tags.last_mut() {
+            Some(tag) if in_schema_tag => {
+                tag.value.push('\n');
+                tag.value.push_str(line);
+            }
+            _ => description.push(line),
+        }
+    }
+    for tag in tags.iter_mut() {
+        tag.value = tag.value.trim().to_string();
+    }
+    while description
+        .last()
+        .is_some_and(|line| line.trim().is_empty())
+    {
+        description.pop();
+    }
+    let description = description.join("\n");
+    if description.trim().is_empty() {
+        (None, tags)
+    } else {
+        (Some(description), tags)
+    }
+}
+
 #[cfg(test)]
 mod jsdoc_tests {
-    use super::{clean_jsdoc_comment, is_jsdoc_comment};
-    use swc_common::comments::{Comment, CommentKind};
+    use super::{JsdocTag, clean_jsdoc_comment, is_jsdoc_comment, split_jsdoc_tags};
     use swc_common::DUMMY_SP;
+    use swc_common::comments::{Comment, CommentKind};
 
     fn comment(kind: CommentKind, text: &str) -> Comment {
         Comment {
@@ -112,11 +481,58 @@ mod jsdoc_tests {
         );
     }
 
+    #[test]
+    fn split_jsdoc_tags_keeps_unknown_tags_in_description() {
+        let tag = |name: &str, value: &str| JsdocTag {
+            name: name.to_string(),
+            value: value.to_string(),
+        };
+        assert_eq!(
+            split_jsdoc_tags("User name.\n@minLength 3\n@since 2.0.0\n@example\n{\n  \"a\": 1\n}"),
+            (
+                Some("User name.\n@since 2.0.0".to_string()),
+                vec![tag("minLength", "3"), tag("example", "{\n  \"a\": 1\n}")]
+            )
+        );
+        assert_eq!(
+            split_jsdoc_tags("@deprecated"),
+            (None, vec![tag("deprecated", "")])
+        );
+    }
+
+    #[test]
+    fn split_jsdoc_tags_splits_tags_on_one_line() {
+        let tag = |name: &str, value: &str| JsdocTag {
+            name: name.to_string(),
+            value: value.to_string(),
+        };
+        assert_eq!(
+            split_jsdoc_tags("Ask @admin.\n@minItems 1 @maxItems 3 @example a@b.c"),
+            (
+                Some("Ask @admin.".to_string()),
+                vec![
+                    tag("minItems", "1"),
+                    tag("maxItems", "3"),
+                    tag("example", "a@b.c")
+                ]
+            )
+        );
+    }
+
     #[test]
     fn is_jsdoc_comment_only_accepts_block_jsdoc_comments() {
-        assert!(is_jsdoc_comment(&comment(CommentKind::Block, "* Description.")));
-        assert!(!is_jsdoc_comment(&comment(CommentKind::Block, " Description.")));
-        assert!(!is_jsdoc_comment(&comment(CommentKind::Line, "* Description.")));
+        assert!(is_jsdoc_comment(&comment(
+            CommentKind::Block,
+            "* Description."
+        )));
+        assert!(!is_jsdoc_comment(&comment(
+            CommentKind::Block,
+            " Description."
+        )));
+        assert!(!is_jsdoc_comment(&comment(
+            CommentKind::Line,
+            "* Description."
+        )));
     }
 }
 
@@ -250,6 +666,12 @@ fn find_cached_jsdoc_description(
     }
 }
 
+// how many instantiations of the same generic can be in progress at once
+const MAX_GENERIC_INSTANTIATION_DEPTH: usize = 16;
+
+// tsc refuses to build larger unions out of template literal types
+const MAX_TPL_LIT_EXPANSION: usize = 100_000;
+
 pub struct FrontendCtx<'a, R: FileManager> {
     pub files: &'a mut R,
     pub settings: &'a BeffUserSettings,
@@ -258,13 +680,31 @@ pub struct FrontendCtx<'a, R: FileManager> {
     pub errors: Vec<DiagnosticInformation>,
     pub partial_validators: BTreeMap<RuntypeUUID, Option<Runtype>>,
     pub recursive_generic_uuids: BTreeSet<RuntypeUUID>,
+    // where the named types are declared, for the source maps of the generated code
+    pub declaration_positions: BTreeMap<RuntypeName, SourcePosition>,
 
     pub counter: usize,
 
     pub type_application_stack: Vec<(String, Runtype)>,
     jsdoc_cache_by_file: BTreeMap<BffFileName, JsdocFileCache>,
+    // namespaces of the files fetched so far, they are addressed like files
+    namespace_scopes: BTreeMap<BffFileName, Rc<ParsedModule>>,
+    interface_augmentations: Option<Rc<InterfaceAugmentations>>,
+    // type arguments of an instantiation expression, `typeof f<T>`, the next
+    // generic signature that is extracted takes them
+    instantiation: Option<(Vec<Runtype>, Anchor)>,
+    // classes checked with `instanceof`, by the name `buildParsers` takes them under
+    instance_of_classes: BTreeMap<String, TypeAddress>,
+    // the classes of `instance_of_classes` their file exports under the same name
+    pub exported_classes: BTreeSet<TypeAddress>,
 }
 
+type InterfaceAugmentations = BTreeMap<TypeAddress, Vec<(Rc<TsInterfaceDecl>, BffFileName)>>;
+
+type ObjectShape = (BTreeMap<String, Optionality<Runtype>>, Vec<IndexedProperty>);
+
+type ObjectProperties = BTreeMap<String, Optionality<Runtype>>;
+
 #[derive(Debug)]
 enum AddressedType {
     Type {
@@ -281,6 +721,11 @@ enum AddressedType {
         t: Rc<TsEnumDecl>,
         local_address: TypeAddress,
     },
+    Class {
+        t: Rc<ClassDecl>,
+        local_address: TypeAddress,
+        declaration_span: Span,
+    },
 }
 impl AddressedType {
     fn type_address(&self) -> TypeAddress {
@@ -299,6 +744,11 @@ impl AddressedType {
                 t: _,
                 local_address: address,
             } => address.clone(),
+            AddressedType::Class {
+                t: _,
+                local_address: address,
+                declaration_span: _,
+            } => address.clone(),
         }
     }
 }
@@ -363,6 +813,19 @@ trait TypeModuleWalker<'a, R: FileManager + 'a, U> {
         name: String,
         anchor: &Anchor,
     ) -> Res<U>;
+    fn get_addressed_item_from_local_class(
+        &mut self,
+        class: &Rc<ClassDecl>,
+        file: BffFileName,
+        name: String,
+        anchor: &Anchor,
+    ) -> Res<U>;
+    // namespaces are not types, walkers that don't resolve qualified names skip them
+    fn get_addressed_item_from_local_namespace(
+        &mut self,
+        scope: BffFileName,
+        anchor: &Anchor,
+    ) -> Option<Res<U>>;
 
     fn get_addressed_item_from_default_import(
         &mut self,
@@ -445,6 +908,13 @@ trait TypeModuleWalker<'a, R: FileManager + 'a, U> {
         let parsed_module = self.get_ctx().get_or_fetch_file(&addr.file, err_anchor)?;
         match addr.visibility {
             Visibility::Local => {
+                if let Some(scope) = parsed_module.locals.namespaces.get(&addr.name)
+                    && let Some(res) =
+                        self.get_addressed_item_from_local_namespace(scope.clone(), err_anchor)
+                {
+                    return res;
+                }
+
                 if let Some(ts_type) = parsed_module.locals.type_aliases.get(&addr.name) {
                     return self.get_addressed_item_from_local_ts_type(
                         ts_type,
@@ -462,7 +932,12 @@ trait TypeModuleWalker<'a, R: FileManager + 'a, U> {
                     );
                 }
 
-                if let Some(interface) = parsed_module.locals.interfaces.get(&addr.name) {
+                if let Some(interface) = parsed_module
+                    .locals
+                    .interfaces
+                    .get(&addr.name)
+                    .and_then(|it| it.first())
+                {
                     return self.get_addressed_item_from_local_ts_interface(
                         interface,
                         addr.file.clone(),
@@ -471,10 +946,28 @@ trait TypeModuleWalker<'a, R: FileManager + 'a, U> {
                     );
                 }
 
+                if let Some(class) = parsed_module.locals.classes.get(&addr.name) {
+                    return self.get_addressed_item_from_local_class(
+                        class,
+                        addr.file.clone(),
+                        addr.name.clone(),
+                        err_anchor,
+                    );
+                }
+
                 if let Some(imported) = parsed_module.imports.get(&addr.name) {
                     return self.get_addressed_item_from_import_reference(imported, err_anchor);
                 }
 
+                if let Some(parent_scope) = &parsed_module.parent_scope {
+                    let new_addr = ModuleItemAddress {
+                        file: parent_scope.clone(),
+                        name: addr.name.clone(),
+                        visibility: Visibility::Local,
+                    };
+                    return self.get_addressed_item(&new_addr, err_anchor);
+                }
+
                 Err(self.get_ctx().box_error(
                     err_anchor,
                     DiagnosticInfoMessage::CannotNotResolveType(addr.clone()),
@@ -558,6 +1051,18 @@ impl<'a, 'b, R: FileManager> TypeModuleWalker<'a, R, AddressedType> for TypeWalk
                     name: decl.id.sym.to_string(),
                 },
             }),
+            SymbolExport::ClassDecl {
+                decl,
+                original_file,
+                span,
+            } => Ok(AddressedType::Class {
+                t: decl.clone(),
+                local_address: TypeAddress {
+                    file: original_file.clone(),
+                    name: decl.ident.sym.to_string(),
+                },
+                declaration_span: *span,
+            }),
             SymbolExport::ExprDecl { .. } | SymbolExport::ValueExpr { .. } => self
                 .get_ctx()
                 .error(anchor, DiagnosticInfoMessage::CannotUseValueInTypePosition),
@@ -626,6 +1131,28 @@ impl<'a, 'b, R: FileManager> TypeModuleWalker<'a, R, AddressedType> for TypeWalk
             declaration_span: ts_interface.span,
         })
     }
+
+    fn get_addressed_item_from_local_class(
+        &mut self,
+        class: &Rc<ClassDecl>,
+        file: BffFileName,
+        name: String,
+        _anchor: &Anchor,
+    ) -> Res<AddressedType> {
+        Ok(AddressedType::Class {
+            t: class.clone(),
+            local_address: TypeAddress { file, name },
+            declaration_span: class.class.span,
+        })
+    }
+
+    fn get_addressed_item_from_local_namespace(
+        &mut self,
+        _scope: BffFileName,
+        _anchor: &Anchor,
+    ) -> Option<Res<AddressedType>> {
+        None
+    }
 }
 
 impl<'a, 'b, R: FileManager> TypeModuleWalker<'a, R, AddressedQualifiedType>
@@ -669,6 +1196,10 @@ impl<'a, 'b, R: FileManager> TypeModuleWalker<'a, R, AddressedQualifiedType>
                 anchor,
                 DiagnosticInfoMessage::CannotUseInterfaceInQualifiedTypePosition,
             ),
+            SymbolExport::ClassDecl { .. } => self.ctx.error(
+                anchor,
+                DiagnosticInfoMessage::CannotUseClassInQualifiedTypePosition,
+            ),
             SymbolExport::ValueExpr { .. } => {
                 unreachable!("we use get_type which filters these out")
             }
@@ -723,6 +1254,27 @@ impl<'a, 'b, R: FileManager> TypeModuleWalker<'a, R, AddressedQualifiedType>
             DiagnosticInfoMessage::CannotUseInterfaceInQualifiedTypePosition,
         ))
     }
+
+    fn get_addressed_item_from_local_class(
+        &mut self,
+        _class: &Rc<ClassDecl>,
+        _file: BffFileName,
+        _name: String,
+        anchor: &Anchor,
+    ) -> Res<AddressedQualifiedType> {
+        Err(self.get_ctx().box_error(
+            anchor,
+            DiagnosticInfoMessage::CannotUseClassInQualifiedTypePosition,
+        ))
+    }
+
+    fn get_addressed_item_from_local_namespace(
+        &mut self,
+        scope: BffFileName,
+        _anchor: &Anchor,
+    ) -> Option<Res<AddressedQualifiedType>> {
+        Some(Ok(AddressedQualifiedType::StarImport(scope)))
+    }
 }
 
 trait ValueModuleWalker<'a, R: FileManager + 'a, U> {
@@ -836,6 +1388,17 @@ trait ValueModuleWalker<'a, R: FileManager + 'a, U> {
                 if let Some(enum_) = parsed_module.locals.enums.get(&addr.name) {
                     return self.handle_symbol_enum(enum_, &addr.file);
                 }
+                if let Some(scope) = parsed_module.locals.namespaces.get(&addr.name) {
+                    return self.handle_import_star(scope.clone(), anchor);
+                }
+                if let Some(parent_scope) = &parsed_module.parent_scope {
+                    let new_addr = ModuleItemAddress {
+                        file: parent_scope.clone(),
+                        name: addr.name.clone(),
+                        visibility: Visibility::Local,
+                    };
+                    return self.get_addressed_item(&new_addr, anchor);
+                }
 
                 self.get_ctx().error(
                     anchor,
@@ -893,6 +1456,14 @@ impl<'a, 'b, R: FileManager> ValueModuleWalker<'a, R, AddressedValue> for ValueW
                 decl,
                 original_file,
             } => Ok(AddressedValue::Enum(decl.clone(), original_file.clone())),
+            SymbolExport::ClassDecl {
+                decl,
+                original_file,
+                span: _,
+            } => Ok(AddressedValue::ValueExpr(
+                Rc::new(class_decl_to_expr(decl)),
+                original_file.clone(),
+            )),
             SymbolExport::ValueExpr {
                 expr,
                 original_file,
@@ -995,6 +1566,14 @@ impl<'a, 'b, R: FileManager> ValueModuleWalker<'a, R, AddressedQualifiedValue>
                 decl.clone(),
                 original_file.clone(),
             )),
+            SymbolExport::ClassDecl {
+                decl,
+                original_file,
+                span: _,
+            } => Ok(AddressedQualifiedValue::ValueExpr(
+                Rc::new(class_decl_to_expr(decl)),
+                original_file.clone(),
+            )),
             SymbolExport::ValueExpr {
                 expr,
                 name: _,
@@ -1074,16 +1653,55 @@ impl<'a, R: FileManager> FrontendCtx<'a, R> {
             settings,
             errors: vec![],
             partial_validators: BTreeMap::new(),
+            declaration_positions: BTreeMap::new(),
             counter: 0,
 
             type_application_stack: vec![],
             recursive_generic_uuids: BTreeSet::new(),
             jsdoc_cache_by_file: BTreeMap::new(),
+            namespace_scopes: BTreeMap::new(),
+            interface_augmentations: None,
+            instantiation: None,
+            instance_of_classes: BTreeMap::new(),
+            exported_classes: BTreeSet::new(),
+        }
+    }
+
+    fn get_existing_file(&self, file: &BffFileName) -> Option<Rc<ParsedModule>> {
+        match self.namespace_scopes.get(file) {
+            Some(scope) => Some(scope.clone()),
+            None => self.files.get_existing_file(file),
+        }
+    }
+
+    pub fn source_position(&self, anchor: &Anchor) -> Option<SourcePosition> {
+        if anchor.s.is_dummy() {
+            return None;
+        }
+        let file = self.get_existing_file(&anchor.f)?;
+        let loc = file.module.source_map.lookup_char_pos(anchor.s.lo);
+        Some(SourcePosition {
+            file: file.module.bff_fname.clone(),
+            line: loc.line - 1,
+            col: loc.col.0,
+        })
+    }
+
+    fn record_declaration(&mut self, name: &RuntypeName, file: &BffFileName, span: Span) {
+        if self.declaration_positions.contains_key(name) {
+            return;
+        }
+        let anchor = Anchor {
+            f: file.clone(),
+            s: span,
+        };
+        if let Some(position) = self.source_position(&anchor) {
+            self.declaration_positions.insert(name.clone(), position);
         }
     }
 
     fn build_error(&self, anchor: &Anchor, msg: DiagnosticInfoMessage) -> DiagnosticInformation {
-        let file_content = self.files.get_existing_file(&anchor.f);
+        let file_content = self.get_existing_file(&anchor.f);
         Location::build(file_content, &anchor.s, &anchor.f).to_info(msg)
     }
     fn push_error(&mut self, anchor: &Anchor, msg: DiagnosticInfoMessage) {
@@ -1114,12 +1732,18 @@ impl<'a, R: FileManager> FrontendCtx<'a, R> {
         file: &BffFileName,
         err_anchor: &Anchor,
     ) -> Res<Rc<ParsedModule>> {
+        if let Some(scope) = self.namespace_scopes.get(file) {
+            return Ok(scope.clone());
+        }
         let parsed_module = self.files.get_or_fetch_file(file).ok_or_else(|| {
             Box::new(self.build_error(
                 err_anchor,
                 DiagnosticInfoMessage::CannotNotFindFile(file.clone()),
             ))
         })?;
+        for (name, scope) in &parsed_module.namespaces {
+            self.namespace_scopes.insert(name.clone(), scope.clone());
+        }
         Ok(parsed_module)
     }
 
@@ -1133,7 +1757,7 @@ impl<'a, R: FileManager> FrontendCtx<'a, R> {
             return cached.clone();
         }
 
-        let parsed = self.files.get_existing_file(file)?;
+        let parsed = self.get_existing_file(file)?;
         if !self.jsdoc_cache_by_file.contains_key(file) {
             self.jsdoc_cache_by_file
                 .insert(file.clone(), build_jsdoc_file_cache(&parsed));
@@ -1155,11 +1779,67 @@ impl<'a, R: FileManager> FrontendCtx<'a, R> {
         description
     }
 
-    fn with_jsdoc(&mut self, file: &BffFileName, span: Span, runtype: Runtype) -> Runtype {
-        match self.jsdoc_description(file, span) {
-            Some(description) => runtype.with_description(description),
-            None => runtype,
+    fn with_jsdoc(&mut self, file: &BffFileName, span: Span, runtype: Runtype) -> Res<Runtype> {
+        let Some(text) = self.jsdoc_description(file, span) else {
+            return Ok(runtype);
+        };
+        let (description, tags) = split_jsdoc_tags(&text);
+        let mut metadata = runtype.metadata.clone();
+        metadata.description = description.or(metadata.description);
+        let mut keywords = metadata.keywords.take().map(|it| *it).unwrap_or_default();
+        let anchor = Anchor {
+            f: file.clone(),
+            s: span,
+        };
+        for JsdocTag { name, value } in tags {
+            let json = |value: &str| {
+                serde_json::from_str::<serde_json::Value>(value)
+                    .map(|it| Json::from_serde(&it))
+                    .unwrap_or_else(|_| Json::String(value.to_string()))
+            };
+            if value.is_empty() && name != "deprecated" {
+                return self.error(
+                    &anchor,
+                    DiagnosticInfoMessage::JsdocTagShouldHaveValue(name),
+                );
+            }
+            match name.as_str() {
+                "minLength" | "maxLength" | "minItems" | "maxItems" => {
+                    let Ok(count) = value.parse::<usize>() else {
+                        return self.error(
+                            &anchor,
+                            DiagnosticInfoMessage::JsdocTagShouldBeNonNegativeInteger(name),
+                        );
+                    };
+                    match name.as_str() {
+                        "minLength" => keywords.min_length = Some(count),
+                        "maxLength" => keywords.max_length = Some(count),
+                        "minItems" => keywords.min_items = Some(count),
+                        _ => keywords.max_items = Some(count),
+                    }
+                }
+                "minimum" | "maximum" => {
+                    let Some(n) = value.parse::<f64>().ok().filter(|it| it.is_finite()) else {
+                        return self
+                            .error(&anchor, DiagnosticInfoMessage::JsdocTagShouldBeNumber(name));
+                    };
+                    if name == "minimum" {
+                        keywords.minimum = Some(N::parse_f64(n));
+                    } else {
+                        keywords.maximum = Some(N::parse_f64(n));
+                    }
+                }
+                "pattern" => keywords.pattern = Some(value),
+                "format" => keywords.format = Some(value),
+                "default" => keywords.default = Some(json(&value)),
+                "deprecated" => keywords.deprecated = true,
+                _ => keywords.examples.push(json(&value)),
+            }
         }
+        if keywords != SchemaKeywords::default() {
+            metadata.keywords = Some(Box::new(keywords));
+        }
+        Ok(runtype.with_metadata(metadata))
     }
 
     fn get_addressed_type(
@@ -1199,25 +1879,116 @@ impl<'a, R: FileManager> FrontendCtx<'a, R> {
     }
 
     fn extract_enum_decl(&mut self, typ: &TsEnumDecl, file: BffFileName) -> Res<Runtype> {
-        let mut values = vec![];
-
+        let values = self
+            .enum_member_values(typ, file)?
+            .into_iter()
+            .map(|(_, value)| value)
+            .collect();
+        Ok(Runtype::any_of(values))
+    }
+
+    fn enum_member_value(
+        &mut self,
+        typ: &TsEnumDecl,
+        member: &str,
+        file: BffFileName,
+        anchor: &Anchor,
+    ) -> Res<Runtype> {
+        let values = self.enum_member_values(typ, file)?;
+        match values.into_iter().find(|(name, _)| name == member) {
+            Some((_, value)) => Ok(value),
+            None => self.error(anchor, DiagnosticInfoMessage::EnumMemberNotFound),
+        }
+    }
+
+    // members without initializer continue the numbering of the previous one,
+    // initializers are folded when they are constant expressions
+    fn enum_member_values(
+        &mut self,
+        typ: &TsEnumDecl,
+        file: BffFileName,
+    ) -> Res<Vec<(String, Runtype)>> {
+        let mut acc: Vec<(String, Runtype)> = vec![];
         for member in &typ.members {
-            match &member.init {
-                Some(init) => {
-                    let expr_ty = self.typeof_expr(init, true, file.clone())?;
-                    values.push(expr_ty);
-                }
-                None => {
-                    let anchor = Anchor {
-                        f: file.clone(),
-                        s: member.span,
-                    };
-                    return self.error(&anchor, DiagnosticInfoMessage::EnumMemberNoInit)?;
+            let value = match &member.init {
+                Some(init) => match self.fold_enum_initializer(init, typ, &acc, file.clone()) {
+                    Some(value) => value.to_runtype(),
+                    None => self.typeof_expr(init, true, file.clone())?,
+                },
+                None => match acc.last().map(|(_, prev)| EnumConst::from_runtype(prev)) {
+                    None => EnumConst::Number(0.0).to_runtype(),
+                    Some(Some(EnumConst::Number(prev))) => {
+                        EnumConst::Number(prev + 1.0).to_runtype()
+                    }
+                    Some(_) => {
+                        let anchor = Anchor {
+                            f: file.clone(),
+                            s: member.span,
+                        };
+                        return self.error(&anchor, DiagnosticInfoMessage::EnumMemberNoInit);
+                    }
+                },
+            };
+            acc.push((enum_member_name(&member.id), value));
+        }
+        Ok(acc)
+    }
+
+    fn fold_enum_initializer(
+        &mut self,
+        init: &Expr,
+        typ: &TsEnumDecl,
+        previous: &[(String, Runtype)],
+        file: BffFileName,
+    ) -> Option<EnumConst> {
+        let previous_member = |name: &str| {
+            previous
+                .iter()
+                .find(|(it, _)| it == name)
+                .and_then(|(_, value)| EnumConst::from_runtype(value))
+        };
+        match init {
+            Expr::Lit(Lit::Num(n)) => Some(EnumConst::Number(n.value)),
+            Expr::Lit(Lit::Str(s)) => {
+                Some(EnumConst::String(s.value.to_string_lossy().to_string()))
+            }
+            Expr::Paren(p) => self.fold_enum_initializer(&p.expr, typ, previous, file),
+            Expr::Tpl(tpl) => {
+                let mut acc = String::new();
+                for (idx, quasi) in tpl.quasis.iter().enumerate() {
+                    acc.push_str(&quasi.cooked.as_ref()?.to_string_lossy());
+                    if let Some(expr) = tpl.exprs.get(idx) {
+                        let value =
+                            self.fold_enum_initializer(expr, typ, previous, file.clone())?;
+                        acc.push_str(&value.to_js_string());
+                    }
                 }
+                Some(EnumConst::String(acc))
+            }
+            Expr::Ident(i) if previous_member(&i.sym).is_some() => previous_member(&i.sym),
+            Expr::Member(MemberExpr {
+                obj,
+                prop: MemberProp::Ident(prop),
+                ..
+            }) if matches!(&**obj, Expr::Ident(o) if o.sym == typ.id.sym) => {
+                previous_member(&prop.sym)
+            }
+            Expr::Unary(u) => {
+                let arg = self.fold_enum_initializer(&u.arg, typ, previous, file)?;
+                fold_enum_unary(u.op, arg)
             }
+            Expr::Bin(b) => {
+                let left = self.fold_enum_initializer(&b.left, typ, previous, file.clone())?;
+                let right = self.fold_enum_initializer(&b.right, typ, previous, file)?;
+                fold_enum_binary(b.op, left, right)
+            }
+            // constants declared elsewhere, like members of other enums
+            Expr::Ident(_) | Expr::Member(_) => {
+                let value = self.typeof_expr(init, true, file).ok()?;
+                EnumConst::from_runtype(&value)
+            }
+            _ => None,
         }
-
-        Ok(Runtype::any_of(values))
     }
     fn extract_object_from_runtype(
         &mut self,
@@ -1228,7 +1999,7 @@ impl<'a, R: FileManager> FrontendCtx<'a, R> {
             RuntypeKind::Object {
                 vs,
                 indexed_properties,
-            } => match indexed_properties.is_none() {
+            } => match indexed_properties.is_empty() {
                 true => Ok(vs.clone()),
                 false => self.error(anchor, DiagnosticInfoMessage::RestFoundOnExtractObject),
             },
@@ -1276,6 +2047,22 @@ impl<'a, R: FileManager> FrontendCtx<'a, R> {
             ),
         }
     }
+    // a heritage clause is an entity name written as an expression, `A` or `ns.A`
+    fn heritage_entity_name(expr: &Expr) -> Option<TsEntityName> {
+        match expr {
+            Expr::Ident(id) => Some(TsEntityName::Ident(id.clone())),
+            Expr::Member(MemberExpr {
+                span,
+                obj,
+                prop: MemberProp::Ident(prop),
+            }) => Some(TsEntityName::TsQualifiedName(Box::new(TsQualifiedName {
+                span: *span,
+                left: Self::heritage_entity_name(obj)?,
+                right: prop.clone(),
+            }))),
+            _ => None,
+        }
+    }
     fn extract_interface_extends(
         &mut self,
         typ: &Vec<TsExprWithTypeArgs>,
@@ -1289,10 +2076,10 @@ impl<'a, R: FileManager> FrontendCtx<'a, R> {
                 s: it.span,
             };
 
-            match it.expr.as_ref() {
-                Expr::Ident(id) => {
+            match Self::heritage_entity_name(&it.expr) {
+                Some(type_name) => {
                     let id_ty = self.extract_type_from_ts_entity_name(
-                        &TsEntityName::Ident(id.clone()),
+                        &type_name,
                         &it.type_args,
                         file.clone(),
                         Visibility::Local,
@@ -1301,7 +2088,7 @@ impl<'a, R: FileManager> FrontendCtx<'a, R> {
 
                     vs.push(id_ty);
                 }
-                _ => {
+                None => {
                     return self.error(&anchor, DiagnosticInfoMessage::ExtendsShouldBeIdent);
                 }
             }
@@ -1309,19 +2096,224 @@ impl<'a, R: FileManager> FrontendCtx<'a, R> {
 
         Ok(vs)
     }
+    // properties redeclared by the child replace the ones of its bases, as in TypeScript,
+    // and like TypeScript the redeclared property must be assignable to the base one
+    fn merge_heritage(&mut self, ext: Vec<Runtype>, own: Runtype, anchor: &Anchor) -> Res<Runtype> {
+        let own_vs = self
+            .extract_object_from_runtype(&own, anchor)
+            .unwrap_or_default();
+        let mut bases = vec![];
+        for base in ext {
+            let base_vs = match self.extract_object_from_runtype(&base, anchor) {
+                Ok(vs) if vs.keys().any(|k| own_vs.contains_key(k)) => vs,
+                _ => {
+                    bases.push(base);
+                    continue;
+                }
+            };
+            for (k, base_v) in &base_vs {
+                if let Some(own_v) = own_vs.get(k)
+                    && !self.overrides_property(own_v, base_v, anchor)
+                {
+                    return self.error(
+                        anchor,
+                        DiagnosticInfoMessage::InterfacePropertyNotAssignableToBase(k.clone()),
+                    );
+                }
+            }
+            bases.push(Runtype::object(
+                base_vs
+                    .into_iter()
+                    .filter(|(k, _)| !own_vs.contains_key(k))
+                    .collect(),
+            ));
+        }
+        let merged = Runtype::all_of(bases.into_iter().chain(std::iter::once(own)).collect());
+        match self.extract_object_from_runtype(&merged, anchor) {
+            Ok(vs) => Ok(Runtype::object(vs.into_iter().collect())),
+            Err(_) => Ok(merged),
+        }
+    }
+    fn overrides_property(
+        &mut self,
+        own: &Optionality<Runtype>,
+        base: &Optionality<Runtype>,
+        anchor: &Anchor,
+    ) -> bool {
+        if base.is_required() && !own.is_required() {
+            return false;
+        }
+        // methods have no semantic type to compare, they are accepted as declared
+        self.is_sub_runtype(own.inner(), base.inner(), anchor)
+            .unwrap_or(true)
+    }
+    // the declarations of an interface in its own scope and the `declare module`
+    // blocks that augment it, each with the file its names resolve in
+    fn interface_declarations(
+        &mut self,
+        decl: &Rc<TsInterfaceDecl>,
+        address: &TypeAddress,
+    ) -> Vec<(Rc<TsInterfaceDecl>, BffFileName)> {
+        let local = self
+            .get_existing_file(&address.file)
+            .and_then(|module| module.locals.interfaces.get(&address.name).cloned())
+            .unwrap_or_else(|| vec![decl.clone()]);
+        let mut acc: Vec<_> = local
+            .into_iter()
+            .map(|it| (it, address.file.clone()))
+            .collect();
+        if let Some(augmentations) = self.interface_augmentations().get(address) {
+            acc.extend(augmentations.iter().cloned());
+        }
+        acc
+    }
+
+    // augmentations are collected once, from every file the parser file reaches
+    fn interface_augmentations(&mut self) -> Rc<InterfaceAugmentations> {
+        if let Some(found) = &self.interface_augmentations {
+            return found.clone();
+        }
+        let mut acc = InterfaceAugmentations::new();
+        let mut seen = BTreeSet::from([self.parser_file.clone()]);
+        let mut queue = vec![self.parser_file.clone()];
+        while let Some(file) = queue.pop() {
+            let Some(module) = self.files.get_or_fetch_file(&file) else {
+                continue;
+            };
+            for it in &module.augmentations {
+                let address = TypeAddress {
+                    file: it.target_file.clone(),
+                    name: it.decl.id.sym.to_string(),
+                };
+                acc.entry(address)
+                    .or_default()
+                    .push((it.decl.clone(), file.clone()));
+            }
+            for dependency in &module.dependencies {
+                if seen.insert(dependency.clone()) {
+                    queue.push(dependency.clone());
+                }
+            }
+        }
+        let acc = Rc::new(acc);
+        self.interface_augmentations = Some(acc.clone());
+        acc
+    }
+
     fn extract_interface_decl(
         &mut self,
-        typ: &TsInterfaceDecl,
+        decls: &[(Rc<TsInterfaceDecl>, BffFileName)],
         type_args: Vec<Runtype>,
-        file: BffFileName,
     ) -> Res<Runtype> {
+        let (typ, file) = &decls[0];
         let anchor = Anchor {
             f: file.clone(),
             s: typ.span,
         };
 
+        let type_param_names = |decl: &TsInterfaceDecl| -> Vec<String> {
+            decl.type_params
+                .iter()
+                .flat_map(|tps| tps.params.iter())
+                .map(|it| it.name.sym.to_string())
+                .collect()
+        };
+        let type_params = type_param_names(typ);
+        for (other, other_file) in &decls[1..] {
+            if type_param_names(other) != type_params {
+                let anchor = Anchor {
+                    f: other_file.clone(),
+                    s: other.span,
+                };
+                return self.error(
+                    &anchor,
+                    DiagnosticInfoMessage::InterfaceDeclarationsTypeParamsMismatch(
+                        typ.id.sym.to_string(),
+                    ),
+                );
+            }
+        }
+
+        if type_params.len() != type_args.len() {
+            return self.error(&anchor, DiagnosticInfoMessage::TypeArgumentCountMismatch);
+        }
+
+        for (k, v) in type_params.iter().zip(type_args.iter()) {
+            self.type_application_stack.push((k.clone(), v.clone()));
+        }
+
+        // the heritage clauses can refer to the type parameters of the interface
+        let inferred = self.extract_merged_interface_members(decls, &anchor);
+
+        for _ in &type_params {
+            self.type_application_stack.pop();
+        }
+
+        self.with_jsdoc(file, typ.span, inferred?)
+    }
+
+    fn extract_merged_interface_members(
+        &mut self,
+        decls: &[(Rc<TsInterfaceDecl>, BffFileName)],
+        anchor: &Anchor,
+    ) -> Res<Runtype> {
+        let mut vs: BTreeMap<String, Optionality<Runtype>> = BTreeMap::new();
+        let mut indexed_properties: Vec<IndexedProperty> = vec![];
+        let mut ext = vec![];
+        for (decl, file) in decls {
+            let own = self.extract_ts_type_lit_members(&decl.body.body, file.clone())?;
+            if let RuntypeKind::Object {
+                vs: own_vs,
+                indexed_properties: own_indexed_properties,
+            } = own.kind
+            {
+                for (k, v) in own_vs {
+                    match vs.get(&k) {
+                        Some(existing) if *existing != v => {
+                            let anchor = Anchor {
+                                f: file.clone(),
+                                s: decl.span,
+                            };
+                            return self.error(
+                                &anchor,
+                                DiagnosticInfoMessage::InterfacePropertyRedeclaredWithDifferentType(
+                                    k,
+                                ),
+                            );
+                        }
+                        Some(_) => {}
+                        None => {
+                            vs.insert(k, v);
+                        }
+                    }
+                }
+                indexed_properties.extend(own_indexed_properties);
+            }
+            ext.extend(self.extract_interface_extends(&decl.extends, file.clone())?);
+        }
+        let own = Runtype::new(RuntypeKind::Object {
+            vs,
+            indexed_properties,
+        });
+        if ext.is_empty() {
+            return Ok(own);
+        }
+        self.merge_heritage(ext, own, anchor)
+    }
+
+    fn extract_class_decl(
+        &mut self,
+        typ: &ClassDecl,
+        type_args: Vec<Runtype>,
+        file: BffFileName,
+    ) -> Res<Runtype> {
+        let anchor = Anchor {
+            f: file.clone(),
+            s: typ.class.span,
+        };
+
         let empty = vec![];
-        let type_params = match typ.type_params.as_ref() {
+        let type_params = match typ.class.type_params.as_ref() {
             Some(tps) => tps.params.as_ref(),
             None => &empty,
         };
@@ -1335,57 +2327,269 @@ impl<'a, R: FileManager> FrontendCtx<'a, R> {
                 .push((k.name.sym.to_string(), v.clone()));
         }
 
-        let inferred = self.extract_ts_type_lit_members(&typ.body.body, file.clone());
+        let shape = self.extract_class_shape(&typ.class, file);
 
         for _ in type_params {
             self.type_application_stack.pop();
         }
 
-        let r = inferred;
+        shape
+    }
 
-        let runtype = if typ.extends.is_empty() {
-            r
-        } else {
-            let ext = self.extract_interface_extends(&typ.extends, file.clone())?;
-            let merged = Runtype::all_of(ext.into_iter().chain(std::iter::once(r?)).collect());
-            let res = self.extract_object_from_runtype(&merged, &anchor);
-            match res {
-                Ok(vs) => Ok(Runtype::object(vs.into_iter().collect())),
-                Err(_) => Ok(merged),
+    fn extract_class_shape(&mut self, class: &Class, file: BffFileName) -> Res<Runtype> {
+        let r = self.extract_class_members(class, file.clone())?;
+        match &class.super_class {
+            None => Ok(r),
+            Some(super_class) => {
+                let anchor = Anchor {
+                    f: file.clone(),
+                    s: class.span,
+                };
+                let ext = self.extract_interface_extends(
+                    &vec![TsExprWithTypeArgs {
+                        span: super_class.span(),
+                        expr: super_class.clone(),
+                        type_args: class.super_type_params.clone(),
+                    }],
+                    file,
+                )?;
+                self.merge_heritage(ext, r, &anchor)
             }
-        }?;
+        }
+    }
 
-        Ok(self.with_jsdoc(&file, typ.span, runtype))
+    fn is_public_member(accessibility: Option<Accessibility>) -> bool {
+        matches!(accessibility, None | Some(Accessibility::Public))
     }
 
-    fn convert_required(&mut self, obj: &BTreeMap<String, Optionality<Runtype>>) -> Runtype {
-        let mut acc = vec![];
-        for (k, v) in obj {
-            acc.push((k.clone(), v.clone().to_required()));
+    // the public instance fields of a class, declared in its body or as constructor
+    // parameter properties, methods and accessors are not part of the data shape
+    fn extract_class_members(&mut self, class: &Class, file: BffFileName) -> Res<Runtype> {
+        let mut properties: Vec<(String, Optionality<Runtype>)> = vec![];
+        let mut index_signatures: Vec<TsTypeElement> = vec![];
+
+        for member in &class.body {
+            match member {
+                ClassMember::ClassProp(prop) => {
+                    if prop.is_static || !Self::is_public_member(prop.accessibility) {
+                        continue;
+                    }
+                    let anchor = Anchor {
+                        f: file.clone(),
+                        s: prop.span,
+                    };
+                    let key = match &prop.key {
+                        PropName::Ident(ident) => ident.sym.to_string(),
+                        PropName::Str(st) => st.value.to_string_lossy().to_string(),
+                        _ => {
+                            return self
+                                .error(&anchor, DiagnosticInfoMessage::PropKeyShouldBeIdent);
+                        }
+                    };
+                    let value = match (&prop.type_ann, &prop.value) {
+                        (Some(ann), _) => self.extract_type(&ann.type_ann, file.clone())?,
+                        (None, Some(init)) => self.typeof_expr(init, false, file.clone())?,
+                        (None, None) => {
+                            return self.error(
+                                &anchor,
+                                DiagnosticInfoMessage::PropShouldHaveTypeAnnotation,
+                            );
+                        }
+                    };
+                    let value = self.with_jsdoc(&file, prop.span, value)?;
+                    let value = if prop.is_optional {
+                        value.optional()
+                    } else {
+                        value.required()
+                    };
+                    properties.push((key, value));
+                }
+                ClassMember::Constructor(ctor) => {
+                    for param in &ctor.params {
+                        let ParamOrTsParamProp::TsParamProp(param_prop) = param else {
+                            continue;
+                        };
+                        if !Self::is_public_member(param_prop.accessibility) {
+                            continue;
+                        }
+                        let anchor = Anchor {
+                            f: file.clone(),
+                            s: param_prop.span,
+                        };
+                        let (binding, init) = match &param_prop.param {
+                            TsParamPropParam::Ident(binding) => (binding, None),
+                            TsParamPropParam::Assign(assign) => match &*assign.left {
+                                Pat::Ident(binding) => (binding, Some(&assign.right)),
+                                _ => {
+                                    return self.error(
+                                        &anchor,
+                                        DiagnosticInfoMessage::PropKeyShouldBeIdent,
+                                    );
+                                }
+                            },
+                        };
+                        let value = match (&binding.type_ann, init) {
+                            (Some(ann), _) => self.extract_type(&ann.type_ann, file.clone())?,
+                            (None, Some(init)) => self.typeof_expr(init, false, file.clone())?,
+                            (None, None) => {
+                                return self.error(
+                                    &anchor,
+                                    DiagnosticInfoMessage::PropShouldHaveTypeAnnotation,
+                                );
+                            }
+                        };
+                        let value = if binding.id.optional {
+                            value.optional()
+                        } else {
+                            value.required()
+                        };
+                        properties.push((binding.id.sym.to_string(), value));
+                    }
+                }
+                ClassMember::TsIndexSignature(sig) if !sig.is_static => {
+                    index_signatures.push(TsTypeElement::TsIndexSignature(sig.clone()));
+                }
+                _ => {}
+            }
+        }
+
+        let indexed_properties = match self
+            .extract_ts_type_lit_members(&index_signatures, file)?
+            .kind
+        {
+            RuntypeKind::Object {
+                indexed_properties, ..
+            } => indexed_properties,
+            _ => vec![],
+        };
+
+        Ok(Runtype::new(RuntypeKind::Object {
+            vs: properties.into_iter().collect(),
+            indexed_properties,
+        }))
+    }
+
+    // the constructor of a class, generic classes are instantiated with their constraints
+    fn typeof_class(&mut self, class: &Class, file: BffFileName) -> Res<Runtype> {
+        let type_params = class
+            .type_params
+            .as_ref()
+            .map(|it| it.params.as_slice())
+            .unwrap_or(&[]);
+        let pushed = self.push_type_params_as_constraints(type_params, file.clone())?;
+        let res = self.typeof_class_inner(class, file);
+        for _ in 0..pushed {
+            self.type_application_stack.pop();
         }
-        Runtype::object(acc)
+        res
     }
-    fn convert_partial(&mut self, obj: &Runtype, anchor: &Anchor) -> Res<Runtype> {
+
+    fn typeof_class_inner(&mut self, class: &Class, file: BffFileName) -> Res<Runtype> {
+        let instance = self.extract_class_shape(class, file.clone())?;
+        // like functions, an overloaded constructor is seen through its last overload, the
+        // implementation signature after the overloads is not visible to callers
+        let constructors = class
+            .body
+            .iter()
+            .filter_map(|it| match it {
+                ClassMember::Constructor(ctor) => Some(ctor),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        let constructor = constructors
+            .iter()
+            .rev()
+            .find(|it| it.body.is_none())
+            .or(constructors.last());
+        let params = match constructor {
+            Some(ctor) => {
+                let pats = ctor
+                    .params
+                    .iter()
+                    .map(|it| match it {
+                        ParamOrTsParamProp::Param(param) => param.pat.clone(),
+                        ParamOrTsParamProp::TsParamProp(param_prop) => match &param_prop.param {
+                            TsParamPropParam::Ident(binding) => Pat::Ident(binding.clone()),
+                            TsParamPropParam::Assign(assign) => Pat::Assign(assign.clone()),
+                        },
+                    })
+                    .collect::<Vec<_>>();
+                let signature = self.extract_fn_signature_inner(
+                    &FnSignature {
+                        type_params: None,
+                        params: &pats.iter().collect::<Vec<_>>(),
+                        return_type: None,
+                        is_async: false,
+                        is_constructor: true,
+                    },
+                    file,
+                )?;
+                match signature.kind {
+                    RuntypeKind::Function { params, .. } => *params,
+                    _ => unreachable!("signatures are extracted as functions"),
+                }
+            }
+            // a derived class without a constructor inherits the one of its base
+            None => match &class.super_class {
+                Some(super_class) => match self.typeof_expr(super_class, false, file)?.kind {
+                    RuntypeKind::Function { params, .. } => *params,
+                    _ => Runtype::tuple(vec![], None),
+                },
+                None => Runtype::tuple(vec![], None),
+            },
+        };
+        Ok(Runtype::function_signature(params, instance, true))
+    }
+
+    fn convert_instance_of(&mut self, ty: &Runtype, anchor: &Anchor) -> Res<Runtype> {
+        if let RuntypeKind::Ref(RuntypeUUID {
+            ty: RuntypeName::Address(address),
+            ..
+        }) = &ty.kind
+            && let AddressedType::Class { .. } =
+                self.get_addressed_type(&address.to_module_item_addr(), anchor)?
+        {
+            let previous = self
+                .instance_of_classes
+                .insert(address.name.clone(), address.clone());
+            if previous.is_some_and(|it| it != *address) {
+                return self.error(
+                    anchor,
+                    DiagnosticInfoMessage::InstanceOfClassNameCollision(address.name.clone()),
+                );
+            }
+            if self.is_exported_class(address) {
+                self.exported_classes.insert(address.clone());
+            }
+            return Ok(Runtype::instance_of(address.clone()));
+        }
+        self.error(anchor, DiagnosticInfoMessage::InstanceOfShouldReferToClass)
+    }
+
+    // declaration files can only name the classes that are exported, classes of namespaces
+    // are not files and are never found
+    fn is_exported_class(&self, address: &TypeAddress) -> bool {
+        let Some(module) = self.files.get_existing_file(&address.file) else {
+            return false;
+        };
+        match module
+            .symbol_exports
+            .named_types
+            .get(&address.name)
+            .map(|it| it.as_ref())
+        {
+            Some(SymbolExport::ClassDecl { original_file, .. }) => *original_file == address.file,
+            _ => false,
+        }
+    }
+
+    // the named and indexed properties of an object type, references are followed
+    fn object_shape(&mut self, obj: &Runtype, anchor: &Anchor) -> Res<ObjectShape> {
         match &obj.kind {
             RuntypeKind::Object {
                 vs,
                 indexed_properties,
-            } => {
-                let new_vs = vs
-                    .iter()
-                    .map(|(k, v)| (k.clone(), v.clone().to_optional()))
-                    .collect();
-                let new_indexed = indexed_properties.as_ref().map(|ip| {
-                    Box::new(IndexedProperty {
-                        key: ip.key.clone(),
-                        value: ip.value.clone().to_optional(),
-                    })
-                });
-                Ok(Runtype::new(RuntypeKind::Object {
-                    vs: new_vs,
-                    indexed_properties: new_indexed,
-                }))
-            }
+            } => Ok((vs.clone(), indexed_properties.clone())),
             RuntypeKind::Ref(r) => {
                 let map = self
                     .partial_validators
@@ -1393,46 +2597,63 @@ impl<'a, R: FileManager> FrontendCtx<'a, R> {
                     .and_then(|it| it.as_ref())
                     .cloned();
                 match map {
-                    Some(schema) => self.convert_partial(&schema, anchor),
+                    Some(schema) => self.object_shape(&schema, anchor),
                     None => self.error(
                         anchor,
                         DiagnosticInfoMessage::ShouldHaveObjectAsTypeArgument,
                     ),
                 }
             }
-            _ => {
-                let extracted = self.extract_object_from_runtype(obj, anchor)?;
-                let new_vs = extracted
-                    .into_iter()
-                    .map(|(k, v)| (k, v.to_optional()))
-                    .collect();
-                Ok(Runtype::new(RuntypeKind::Object {
-                    vs: new_vs,
-                    indexed_properties: None,
-                }))
-            }
+            RuntypeKind::AllOf(_) => Ok((self.extract_object_from_runtype(obj, anchor)?, vec![])),
+            _ => self.error(
+                anchor,
+                DiagnosticInfoMessage::ShouldHaveObjectAsTypeArgument,
+            ),
         }
     }
-    fn convert_pick_keys(
-        obj: &BTreeMap<String, Optionality<Runtype>>,
-        keys: Vec<String>,
-    ) -> Runtype {
+
+    // homomorphic mapped types like Partial distribute over unions, the members
+    // the mapping leaves unchanged keep their names
+    fn convert_homomorphic(
+        &mut self,
+        obj: &Runtype,
+        map: fn(Optionality<Runtype>) -> Optionality<Runtype>,
+        anchor: &Anchor,
+    ) -> Res<Runtype> {
         let mut acc = vec![];
-        for (k, v) in obj {
-            if keys.contains(k) {
-                acc.push((k.clone(), v.clone()));
+        for member in self.distributive_members(obj) {
+            let (vs, indexed_properties) = self.object_shape(&member, anchor)?;
+            let new_vs: BTreeMap<_, _> = vs
+                .iter()
+                .map(|(k, v)| (k.clone(), map(v.clone())))
+                .collect();
+            let new_indexed: Vec<_> = indexed_properties
+                .iter()
+                .map(|ip| IndexedProperty {
+                    key: ip.key.clone(),
+                    value: map(ip.value.clone()),
+                })
+                .collect();
+            if new_vs == vs && new_indexed == indexed_properties {
+                acc.push(member);
+            } else {
+                acc.push(Runtype::new(RuntypeKind::Object {
+                    vs: new_vs,
+                    indexed_properties: new_indexed,
+                }));
             }
         }
-        Runtype::object(acc)
+        Ok(Runtype::any_of(acc))
     }
-    fn convert_pick(
-        &mut self,
-        obj: &BTreeMap<String, Optionality<Runtype>>,
-        keys: Runtype,
-        anchor: &Anchor,
-    ) -> Res<Runtype> {
+    fn convert_required(&mut self, obj: &Runtype, anchor: &Anchor) -> Res<Runtype> {
+        self.convert_homomorphic(obj, Optionality::to_required, anchor)
+    }
+    fn convert_partial(&mut self, obj: &Runtype, anchor: &Anchor) -> Res<Runtype> {
+        self.convert_homomorphic(obj, Optionality::to_optional, anchor)
+    }
+    fn pick_keys(&mut self, keys: Runtype, anchor: &Anchor) -> Res<Vec<String>> {
         match keys.extract_single_string_const() {
-            Some(str) => Ok(Self::convert_pick_keys(obj, vec![str])),
+            Some(str) => Ok(vec![str]),
             None => match keys.kind {
                 RuntypeKind::AnyOf(rms) => {
                     let mut keys = vec![];
@@ -1466,7 +2687,7 @@ impl<'a, R: FileManager> FrontendCtx<'a, R> {
                             },
                         }
                     }
-                    Ok(Self::convert_pick_keys(obj, keys))
+                    Ok(keys)
                 }
                 _ => self.error(
                     anchor,
@@ -1475,30 +2696,11 @@ impl<'a, R: FileManager> FrontendCtx<'a, R> {
             },
         }
     }
-    fn convert_omit_keys(
-        obj: &BTreeMap<String, Optionality<Runtype>>,
-        keys: Vec<String>,
-    ) -> Runtype {
-        let mut acc = vec![];
-        for (k, v) in obj {
-            if !keys.contains(k) {
-                acc.push((k.clone(), v.clone()));
-            }
-        }
-        Runtype::object(acc)
-    }
-
-    fn convert_omit(
-        &mut self,
-        obj: &BTreeMap<String, Optionality<Runtype>>,
-        keys: Runtype,
-        anchor: &Anchor,
-    ) -> Res<Runtype> {
+    fn omit_keys(&mut self, keys: Runtype, anchor: &Anchor) -> Res<Vec<String>> {
         let keys = self
             .extract_union(keys)
             .map_err(|e| self.box_error(anchor, e))?;
-        let str_keys = keys
-            .iter()
+        keys.iter()
             .map(|it| match it.extract_single_string_const() {
                 Some(str) => Ok(str.clone()),
                 _ => self.error(
@@ -1506,8 +2708,417 @@ impl<'a, R: FileManager> FrontendCtx<'a, R> {
                     DiagnosticInfoMessage::OmitShouldHaveStringAsTypeArgument,
                 ),
             })
-            .collect::<Res<Vec<_>>>()?;
-        Ok(Self::convert_omit_keys(obj, str_keys))
+            .collect::<Res<Vec<_>>>()
+    }
+
+    // Pick and Omit are not distributive, on a union they see the keys every member
+    // has, the value of a key is the union of its values in the members
+    fn convert_key_filter(
+        &mut self,
+        obj: &Runtype,
+        keep: impl Fn(&str) -> bool,
+        keep_indexed_properties: bool,
+        anchor: &Anchor,
+    ) -> Res<Runtype> {
+        let members = self.distributive_members(obj);
+        if let [member] = members.as_slice() {
+            let (vs, indexed_properties) = self.object_shape(member, anchor)?;
+            if vs.keys().all(|k| keep(k))
+                && (keep_indexed_properties || indexed_properties.is_empty())
+            {
+                return Ok(member.clone());
+            }
+            let vs = vs.into_iter().filter(|(k, _)| keep(k)).collect();
+            let indexed_properties = match keep_indexed_properties {
+                true => indexed_properties,
+                false => vec![],
+            };
+            return Ok(Runtype::new(RuntypeKind::Object {
+                vs,
+                indexed_properties,
+            }));
+        }
+        let mut common: Option<BTreeMap<String, Vec<Optionality<Runtype>>>> = None;
+        for member in &members {
+            let (vs, _) = self.object_shape(member, anchor)?;
+            common = Some(match common {
+                None => vs.into_iter().map(|(k, v)| (k, vec![v])).collect(),
+                Some(acc) => acc
+                    .into_iter()
+                    .filter_map(|(k, mut values)| {
+                        values.push(vs.get(&k)?.clone());
+                        Some((k, values))
+                    })
+                    .collect(),
+            });
+        }
+        let vs = common
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|(k, _)| keep(k))
+            .map(|(k, values)| {
+                let required = values.iter().all(|it| it.is_required());
+                let mut values: Vec<_> = values.into_iter().map(|it| it.inner_move()).collect();
+                values.sort();
+                values.dedup();
+                let value = Runtype::any_of(values);
+                let value = match required {
+                    true => value.required(),
+                    false => value.optional(),
+                };
+                (k, value)
+            })
+            .collect();
+        Ok(Runtype::new(RuntypeKind::Object {
+            vs,
+            indexed_properties: vec![],
+        }))
+    }
+    fn convert_pick(&mut self, obj: &Runtype, keys: Runtype, anchor: &Anchor) -> Res<Runtype> {
+        let keys = self.pick_keys(keys, anchor)?;
+        self.convert_key_filter(obj, |k| keys.iter().any(|it| it == k), false, anchor)
+    }
+    fn convert_omit(&mut self, obj: &Runtype, keys: Runtype, anchor: &Anchor) -> Res<Runtype> {
+        let keys = self.omit_keys(keys, anchor)?;
+        self.convert_key_filter(obj, |k| !keys.iter().any(|it| it == k), true, anchor)
+    }
+
+    fn convert_semtype_filter(
+        &mut self,
+        left: &Runtype,
+        right: &Runtype,
+        filter: SemTypeFilter,
+        anchor: &Anchor,
+    ) -> Res<Runtype> {
+        let mut ctx = SemTypeContext::new();
+
+        let validators_vec = self.validators_vec();
+        let validators_reference_vec: Vec<&NamedSchema> = validators_vec.iter().collect();
+
+        let right_st = right
+            .to_sem_type(&validators_reference_vec, &mut ctx)
+            .map_err(|e| {
+                self.box_error(anchor, DiagnosticInfoMessage::AnyhowError(e.to_string()))
+            })?;
+
+        // members that are kept or removed as a whole keep their names,
+        // only the ones that overlap partially go through the semantic conversion
+        let mut acc = vec![];
+        for member in self.distributive_members(left) {
+            let member_st = member
+                .to_sem_type(&validators_reference_vec, &mut ctx)
+                .map_err(|e| {
+                    self.box_error(anchor, DiagnosticInfoMessage::AnyhowError(e.to_string()))
+                })?;
+            let filtered_st = match filter {
+                SemTypeFilter::Exclude => member_st.diff(&right_st),
+                SemTypeFilter::Extract => member_st.intersect(&right_st),
+            }
+            .map_err(|e| {
+                self.box_error(anchor, DiagnosticInfoMessage::AnyhowError(e.to_string()))
+            })?;
+            let is_same = filtered_st
+                .is_same_type(&member_st, &mut ctx)
+                .map_err(|e| {
+                    self.box_error(anchor, DiagnosticInfoMessage::AnyhowError(e.to_string()))
+                })?;
+            if is_same {
+                acc.push(member);
+                continue;
+            }
+            let res = self
+                .semtype_to_runtype(filtered_st, &mut ctx, anchor)?
+                .remove_nots_of_intersections_and_empty_of_union(
+                    &validators_reference_vec,
+                    &mut ctx,
+                )
+                .map_err(|e| {
+                    self.box_error(anchor, DiagnosticInfoMessage::AnyhowError(e.to_string()))
+                })?;
+            if !matches!(res.kind, RuntypeKind::Never) {
+                acc.push(res);
+            }
+        }
+        Ok(Runtype::any_of(acc))
+    }
+
+    fn extract_fn_param(&mut self, pat: &Pat, file: BffFileName) -> Res<(Runtype, bool)> {
+        let (type_ann, optional) = match pat {
+            Pat::Ident(it) => (it.type_ann.as_deref(), it.id.optional),
+            Pat::Array(it) => (it.type_ann.as_deref(), it.optional),
+            Pat::Object(it) => (it.type_ann.as_deref(), it.optional),
+            Pat::Rest(it) => (it.type_ann.as_deref(), false),
+            // a parameter with a default value can be omitted by callers
+            Pat::Assign(it) => return Ok((self.extract_fn_param(&it.left, file)?.0, true)),
+            Pat::Invalid(_) | Pat::Expr(_) => (None, false),
+        };
+        let ty = match type_ann {
+            Some(ann) => self.extract_type(&ann.type_ann, file)?,
+            None => Runtype::any(),
+        };
+        Ok((ty, optional))
+    }
+
+    fn push_type_params_as_constraints(
+        &mut self,
+        type_params: &[TsTypeParam],
+        file: BffFileName,
+    ) -> Res<usize> {
+        let mut pushed = 0;
+        for tp in type_params {
+            let bound = match &tp.constraint {
+                Some(c) => self.extract_type(c, file.clone()),
+                None => Ok(Runtype::any()),
+            };
+            match bound {
+                Ok(bound) => {
+                    self.type_application_stack
+                        .push((tp.name.sym.to_string(), bound));
+                    pushed += 1;
+                }
+                Err(e) => {
+                    for _ in 0..pushed {
+                        self.type_application_stack.pop();
+                    }
+                    return Err(e);
+                }
+            }
+        }
+        Ok(pushed)
+    }
+
+    fn extract_fn_signature(&mut self, sig: FnSignature, file: BffFileName) -> Res<Runtype> {
+        // generic signatures are instantiated with their constraints
+        let type_params = sig
+            .type_params
+            .map(|it| it.params.as_slice())
+            .unwrap_or(&[]);
+        let pushed = match self.instantiation.take() {
+            Some((type_args, anchor)) => {
+                if type_args.len() != type_params.len() {
+                    return self.error(&anchor, DiagnosticInfoMessage::TypeArgumentCountMismatch);
+                }
+                for (tp, arg) in type_params.iter().zip(type_args) {
+                    self.type_application_stack
+                        .push((tp.name.sym.to_string(), arg));
+                }
+                type_params.len()
+            }
+            None => self.push_type_params_as_constraints(type_params, file.clone())?,
+        };
+        let signature = self.extract_fn_signature_inner(&sig, file);
+        for _ in 0..pushed {
+            self.type_application_stack.pop();
+        }
+        signature
+    }
+
+    fn extract_fn_signature_inner(&mut self, sig: &FnSignature, file: BffFileName) -> Res<Runtype> {
+        let mut parts = TupleParts::default();
+        for pat in sig.params {
+            if let Pat::Ident(it) = pat
+                && it.id.sym == "this"
+            {
+                continue;
+            }
+            let anchor = Anchor {
+                f: file.clone(),
+                s: pat.span(),
+            };
+            let (ty, optional) = self.extract_fn_param(pat, file.clone())?;
+            let pushed = if let Pat::Rest(_) = pat {
+                match self.resolve_ref(&ty).kind {
+                    RuntypeKind::Array(item) => parts.push_rest(*item),
+                    RuntypeKind::Tuple {
+                        prefix_items,
+                        optional_items,
+                        items,
+                        suffix_items,
+                    } => {
+                        if !prefix_items.is_empty() || !suffix_items.is_empty() {
+                            parts.require_optional();
+                        }
+                        parts.push_spread(prefix_items, optional_items, items, suffix_items)
+                    }
+                    RuntypeKind::Any => parts.push_rest(Runtype::any()),
+                    _ => Err(DiagnosticInfoMessage::RestParameterShouldBeArray),
+                }
+            } else if optional {
+                parts.push_optional(ty)
+            } else {
+                parts.require_optional();
+                parts.push_required(ty)
+            };
+            if let Err(msg) = pushed {
+                return self.error(&anchor, msg);
+            }
+        }
+        let params = parts.into_runtype();
+
+        let return_type = match sig.return_type.map(|it| &*it.type_ann) {
+            Some(TsType::TsTypePredicate(TsTypePredicate { asserts, .. })) => {
+                if *asserts {
+                    Runtype::void()
+                } else {
+                    Runtype::boolean()
+                }
+            }
+            Some(ty) => self.extract_type(ty, file)?,
+            None if sig.is_async => Runtype::promise(Runtype::any().into()),
+            None => Runtype::any(),
+        };
+
+        Ok(Runtype::function_signature(
+            params,
+            return_type,
+            sig.is_constructor,
+        ))
+    }
+
+    fn resolve_ref(&self, ty: &Runtype) -> Runtype {
+        match &ty.kind {
+            RuntypeKind::Ref(r) => match self.partial_validators.get(r).and_then(|it| it.clone()) {
+                Some(v) => self.resolve_ref(&v),
+                None => ty.clone(),
+            },
+            _ => ty.clone(),
+        }
+    }
+
+    fn convert_function_utility(
+        &mut self,
+        utility: FunctionUtility,
+        ty: &Runtype,
+        anchor: &Anchor,
+    ) -> Res<Runtype> {
+        let mut acc = vec![];
+        for member in self.distributive_members(ty) {
+            let member = self.resolve_ref(&member);
+            let res = match (&member.kind, utility) {
+                (RuntypeKind::Any, FunctionUtility::ReturnType) => Runtype::any(),
+                (RuntypeKind::Any, _) => Runtype::array(Runtype::any().into()),
+                (
+                    RuntypeKind::Function {
+                        params,
+                        is_constructor: false,
+                        ..
+                    },
+                    FunctionUtility::Parameters,
+                )
+                | (
+                    RuntypeKind::Function {
+                        params,
+                        is_constructor: true,
+                        ..
+                    },
+                    FunctionUtility::ConstructorParameters,
+                ) => *params.clone(),
+                (
+                    RuntypeKind::Function {
+                        return_type,
+                        is_constructor: false,
+                        ..
+                    },
+                    FunctionUtility::ReturnType,
+                ) => *return_type.clone(),
+                (RuntypeKind::Ref(r), _) => {
+                    return self.error(
+                        anchor,
+                        DiagnosticInfoMessage::CannotResolveRefInExtractUnion(r.clone()),
+                    );
+                }
+                _ => {
+                    return self.error(
+                        anchor,
+                        DiagnosticInfoMessage::FunctionUtilityOnNonFunction(
+                            utility.name().to_string(),
+                        ),
+                    );
+                }
+            };
+            acc.push(res);
+        }
+        Ok(Runtype::any_of(acc))
+    }
+
+    fn convert_awaited(&mut self, ty: &Runtype) -> Runtype {
+        let members = self
+            .distributive_members(ty)
+            .into_iter()
+            .map(|member| match self.resolve_ref(&member).kind {
+                RuntypeKind::Promise(inner) => self.convert_awaited(&inner),
+                _ => member,
+            })
+            .collect();
+        Runtype::any_of(members)
+    }
+
+    // `K & string` in generic code, `"a" & string` becomes `"a"`
+    fn normalize_intersection(&mut self, ty: &Runtype, anchor: &Anchor) -> Res<Option<Runtype>> {
+        let mut ctx = SemTypeContext::new();
+        let validators_vec = self.validators_vec();
+        let validators_reference_vec: Vec<&NamedSchema> = validators_vec.iter().collect();
+        let st = ty
+            .to_sem_type(&validators_reference_vec, &mut ctx)
+            .map_err(|e| {
+                self.box_error(anchor, DiagnosticInfoMessage::AnyhowError(e.to_string()))
+            })?;
+        let normalized = self
+            .semtype_to_runtype(st, &mut ctx, anchor)?
+            .remove_nots_of_intersections_and_empty_of_union(&validators_reference_vec, &mut ctx)
+            .map_err(|e| {
+                self.box_error(anchor, DiagnosticInfoMessage::AnyhowError(e.to_string()))
+            })?;
+        // `None` when the semantic types cannot simplify it further
+        match normalized.kind {
+            RuntypeKind::AllOf(_) => Ok(None),
+            _ => Ok(Some(normalized)),
+        }
+    }
+
+    fn convert_string_intrinsic(
+        &mut self,
+        intrinsic: StringIntrinsic,
+        ty: &Runtype,
+        anchor: &Anchor,
+    ) -> Res<Runtype> {
+        match &ty.kind {
+            RuntypeKind::String => Ok(Runtype::tpl_lit_type(TplLitType(vec![
+                TplLitTypeItem::IntrinsicString(intrinsic),
+            ]))),
+            RuntypeKind::TplLitType(tpl) => {
+                Ok(Runtype::tpl_lit_type(tpl.apply_intrinsic(intrinsic)))
+            }
+            RuntypeKind::AnyOf(vs) => {
+                let mut acc = vec![];
+                for v in vs {
+                    acc.push(self.convert_string_intrinsic(intrinsic, v, anchor)?);
+                }
+                Ok(Runtype::any_of(acc))
+            }
+            RuntypeKind::Ref(r) => {
+                let v = self.partial_validators.get(r).and_then(|it| it.clone());
+                match v {
+                    Some(v) => self.convert_string_intrinsic(intrinsic, &v, anchor),
+                    None => self.error(
+                        anchor,
+                        DiagnosticInfoMessage::CannotResolveRefInExtractUnion(r.clone()),
+                    ),
+                }
+            }
+            RuntypeKind::AllOf(_) => match self.normalize_intersection(ty, anchor)? {
+                Some(normalized) => self.convert_string_intrinsic(intrinsic, &normalized, anchor),
+                None => self.error(
+                    anchor,
+                    DiagnosticInfoMessage::StringIntrinsicOnNonString(intrinsic.name().to_string()),
+                ),
+            },
+            RuntypeKind::Never => Ok(Runtype::never()),
+            _ => self.error(
+                anchor,
+                DiagnosticInfoMessage::StringIntrinsicOnNonString(intrinsic.name().to_string()),
+            ),
+        }
     }
 
     fn extract_addressed_type(
@@ -1526,6 +3137,7 @@ impl<'a, R: FileManager> FrontendCtx<'a, R> {
                         local_address: address,
                         declaration_span,
                     } => {
+                        self.record_declaration(runtype_name, &address.file, declaration_span);
                         let type_params = match &decl.type_params {
                             Some(p) => {
                                 //
@@ -1546,27 +3158,36 @@ impl<'a, R: FileManager> FrontendCtx<'a, R> {
                             self.type_application_stack.pop();
                         }
                         let runtype = runtype?;
-                        Ok(self.with_jsdoc(&address.file, declaration_span, runtype))
+                        self.with_jsdoc(&address.file, declaration_span, runtype)
                     }
                     AddressedType::Interface {
                         t,
                         local_address: address,
                         declaration_span,
                     } => {
-                        let runtype = self.extract_interface_decl(
-                            &t,
-                            type_args,
-                            module_item_address.file.clone(),
-                        )?;
-                        Ok(self.with_jsdoc(&address.file, declaration_span, runtype))
+                        self.record_declaration(runtype_name, &address.file, declaration_span);
+                        let decls = self.interface_declarations(&t, &address);
+                        let runtype = self.extract_interface_decl(&decls, type_args)?;
+                        self.with_jsdoc(&address.file, declaration_span, runtype)
                     }
                     AddressedType::Enum {
                         t,
                         local_address: address,
                     } => {
+                        self.record_declaration(runtype_name, &address.file, t.span);
                         let runtype = self.extract_enum_decl(&t, address.file.clone())?;
                         Ok(runtype)
                     }
+                    AddressedType::Class {
+                        t,
+                        local_address: address,
+                        declaration_span,
+                    } => {
+                        self.record_declaration(runtype_name, &address.file, declaration_span);
+                        let runtype =
+                            self.extract_class_decl(&t, type_args, address.file.clone())?;
+                        self.with_jsdoc(&address.file, declaration_span, runtype)
+                    }
                 }
             }
             RuntypeName::EnumItem {
@@ -1576,14 +3197,12 @@ impl<'a, R: FileManager> FrontendCtx<'a, R> {
                 let ty =
                     self.get_addressed_qualified_type(&enum_type.to_module_item_addr(), anchor)?;
                 if let AddressedQualifiedType::WillBeUsedForEnumItem { enum_type, address } = ty {
-                    let found = enum_type.members.iter().find(|it| match &it.id {
-                        TsEnumMemberId::Ident(ident) => &ident.sym == member_name,
-                        TsEnumMemberId::Str(_) => unreachable!(),
-                    });
-                    return match found.and_then(|it| it.init.clone()) {
-                        Some(init) => self.typeof_expr(&init, true, address.file.clone()),
-                        None => self.error(anchor, DiagnosticInfoMessage::EnumMemberNoInit),
-                    };
+                    return self.enum_member_value(
+                        &enum_type,
+                        member_name,
+                        address.file.clone(),
+                        anchor,
+                    );
                 };
                 self.error(anchor, DiagnosticInfoMessage::EnumItemShouldBeFromEnumType)
             }
@@ -1605,6 +3224,20 @@ impl<'a, R: FileManager> FrontendCtx<'a, R> {
                     self.get_string_format_extends(&type_args, anchor)
                 }
                 TsBuiltIn::NumberFormat => self.get_number_with_format(&type_args, anchor),
+                TsBuiltIn::Int => Ok(Runtype::number_range(NumberRange {
+                    integer: true,
+                    minimum: None,
+                    maximum: None,
+                })),
+                TsBuiltIn::NonNegative => Ok(Runtype::number_range(NumberRange {
+                    integer: false,
+                    minimum: Some(NumberBound {
+                        value: N::parse_int(0),
+                        exclusive: false,
+                    }),
+                    maximum: None,
+                })),
+                TsBuiltIn::Range => self.get_number_range(&type_args, anchor),
                 TsBuiltIn::NumberFormatExtends => {
                     self.get_number_format_extends(&type_args, anchor)
                 }
@@ -1650,12 +3283,12 @@ impl<'a, R: FileManager> FrontendCtx<'a, R> {
                                 .map(|k| (k, value.clone().required()))
                                 .collect();
                             let indexed_properties = if indexed_parts.is_empty() {
-                                None
+                                vec![]
                             } else {
-                                Some(Box::new(IndexedProperty {
+                                vec![IndexedProperty {
                                     key: Runtype::any_of(indexed_parts),
                                     value: value.clone().required(),
-                                }))
+                                }]
                             };
                             Ok(Runtype::new(RuntypeKind::Object {
                                 vs,
@@ -1664,31 +3297,22 @@ impl<'a, R: FileManager> FrontendCtx<'a, R> {
                         }
                         Err(_) => Ok(Runtype::new(RuntypeKind::Object {
                             vs: BTreeMap::new(),
-                            indexed_properties: Some(
-                                IndexedProperty {
-                                    key: key_clone,
-                                    value: value.required(),
-                                }
-                                .into(),
-                            ),
+                            indexed_properties: vec![IndexedProperty {
+                                key: key_clone,
+                                value: value.required(),
+                            }],
                         })),
                     }
                 }
                 TsBuiltIn::Pick => match type_args.as_slice() {
-                    [obj, keys] => {
-                        let vs = self.extract_object_from_runtype(obj, anchor)?;
-                        self.convert_pick(&vs, keys.clone(), anchor)
-                    }
+                    [obj, keys] => self.convert_pick(obj, keys.clone(), anchor),
                     _ => self.error(
                         anchor,
                         DiagnosticInfoMessage::PickShouldHaveTwoTypeArguments,
                     ),
                 },
                 TsBuiltIn::Omit => match type_args.as_slice() {
-                    [obj, keys] => {
-                        let vs = self.extract_object_from_runtype(obj, anchor)?;
-                        self.convert_omit(&vs, keys.clone(), anchor)
-                    }
+                    [obj, keys] => self.convert_omit(obj, keys.clone(), anchor),
                     _ => self.error(
                         anchor,
                         DiagnosticInfoMessage::OmitShouldHaveTwoTypeArguments,
@@ -1696,59 +3320,41 @@ impl<'a, R: FileManager> FrontendCtx<'a, R> {
                 },
                 TsBuiltIn::Exclude => match type_args.as_slice() {
                     [left, right] => {
-                        let mut ctx = SemTypeContext::new();
-
-                        let left_ty = left.clone();
-
-                        let validators_vec = self.validators_vec();
-                        let validators_reference_vec: Vec<&NamedSchema> =
-                            validators_vec.iter().collect();
-
-                        let left_st = left_ty
-                            .to_sem_type(&validators_reference_vec, &mut ctx)
-                            .map_err(|e| {
-                                self.box_error(
-                                    anchor,
-                                    DiagnosticInfoMessage::AnyhowError(e.to_string()),
-                                )
-                            })?;
-
-                        let right_ty = right.clone();
-
-                        let right_st = right_ty
-                            .to_sem_type(&validators_reference_vec, &mut ctx)
-                            .map_err(|e| {
-                                self.box_error(
-                                    anchor,
-                                    DiagnosticInfoMessage::AnyhowError(e.to_string()),
-                                )
-                            })?;
-
-                        let subtracted_ty = left_st.diff(&right_st).map_err(|e| {
-                            self.box_error(
-                                anchor,
-                                DiagnosticInfoMessage::AnyhowError(e.to_string()),
-                            )
-                        })?;
-                        let res = self
-                            .semtype_to_runtype(subtracted_ty, &mut ctx, anchor)?
-                            .remove_nots_of_intersections_and_empty_of_union(
-                                &validators_reference_vec,
-                                &mut ctx,
-                            )
-                            .map_err(|e| {
-                                self.box_error(
-                                    anchor,
-                                    DiagnosticInfoMessage::AnyhowError(e.to_string()),
-                                )
-                            })?;
-                        Ok(res)
+                        self.convert_semtype_filter(left, right, SemTypeFilter::Exclude, anchor)
                     }
                     _ => self.error(
                         anchor,
                         DiagnosticInfoMessage::ExcludeShouldHaveTwoTypeArguments,
                     ),
                 },
+                TsBuiltIn::Extract => match type_args.as_slice() {
+                    [left, right] => {
+                        self.convert_semtype_filter(left, right, SemTypeFilter::Extract, anchor)
+                    }
+                    _ => self.error(
+                        anchor,
+                        DiagnosticInfoMessage::ExtractShouldHaveTwoTypeArguments,
+                    ),
+                },
+                TsBuiltIn::NonNullable => match type_args.as_slice() {
+                    [ty] => {
+                        let nullish = Runtype::any_of(vec![Runtype::null(), Runtype::undefined()]);
+                        self.convert_semtype_filter(ty, &nullish, SemTypeFilter::Exclude, anchor)
+                    }
+                    _ => self.error(
+                        anchor,
+                        DiagnosticInfoMessage::NonNullableShouldHaveOneTypeArgument,
+                    ),
+                },
+                TsBuiltIn::StringIntrinsic(intrinsic) => match type_args.as_slice() {
+                    [ty] => self.convert_string_intrinsic(*intrinsic, ty, anchor),
+                    _ => self.error(
+                        anchor,
+                        DiagnosticInfoMessage::StringIntrinsicShouldHaveOneTypeArgument(
+                            intrinsic.name().to_string(),
+                        ),
+                    ),
+                },
                 TsBuiltIn::Readonly => match type_args.as_slice() {
                     [obj] => Ok(obj.clone()),
                     _ => self.error(
@@ -1757,10 +3363,7 @@ impl<'a, R: FileManager> FrontendCtx<'a, R> {
                     ),
                 },
                 TsBuiltIn::Required => match type_args.as_slice() {
-                    [obj] => {
-                        let vs = self.extract_object_from_runtype(obj, anchor)?;
-                        Ok(self.convert_required(&vs))
-                    }
+                    [obj] => self.convert_required(obj, anchor),
                     _ => self.error(
                         anchor,
                         DiagnosticInfoMessage::RequiredShouldHaveTwoTypeArguments,
@@ -1773,6 +3376,58 @@ impl<'a, R: FileManager> FrontendCtx<'a, R> {
                         DiagnosticInfoMessage::PartialShouldHaveTwoTypeArguments,
                     ),
                 },
+                TsBuiltIn::Parameters => match type_args.as_slice() {
+                    [ty] => self.convert_function_utility(FunctionUtility::Parameters, ty, anchor),
+                    _ => self.error(
+                        anchor,
+                        DiagnosticInfoMessage::FunctionUtilityShouldHaveOneTypeArgument(
+                            FunctionUtility::Parameters.name().to_string(),
+                        ),
+                    ),
+                },
+                TsBuiltIn::ConstructorParameters => match type_args.as_slice() {
+                    [ty] => self.convert_function_utility(
+                        FunctionUtility::ConstructorParameters,
+                        ty,
+                        anchor,
+                    ),
+                    _ => self.error(
+                        anchor,
+                        DiagnosticInfoMessage::FunctionUtilityShouldHaveOneTypeArgument(
+                            FunctionUtility::ConstructorParameters.name().to_string(),
+                        ),
+                    ),
+                },
+                TsBuiltIn::ReturnType => match type_args.as_slice() {
+                    [ty] => self.convert_function_utility(FunctionUtility::ReturnType, ty, anchor),
+                    _ => self.error(
+                        anchor,
+                        DiagnosticInfoMessage::FunctionUtilityShouldHaveOneTypeArgument(
+                            FunctionUtility::ReturnType.name().to_string(),
+                        ),
+                    ),
+                },
+                TsBuiltIn::Promise => match type_args.as_slice() {
+                    [value] => Ok(Runtype::promise(Box::new(value.clone()))),
+                    _ => self.error(
+                        anchor,
+                        DiagnosticInfoMessage::PromiseShouldHaveOneTypeArgument,
+                    ),
+                },
+                TsBuiltIn::InstanceOf => match type_args.as_slice() {
+                    [ty] => self.convert_instance_of(ty, anchor),
+                    _ => self.error(
+                        anchor,
+                        DiagnosticInfoMessage::InstanceOfShouldHaveOneTypeArgument,
+                    ),
+                },
+                TsBuiltIn::Awaited => match type_args.as_slice() {
+                    [ty] => Ok(self.convert_awaited(ty)),
+                    _ => self.error(
+                        anchor,
+                        DiagnosticInfoMessage::AwaitedShouldHaveOneTypeArgument,
+                    ),
+                },
                 TsBuiltIn::Map => match type_args.as_slice() {
                     [key, value] => {
                         Ok(Runtype::map(Box::new(key.clone()), Box::new(value.clone())))
@@ -1839,6 +3494,9 @@ impl<'a, R: FileManager> FrontendCtx<'a, R> {
             "StringFormatExtends" => Some(TsBuiltIn::StringFormatExtends),
             "NumberFormat" => Some(TsBuiltIn::NumberFormat),
             "NumberFormatExtends" => Some(TsBuiltIn::NumberFormatExtends),
+            "Int" => Some(TsBuiltIn::Int),
+            "NonNegative" => Some(TsBuiltIn::NonNegative),
+            "Range" => Some(TsBuiltIn::Range),
             "Record" => Some(TsBuiltIn::Record),
             "Omit" => Some(TsBuiltIn::Omit),
             "Object" => Some(TsBuiltIn::Object),
@@ -1847,6 +3505,18 @@ impl<'a, R: FileManager> FrontendCtx<'a, R> {
             "Partial" => Some(TsBuiltIn::Partial),
             "Pick" => Some(TsBuiltIn::Pick),
             "Exclude" => Some(TsBuiltIn::Exclude),
+            "Extract" => Some(TsBuiltIn::Extract),
+            "NonNullable" => Some(TsBuiltIn::NonNullable),
+            "Uppercase" => Some(TsBuiltIn::StringIntrinsic(StringIntrinsic::Uppercase)),
+            "Lowercase" => Some(TsBuiltIn::StringIntrinsic(StringIntrinsic::Lowercase)),
+            "Capitalize" => Some(TsBuiltIn::StringIntrinsic(StringIntrinsic::Capitalize)),
+            "Uncapitalize" => Some(TsBuiltIn::StringIntrinsic(StringIntrinsic::Uncapitalize)),
+            "Parameters" => Some(TsBuiltIn::Parameters),
+            "ConstructorParameters" => Some(TsBuiltIn::ConstructorParameters),
+            "ReturnType" => Some(TsBuiltIn::ReturnType),
+            "Promise" => Some(TsBuiltIn::Promise),
+            "Awaited" => Some(TsBuiltIn::Awaited),
+            "InstanceOf" => Some(TsBuiltIn::InstanceOf),
             "Map" => Some(TsBuiltIn::Map),
             "Set" => Some(TsBuiltIn::Set),
             "Uint8Array" => Some(TsBuiltIn::TypedArray(TypedArrayKind::Uint8Array)),
@@ -1865,6 +3535,24 @@ impl<'a, R: FileManager> FrontendCtx<'a, R> {
         Ok(bt)
     }
 
+    // user types shadow the built-ins of the same name
+    fn declares_type(&mut self, name: &str, file: &BffFileName, anchor: &Anchor) -> Res<bool> {
+        let parsed_module = self.get_or_fetch_file(file, anchor)?;
+        let locals = &parsed_module.locals;
+        if locals.type_aliases.contains_key(name)
+            || locals.interfaces.contains_key(name)
+            || locals.enums.contains_key(name)
+            || locals.classes.contains_key(name)
+            || parsed_module.imports.contains_key(name)
+        {
+            return Ok(true);
+        }
+        match &parsed_module.parent_scope {
+            Some(parent_scope) => self.declares_type(name, parent_scope, anchor),
+            None => Ok(false),
+        }
+    }
+
     fn get_runtype_name_from_ts_entity_name(
         &mut self,
         type_name: &TsEntityName,
@@ -1874,7 +3562,9 @@ impl<'a, R: FileManager> FrontendCtx<'a, R> {
     ) -> Res<RuntypeName> {
         match type_name {
             TsEntityName::Ident(ident) => {
-                if let Some(builtin) = self.maybe_generate_ts_builtin(&ident.sym)? {
+                if !self.declares_type(&ident.sym, &file, anchor)?
+                    && let Some(builtin) = self.maybe_generate_ts_builtin(&ident.sym)?
+                {
                     Ok(RuntypeName::BuiltIn(builtin))
                 } else {
                     let addr: ModuleItemAddress =
@@ -1984,10 +3674,58 @@ impl<'a, R: FileManager> FrontendCtx<'a, R> {
                 return self.error(anchor, DiagnosticInfoMessage::CustomStringIsNotRegistered);
             }
         }
-        self.error(
-            anchor,
-            DiagnosticInfoMessage::InvalidUsageOfStringFormatExtendsTypeParameter,
-        )
+        self.error(
+            anchor,
+            DiagnosticInfoMessage::InvalidUsageOfStringFormatExtendsTypeParameter,
+        )
+    }
+    /// A bound of `Range`, `None` when the argument is `number`.
+    fn get_number_range_bound(&mut self, arg: &Runtype, anchor: &Anchor) -> Res<Option<N>> {
+        match &arg.kind {
+            RuntypeKind::Const(RuntypeConst::Number(n)) => Ok(Some(n.clone())),
+            RuntypeKind::Number => Ok(None),
+            RuntypeKind::Ref(r) => {
+                let v = self.partial_validators.get(r).and_then(|it| it.clone());
+                match v {
+                    Some(v) => self.get_number_range_bound(&v, anchor),
+                    None => self.error(
+                        anchor,
+                        DiagnosticInfoMessage::RangeBoundShouldBeNumberLiteral,
+                    ),
+                }
+            }
+            _ => self.error(
+                anchor,
+                DiagnosticInfoMessage::RangeBoundShouldBeNumberLiteral,
+            ),
+        }
+    }
+    fn get_number_range(&mut self, type_args: &[Runtype], anchor: &Anchor) -> Res<Runtype> {
+        let [minimum, maximum] = type_args else {
+            return self.error(
+                anchor,
+                DiagnosticInfoMessage::RangeShouldHaveTwoTypeArguments,
+            );
+        };
+        let minimum = self.get_number_range_bound(minimum, anchor)?;
+        let maximum = self.get_number_range_bound(maximum, anchor)?;
+        if let (Some(min), Some(max)) = (&minimum, &maximum)
+            && min.to_f64() > max.to_f64()
+        {
+            return self.error(anchor, DiagnosticInfoMessage::RangeMinimumAboveMaximum);
+        }
+        if minimum.is_none() && maximum.is_none() {
+            return Ok(Runtype::number());
+        }
+        let bound = |value| NumberBound {
+            value,
+            exclusive: false,
+        };
+        Ok(Runtype::number_range(NumberRange {
+            integer: false,
+            minimum: minimum.map(bound),
+            maximum: maximum.map(bound),
+        }))
     }
     fn get_number_with_format(&mut self, type_args: &[Runtype], anchor: &Anchor) -> Res<Runtype> {
         if let [head] = type_args
@@ -2072,18 +3810,42 @@ impl<'a, R: FileManager> FrontendCtx<'a, R> {
             }
         }
 
-        let type_args = match ts_type_args {
-            Some(its) => {
-                let mut args = vec![];
-                for ty in &its.params {
-                    let arg_ty = self.extract_type(ty, file.clone())?;
-                    args.push(arg_ty);
-                }
-                args
-            }
-            None => vec![],
-        };
+        let type_args = self.extract_type_args(ts_type_args, file.clone())?;
+        self.extract_type_from_ts_entity_name_with_args(
+            type_name,
+            type_args,
+            ts_type_args.is_some(),
+            file,
+            visibility,
+            anchor,
+        )
+    }
+
+    fn extract_type_args(
+        &mut self,
+        ts_type_args: &Option<Box<TsTypeParamInstantiation>>,
+        file: BffFileName,
+    ) -> Res<Vec<Runtype>> {
+        match ts_type_args {
+            Some(its) => its
+                .params
+                .iter()
+                .map(|ty| self.extract_type(ty, file.clone()))
+                .collect(),
+            None => Ok(vec![]),
+        }
+    }
 
+    // the type arguments are already extracted, in the scope they are written in
+    fn extract_type_from_ts_entity_name_with_args(
+        &mut self,
+        type_name: &TsEntityName,
+        type_args: Vec<Runtype>,
+        has_type_args: bool,
+        file: BffFileName,
+        visibility: Visibility,
+        anchor: &Anchor,
+    ) -> Res<Runtype> {
         let fat =
             self.get_runtype_name_from_ts_entity_name(type_name, file.clone(), visibility, anchor)?;
         if fat.is_builtin() {
@@ -2096,11 +3858,26 @@ impl<'a, R: FileManager> FrontendCtx<'a, R> {
         };
         let found = self.partial_validators.get(&rt_uuid);
         if let Some(_found_in_map) = found {
-            if ts_type_args.is_some() {
+            if has_type_args {
                 self.recursive_generic_uuids.insert(rt_uuid.clone());
             }
             return Ok(Runtype::ref_(rt_uuid));
         }
+        if !type_args.is_empty() {
+            // a generic that refers to itself with different type arguments would
+            // instantiate a new definition at every step, it is never finite
+            let in_progress = self
+                .partial_validators
+                .iter()
+                .filter(|(k, v)| v.is_none() && k.ty == fat)
+                .count();
+            if in_progress >= MAX_GENERIC_INSTANTIATION_DEPTH {
+                return self.error(
+                    anchor,
+                    DiagnosticInfoMessage::CannotHaveRecursiveGenericTypes,
+                );
+            }
+        }
         self.partial_validators.insert(rt_uuid.clone(), None);
 
         let ty = self.extract_addressed_type(&fat, type_args, anchor);
@@ -2191,11 +3968,15 @@ impl<'a, R: FileManager> FrontendCtx<'a, R> {
         match arr.kind {
             RuntypeKind::Tuple {
                 mut prefix_items,
+                optional_items,
                 items,
+                suffix_items,
             } => {
+                prefix_items.extend(optional_items);
                 if let Some(r) = items {
                     prefix_items.push(*r);
                 }
+                prefix_items.extend(suffix_items);
                 Ok(prefix_items)
             }
             RuntypeKind::Ref(n) => {
@@ -2221,18 +4002,22 @@ impl<'a, R: FileManager> FrontendCtx<'a, R> {
         match e {
             Expr::Tpl(s) => {
                 if as_const {
-                    let mut acc: Vec<TplLitTypeItem> = vec![];
-
-                    for it in &s.exprs {
-                        let ty = match it.as_ref() {
-                            Expr::Call(_) => Ok(Runtype::string()),
-                            _ => self.typeof_expr(it, as_const, file.clone()),
-                        }?;
-                        let res = self.runtype_to_tpl_lit(&it.span(), &ty, file.clone())?;
-                        acc.push(res);
+                    let mut parts = vec![];
+                    for (idx, quasi) in s.quasis.iter().enumerate() {
+                        let text = match &quasi.cooked {
+                            Some(cooked) => cooked.to_string_lossy().to_string(),
+                            None => quasi.raw.to_string(),
+                        };
+                        parts.push(vec![vec![TplLitTypeItem::StringConst(text)]]);
+                        if let Some(it) = s.exprs.get(idx) {
+                            let ty = match it.as_ref() {
+                                Expr::Call(_) => Ok(Runtype::string()),
+                                _ => self.typeof_expr(it, as_const, file.clone()),
+                            }?;
+                            parts.push(self.runtype_to_tpl_lit(&it.span(), &ty, file.clone())?);
+                        }
                     }
-
-                    Ok(Runtype::tpl_lit_type(TplLitType(acc)))
+                    self.tpl_lit_from_parts(&anchor, parts)
                 } else {
                     Ok(Runtype::string())
                 }
@@ -2413,17 +4198,12 @@ impl<'a, R: FileManager> FrontendCtx<'a, R> {
                         if !as_const {
                             return self.extract_enum_decl(&from_enum, enum_file_name.clone());
                         }
-                        let Some(enum_value) = from_enum.members.iter().find(|it| match &it.id {
-                            TsEnumMemberId::Ident(i) => i.sym == *key,
-                            TsEnumMemberId::Str(_) => unreachable!(),
-                        }) else {
-                            return self.error(&anchor, DiagnosticInfoMessage::EnumMemberNotFound);
-                        };
-                        let Some(init) = &enum_value.init else {
-                            return self.error(&anchor, DiagnosticInfoMessage::EnumMemberNoInit);
-                        };
-
-                        return self.typeof_expr(init, true, enum_file_name.clone());
+                        return self.enum_member_value(
+                            &from_enum,
+                            key,
+                            enum_file_name.clone(),
+                            &anchor,
+                        );
                     }
                 }
                 let obj = self.typeof_expr(&m.obj, as_const, file.clone())?;
@@ -2440,7 +4220,27 @@ impl<'a, R: FileManager> FrontendCtx<'a, R> {
                 };
                 self.do_indexed_access_on_types(&obj, &key, &anchor)
             }
-            Expr::Arrow(_a) => Ok(Runtype::function()),
+            Expr::Arrow(a) => self.extract_fn_signature(
+                FnSignature {
+                    type_params: a.type_params.as_deref(),
+                    params: &a.params.iter().collect::<Vec<_>>(),
+                    return_type: a.return_type.as_deref(),
+                    is_async: a.is_async,
+                    is_constructor: false,
+                },
+                file,
+            ),
+            Expr::Fn(FnExpr { function, .. }) => self.extract_fn_signature(
+                FnSignature {
+                    type_params: function.type_params.as_deref(),
+                    params: &function.params.iter().map(|it| &it.pat).collect::<Vec<_>>(),
+                    return_type: function.return_type.as_deref(),
+                    is_async: function.is_async,
+                    is_constructor: false,
+                },
+                file,
+            ),
+            Expr::Class(ClassExpr { class, .. }) => self.typeof_class(class, file),
             Expr::Bin(e) => {
                 let left = self.typeof_expr(&e.left, as_const, file.clone())?;
                 let right = self.typeof_expr(&e.right, as_const, file.clone())?;
@@ -2455,10 +4255,13 @@ impl<'a, R: FileManager> FrontendCtx<'a, R> {
                     _ => self.error(&anchor, DiagnosticInfoMessage::CannotConvertExpr),
                 }
             }
-            _ => {
-                dbg!(&e);
-                self.error(&anchor, DiagnosticInfoMessage::CannotConvertExpr)
+            Expr::TsInstantiation(i) => {
+                let type_args = self.extract_type_args(&Some(i.type_args.clone()), file.clone())?;
+                self.with_instantiation(type_args, &anchor, |ctx| {
+                    ctx.typeof_expr(&i.expr, as_const, file)
+                })
             }
+            _ => self.error(&anchor, DiagnosticInfoMessage::CannotConvertExpr),
         }
     }
 
@@ -2606,17 +4409,7 @@ impl<'a, R: FileManager> FrontendCtx<'a, R> {
                 self.do_indexed_access_on_types(&obj, &key, anchor)
             }
             AddressedQualifiedValue::Enum(ts_enum_decl, bff_file_name) => {
-                let Some(enum_value) = ts_enum_decl.members.iter().find(|it| match &it.id {
-                    TsEnumMemberId::Ident(i) => i.sym == *member,
-                    TsEnumMemberId::Str(_) => unreachable!(),
-                }) else {
-                    return self.error(anchor, DiagnosticInfoMessage::EnumMemberNotFound);
-                };
-                let Some(init) = &enum_value.init else {
-                    return self.error(anchor, DiagnosticInfoMessage::EnumMemberNoInit);
-                };
-
-                self.typeof_expr(init, true, bff_file_name.clone())
+                self.enum_member_value(ts_enum_decl, member, bff_file_name.clone(), anchor)
             }
         }
     }
@@ -2663,18 +4456,49 @@ impl<'a, R: FileManager> FrontendCtx<'a, R> {
             f: file.clone(),
             s: ty.span,
         };
-        if ty.type_args.is_some() {
-            return self.error(&anchor, DiagnosticInfoMessage::TypeQueryArgsNotSupported);
+        let type_args = match &ty.expr_name {
+            TsTypeQueryExpr::Import(import_type) if ty.type_args.is_none() => {
+                &import_type.type_args
+            }
+            _ => &ty.type_args,
+        };
+        if type_args.is_none() {
+            return self.extract_type_query_value(ty, file, visibility, &anchor);
+        }
+        // an instantiation expression, its arguments are written in this file
+        let type_args = self.extract_type_args(type_args, file.clone())?;
+        self.with_instantiation(type_args, &anchor, |ctx| {
+            ctx.extract_type_query_value(ty, file, visibility, &anchor)
+        })
+    }
+
+    fn with_instantiation(
+        &mut self,
+        type_args: Vec<Runtype>,
+        anchor: &Anchor,
+        extract: impl FnOnce(&mut Self) -> Res<Runtype>,
+    ) -> Res<Runtype> {
+        self.instantiation = Some((type_args, anchor.clone()));
+        let res = extract(self);
+        let unused = self.instantiation.take().is_some();
+        match res {
+            Ok(_) if unused => self.error(anchor, DiagnosticInfoMessage::TypeQueryArgsNotSupported),
+            res => res,
         }
+    }
 
+    fn extract_type_query_value(
+        &mut self,
+        ty: &TsTypeQuery,
+        file: BffFileName,
+        visibility: Visibility,
+        anchor: &Anchor,
+    ) -> Res<Runtype> {
         match &ty.expr_name {
             TsTypeQueryExpr::TsEntityName(ts_entity_name) => {
-                self.extract_value_ts_entity_name(ts_entity_name, file, visibility, &anchor)
+                self.extract_value_ts_entity_name(ts_entity_name, file, visibility, anchor)
             }
             TsTypeQueryExpr::Import(import_type) => {
-                if import_type.type_args.is_some() {
-                    return self.error(&anchor, DiagnosticInfoMessage::TypeQueryArgsNotSupported);
-                }
                 if let Some(resolved) = self
                     .files
                     .resolve_import(file.clone(), &import_type.arg.value.to_string_lossy())
@@ -2685,7 +4509,7 @@ impl<'a, R: FileManager> FrontendCtx<'a, R> {
                                 ts_entity_name,
                                 resolved,
                                 Visibility::Export,
-                                &anchor,
+                                anchor,
                             );
                         }
                         None => {
@@ -2694,12 +4518,12 @@ impl<'a, R: FileManager> FrontendCtx<'a, R> {
                                 name: "default".to_string(),
                                 visibility: Visibility::Export,
                             };
-                            return self.extract_addressed_value_from_address(&new_addr, &anchor);
+                            return self.extract_addressed_value_from_address(&new_addr, anchor);
                         }
                     }
                 }
                 self.error(
-                    &anchor,
+                    anchor,
                     DiagnosticInfoMessage::CannotResolveImport(
                         import_type.arg.value.to_string_lossy().to_string(),
                     ),
@@ -2722,28 +4546,20 @@ impl<'a, R: FileManager> FrontendCtx<'a, R> {
             .files
             .resolve_import(file.clone(), &import_type.arg.value.to_string_lossy())
         {
+            // the type arguments are written in this file, the name is resolved in the other
+            let type_args = self.extract_type_args(&import_type.type_args, file.clone())?;
             match &import_type.qualifier {
                 Some(ts_entity_name) => {
-                    return self.extract_type_from_ts_entity_name(
+                    return self.extract_type_from_ts_entity_name_with_args(
                         ts_entity_name,
-                        &import_type.type_args,
+                        type_args,
+                        import_type.type_args.is_some(),
                         resolved,
                         Visibility::Export,
                         &anchor,
                     );
                 }
                 None => {
-                    let type_args = match &import_type.type_args {
-                        Some(its) => {
-                            let mut args = vec![];
-                            for ty in &its.params {
-                                let arg_ty = self.extract_type(ty, resolved.clone())?;
-                                args.push(arg_ty);
-                            }
-                            args
-                        }
-                        None => vec![],
-                    };
                     let new_addr = ModuleItemAddress {
                         file: resolved.clone(),
                         name: "default".to_string(),
@@ -2785,20 +4601,11 @@ impl<'a, R: FileManager> FrontendCtx<'a, R> {
         file: BffFileName,
     ) -> Res<Runtype> {
         let mut properties: Vec<(String, Optionality<Runtype>)> = vec![];
-        let mut indexed_property: Option<IndexedProperty> = None;
+        let mut indexed_properties: Vec<IndexedProperty> = vec![];
 
         for member in members {
             match member {
                 TsTypeElement::TsIndexSignature(sig) => {
-                    if indexed_property.is_some() {
-                        return self.error(
-                            &Anchor {
-                                f: file.clone(),
-                                s: member.span(),
-                            },
-                            DiagnosticInfoMessage::MultipleIndexSignaturesNotSupported,
-                        );
-                    }
                     let anchor = Anchor {
                         f: file.clone(),
                         s: sig.span,
@@ -2829,7 +4636,7 @@ impl<'a, R: FileManager> FrontendCtx<'a, R> {
                             );
                         }
                     };
-                    indexed_property = Some(IndexedProperty {
+                    indexed_properties.push(IndexedProperty {
                         key: key_type,
                         value: value_type.required(),
                     });
@@ -2843,7 +4650,7 @@ impl<'a, R: FileManager> FrontendCtx<'a, R> {
 
         Ok(Runtype::new(RuntypeKind::Object {
             vs: properties.into_iter().collect(),
-            indexed_properties: indexed_property.map(Box::new),
+            indexed_properties,
         }))
     }
 
@@ -2868,7 +4675,7 @@ impl<'a, R: FileManager> FrontendCtx<'a, R> {
                 match &prop.type_ann.as_ref() {
                     Some(val) => {
                         let value = self.extract_type(&val.type_ann, file.clone())?;
-                        let value = self.with_jsdoc(&file, prop.span, value);
+                        let value = self.with_jsdoc(&file, prop.span, value)?;
                         let value = if prop.optional {
                             value.optional()
                         } else {
@@ -2895,27 +4702,42 @@ impl<'a, R: FileManager> FrontendCtx<'a, R> {
         }
     }
 
+    /// The item sequences a type interpolated in a template literal stands for, one per alternative.
     fn runtype_to_tpl_lit(
         &mut self,
         span: &Span,
         schema: &Runtype,
         file_name: BffFileName,
-    ) -> Res<TplLitTypeItem> {
+    ) -> Res<Vec<Vec<TplLitTypeItem>>> {
         let anchor = Anchor {
             f: file_name.clone(),
             s: *span,
         };
+        let single = |item: TplLitTypeItem| Ok(vec![vec![item]]);
         match &schema.kind {
-            RuntypeKind::Boolean => Ok(TplLitTypeItem::Boolean),
-            RuntypeKind::String => Ok(TplLitTypeItem::String),
-            RuntypeKind::Number => Ok(TplLitTypeItem::Number),
+            RuntypeKind::Boolean => single(TplLitTypeItem::Boolean),
+            RuntypeKind::String => single(TplLitTypeItem::String),
+            RuntypeKind::Number => single(TplLitTypeItem::Number),
+            RuntypeKind::BigInt => single(TplLitTypeItem::BigInt),
+            RuntypeKind::Null => single(TplLitTypeItem::StringConst("null".to_string())),
+            RuntypeKind::Undefined => single(TplLitTypeItem::StringConst("undefined".to_string())),
+            RuntypeKind::Const(RuntypeConst::Bool(b)) => {
+                single(TplLitTypeItem::StringConst(b.to_string()))
+            }
+            RuntypeKind::Const(RuntypeConst::Number(n)) => {
+                single(TplLitTypeItem::StringConst(n.to_f64().to_string()))
+            }
             RuntypeKind::AnyOf(vs) => {
                 let mut acc = vec![];
                 for v in vs {
-                    let item = self.runtype_to_tpl_lit(span, v, file_name.clone())?;
-                    acc.push(item);
+                    acc.extend(self.runtype_to_tpl_lit(span, v, file_name.clone())?);
+                }
+                // alternatives of a single item stay together, e.g. `${"a" | number}`
+                if acc.iter().all(|it| it.len() == 1) {
+                    single(TplLitTypeItem::one_of(acc.into_iter().flatten().collect()))
+                } else {
+                    Ok(acc)
                 }
-                Ok(TplLitTypeItem::one_of(acc))
             }
             RuntypeKind::Ref(name) => {
                 let v = self.partial_validators.get(name);
@@ -2925,9 +4747,13 @@ impl<'a, R: FileManager> FrontendCtx<'a, R> {
                     None => self.error(&anchor, DiagnosticInfoMessage::CannotResolveRefToTplLit),
                 }
             }
-            RuntypeKind::TplLitType(it) => match it.0.as_slice() {
-                [single] => Ok(single.clone()),
-                _ => self.error(&anchor, DiagnosticInfoMessage::NestedTplLitToTplLit),
+            RuntypeKind::TplLitType(it) => Ok(vec![it.0.clone()]),
+            RuntypeKind::AllOf(_) => match self.normalize_intersection(schema, &anchor)? {
+                Some(normalized) => self.runtype_to_tpl_lit(span, &normalized, file_name),
+                None => self.error(
+                    &anchor,
+                    DiagnosticInfoMessage::TplLitTypeNonStringNonNumberNonBoolean,
+                ),
             },
             _ => self.error(
                 &anchor,
@@ -2936,35 +4762,71 @@ impl<'a, R: FileManager> FrontendCtx<'a, R> {
         }
     }
 
+    /// Builds a template literal from its parts, each a list of alternative item sequences.
+    /// Nested templates are flattened, and templates matching a single string become that string.
+    fn tpl_lit_from_parts(
+        &mut self,
+        anchor: &Anchor,
+        parts: Vec<Vec<Vec<TplLitTypeItem>>>,
+    ) -> Res<Runtype> {
+        let mut templates: Vec<Vec<TplLitTypeItem>> = vec![vec![]];
+        for alternatives in parts {
+            if templates.len() * alternatives.len() > MAX_TPL_LIT_EXPANSION {
+                return self.error(anchor, DiagnosticInfoMessage::TplLitTypeTooComplex);
+            }
+            templates = templates
+                .iter()
+                .flat_map(|prefix| {
+                    alternatives.iter().map(move |items| {
+                        let mut acc = prefix.clone();
+                        for item in items {
+                            match (acc.last_mut(), item) {
+                                (
+                                    Some(TplLitTypeItem::StringConst(last)),
+                                    TplLitTypeItem::StringConst(next),
+                                ) => last.push_str(next),
+                                _ => acc.push(item.clone()),
+                            }
+                        }
+                        acc
+                    })
+                })
+                .collect();
+        }
+        Ok(Runtype::any_of(
+            templates
+                .into_iter()
+                .map(|items| {
+                    let tpl = TplLitType(items);
+                    match tpl.finite_strings_up_to(1).as_deref() {
+                        Some([single]) => Runtype::single_string_const(single),
+                        _ => Runtype::tpl_lit_type(tpl),
+                    }
+                })
+                .collect(),
+        ))
+    }
+
     fn convert_ts_tpl_lit_type_non_trivial(
         &mut self,
         it: &TsTplLitType,
         file_name: BffFileName,
     ) -> Res<Runtype> {
-        let mut quasis_idx = 0;
-        let mut types_idx = 0;
-        let mut selecting_quasis = true;
-
-        let mut acc: Vec<TplLitTypeItem> = vec![];
-        let iter_count = it.quasis.len() + it.types.len();
-        for _ in 0..iter_count {
-            if selecting_quasis {
-                let quasis = &it.quasis[quasis_idx];
-                quasis_idx += 1;
-                acc.push(TplLitTypeItem::StringConst(quasis.raw.to_string()));
-                selecting_quasis = false;
-            } else {
-                let type_ = &it.types[types_idx];
-                types_idx += 1;
-
+        let mut parts = vec![];
+        for (idx, quasi) in it.quasis.iter().enumerate() {
+            parts.push(vec![vec![TplLitTypeItem::StringConst(
+                quasi.raw.to_string(),
+            )]]);
+            if let Some(type_) = it.types.get(idx) {
                 let ty = self.extract_type(type_, file_name.clone())?;
-                acc.push(self.runtype_to_tpl_lit(&it.span, &ty, file_name.clone())?);
-
-                selecting_quasis = true;
+                parts.push(self.runtype_to_tpl_lit(&it.span, &ty, file_name.clone())?);
             }
         }
-
-        Ok(Runtype::tpl_lit_type(TplLitType(acc)))
+        let anchor = Anchor {
+            f: file_name,
+            s: it.span,
+        };
+        self.tpl_lit_from_parts(&anchor, parts)
     }
 
     fn convert_ts_tpl_lit_type(
@@ -3057,10 +4919,10 @@ impl<'a, R: FileManager> FrontendCtx<'a, R> {
             (
                 RuntypeKind::Object {
                     vs,
-                    indexed_properties: _,
+                    indexed_properties,
                 },
                 other,
-            ) => {
+            ) if indexed_properties.is_empty() => {
                 if let Some(s) = other.extract_single_string_const() {
                     let v = vs.get(&s);
                     if let Some(Optionality::Required(v)) = v {
@@ -3162,8 +5024,10 @@ impl<'a, R: FileManager> FrontendCtx<'a, R> {
             s: k.span(),
         };
         let json_schema = self.extract_type(k, file_name.clone())?;
-
-        let object_extracted = self.extract_object_from_runtype(&json_schema, &anchor);
+        self.keyof_runtype(&json_schema, &anchor)
+    }
+    fn keyof_runtype(&mut self, json_schema: &Runtype, anchor: &Anchor) -> Res<Runtype> {
+        let object_extracted = self.extract_object_from_runtype(json_schema, anchor);
         if let Ok(object_schema) = object_extracted {
             let keys = object_schema.keys();
             let mut vs = vec![];
@@ -3172,167 +5036,613 @@ impl<'a, R: FileManager> FrontendCtx<'a, R> {
             }
             return Ok(Runtype::any_of(vs));
         }
-
-        let mut ctx = SemTypeContext::new();
-        let vs = self.validators_vec();
-        let st = json_schema
-            .to_sem_type(&vs.iter().collect::<Vec<_>>(), &mut ctx)
-            .map_err(|e| {
-                self.box_error(&anchor, DiagnosticInfoMessage::AnyhowError(e.to_string()))
-            })?;
-
-        let keyof_st: Rc<SemType> = ctx.keyof(st).map_err(|e| {
-            self.box_error(&anchor, DiagnosticInfoMessage::AnyhowError(e.to_string()))
-        })?;
-
-        self.semtype_to_runtype(keyof_st, &mut ctx, &anchor)
+
+        let mut ctx = SemTypeContext::new();
+        let vs = self.validators_vec();
+        let st = json_schema
+            .to_sem_type(&vs.iter().collect::<Vec<_>>(), &mut ctx)
+            .map_err(|e| {
+                self.box_error(anchor, DiagnosticInfoMessage::AnyhowError(e.to_string()))
+            })?;
+
+        let keyof_st: Rc<SemType> = ctx.keyof(st).map_err(|e| {
+            self.box_error(anchor, DiagnosticInfoMessage::AnyhowError(e.to_string()))
+        })?;
+
+        self.semtype_to_runtype(keyof_st, &mut ctx, anchor)
+    }
+    fn remap_mapped_type_key(
+        &mut self,
+        k: &TsMappedType,
+        name: &str,
+        key: &Runtype,
+        file_name: BffFileName,
+    ) -> Res<Vec<Runtype>> {
+        let anchor = Anchor {
+            f: file_name.clone(),
+            s: k.span,
+        };
+        match &k.name_type {
+            None => Ok(vec![key.clone()]),
+            Some(name_type) => {
+                self.type_application_stack
+                    .push((name.to_string(), key.clone()));
+                let remapped = self.extract_type(name_type, file_name);
+                self.type_application_stack.pop();
+                // keys remapped to never are dropped
+                let keys = self
+                    .extract_union(remapped?)
+                    .map_err(|e| self.box_error(&anchor, e))?;
+                // a template matching finitely many strings stands for a property per string
+                let mut acc = vec![];
+                for key in keys {
+                    match &key.kind {
+                        RuntypeKind::TplLitType(tpl) if tpl.is_finite() => {
+                            match tpl.finite_strings_up_to(MAX_TPL_LIT_EXPANSION) {
+                                Some(strings) => acc.extend(
+                                    strings.iter().map(|it| Runtype::single_string_const(it)),
+                                ),
+                                None => {
+                                    return self.error(
+                                        &anchor,
+                                        DiagnosticInfoMessage::TplLitTypeTooComplex,
+                                    );
+                                }
+                            }
+                        }
+                        _ => acc.push(key),
+                    }
+                }
+                Ok(acc)
+            }
+        }
+    }
+
+    // the keys of a mapped type, and the properties they come from when it is homomorphic
+    fn extract_mapped_type_constraint(
+        &mut self,
+        constraint: &TsType,
+        file_name: BffFileName,
+    ) -> Res<(Runtype, Option<ObjectProperties>)> {
+        // `[K in keyof T]` is homomorphic, it keeps the optionality of the properties of `T`
+        let TsType::TsTypeOperator(TsTypeOperator {
+            op: TsTypeOperatorOp::KeyOf,
+            type_ann,
+            ..
+        }) = constraint
+        else {
+            return Ok((self.extract_type(constraint, file_name)?, None));
+        };
+        let anchor = Anchor {
+            f: file_name.clone(),
+            s: constraint.span(),
+        };
+        let source = self.extract_type(type_ann, file_name)?;
+        let keys = self.keyof_runtype(&source, &anchor)?;
+        // the keys of a source that is not an object (an array, a union...) have no optionality
+        let source_object = self.extract_object_from_runtype(&source, &anchor).ok();
+        Ok((keys, source_object))
+    }
+
+    fn convert_mapped_type(&mut self, k: &TsMappedType, file_name: BffFileName) -> Res<Runtype> {
+        let anchor = Anchor {
+            f: file_name.clone(),
+            s: k.span,
+        };
+        let name = k.type_param.name.sym.to_string();
+        let constraint = match k.type_param.constraint {
+            Some(ref it) => it.as_ref(),
+            None => {
+                return self.error(&anchor, DiagnosticInfoMessage::NoConstraintInMappedType);
+            }
+        };
+        let (constraint_schema, source_object) =
+            self.extract_mapped_type_constraint(constraint, file_name.clone())?;
+        let values = self
+            .extract_union(constraint_schema)
+            .map_err(|e| self.box_error(&anchor, e))?;
+
+        let type_ann = match &k.type_ann {
+            Some(type_ann) => type_ann.as_ref(),
+            None => {
+                return self.error(&anchor, DiagnosticInfoMessage::NoTypeAnnotationInMappedType);
+            }
+        };
+        let make_opt =
+            |source: Option<&Optionality<Runtype>>, ty: Runtype| -> Optionality<Runtype> {
+                match k.optional {
+                    Some(TruePlusMinus::True | TruePlusMinus::Plus) => Optionality::Optional(ty),
+                    // only the `undefined` that optionality added is removed, like `Required`
+                    Some(TruePlusMinus::Minus) => match source {
+                        Some(Optionality::Optional(o)) => {
+                            // reading an optional property through indexed access adds null to it
+                            let ty = if ty == Runtype::any_of(vec![o.clone(), Runtype::null()]) {
+                                o.clone()
+                            } else {
+                                ty
+                            };
+                            Optionality::Required(remove_union_members(ty, |it| {
+                                matches!(it, RuntypeKind::Undefined | RuntypeKind::Void)
+                            }))
+                        }
+                        _ => Optionality::Required(ty),
+                    },
+                    None if matches!(source, Some(Optionality::Optional(_))) => {
+                        Optionality::Optional(ty)
+                    }
+                    None => Optionality::Required(ty),
+                }
+            };
+
+        let mut vs: BTreeMap<String, Optionality<Runtype>> = BTreeMap::new();
+        let mut infinite_keys: Vec<Runtype> = vec![];
+        let mut infinite_values: Vec<Runtype> = vec![];
+
+        for key in values {
+            let remapped = self.remap_mapped_type_key(k, &name, &key, file_name.clone())?;
+            if remapped.is_empty() {
+                continue;
+            }
+            self.type_application_stack
+                .push((name.clone(), key.clone()));
+            let ty = self.extract_type(type_ann, file_name.clone());
+            self.type_application_stack.pop();
+            let ty = ty?;
+
+            let original_key = key.extract_single_string_const();
+            for new_key in remapped {
+                match new_key.extract_single_string_const() {
+                    Some(s) => {
+                        let source = source_object
+                            .as_ref()
+                            .and_then(|it| it.get(original_key.as_deref().unwrap_or(&s)));
+                        let opt = make_opt(source, ty.clone());
+                        // several keys remapped to the same name merge their values
+                        let merged = match vs.remove(&s) {
+                            Some(prev) => match (prev, opt) {
+                                (Optionality::Required(a), Optionality::Required(b)) => {
+                                    Optionality::Required(Runtype::any_of(vec![a, b]))
+                                }
+                                (a, b) => Optionality::Optional(Runtype::any_of(vec![
+                                    a.inner().clone(),
+                                    b.inner().clone(),
+                                ])),
+                            },
+                            None => opt,
+                        };
+                        vs.insert(s, merged);
+                    }
+                    None => {
+                        infinite_keys.push(new_key);
+                        infinite_values.push(ty.clone());
+                    }
+                }
+            }
+        }
+
+        let indexed_properties = if infinite_keys.is_empty() {
+            vec![]
+        } else {
+            let value = Runtype::any_of(infinite_values);
+            vec![IndexedProperty {
+                key: Runtype::any_of(infinite_keys),
+                value: make_opt(None, value),
+            }]
+        };
+
+        Ok(Runtype::new(RuntypeKind::Object {
+            vs,
+            indexed_properties,
+        }))
+    }
+    fn naked_type_parameter(&self, ty: &TsType) -> Option<(String, Runtype)> {
+        if let TsType::TsTypeRef(TsTypeRef {
+            type_name: TsEntityName::Ident(ident),
+            type_params: None,
+            ..
+        }) = ty
+        {
+            for (n, t) in self.type_application_stack.iter().rev() {
+                if ident.sym == *n {
+                    return Some((n.clone(), t.clone()));
+                }
+            }
+        }
+        None
+    }
+
+    fn distributive_members(&self, tp: &Runtype) -> Vec<Runtype> {
+        match &tp.kind {
+            RuntypeKind::AnyOf(vs) => vs
+                .iter()
+                .flat_map(|it| self.distributive_members(it))
+                .collect(),
+            RuntypeKind::Ref(r) => {
+                let v = self.partial_validators.get(r).and_then(|it| it.clone());
+                match v {
+                    Some(v) if matches!(v.kind, RuntypeKind::AnyOf(_) | RuntypeKind::Never) => {
+                        self.distributive_members(&v)
+                    }
+                    _ => vec![tp.clone()],
+                }
+            }
+            RuntypeKind::Boolean => vec![
+                Runtype::const_(RuntypeConst::Bool(true)),
+                Runtype::const_(RuntypeConst::Bool(false)),
+            ],
+            RuntypeKind::Never => vec![],
+            _ => vec![tp.clone()],
+        }
+    }
+
+    fn infer_indexed_access(
+        &mut self,
+        source: &Runtype,
+        index: &Runtype,
+        anchor: &Anchor,
+    ) -> Runtype {
+        // a source without the accessed member can't match the pattern,
+        // binding never makes the extends check fall to the false branch
+        self.do_indexed_access_on_types(source, index, anchor)
+            .unwrap_or_else(|_| Runtype::never())
+    }
+
+    // the elements of a tuple from `index` on, as matched by a rest element or parameter
+    fn infer_tuple_rest(&self, source: &Runtype, index: usize) -> Runtype {
+        match self.resolve_ref(source).kind {
+            RuntypeKind::Tuple {
+                prefix_items,
+                optional_items,
+                items,
+                suffix_items,
+            } => {
+                let skip_optional = index.saturating_sub(prefix_items.len());
+                Runtype::variadic_tuple(
+                    prefix_items.into_iter().skip(index).collect(),
+                    optional_items.into_iter().skip(skip_optional).collect(),
+                    items,
+                    suffix_items,
+                )
+            }
+            RuntypeKind::Array(_) | RuntypeKind::Any => source.clone(),
+            _ => Runtype::never(),
+        }
+    }
+
+    fn infer_from_fn_type(
+        &mut self,
+        params: &[TsFnParam],
+        type_ann: &TsType,
+        is_constructor: bool,
+        source: &Runtype,
+        file: BffFileName,
+        bindings: &mut BTreeMap<String, Vec<Runtype>>,
+    ) -> Res<()> {
+        let anchor = Anchor {
+            f: file.clone(),
+            s: type_ann.span(),
+        };
+        for member in self.distributive_members(source) {
+            let (source_params, source_return) = match self.resolve_ref(&member).kind {
+                RuntypeKind::Function {
+                    params,
+                    return_type,
+                    is_constructor: source_is_constructor,
+                } if source_is_constructor == is_constructor => (*params, *return_type),
+                RuntypeKind::Any => (Runtype::any(), Runtype::any()),
+                // not a signature, the extends check falls to the false branch
+                _ => continue,
+            };
+            for (idx, param) in params.iter().enumerate() {
+                let (pattern, is_rest) = match param {
+                    TsFnParam::Ident(it) => (it.type_ann.as_deref(), false),
+                    TsFnParam::Array(it) => (it.type_ann.as_deref(), false),
+                    TsFnParam::Object(it) => (it.type_ann.as_deref(), false),
+                    TsFnParam::Rest(it) => (it.type_ann.as_deref(), true),
+                };
+                let Some(pattern) = pattern else {
+                    continue;
+                };
+                let item = if is_rest {
+                    self.infer_tuple_rest(&source_params, idx)
+                } else {
+                    let index = Runtype::const_(RuntypeConst::parse_f64(idx as f64));
+                    self.infer_indexed_access(&source_params, &index, &anchor)
+                };
+                self.infer_from_extends_type(&pattern.type_ann, &item, file.clone(), bindings)?;
+            }
+            self.infer_from_extends_type(type_ann, &source_return, file.clone(), bindings)?;
+        }
+        Ok(())
     }
-    fn convert_mapped_type(&mut self, k: &TsMappedType, file_name: BffFileName) -> Res<Runtype> {
+
+    fn infer_from_extends_type(
+        &mut self,
+        pattern: &TsType,
+        source: &Runtype,
+        file: BffFileName,
+        bindings: &mut BTreeMap<String, Vec<Runtype>>,
+    ) -> Res<()> {
         let anchor = Anchor {
-            f: file_name.clone(),
-            s: k.span,
+            f: file.clone(),
+            s: pattern.span(),
         };
-        let name = k.type_param.name.sym.to_string();
-        let constraint = match k.type_param.constraint {
-            Some(ref it) => it.as_ref(),
-            None => {
-                return self.error(&anchor, DiagnosticInfoMessage::NoConstraintInMappedType);
+        match pattern {
+            TsType::TsInferType(TsInferType { type_param, .. }) => {
+                bindings
+                    .entry(type_param.name.sym.to_string())
+                    .or_default()
+                    .push(source.clone());
             }
-        };
-        let constraint_schema = self.extract_type(constraint, file_name.clone())?;
-        let values = self
-            .extract_union(constraint_schema)
-            .map_err(|e| self.box_error(&anchor, e))?;
-
-        // Handle `[K in string]: V` as Record<string, V>
-        // Handle `[K in number]: V` as Record<number, V>
-        if values.len() == 1 && (values[0] == Runtype::string() || values[0] == Runtype::number()) {
-            let key_type = values.into_iter().next().unwrap();
-            let type_ann = match &k.type_ann {
-                Some(type_ann) => type_ann.as_ref(),
-                None => {
-                    return self
-                        .error(&anchor, DiagnosticInfoMessage::NoTypeAnnotationInMappedType);
-                }
-            };
-            let ty = self.extract_type(type_ann, file_name.clone())?;
-            let opt_ty = match k.optional {
-                Some(opt) => match opt {
-                    TruePlusMinus::True => Optionality::Optional(ty),
-                    TruePlusMinus::Plus => Optionality::Required(ty),
-                    TruePlusMinus::Minus => {
-                        return self
-                            .error(&anchor, DiagnosticInfoMessage::MappedTypeMinusNotSupported);
+            TsType::TsParenthesizedType(TsParenthesizedType { type_ann, .. })
+            | TsType::TsTypeOperator(TsTypeOperator {
+                op: TsTypeOperatorOp::ReadOnly,
+                type_ann,
+                ..
+            }) => {
+                self.infer_from_extends_type(type_ann, source, file, bindings)?;
+            }
+            TsType::TsArrayType(TsArrayType { elem_type, .. }) => {
+                let item = self.infer_indexed_access(source, &Runtype::number(), &anchor);
+                self.infer_from_extends_type(elem_type, &item, file, bindings)?;
+            }
+            TsType::TsTupleType(TsTupleType { elem_types, .. }) => {
+                for (idx, it) in elem_types.iter().enumerate() {
+                    if let TsType::TsRestType(TsRestType { type_ann, .. }) = &*it.ty {
+                        // elements after the rest are matched from the end, which is not supported
+                        if let Some(after) = elem_types.get(idx + 1..)
+                            && after.iter().any(|it| contains_infer(&it.ty))
+                        {
+                            return self.error(
+                                &anchor,
+                                DiagnosticInfoMessage::InferTypeInUnsupportedPosition,
+                            );
+                        }
+                        let rest = self.infer_tuple_rest(source, idx);
+                        return self.infer_from_extends_type(type_ann, &rest, file, bindings);
                     }
+                    let index = Runtype::const_(RuntypeConst::parse_f64(idx as f64));
+                    let item = self.infer_indexed_access(source, &index, &anchor);
+                    self.infer_from_extends_type(&it.ty, &item, file.clone(), bindings)?;
+                }
+            }
+            TsType::TsTypeLit(TsTypeLit { members, .. }) => {
+                for member in members {
+                    let TsTypeElement::TsPropertySignature(TsPropertySignature {
+                        key,
+                        type_ann: Some(type_ann),
+                        computed: false,
+                        ..
+                    }) = member
+                    else {
+                        self.no_infer_in(member, &anchor)?;
+                        continue;
+                    };
+                    let key = match &**key {
+                        Expr::Ident(ident) => ident.sym.to_string(),
+                        Expr::Lit(Lit::Str(st)) => st.value.to_string_lossy().to_string(),
+                        _ => {
+                            self.no_infer_in(member, &anchor)?;
+                            continue;
+                        }
+                    };
+                    let value = self.infer_indexed_access(
+                        source,
+                        &Runtype::single_string_const(&key),
+                        &anchor,
+                    );
+                    self.infer_from_extends_type(
+                        &type_ann.type_ann,
+                        &value,
+                        file.clone(),
+                        bindings,
+                    )?;
+                }
+            }
+            TsType::TsTypeRef(TsTypeRef {
+                type_name: TsEntityName::Ident(ident),
+                type_params: Some(type_params),
+                ..
+            }) => match (ident.sym.as_ref(), type_params.params.as_slice()) {
+                ("Array" | "ReadonlyArray", [elem_type]) => {
+                    let item = self.infer_indexed_access(source, &Runtype::number(), &anchor);
+                    self.infer_from_extends_type(elem_type, &item, file, bindings)?;
+                }
+                ("Record", [key_type, value_type]) => {
+                    let keys = self
+                        .keyof_runtype(source, &anchor)
+                        .unwrap_or_else(|_| Runtype::never());
+                    let values = self.infer_indexed_access(source, &keys, &anchor);
+                    self.infer_from_extends_type(key_type, &keys, file.clone(), bindings)?;
+                    self.infer_from_extends_type(value_type, &values, file, bindings)?;
+                }
+                _ => self.no_infer_in(pattern, &anchor)?,
+            },
+            TsType::TsFnOrConstructorType(TsFnOrConstructorType::TsFnType(TsFnType {
+                params,
+                type_ann,
+                ..
+            })) => {
+                self.infer_from_fn_type(params, &type_ann.type_ann, false, source, file, bindings)?;
+            }
+            TsType::TsFnOrConstructorType(TsFnOrConstructorType::TsConstructorType(
+                TsConstructorType {
+                    params, type_ann, ..
                 },
-                None => Optionality::Required(ty),
-            };
-            return Ok(Runtype::record(key_type, opt_ty));
+            )) => {
+                self.infer_from_fn_type(params, &type_ann.type_ann, true, source, file, bindings)?;
+            }
+            _ => self.no_infer_in(pattern, &anchor)?,
         }
+        Ok(())
+    }
 
-        let mut string_keys: Vec<String> = vec![];
-        let mut infinite_keys: Vec<Runtype> = vec![];
-
-        for v in values {
-            match v.extract_single_string_const() {
-                Some(s) => string_keys.push(s),
-                None => infinite_keys.push(v),
-            }
+    // an `infer` the patterns above do not reach would silently fall back to `any`
+    fn no_infer_in<N: VisitWith<InferNameCollector>>(
+        &mut self,
+        pattern: &N,
+        anchor: &Anchor,
+    ) -> Res<()> {
+        if contains_infer(pattern) {
+            return self.error(
+                anchor,
+                DiagnosticInfoMessage::InferTypeInUnsupportedPosition,
+            );
         }
+        Ok(())
+    }
 
-        let type_ann = match &k.type_ann {
-            Some(type_ann) => type_ann.as_ref(),
-            None => {
-                return self.error(&anchor, DiagnosticInfoMessage::NoTypeAnnotationInMappedType);
+    fn convert_conditional_type(
+        &mut self,
+        t: &TsConditionalType,
+        file_name: BffFileName,
+    ) -> Res<Runtype> {
+        // conditional types distribute over unions when the checked type is a naked type parameter
+        if let Some((name, bound)) = self.naked_type_parameter(&t.check_type) {
+            let mut acc = vec![];
+            for member in self.distributive_members(&bound) {
+                self.type_application_stack.push((name.clone(), member));
+                let res = self.convert_conditional_type_non_distributive(t, file_name.clone());
+                self.type_application_stack.pop();
+                let res = res?;
+                if !matches!(res.kind, RuntypeKind::Never) {
+                    acc.push(res);
+                }
             }
-        };
-
-        if matches!(k.optional, Some(TruePlusMinus::Minus)) {
-            return self.error(&anchor, DiagnosticInfoMessage::MappedTypeMinusNotSupported);
+            return Ok(Runtype::any_of(acc));
         }
-        let make_opt = |ty: Runtype| -> Optionality<Runtype> {
-            match k.optional {
-                Some(TruePlusMinus::True) => Optionality::Optional(ty),
-                _ => Optionality::Required(ty),
-            }
-        };
+        self.convert_conditional_type_non_distributive(t, file_name)
+    }
 
-        let mut vs: BTreeMap<String, Optionality<Runtype>> = BTreeMap::new();
-        for key in string_keys.into_iter() {
-            self.type_application_stack
-                .push((name.clone(), Runtype::single_string_const(&key)));
+    fn convert_conditional_type_non_distributive(
+        &mut self,
+        t: &TsConditionalType,
+        file_name: BffFileName,
+    ) -> Res<Runtype> {
+        let check_type_schema = self.extract_type(&t.check_type, file_name.clone())?;
 
-            let ty = self.extract_type(type_ann, file_name.clone());
-            self.type_application_stack.pop();
-            let ty = ty?;
-            vs.insert(key, make_opt(ty));
+        let mut infer_names = InferNameCollector::default();
+        t.extends_type.visit_with(&mut infer_names);
+        let mut bindings = BTreeMap::new();
+        self.infer_from_extends_type(
+            &t.extends_type,
+            &check_type_schema,
+            file_name.clone(),
+            &mut bindings,
+        )?;
+        for name in &infer_names.names {
+            // like tsc, fall back to the constraint (unknown) when nothing could be inferred
+            let inferred = match bindings.remove(name) {
+                Some(candidates) => Runtype::any_of(candidates),
+                None => Runtype::any(),
+            };
+            self.type_application_stack.push((name.clone(), inferred));
         }
-
-        let indexed_properties = if infinite_keys.is_empty() {
-            None
-        } else {
-            let key_runtype = Runtype::any_of(infinite_keys);
-            self.type_application_stack
-                .push((name.clone(), key_runtype.clone()));
-            let ty = self.extract_type(type_ann, file_name.clone());
+        // the inferred names are only in scope in the extends clause and in the true branch
+        let true_branch =
+            self.convert_conditional_type_true_branch(t, &check_type_schema, file_name.clone());
+        for _ in &infer_names.names {
             self.type_application_stack.pop();
-            let ty = ty?;
-            Some(Box::new(IndexedProperty {
-                key: key_runtype,
-                value: make_opt(ty),
-            }))
-        };
-
-        Ok(Runtype::new(RuntypeKind::Object {
-            vs,
-            indexed_properties,
-        }))
+        }
+        match true_branch? {
+            Some(it) => Ok(it),
+            None => self.extract_type(&t.false_type, file_name),
+        }
     }
-    fn convert_conditional_type(
+
+    fn convert_conditional_type_true_branch(
         &mut self,
         t: &TsConditionalType,
+        check_type_schema: &Runtype,
         file_name: BffFileName,
-    ) -> Res<Runtype> {
+    ) -> Res<Option<Runtype>> {
         let anchor = Anchor {
             f: file_name.clone(),
             s: t.span,
         };
-        let check_type_schema = self.extract_type(&t.check_type, file_name.clone())?;
         let extends_type_schema = self.extract_type(&t.extends_type, file_name.clone())?;
 
+        let is_true = match self.resolve_ref(&extends_type_schema).kind {
+            RuntypeKind::Function {
+                params,
+                return_type,
+                is_constructor,
+            } => self.signature_extends(
+                check_type_schema,
+                &params,
+                &return_type,
+                is_constructor,
+                &anchor,
+            )?,
+            _ => self.is_sub_runtype(check_type_schema, &extends_type_schema, &anchor)?,
+        };
+
+        if is_true {
+            Ok(Some(self.extract_type(&t.true_type, file_name)?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn is_sub_runtype(&mut self, sub: &Runtype, sup: &Runtype, anchor: &Anchor) -> Res<bool> {
         let mut ctx = SemTypeContext::new();
         let validators_vec = self.validators_vec();
         let validators_reference_vec: Vec<&NamedSchema> = validators_vec.iter().collect();
 
-        let check_type_st = check_type_schema
+        let sub_st = sub
             .to_sem_type(&validators_reference_vec, &mut ctx)
             .map_err(|e| {
-                self.box_error(&anchor, DiagnosticInfoMessage::AnyhowError(e.to_string()))
+                self.box_error(anchor, DiagnosticInfoMessage::AnyhowError(e.to_string()))
             })?;
 
-        let extends_type_st = extends_type_schema
+        let sup_st = sup
             .to_sem_type(&validators_reference_vec, &mut ctx)
             .map_err(|e| {
-                self.box_error(&anchor, DiagnosticInfoMessage::AnyhowError(e.to_string()))
+                self.box_error(anchor, DiagnosticInfoMessage::AnyhowError(e.to_string()))
             })?;
 
-        let is_true = check_type_st
-            .is_subtype(&extends_type_st, &mut ctx)
-            .map_err(|e| {
-                self.box_error(&anchor, DiagnosticInfoMessage::AnyhowError(e.to_string()))
-            })?;
+        sub_st
+            .is_subtype(&sup_st, &mut ctx)
+            .map_err(|e| self.box_error(anchor, DiagnosticInfoMessage::AnyhowError(e.to_string())))
+    }
 
-        if is_true {
-            self.extract_type(&t.true_type, file_name.clone())
-        } else {
-            self.extract_type(&t.false_type, file_name.clone())
+    // signatures have no semantic type, they are compared on their own: the return type is
+    // covariant and the parameters are bivariant, like tsc compares methods, so that a pattern
+    // with `any[]` parameters matches every signature
+    fn signature_extends(
+        &mut self,
+        source: &Runtype,
+        params: &Runtype,
+        return_type: &Runtype,
+        is_constructor: bool,
+        anchor: &Anchor,
+    ) -> Res<bool> {
+        for member in self.distributive_members(source) {
+            let matches = match self.resolve_ref(&member).kind {
+                RuntypeKind::Function {
+                    params: source_params,
+                    return_type: source_return,
+                    is_constructor: source_is_constructor,
+                } => {
+                    source_is_constructor == is_constructor
+                        && (self.is_sub_runtype(params, &source_params, anchor)?
+                            || self.is_sub_runtype(&source_params, params, anchor)?)
+                        && self.is_sub_runtype(&source_return, return_type, anchor)?
+                }
+                RuntypeKind::Any => true,
+                _ => false,
+            };
+            if !matches {
+                return Ok(false);
+            }
         }
+        Ok(true)
     }
 
     fn extract_type(&mut self, ty: &TsType, file: BffFileName) -> Res<Runtype> {
         let span = ty.span();
         let runtype = self.extract_type_inner(ty, file.clone())?;
-        Ok(self.with_jsdoc(&file, span, runtype))
+        self.with_jsdoc(&file, span, runtype)
     }
 
     fn extract_type_inner(&mut self, ty: &TsType, file: BffFileName) -> Res<Runtype> {
@@ -3373,32 +5683,72 @@ impl<'a, R: FileManager> FrontendCtx<'a, R> {
                 self.extract_type(type_ann, file)
             }
             TsType::TsTupleType(TsTupleType { elem_types, .. }) => {
-                let mut prefix_items = vec![];
-                let mut items = None;
+                let mut parts = TupleParts::default();
                 for it in elem_types {
-                    if let TsType::TsRestType(TsRestType { type_ann, .. }) = &*it.ty {
-                        if items.is_some() {
-                            return self.error(
-                                &anchor,
-                                DiagnosticInfoMessage::DuplicatedRestNonSerializable,
-                            );
+                    // a labelled rest is `...label: T[]`, the label holds the dots
+                    let (label, label_optional, rest_ty) = match (&it.label, &*it.ty) {
+                        (Some(Pat::Rest(rest)), ty) => (
+                            rest.arg.as_ident().map(|it| it.id.sym.to_string()),
+                            false,
+                            Some(ty),
+                        ),
+                        (Some(Pat::Ident(binding)), ty) => (
+                            Some(binding.id.sym.to_string()),
+                            binding.id.optional,
+                            rest_type_ann(ty),
+                        ),
+                        (_, ty) => (None, false, rest_type_ann(ty)),
+                    };
+                    let with_label = |ty: Runtype| match &label {
+                        Some(label) => ty.with_label(label.clone()),
+                        None => ty,
+                    };
+                    let pushed = match (rest_ty, &*it.ty) {
+                        (Some(type_ann), _) => {
+                            let ty = self.extract_type(type_ann, file.clone())?;
+                            match self.resolve_ref(&ty).kind {
+                                RuntypeKind::Array(item) => parts.push_rest(with_label(*item)),
+                                RuntypeKind::Tuple {
+                                    prefix_items,
+                                    optional_items,
+                                    items,
+                                    suffix_items,
+                                } => parts.push_spread(
+                                    prefix_items,
+                                    optional_items,
+                                    items,
+                                    suffix_items,
+                                ),
+                                _ => Err(DiagnosticInfoMessage::TupleRestTypeMustBeArray),
+                            }
                         }
-                        let ann = match self.extract_type(type_ann, file.clone())?.kind {
-                            RuntypeKind::Array(items) => *items,
-                            _ => {
-                                return self.error(
-                                    &anchor,
-                                    DiagnosticInfoMessage::TupleRestTypeMustBeArray,
-                                );
+                        (None, TsType::TsOptionalType(TsOptionalType { type_ann, .. })) => {
+                            let ty = self.extract_type(type_ann, file.clone())?;
+                            parts.push_optional(with_label(ty))
+                        }
+                        (None, ty) => {
+                            let ty = with_label(self.extract_type(ty, file.clone())?);
+                            if label_optional {
+                                parts.push_optional(ty)
+                            } else {
+                                parts.push_required(ty)
                             }
+                        }
+                    };
+                    if let Err(msg) = pushed {
+                        // a rest that is not an array is reported against the whole tuple
+                        let span = match msg {
+                            DiagnosticInfoMessage::TupleRestTypeMustBeArray => ty.span(),
+                            _ => it.span,
                         };
-                        items = Some(ann.into());
-                    } else {
-                        let ty_schema = self.extract_type(&it.ty, file.clone())?;
-                        prefix_items.push(ty_schema);
+                        let anchor = Anchor {
+                            f: file.clone(),
+                            s: span,
+                        };
+                        return self.error(&anchor, msg);
                     }
                 }
-                Ok(Runtype::tuple(prefix_items, items))
+                Ok(parts.into_runtype())
             }
             TsType::TsTypeLit(TsTypeLit { members, .. }) => {
                 self.extract_ts_type_lit_members(members, file)
@@ -3429,12 +5779,63 @@ impl<'a, R: FileManager> FrontendCtx<'a, R> {
             TsType::TsThisType(TsThisType { .. }) => {
                 self.error(&anchor, DiagnosticInfoMessage::ThisTypeNonSerializable)
             }
-            TsType::TsFnOrConstructorType(
-                TsFnOrConstructorType::TsConstructorType(TsConstructorType { .. })
-                | TsFnOrConstructorType::TsFnType(TsFnType { .. }),
-            ) => Ok(Runtype::function()),
-            TsType::TsInferType(TsInferType { .. }) => {
-                self.error(&anchor, DiagnosticInfoMessage::TsInferTypeNonSerializable)
+            TsType::TsFnOrConstructorType(TsFnOrConstructorType::TsFnType(TsFnType {
+                params,
+                type_params,
+                type_ann,
+                ..
+            })) => {
+                let params = params.iter().map(ts_fn_param_to_pat).collect::<Vec<_>>();
+                self.extract_fn_signature(
+                    FnSignature {
+                        type_params: type_params.as_deref(),
+                        params: &params.iter().collect::<Vec<_>>(),
+                        return_type: Some(type_ann),
+                        is_async: false,
+                        is_constructor: false,
+                    },
+                    file,
+                )
+            }
+            TsType::TsFnOrConstructorType(TsFnOrConstructorType::TsConstructorType(
+                TsConstructorType {
+                    params,
+                    type_params,
+                    type_ann,
+                    ..
+                },
+            )) => {
+                let params = params.iter().map(ts_fn_param_to_pat).collect::<Vec<_>>();
+                self.extract_fn_signature(
+                    FnSignature {
+                        type_params: type_params.as_deref(),
+                        params: &params.iter().collect::<Vec<_>>(),
+                        return_type: Some(type_ann),
+                        is_async: false,
+                        is_constructor: true,
+                    },
+                    file,
+                )
+            }
+            TsType::TsInferType(TsInferType { type_param, .. }) => {
+                // bound by the enclosing conditional type
+                let name = &type_param.name.sym;
+                let inferred = self
+                    .type_application_stack
+                    .iter()
+                    .rev()
+                    .find(|(n, _)| name == n)
+                    .map(|(_, t)| t.clone());
+                match (inferred, &type_param.constraint) {
+                    (Some(inferred), None) => Ok(inferred),
+                    (Some(inferred), Some(constraint)) => {
+                        let constraint = self.extract_type(constraint, file.clone())?;
+                        Ok(Runtype::all_of(vec![inferred, constraint]))
+                    }
+                    (None, _) => {
+                        self.error(&anchor, DiagnosticInfoMessage::TsInferTypeNonSerializable)
+                    }
+                }
             }
             TsType::TsTypePredicate(TsTypePredicate { .. }) => self.error(
                 &anchor,
@@ -3449,12 +5850,111 @@ impl<'a, R: FileManager> FrontendCtx<'a, R> {
         }
     }
 
+    // `Name<T>(item: BeffParser<T>): BeffParser<Envelope<T>>` becomes a function of the argument parsers
+    fn extract_parser_factory(
+        &mut self,
+        exported_name: String,
+        type_params: &TsTypeParamDecl,
+        params: &[TsFnParam],
+        return_type: Option<&TsTypeAnn>,
+        anchor: &Anchor,
+    ) -> Result<BuiltDecoder> {
+        let declared = type_params
+            .params
+            .iter()
+            .map(|it| it.name.sym.to_string())
+            .collect::<Vec<_>>();
+        let mut factory_params: Vec<String> = vec![];
+        for param in params {
+            let param_anchor = Anchor {
+                f: self.parser_file.clone(),
+                s: param.span(),
+            };
+            let name = match param {
+                TsFnParam::Ident(ident) => match ident
+                    .type_ann
+                    .as_ref()
+                    .and_then(|ann| parser_type_argument(&ann.type_ann))
+                {
+                    Some(TsType::TsTypeRef(TsTypeRef {
+                        type_name: TsEntityName::Ident(ident),
+                        type_params: None,
+                        ..
+                    })) => Some(ident.sym.to_string()),
+                    _ => None,
+                },
+                _ => None,
+            };
+            match name {
+                Some(name) if declared.contains(&name) && !factory_params.contains(&name) => {
+                    factory_params.push(name)
+                }
+                _ => {
+                    return self.anyhow_error(
+                        &param_anchor,
+                        DiagnosticInfoMessage::ParserFactoryParamShouldBeParserOfTypeParam,
+                    );
+                }
+            }
+        }
+        if let Some(missing) = declared.iter().find(|it| !factory_params.contains(it)) {
+            return self.anyhow_error(
+                anchor,
+                DiagnosticInfoMessage::ParserFactoryTypeParamIsNotAParam(missing.clone()),
+            );
+        }
+        let Some(result) = return_type.and_then(|it| parser_type_argument(&it.type_ann)) else {
+            return self.anyhow_error(
+                anchor,
+                DiagnosticInfoMessage::ParserFactoryShouldReturnParser,
+            );
+        };
+
+        for name in &declared {
+            self.type_application_stack
+                .push((name.clone(), Runtype::type_parameter(name.clone())));
+        }
+        let schema = self.extract_type(result, self.parser_file.clone());
+        for _ in &declared {
+            self.type_application_stack.pop();
+        }
+        let schema = match schema {
+            Ok(s) => s,
+            Err(diag) => {
+                self.errors.push(*diag);
+                Runtype::any()
+            }
+        };
+        Ok(BuiltDecoder {
+            exported_name,
+            schema,
+            type_params: factory_params,
+            position: self.source_position(anchor),
+        })
+    }
+
     fn extract_one_built_decoder_v2(&mut self, prop: &TsTypeElement) -> Result<BuiltDecoder> {
         let anchor = Anchor {
             f: self.parser_file.clone(),
             s: prop.span(),
         };
         match prop {
+            TsTypeElement::TsMethodSignature(TsMethodSignature {
+                key,
+                params,
+                type_params: Some(type_params),
+                type_ann,
+                ..
+            }) => {
+                let key = match &**key {
+                    Expr::Ident(ident) => ident.sym.to_string(),
+                    _ => {
+                        return self
+                            .anyhow_error(&anchor, DiagnosticInfoMessage::InvalidDecoderKey);
+                    }
+                };
+                self.extract_parser_factory(key, type_params, params, type_ann.as_deref(), &anchor)
+            }
             TsTypeElement::TsPropertySignature(TsPropertySignature { key, type_ann, .. }) => {
                 let key = match &**key {
                     Expr::Ident(ident) => ident.sym.to_string(),
@@ -3465,6 +5965,24 @@ impl<'a, R: FileManager> FrontendCtx<'a, R> {
                 };
                 match type_ann.as_ref().map(|it| &it.type_ann) {
                     Some(ann) => {
+                        if let TsType::TsFnOrConstructorType(TsFnOrConstructorType::TsFnType(
+                            TsFnType {
+                                params,
+                                type_params: Some(type_params),
+                                type_ann,
+                                ..
+                            },
+                        )) = &**ann
+                            && parser_type_argument(&type_ann.type_ann).is_some()
+                        {
+                            return self.extract_parser_factory(
+                                key,
+                                type_params,
+                                params,
+                                Some(type_ann),
+                                &anchor,
+                            );
+                        }
                         let schema = match self.extract_type(ann, self.parser_file.clone()) {
                             Ok(s) => s,
                             Err(diag) => {
@@ -3477,6 +5995,8 @@ impl<'a, R: FileManager> FrontendCtx<'a, R> {
                         Ok(BuiltDecoder {
                             exported_name: key,
                             schema,
+                            type_params: vec![],
+                            position: self.source_position(&anchor),
                         })
                     }
                     None => self.anyhow_error(
@@ -3527,4 +6047,123 @@ impl<'a, R: FileManager> FrontendCtx<'a, R> {
             None => self.anyhow_error(&anchor, DiagnosticInfoMessage::TooFewTypeParamsOnDecoder),
         }
     }
+
+    fn extract_one_built_route(&mut self, prop: &TsTypeElement) -> Result<BuiltRoute> {
+        let anchor = Anchor {
+            f: self.parser_file.clone(),
+            s: prop.span(),
+        };
+        let TsTypeElement::TsPropertySignature(TsPropertySignature {
+            key,
+            type_ann: Some(type_ann),
+            ..
+        }) = prop
+        else {
+            return self.anyhow_error(&anchor, DiagnosticInfoMessage::InvalidRouteProperty);
+        };
+        let key = match &**key {
+            Expr::Lit(Lit::Str(st)) => st.value.to_string_lossy().to_string(),
+            Expr::Ident(ident) => ident.sym.to_string(),
+            _ => return self.anyhow_error(&anchor, DiagnosticInfoMessage::InvalidRouteProperty),
+        };
+        let Some((method, path)) = parse_route_key(&key) else {
+            return self.anyhow_error(&anchor, DiagnosticInfoMessage::InvalidRouteKey(key));
+        };
+        let schema = match self.extract_type(&type_ann.type_ann, self.parser_file.clone()) {
+            Ok(s) => s,
+            Err(diag) => {
+                self.errors.push(*diag);
+                Runtype::any()
+            }
+        };
+        Ok(BuiltRoute {
+            method,
+            path,
+            schema,
+        })
+    }
+
+    // `buildRoutes<Routes>()`, the routes are the properties of the resolved type
+    fn extract_built_routes_from_type(&mut self, ty: &TsType) -> Result<Vec<BuiltRoute>> {
+        let anchor = Anchor {
+            f: self.parser_file.clone(),
+            s: ty.span(),
+        };
+        let mut schema = match self.extract_type(ty, self.parser_file.clone()) {
+            Ok(s) => s,
+            Err(diag) => {
+                self.errors.push(*diag);
+                return Err(anyhow!("cannot extract routes"));
+            }
+        };
+        while let RuntypeKind::Ref(to) = &schema.kind {
+            match self.partial_validators.get(to) {
+                Some(Some(resolved)) => schema = resolved.clone(),
+                _ => break,
+            }
+        }
+        let RuntypeKind::Object {
+            vs,
+            indexed_properties,
+        } = schema.kind
+        else {
+            return self.anyhow_error(
+                &anchor,
+                DiagnosticInfoMessage::RoutesShouldBeObjectWithRouteKeys,
+            );
+        };
+        if !indexed_properties.is_empty() {
+            return self.anyhow_error(
+                &anchor,
+                DiagnosticInfoMessage::RoutesShouldBeObjectWithRouteKeys,
+            );
+        }
+        let mut acc = vec![];
+        for (key, value) in vs {
+            let Some((method, path)) = parse_route_key(&key) else {
+                return self.anyhow_error(&anchor, DiagnosticInfoMessage::InvalidRouteKey(key));
+            };
+            acc.push(BuiltRoute {
+                method,
+                path,
+                schema: value.inner().clone(),
+            });
+        }
+        Ok(acc)
+    }
+
+    pub fn extract_built_routes_from_call(
+        &mut self,
+        params: &TsTypeParamInstantiation,
+    ) -> Result<Vec<BuiltRoute>> {
+        let anchor = Anchor {
+            f: self.parser_file.clone(),
+            s: params.span,
+        };
+        match params.params.as_slice() {
+            [head] => match &**head {
+                TsType::TsTypeLit(TsTypeLit { members, .. }) => members
+                    .iter()
+                    .map(|prop| self.extract_one_built_route(prop))
+                    .collect(),
+                _ => self.extract_built_routes_from_type(head),
+            },
+            [] => self.anyhow_error(&anchor, DiagnosticInfoMessage::TooFewTypeParamsOnDecoder),
+            _ => self.anyhow_error(&anchor, DiagnosticInfoMessage::TooManyTypeParamsOnDecoder),
+        }
+    }
+}
+
+const ROUTE_METHODS: [&str; 8] = [
+    "GET", "PUT", "POST", "DELETE", "OPTIONS", "HEAD", "PATCH", "TRACE",
+];
+
+// `"GET /users/{id}"` becomes `("get", "/users/{id}")`
+fn parse_route_key(key: &str) -> Option<(String, String)> {
+    let (method, path) = key.split_once(' ')?;
+    let path = path.trim();
+    if !ROUTE_METHODS.contains(&method) || !path.starts_with('/') || path.contains(' ') {
+        return None;
+    }
+    Some((method.to_lowercase(), path.to_string()))
 }
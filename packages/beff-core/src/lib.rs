@@ -10,8 +10,11 @@ pub mod wasm_diag;
 
 use crate::ast::runtype::DebugPrintCtx;
 use crate::ast::runtype::Runtype;
+use crate::ast::runtype::RuntypeKind;
+use crate::ast::runtype::StringIntrinsic;
 use crate::ast::runtype::TypedArrayKind;
 use crate::swc_tools::ImportReference;
+use crate::swc_tools::InterfaceAugmentation;
 use crate::swc_tools::SymbolsExportsModule;
 use crate::swc_tools::bind_locals::ParsedModuleLocals;
 use core::fmt;
@@ -51,6 +54,14 @@ impl Anchor {
     }
 }
 
+/// Where a declaration starts, zero-based like the positions of a source map.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct SourcePosition {
+    pub file: BffFileName,
+    pub line: usize,
+    pub col: usize,
+}
+
 impl fmt::Display for BffFileName {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{}", self.0)
@@ -71,6 +82,13 @@ pub struct ParsedModule {
     pub imports: HashMap<String, Rc<ImportReference>>,
     pub comments: SwcComments,
     pub symbol_exports: SymbolsExportsModule,
+    // every namespace declared in the module, nested ones included, by scope name
+    pub namespaces: HashMap<BffFileName, Rc<ParsedModule>>,
+    // set for namespaces, names they do not declare resolve in the enclosing scope
+    pub parent_scope: Option<BffFileName>,
+    // files the module imports or re-exports from, side effect imports included
+    pub dependencies: Vec<BffFileName>,
+    pub augmentations: Vec<InterfaceAugmentation>,
 }
 
 #[derive(Debug)]
@@ -174,10 +192,14 @@ impl ParserExtractResult {
         sorted_decoders.sort_by(|a, b| a.exported_name.cmp(&b.exported_name));
 
         for v in sorted_decoders {
-            decoders_vs.push((
-                v.exported_name.clone(),
-                v.schema.debug_print(&debug_print_ctx),
-            ));
+            let schema = v.schema.debug_print(&debug_print_ctx);
+            let printed = if v.type_params.is_empty() {
+                schema
+            } else {
+                let params = v.type_params.join(", ");
+                format!("<{}>({}) => {}", params, params, schema)
+            };
+            decoders_vs.push((v.exported_name.clone(), printed));
         }
 
         let decoders_printed = format!(
@@ -336,6 +358,9 @@ pub enum TsBuiltIn {
     StringFormatExtends,
     NumberFormat,
     NumberFormatExtends,
+    Int,
+    NonNegative,
+    Range,
 
     Record,
     Omit,
@@ -345,8 +370,18 @@ pub enum TsBuiltIn {
     Partial,
     Pick,
     Exclude,
+    Extract,
+    NonNullable,
+    StringIntrinsic(StringIntrinsic),
     TypedArray(TypedArrayKind),
 
+    Parameters,
+    ConstructorParameters,
+    ReturnType,
+    Promise,
+    Awaited,
+    InstanceOf,
+
     Map,
     Set,
 }
@@ -354,6 +389,7 @@ impl fmt::Display for TsBuiltIn {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             TsBuiltIn::TypedArray(kind) => write!(f, "{}", kind.js_name()),
+            TsBuiltIn::StringIntrinsic(intrinsic) => write!(f, "{}", intrinsic.name()),
             _ => write!(f, "{:?}", self),
         }
     }
@@ -424,6 +460,10 @@ impl RuntypeUUID {
 
     fn type_with_args_str(it: usize, args: &[Runtype], ctx: &mut DebugPrintCtx<'_>) -> String {
         match args {
+            // a type parameter can share its name with a type
+            [single] if matches!(single.kind, RuntypeKind::TypeParameter(_)) => {
+                format!("_instance_{}", it)
+            }
             [single] => {
                 let printed = single.debug_print(ctx);
                 if is_valid_ts_identifier(&printed) {
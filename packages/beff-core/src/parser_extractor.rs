@@ -1,21 +1,44 @@
 use crate::ast::runtype::Runtype;
 use crate::diag::{DiagnosticInfoMessage, DiagnosticInformation, Location};
 use crate::frontend::FrontendCtx;
-use crate::{BeffUserSettings, ParsedModule, RuntypeUUID};
+use crate::{
+    BeffUserSettings, ParsedModule, RuntypeName, RuntypeUUID, SourcePosition, TypeAddress,
+};
 use crate::{BffFileName, FileManager, NamedSchema};
 use anyhow::Result;
 use anyhow::anyhow;
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
 use std::rc::Rc;
 use swc_atoms::Atom;
-use swc_common::{DUMMY_SP, Span};
-use swc_ecma_ast::{CallExpr, Callee, Expr, MemberExpr, MemberProp};
+use swc_common::{DUMMY_SP, Span, Spanned};
+use swc_ecma_ast::{
+    CallExpr, Callee, Expr, Lit, MemberExpr, MemberProp, ObjectLit, Prop, PropName, PropOrSpread,
+};
 use swc_ecma_visit::Visit;
 
 #[derive(Debug)]
 pub struct BuiltDecoder {
     pub exported_name: String,
     pub schema: Runtype,
+    // set for generic parser factories, one per argument parser
+    pub type_params: Vec<String>,
+    // the property of the `buildParsers` type argument
+    pub position: Option<SourcePosition>,
+}
+
+#[derive(Debug)]
+pub struct BuiltRoute {
+    // lowercase, as in the OpenAPI path items
+    pub method: String,
+    pub path: String,
+    pub schema: Runtype,
+}
+
+#[derive(Debug)]
+pub struct BuiltRoutes {
+    // the `info` object of the OpenAPI document
+    pub info: BTreeMap<String, String>,
+    pub routes: Vec<BuiltRoute>,
 }
 
 #[derive(Debug)]
@@ -24,6 +47,9 @@ pub struct ParserExtractResult {
     pub entry_file_name: BffFileName,
     pub validators: Vec<NamedSchema>,
     pub built_decoders: Option<Vec<BuiltDecoder>>,
+    pub built_routes: Option<BuiltRoutes>,
+    pub declaration_positions: BTreeMap<RuntypeName, SourcePosition>,
+    pub exported_classes: BTreeSet<TypeAddress>,
     pub counter: usize,
     pub recursive_generic_uuids: BTreeSet<RuntypeUUID>,
 }
@@ -34,6 +60,9 @@ struct ExtractParserVisitor<'a, R: FileManager> {
     validators: Vec<NamedSchema>,
     errors: Vec<DiagnosticInformation>,
     built_decoders: Option<Vec<BuiltDecoder>>,
+    built_routes: Option<BuiltRoutes>,
+    declaration_positions: BTreeMap<RuntypeName, SourcePosition>,
+    exported_classes: BTreeSet<TypeAddress>,
     settings: &'a BeffUserSettings,
     counter: usize,
     recursive_generic_uuids: BTreeSet<RuntypeUUID>,
@@ -50,6 +79,9 @@ impl<'a, R: FileManager> ExtractParserVisitor<'a, R> {
             validators: vec![],
             errors: vec![],
             built_decoders: None,
+            built_routes: None,
+            declaration_positions: BTreeMap::new(),
+            exported_classes: BTreeSet::new(),
             settings,
             counter: 0,
             recursive_generic_uuids: BTreeSet::new(),
@@ -111,6 +143,82 @@ impl<R: FileManager> ExtractParserVisitor<'_, R> {
             }
         }
     }
+    fn extend_from_frontend(
+        &mut self,
+        errors: Vec<DiagnosticInformation>,
+        partial_validators: BTreeMap<RuntypeUUID, Option<Runtype>>,
+        recursive_generic_uuids: BTreeSet<RuntypeUUID>,
+        declaration_positions: BTreeMap<RuntypeName, SourcePosition>,
+        exported_classes: BTreeSet<TypeAddress>,
+        span: &Span,
+    ) {
+        self.recursive_generic_uuids.extend(recursive_generic_uuids);
+        self.exported_classes.extend(exported_classes);
+        for (name, position) in declaration_positions {
+            self.declaration_positions.entry(name).or_insert(position);
+        }
+        self.errors.extend(errors);
+        let mut kvs = vec![];
+        for (k, v) in partial_validators {
+            // We store type in an Option to support self-recursion.
+            // When we encounter the type while transforming it we return string with the type name.
+            // And we need the option to allow a type to refer to itself before it has been resolved.
+            match v {
+                Some(s) => kvs.push((k, s)),
+                None => self.push_error(
+                    span,
+                    DiagnosticInfoMessage::CannotResolveTypeReferenceOnExtracting(k),
+                ),
+            }
+        }
+
+        kvs.sort_by(|(ka, _), (kb, _)| ka.cmp(kb));
+        let mut ext: Vec<NamedSchema> = vec![];
+        for (k, b) in kvs.into_iter() {
+            ext.push(NamedSchema { name: k, schema: b });
+        }
+        self.extend_components(ext, span);
+    }
+
+    // `buildRoutes<Routes>({ title: "Users", version: "1.0.0" })`, the argument is optional
+    fn extract_routes_info(&mut self, n: &CallExpr) -> BTreeMap<String, String> {
+        let mut info = BTreeMap::new();
+        let Some(arg) = n.args.first() else {
+            return info;
+        };
+        let Expr::Object(ObjectLit { props, .. }) = &*arg.expr else {
+            self.push_error(&arg.expr.span(), DiagnosticInfoMessage::InvalidRoutesInfo);
+            return info;
+        };
+        for prop in props {
+            let kv = match prop {
+                PropOrSpread::Prop(prop) => match &**prop {
+                    Prop::KeyValue(kv) => Some(kv),
+                    _ => None,
+                },
+                PropOrSpread::Spread(_) => None,
+            };
+            let entry = kv.and_then(|kv| {
+                let key = match &kv.key {
+                    PropName::Ident(id) => id.sym.to_string(),
+                    PropName::Str(st) => st.value.to_string_lossy().to_string(),
+                    _ => return None,
+                };
+                match &*kv.value {
+                    Expr::Lit(Lit::Str(st)) => Some((key, st.value.to_string_lossy().to_string())),
+                    _ => None,
+                }
+            });
+            match entry {
+                Some((key, value)) => {
+                    info.insert(key, value);
+                }
+                None => self.push_error(&prop.span(), DiagnosticInfoMessage::InvalidRoutesInfo),
+            }
+        }
+        info
+    }
+
     fn extract_special_calls(&mut self, sym: &Atom, span: &Span, n: &CallExpr) {
         if sym == "buildParsers" {
             match self.built_decoders {
@@ -123,30 +231,54 @@ impl<R: FileManager> ExtractParserVisitor<'_, R> {
                         if let Ok(x) = ctx.extract_built_decoders_from_call_v2(params.as_ref()) {
                             self.built_decoders = Some(x)
                         }
-                        self.recursive_generic_uuids = ctx.recursive_generic_uuids;
-                        self.errors.extend(ctx.errors);
-                        let mut kvs = vec![];
-                        for (k, v) in ctx.partial_validators {
-                            // We store type in an Option to support self-recursion.
-                            // When we encounter the type while transforming it we return string with the type name.
-                            // And we need the option to allow a type to refer to itself before it has been resolved.
-                            match v {
-                                Some(s) => kvs.push((k, s)),
-                                None => self.push_error(
-                                    span,
-                                    DiagnosticInfoMessage::CannotResolveTypeReferenceOnExtracting(
-                                        k,
-                                    ),
-                                ),
-                            }
-                        }
+                        let FrontendCtx {
+                            errors,
+                            partial_validators,
+                            recursive_generic_uuids,
+                            declaration_positions,
+                            exported_classes,
+                            ..
+                        } = ctx;
+                        self.extend_from_frontend(
+                            errors,
+                            partial_validators,
+                            recursive_generic_uuids,
+                            declaration_positions,
+                            exported_classes,
+                            span,
+                        );
+                    }
+                }
+            }
+        }
+        if sym == "buildRoutes" {
+            match self.built_routes {
+                Some(_) => self.push_error(span, DiagnosticInfoMessage::TwoCallsToBuildRoutes),
+                None => {
+                    if let Some(ref params) = n.type_args {
+                        let info = self.extract_routes_info(n);
+                        let mut ctx =
+                            FrontendCtx::new(self.files, self.current_file.clone(), self.settings);
 
-                        kvs.sort_by(|(ka, _), (kb, _)| ka.cmp(kb));
-                        let mut ext: Vec<NamedSchema> = vec![];
-                        for (k, b) in kvs.into_iter() {
-                            ext.push(NamedSchema { name: k, schema: b });
+                        if let Ok(routes) = ctx.extract_built_routes_from_call(params.as_ref()) {
+                            self.built_routes = Some(BuiltRoutes { info, routes })
                         }
-                        self.extend_components(ext, span);
+                        let FrontendCtx {
+                            errors,
+                            partial_validators,
+                            recursive_generic_uuids,
+                            declaration_positions,
+                            exported_classes,
+                            ..
+                        } = ctx;
+                        self.extend_from_frontend(
+                            errors,
+                            partial_validators,
+                            recursive_generic_uuids,
+                            declaration_positions,
+                            exported_classes,
+                            span,
+                        );
                     }
                 }
             }
@@ -181,24 +313,18 @@ pub fn extract_parser<R: FileManager>(
     entry_file_name: BffFileName,
     settings: &BeffUserSettings,
 ) -> ParserExtractResult {
-    let (errors, validators, built_decoders, counter, recursive_generic_uuids) = {
-        let mut visitor = ExtractParserVisitor::new(files, entry_file_name.clone(), settings);
-        let _ = visitor.visit_current_file();
-        (
-            visitor.errors,
-            visitor.validators,
-            visitor.built_decoders,
-            visitor.counter,
-            visitor.recursive_generic_uuids,
-        )
-    };
+    let mut visitor = ExtractParserVisitor::new(files, entry_file_name.clone(), settings);
+    let _ = visitor.visit_current_file();
 
     ParserExtractResult {
-        errors,
+        errors: visitor.errors,
         entry_file_name,
-        validators,
-        built_decoders,
-        counter,
-        recursive_generic_uuids,
+        validators: visitor.validators,
+        built_decoders: visitor.built_decoders,
+        built_routes: visitor.built_routes,
+        declaration_positions: visitor.declaration_positions,
+        exported_classes: visitor.exported_classes,
+        counter: visitor.counter,
+        recursive_generic_uuids: visitor.recursive_generic_uuids,
     }
 }
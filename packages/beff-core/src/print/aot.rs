@@ -0,0 +1,2127 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use anyhow::{Result, anyhow};
+use swc_common::{DUMMY_SP, SyntaxContext};
+use swc_ecma_ast::{
+    ArrayLit, ArrayPat, ArrowExpr, AssignExpr, AssignOp, AssignTarget, BinExpr, BinaryOp,
+    BindingIdent, BlockStmt, BlockStmtOrExpr, CallExpr, Callee, ComputedPropName, CondExpr, Decl,
+    Expr, ExprOrSpread, ExprStmt, FnDecl, ForHead, ForOfStmt, ForStmt, Function, Ident, IdentName,
+    IfStmt, KeyValueProp, Lit, MemberExpr, MemberProp, Module, ModuleItem, NewExpr, Null, Number,
+    ObjectLit, Param, Pat, Prop, PropName, PropOrSpread, Regex, ReturnStmt, SimpleAssignTarget,
+    SpreadElement, Stmt, Str, SwitchCase, SwitchStmt, ThrowStmt, Tpl, TplElement, UnaryExpr,
+    UnaryOp, UpdateExpr, UpdateOp, VarDecl, VarDeclKind, VarDeclOrExpr, VarDeclarator,
+};
+use swc_ecma_transforms_base::fixer::fixer;
+use swc_ecma_visit::VisitMutWith;
+
+use crate::ast::json::{Json, N};
+use crate::ast::runtype::{
+    CustomFormat, DebugPrintCtx, IndexedProperty, NumberRange, Optionality, Runtype, RuntypeKind,
+    SchemaKeywords, TplLitTypeItem,
+};
+use crate::parser_extractor::{BuiltDecoder, ParserExtractResult};
+use crate::{NamedSchema, RuntypeUUID};
+
+use super::printer::{
+    DiscriminatedUnion, PrintableRuntypeKey, discriminated_union, emit_module_items, extract_union,
+    mentions_type_parameter, parameterized_names, required_classes, union_consts,
+    validate_type_uniqueness,
+};
+use super::util::{is_identifier, js_string};
+
+// Every hoisted runtype is compiled into three functions taking `(ctx, input)`, with the
+// semantics of the `validate`, `parseAfterValidation` and `reportDecodeError` methods of the
+// runtime classes. The helpers they call are defined by `codegen-aot.js`.
+
+#[derive(Clone, Copy)]
+struct HoistId {
+    in_factory: bool,
+    id: usize,
+}
+
+impl HoistId {
+    fn name(&self, op: &str) -> String {
+        if self.in_factory {
+            format!("factory_{}_{}", op, self.id)
+        } else {
+            format!("{}_{}", op, self.id)
+        }
+    }
+}
+
+#[derive(Default)]
+struct Hoisted {
+    ids: BTreeMap<PrintableRuntypeKey, usize>,
+    functions: Vec<Vec<Stmt>>,
+}
+
+struct AotContext<'a> {
+    named_schemas: &'a [NamedSchema],
+    all_names: Vec<RuntypeUUID>,
+    type_with_args_names: BTreeMap<RuntypeUUID, String>,
+    // named types that depend on the type parameters of a parser factory
+    parameterized: BTreeSet<RuntypeUUID>,
+    hoisted: Hoisted,
+    // functions declared in the body of the parser factory being compiled
+    factory_hoisted: Option<Hoisted>,
+    // module level constants, the compiled JSDoc patterns
+    constants: Vec<Stmt>,
+}
+
+fn js_number(n: &N) -> String {
+    n.to_serde().to_string()
+}
+
+fn js_json(json: &Json) -> String {
+    json.to_serde().to_string()
+}
+
+fn ident(name: &str) -> Expr {
+    Expr::Ident(Ident {
+        span: DUMMY_SP,
+        ctxt: SyntaxContext::empty(),
+        sym: name.into(),
+        optional: false,
+    })
+}
+
+fn binding(name: &str) -> Pat {
+    Pat::Ident(BindingIdent {
+        id: Ident {
+            span: DUMMY_SP,
+            ctxt: SyntaxContext::empty(),
+            sym: name.into(),
+            optional: false,
+        },
+        type_ann: None,
+    })
+}
+
+fn str_lit(s: &str) -> Expr {
+    Expr::Lit(Lit::Str(Str {
+        span: DUMMY_SP,
+        value: s.into(),
+        raw: None,
+    }))
+}
+
+fn num_lit(n: usize) -> Expr {
+    Expr::Lit(Lit::Num(Number {
+        span: DUMMY_SP,
+        value: n as f64,
+        raw: None,
+    }))
+}
+
+fn null_lit() -> Expr {
+    Expr::Lit(Lit::Null(Null { span: DUMMY_SP }))
+}
+
+fn bool_lit(value: bool) -> Expr {
+    Json::Bool(value).to_expr()
+}
+
+fn is_true(expr: &Expr) -> bool {
+    matches!(expr, Expr::Lit(Lit::Bool(it)) if it.value)
+}
+
+fn member(obj: &Expr, key: &str) -> Expr {
+    let prop = if is_identifier(key) {
+        MemberProp::Ident(IdentName {
+            span: DUMMY_SP,
+            sym: key.into(),
+        })
+    } else {
+        MemberProp::Computed(ComputedPropName {
+            span: DUMMY_SP,
+            expr: Box::new(str_lit(key)),
+        })
+    };
+    Expr::Member(MemberExpr {
+        span: DUMMY_SP,
+        obj: Box::new(obj.clone()),
+        prop,
+    })
+}
+
+fn index(obj: &Expr, idx: Expr) -> Expr {
+    Expr::Member(MemberExpr {
+        span: DUMMY_SP,
+        obj: Box::new(obj.clone()),
+        prop: MemberProp::Computed(ComputedPropName {
+            span: DUMMY_SP,
+            expr: Box::new(idx),
+        }),
+    })
+}
+
+fn arg(expr: Expr) -> ExprOrSpread {
+    ExprOrSpread {
+        spread: None,
+        expr: Box::new(expr),
+    }
+}
+
+fn spread(expr: Expr) -> ExprOrSpread {
+    ExprOrSpread {
+        spread: Some(DUMMY_SP),
+        expr: Box::new(expr),
+    }
+}
+
+fn call_args(callee: Expr, args: Vec<ExprOrSpread>) -> Expr {
+    Expr::Call(CallExpr {
+        span: DUMMY_SP,
+        ctxt: SyntaxContext::empty(),
+        callee: Callee::Expr(Box::new(callee)),
+        args,
+        type_args: None,
+    })
+}
+
+fn call(callee: Expr, args: Vec<Expr>) -> Expr {
+    call_args(callee, args.into_iter().map(arg).collect())
+}
+
+fn method(obj: &Expr, name: &str, args: Vec<Expr>) -> Expr {
+    call(member(obj, name), args)
+}
+
+fn new_expr(constructor: &str, args: Vec<Expr>) -> Expr {
+    Expr::New(NewExpr {
+        span: DUMMY_SP,
+        ctxt: SyntaxContext::empty(),
+        callee: Box::new(ident(constructor)),
+        args: Some(args.into_iter().map(arg).collect()),
+        type_args: None,
+    })
+}
+
+fn bin(left: Expr, op: BinaryOp, right: Expr) -> Expr {
+    Expr::Bin(BinExpr {
+        span: DUMMY_SP,
+        op,
+        left: Box::new(left),
+        right: Box::new(right),
+    })
+}
+
+// joins the expressions with `&&` or `||`, the parentheses are added by the fixer
+fn join(exprs: Vec<Expr>, op: BinaryOp) -> Expr {
+    exprs
+        .into_iter()
+        .reduce(|acc, it| bin(acc, op, it))
+        .expect("should join at least one expression")
+}
+
+fn unary(op: UnaryOp, expr: Expr) -> Expr {
+    Expr::Unary(UnaryExpr {
+        span: DUMMY_SP,
+        op,
+        arg: Box::new(expr),
+    })
+}
+
+fn not(expr: Expr) -> Expr {
+    unary(UnaryOp::Bang, expr)
+}
+
+fn typeof_is(input: &Expr, name: &str) -> Expr {
+    bin(
+        unary(UnaryOp::TypeOf, input.clone()),
+        BinaryOp::EqEqEq,
+        str_lit(name),
+    )
+}
+
+fn typeof_is_not(input: &Expr, name: &str) -> Expr {
+    bin(
+        unary(UnaryOp::TypeOf, input.clone()),
+        BinaryOp::NotEqEq,
+        str_lit(name),
+    )
+}
+
+fn is_nullish(input: &Expr) -> Expr {
+    bin(input.clone(), BinaryOp::EqEq, null_lit())
+}
+
+fn is_array(input: &Expr) -> Expr {
+    method(&ident("Array"), "isArray", vec![input.clone()])
+}
+
+fn instance_of(input: &Expr, constructor: Expr) -> Expr {
+    bin(input.clone(), BinaryOp::InstanceOf, constructor)
+}
+
+fn array_lit(elems: Vec<ExprOrSpread>) -> Expr {
+    Expr::Array(ArrayLit {
+        span: DUMMY_SP,
+        elems: elems.into_iter().map(Some).collect(),
+    })
+}
+
+fn object_lit(props: Vec<PropOrSpread>) -> Expr {
+    Expr::Object(ObjectLit {
+        span: DUMMY_SP,
+        props,
+    })
+}
+
+fn key_value(key: &str, value: Expr) -> PropOrSpread {
+    let key = if is_identifier(key) {
+        PropName::Ident(IdentName {
+            span: DUMMY_SP,
+            sym: key.into(),
+        })
+    } else {
+        PropName::Str(Str {
+            span: DUMMY_SP,
+            value: key.into(),
+            raw: None,
+        })
+    };
+    PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+        key,
+        value: Box::new(value),
+    })))
+}
+
+fn spread_prop(expr: Expr) -> PropOrSpread {
+    PropOrSpread::Spread(SpreadElement {
+        dot3_token: DUMMY_SP,
+        expr: Box::new(expr),
+    })
+}
+
+// a template literal, `quasis` has one more item than `exprs`
+fn tpl(quasis: &[&str], exprs: Vec<Expr>) -> Expr {
+    Expr::Tpl(Tpl {
+        span: DUMMY_SP,
+        exprs: exprs.into_iter().map(Box::new).collect(),
+        quasis: quasis
+            .iter()
+            .enumerate()
+            .map(|(i, it)| TplElement {
+                span: DUMMY_SP,
+                tail: i == quasis.len() - 1,
+                cooked: Some((*it).into()),
+                raw: (*it).into(),
+            })
+            .collect(),
+    })
+}
+
+fn expr_stmt(expr: Expr) -> Stmt {
+    Stmt::Expr(ExprStmt {
+        span: DUMMY_SP,
+        expr: Box::new(expr),
+    })
+}
+
+fn assign(target: Expr, value: Expr) -> Stmt {
+    let left = match target {
+        Expr::Member(it) => SimpleAssignTarget::Member(it),
+        _ => unreachable!("only members are assigned to"),
+    };
+    expr_stmt(Expr::Assign(AssignExpr {
+        span: DUMMY_SP,
+        op: AssignOp::Assign,
+        left: AssignTarget::Simple(left),
+        right: Box::new(value),
+    }))
+}
+
+fn ret(expr: Expr) -> Stmt {
+    Stmt::Return(ReturnStmt {
+        span: DUMMY_SP,
+        arg: Some(Box::new(expr)),
+    })
+}
+
+fn block(stmts: Vec<Stmt>) -> Stmt {
+    Stmt::Block(BlockStmt {
+        span: DUMMY_SP,
+        ctxt: SyntaxContext::empty(),
+        stmts,
+    })
+}
+
+fn if_(test: Expr, cons: Vec<Stmt>) -> Stmt {
+    Stmt::If(IfStmt {
+        span: DUMMY_SP,
+        test: Box::new(test),
+        cons: Box::new(block(cons)),
+        alt: None,
+    })
+}
+
+fn if_else(test: Expr, cons: Vec<Stmt>, alt: Vec<Stmt>) -> Stmt {
+    Stmt::If(IfStmt {
+        span: DUMMY_SP,
+        test: Box::new(test),
+        cons: Box::new(block(cons)),
+        alt: Some(Box::new(block(alt))),
+    })
+}
+
+fn var_decl(kind: VarDeclKind, name: Pat, init: Expr) -> VarDecl {
+    VarDecl {
+        span: DUMMY_SP,
+        ctxt: SyntaxContext::empty(),
+        kind,
+        declare: false,
+        decls: vec![VarDeclarator {
+            span: DUMMY_SP,
+            name,
+            init: Some(Box::new(init)),
+            definite: false,
+        }],
+    }
+}
+
+fn const_(name: &str, init: Expr) -> Stmt {
+    Stmt::Decl(Decl::Var(Box::new(var_decl(
+        VarDeclKind::Const,
+        binding(name),
+        init,
+    ))))
+}
+
+// `for (const <names> of <right>)`, with more than one name the items are destructured
+fn for_of(names: &[&str], right: Expr, body: Vec<Stmt>) -> Stmt {
+    let name = match names {
+        [name] => binding(name),
+        _ => Pat::Array(ArrayPat {
+            span: DUMMY_SP,
+            elems: names.iter().map(|it| Some(binding(it))).collect(),
+            optional: false,
+            type_ann: None,
+        }),
+    };
+    Stmt::ForOf(ForOfStmt {
+        span: DUMMY_SP,
+        is_await: false,
+        left: ForHead::VarDecl(Box::new(VarDecl {
+            span: DUMMY_SP,
+            ctxt: SyntaxContext::empty(),
+            kind: VarDeclKind::Const,
+            declare: false,
+            decls: vec![VarDeclarator {
+                span: DUMMY_SP,
+                name,
+                init: None,
+                definite: false,
+            }],
+        })),
+        right: Box::new(right),
+        body: Box::new(block(body)),
+    })
+}
+
+// `for (let i = <start>; i < <end>; i++)`
+fn for_range(start: Expr, end: Expr, body: Vec<Stmt>) -> Stmt {
+    Stmt::For(ForStmt {
+        span: DUMMY_SP,
+        init: Some(VarDeclOrExpr::VarDecl(Box::new(var_decl(
+            VarDeclKind::Let,
+            binding("i"),
+            start,
+        )))),
+        test: Some(Box::new(bin(ident("i"), BinaryOp::Lt, end))),
+        update: Some(Box::new(Expr::Update(UpdateExpr {
+            span: DUMMY_SP,
+            op: UpdateOp::PlusPlus,
+            prefix: false,
+            arg: Box::new(ident("i")),
+        }))),
+        body: Box::new(block(body)),
+    })
+}
+
+fn switch(discriminant: Expr, cases: Vec<SwitchCase>) -> Stmt {
+    Stmt::Switch(SwitchStmt {
+        span: DUMMY_SP,
+        discriminant: Box::new(discriminant),
+        cases,
+    })
+}
+
+fn case(test: Option<Expr>, cons: Vec<Stmt>) -> SwitchCase {
+    SwitchCase {
+        span: DUMMY_SP,
+        test: test.map(Box::new),
+        cons,
+    }
+}
+
+// cases sharing one body, `case a: case b: <cons>`
+fn fallthrough_cases(tests: Vec<Expr>, cons: Vec<Stmt>) -> Vec<SwitchCase> {
+    let count = tests.len();
+    tests
+        .into_iter()
+        .enumerate()
+        .map(|(i, it)| {
+            let cons = if i == count - 1 { cons.clone() } else { vec![] };
+            case(Some(it), cons)
+        })
+        .collect()
+}
+
+fn continue_() -> Stmt {
+    Stmt::Continue(swc_ecma_ast::ContinueStmt {
+        span: DUMMY_SP,
+        label: None,
+    })
+}
+
+fn function_decl(name: &str, params: &[&str], body: Vec<Stmt>) -> Stmt {
+    Stmt::Decl(Decl::Fn(FnDecl {
+        ident: Ident {
+            span: DUMMY_SP,
+            ctxt: SyntaxContext::empty(),
+            sym: name.into(),
+            optional: false,
+        },
+        declare: false,
+        function: Box::new(Function {
+            params: params
+                .iter()
+                .map(|it| Param {
+                    span: DUMMY_SP,
+                    decorators: vec![],
+                    pat: binding(it),
+                })
+                .collect(),
+            decorators: vec![],
+            span: DUMMY_SP,
+            ctxt: SyntaxContext::empty(),
+            body: Some(BlockStmt {
+                span: DUMMY_SP,
+                ctxt: SyntaxContext::empty(),
+                stmts: body,
+            }),
+            is_generator: false,
+            is_async: false,
+            type_params: None,
+            return_type: None,
+        }),
+    }))
+}
+
+fn ctx() -> Expr {
+    ident("ctx")
+}
+
+fn input() -> Expr {
+    ident("input")
+}
+
+fn path_push(segment: Expr) -> Stmt {
+    expr_stmt(method(&member(&ctx(), "path"), "push", vec![segment]))
+}
+
+fn path_pop() -> Stmt {
+    expr_stmt(method(&member(&ctx(), "path"), "pop", vec![]))
+}
+
+// `<acc>.push(...<errors>)`
+fn push_all(acc: &str, errors: Expr) -> Stmt {
+    expr_stmt(call_args(member(&ident(acc), "push"), vec![spread(errors)]))
+}
+
+fn build_error(message: &str, input: &Expr) -> Expr {
+    call(
+        ident("buildError"),
+        vec![ctx(), str_lit(message), input.clone()],
+    )
+}
+
+// lets a loop over the keys of an object skip the known properties
+fn skip_known_keys(keys: &[&String]) -> Option<Stmt> {
+    if keys.is_empty() {
+        return None;
+    }
+    let tests = keys.iter().map(|it| str_lit(it)).collect();
+    Some(switch(
+        ident("k"),
+        fallthrough_cases(tests, vec![continue_()]),
+    ))
+}
+
+fn limited_comma_join(values: &[String]) -> String {
+    let limit = 3;
+    if values.len() < limit {
+        return values.join(", ");
+    }
+    format!("{}...", values[..limit].join(", "))
+}
+
+/// Mirrors `describeRange` of the runtime `NumberRangeRuntype`.
+fn describe_range(range: &NumberRange) -> String {
+    let min = range.minimum.as_ref().map(|it| js_number(&it.value));
+    let max = range.maximum.as_ref().map(|it| js_number(&it.value));
+    let bounds = format!(
+        "Range<{}, {}>",
+        min.as_deref().unwrap_or("number"),
+        max.as_deref().unwrap_or("number")
+    );
+    let mut acc = match (&min, &max) {
+        (None, None) if range.integer => "Int".to_string(),
+        (None, None) => "number".to_string(),
+        _ if range.integer => format!("Int & {}", bounds),
+        (Some(min), None) if min == "0" => "NonNegative".to_string(),
+        _ => bounds,
+    };
+    for bound in [&range.minimum, &range.maximum].into_iter().flatten() {
+        if bound.exclusive {
+            acc = format!("Exclude<{}, {}>", acc, js_number(&bound.value));
+        }
+    }
+    acc
+}
+
+fn has_constraints(schema: &Runtype) -> bool {
+    schema
+        .metadata
+        .keywords
+        .as_ref()
+        .is_some_and(|it| it.has_constraints())
+}
+
+fn without_constraints(schema: &Runtype) -> Runtype {
+    let mut schema = schema.clone();
+    schema.metadata.keywords = None;
+    schema
+}
+
+// the values that the runtime merges into a copy when more than one branch of a union matches
+fn is_mergeable(schema: &Runtype) -> bool {
+    !matches!(
+        schema.kind,
+        RuntypeKind::Null
+            | RuntypeKind::Undefined
+            | RuntypeKind::Void
+            | RuntypeKind::Boolean
+            | RuntypeKind::String
+            | RuntypeKind::Number
+            | RuntypeKind::StringWithFormat(_)
+            | RuntypeKind::NumberWithFormat(_)
+            | RuntypeKind::NumberRange(_)
+            | RuntypeKind::TplLitType(_)
+            | RuntypeKind::Const(_)
+            | RuntypeKind::Never
+            | RuntypeKind::Function { .. }
+            | RuntypeKind::Date
+            | RuntypeKind::BigInt
+            | RuntypeKind::TypedArray(_)
+    )
+}
+
+struct TupleElement {
+    // the index of the element in the input
+    index: Expr,
+    // set when the element might be missing
+    guard: Option<Expr>,
+    schema: Runtype,
+    optional: bool,
+}
+
+struct TupleLayout {
+    preamble: Vec<Stmt>,
+    elements: Vec<TupleElement>,
+    // start and end index of the rest elements
+    rest: Option<(Expr, Expr, Runtype)>,
+    suffix: Vec<TupleElement>,
+}
+
+fn tuple_layout(
+    prefix_items: &[Runtype],
+    optional_items: &[Runtype],
+    items: &Option<Box<Runtype>>,
+    suffix_items: &[Runtype],
+) -> TupleLayout {
+    // mirrors `elementsFor` of the runtime `TupleRuntype`, missing required elements are
+    // checked against `undefined`
+    let required = prefix_items.len() + suffix_items.len();
+    let mut preamble = vec![];
+    if items.is_some() || !optional_items.is_empty() || !suffix_items.is_empty() {
+        preamble.push(const_(
+            "len",
+            method(
+                &ident("Math"),
+                "max",
+                vec![member(&input(), "length"), num_lit(required)],
+            ),
+        ));
+    }
+    if !optional_items.is_empty() {
+        preamble.push(const_(
+            "optionalCount",
+            method(
+                &ident("Math"),
+                "min",
+                vec![
+                    num_lit(optional_items.len()),
+                    bin(ident("len"), BinaryOp::Sub, num_lit(required)),
+                ],
+            ),
+        ));
+    }
+    let mut elements: Vec<TupleElement> = prefix_items
+        .iter()
+        .enumerate()
+        .map(|(i, it)| TupleElement {
+            index: num_lit(i),
+            guard: None,
+            schema: it.clone(),
+            optional: false,
+        })
+        .collect();
+    elements.extend(
+        optional_items
+            .iter()
+            .enumerate()
+            .map(|(j, it)| TupleElement {
+                index: num_lit(prefix_items.len() + j),
+                guard: Some(bin(ident("optionalCount"), BinaryOp::Gt, num_lit(j))),
+                schema: it.clone(),
+                optional: true,
+            }),
+    );
+    let rest = items.as_ref().map(|it| {
+        let start = if optional_items.is_empty() {
+            num_lit(prefix_items.len())
+        } else {
+            bin(
+                num_lit(prefix_items.len()),
+                BinaryOp::Add,
+                ident("optionalCount"),
+            )
+        };
+        let end = if suffix_items.is_empty() {
+            ident("len")
+        } else {
+            bin(ident("len"), BinaryOp::Sub, num_lit(suffix_items.len()))
+        };
+        (start, end, (**it).clone())
+    });
+    let suffix = suffix_items
+        .iter()
+        .enumerate()
+        .map(|(k, it)| TupleElement {
+            index: bin(ident("len"), BinaryOp::Sub, num_lit(suffix_items.len() - k)),
+            guard: None,
+            schema: it.clone(),
+            optional: false,
+        })
+        .collect();
+    TupleLayout {
+        preamble,
+        elements,
+        rest,
+        suffix,
+    }
+}
+
+fn guarded(guard: &Option<Expr>, stmt: Stmt) -> Stmt {
+    match guard {
+        Some(guard) => if_(guard.clone(), vec![stmt]),
+        None => stmt,
+    }
+}
+
+// `if (!<check>) { return false; }`
+fn return_false_unless(check: Expr) -> Stmt {
+    if_(not(check), vec![ret(bool_lit(false))])
+}
+
+impl AotContext<'_> {
+    fn named_schema(&self, to: &RuntypeUUID) -> &Runtype {
+        &self
+            .named_schemas
+            .iter()
+            .find(|it| it.name == *to)
+            .expect("everything should be resolved by now")
+            .schema
+    }
+
+    fn maybe_named_ref(&self, schema: &Runtype) -> Option<Runtype> {
+        self.named_schemas
+            .iter()
+            .find(|it| !self.parameterized.contains(&it.name) && it.schema == *schema)
+            .map(|it| Runtype::ref_(it.name.clone()))
+    }
+
+    fn is_hoisted(schema: &Runtype) -> bool {
+        has_constraints(schema)
+            || matches!(
+                schema.kind,
+                RuntypeKind::Object { .. }
+                    | RuntypeKind::Array(_)
+                    | RuntypeKind::AnyArrayLike
+                    | RuntypeKind::Tuple { .. }
+                    | RuntypeKind::Map(_, _)
+                    | RuntypeKind::Set(_)
+                    | RuntypeKind::AnyOf(_)
+                    | RuntypeKind::AllOf(_)
+            )
+    }
+
+    fn hoist(&mut self, schema: &Runtype) -> HoistId {
+        let key = PrintableRuntypeKey::from_runtype(schema);
+        // anything depending on a type parameter is compiled again in every factory
+        let in_factory =
+            self.factory_hoisted.is_some() && mentions_type_parameter(schema, &self.parameterized);
+        let table = if in_factory {
+            self.factory_hoisted
+                .as_mut()
+                .expect("should be compiling a factory")
+        } else {
+            &mut self.hoisted
+        };
+        if let Some(id) = table.ids.get(&key) {
+            return HoistId {
+                in_factory,
+                id: *id,
+            };
+        }
+        let id = HoistId {
+            in_factory,
+            id: table.functions.len(),
+        };
+        table.ids.insert(key, id.id);
+        table.functions.push(vec![]);
+
+        let mut functions = vec![];
+        let (validate, parse, report) = if has_constraints(schema) {
+            let keywords = schema
+                .metadata
+                .keywords
+                .as_ref()
+                .expect("should have keywords");
+            let constraints = self.constraints_body(keywords);
+            functions.push(function_decl(
+                &id.name("constraints"),
+                &["input"],
+                constraints,
+            ));
+            self.constrained_bodies(&without_constraints(schema), &id.name("constraints"))
+        } else {
+            (
+                self.validate_body(schema),
+                self.parse_body(schema),
+                self.report_body(schema),
+            )
+        };
+        for (op, body) in [("validate", validate), ("parse", parse), ("report", report)] {
+            functions.push(function_decl(&id.name(op), &["ctx", "input"], body));
+        }
+
+        let table = if in_factory {
+            self.factory_hoisted
+                .as_mut()
+                .expect("should be compiling a factory")
+        } else {
+            &mut self.hoisted
+        };
+        table.functions[id.id] = functions;
+        id
+    }
+
+    fn is_identity_parse(&self, schema: &Runtype) -> bool {
+        match &schema.kind {
+            RuntypeKind::Ref(to) => self.is_identity_parse(self.named_schema(to)),
+            RuntypeKind::AnyOf(vs) => vs
+                .iter()
+                .flat_map(|it| extract_union(it, self.named_schemas))
+                .all(|it| !is_mergeable(&it) && self.is_identity_parse(&it)),
+            RuntypeKind::TypeParameter(_)
+            | RuntypeKind::Object { .. }
+            | RuntypeKind::Array(_)
+            | RuntypeKind::AnyArrayLike
+            | RuntypeKind::Tuple { .. }
+            | RuntypeKind::Map(_, _)
+            | RuntypeKind::Set(_)
+            | RuntypeKind::AllOf(_) => false,
+            _ => true,
+        }
+    }
+
+    fn validate_expr(&mut self, schema: &Runtype, input: &Expr) -> Expr {
+        if Self::is_hoisted(schema) {
+            let name = self.hoist(schema).name("validate");
+            return call(ident(&name), vec![ctx(), input.clone()]);
+        }
+        match &schema.kind {
+            RuntypeKind::TypeParameter(name) => {
+                method(&ident(name), "validate", vec![ctx(), input.clone()])
+            }
+            RuntypeKind::Ref(to) => {
+                let target = self.named_schema(to).clone();
+                self.validate_expr(&target, input)
+            }
+            RuntypeKind::InstanceOf(class) => instance_of(
+                input,
+                call(ident("registeredClass"), vec![ctx(), str_lit(&class.name)]),
+            ),
+            _ => leaf_validate(schema, input),
+        }
+    }
+
+    fn parse_expr(&mut self, schema: &Runtype, input: &Expr) -> Expr {
+        if self.is_identity_parse(schema) {
+            return input.clone();
+        }
+        if Self::is_hoisted(schema) {
+            let name = self.hoist(schema).name("parse");
+            return call(ident(&name), vec![ctx(), input.clone()]);
+        }
+        match &schema.kind {
+            RuntypeKind::TypeParameter(name) => {
+                method(&ident(name), "parse", vec![ctx(), input.clone()])
+            }
+            RuntypeKind::Ref(to) => {
+                let target = self.named_schema(to).clone();
+                self.parse_expr(&target, input)
+            }
+            _ => input.clone(),
+        }
+    }
+
+    fn report_expr(&mut self, schema: &Runtype, input: &Expr) -> Expr {
+        if Self::is_hoisted(schema) {
+            let name = self.hoist(schema).name("report");
+            return call(ident(&name), vec![ctx(), input.clone()]);
+        }
+        match &schema.kind {
+            RuntypeKind::TypeParameter(name) => {
+                method(&ident(name), "report", vec![ctx(), input.clone()])
+            }
+            RuntypeKind::Ref(to) => {
+                let target = self.named_schema(to).clone();
+                self.report_expr(&target, input)
+            }
+            RuntypeKind::InstanceOf(class) => {
+                build_error(&format!("expected instance of {}", class.name), input)
+            }
+            _ => leaf_report(schema, input),
+        }
+    }
+
+    fn optional_validate(&mut self, value: &Optionality<Runtype>, input: &Expr) -> Expr {
+        let check = self.validate_expr(value.inner(), input);
+        match value {
+            Optionality::Optional(_) if !is_true(&check) => {
+                bin(is_nullish(input), BinaryOp::LogicalOr, check)
+            }
+            _ => check,
+        }
+    }
+
+    fn optional_parse(&mut self, value: &Optionality<Runtype>, input: &Expr) -> Expr {
+        match value {
+            Optionality::Optional(schema) if !self.is_identity_parse(schema) => {
+                Expr::Cond(CondExpr {
+                    span: DUMMY_SP,
+                    test: Box::new(is_nullish(input)),
+                    cons: Box::new(input.clone()),
+                    alt: Box::new(self.parse_expr(schema, input)),
+                })
+            }
+            _ => self.parse_expr(value.inner(), input),
+        }
+    }
+
+    fn constraints_body(&mut self, keywords: &SchemaKeywords) -> Vec<Stmt> {
+        let input = input();
+        let fail_if = |test: Expr, message: String| if_(test, vec![ret(str_lit(&message))]);
+        let mut out = vec![];
+        let mut strings = vec![];
+        if keywords.min_length.is_some() || keywords.max_length.is_some() {
+            strings.push(const_(
+                "length",
+                member(
+                    &method(&ident("Array"), "from", vec![input.clone()]),
+                    "length",
+                ),
+            ));
+        }
+        if let Some(min_length) = keywords.min_length {
+            strings.push(fail_if(
+                bin(ident("length"), BinaryOp::Lt, num_lit(min_length)),
+                format!("expected string with at least {} characters", min_length),
+            ));
+        }
+        if let Some(max_length) = keywords.max_length {
+            strings.push(fail_if(
+                bin(ident("length"), BinaryOp::Gt, num_lit(max_length)),
+                format!("expected string with at most {} characters", max_length),
+            ));
+        }
+        if let Some(pattern) = &keywords.pattern {
+            let name = format!("pattern_{}", self.constants.len());
+            self.constants.push(const_(
+                &name,
+                new_expr("RegExp", vec![str_lit(pattern), str_lit("u")]),
+            ));
+            strings.push(fail_if(
+                not(method(&ident(&name), "test", vec![input.clone()])),
+                format!("expected string matching {}", pattern),
+            ));
+        }
+        if !strings.is_empty() {
+            out.push(if_(typeof_is(&input, "string"), strings));
+        }
+        let mut numbers = vec![];
+        if let Some(minimum) = &keywords.minimum {
+            numbers.push(fail_if(
+                bin(
+                    input.clone(),
+                    BinaryOp::Lt,
+                    Json::Number(minimum.clone()).to_expr(),
+                ),
+                format!(
+                    "expected number greater than or equal to {}",
+                    js_number(minimum)
+                ),
+            ));
+        }
+        if let Some(maximum) = &keywords.maximum {
+            numbers.push(fail_if(
+                bin(
+                    input.clone(),
+                    BinaryOp::Gt,
+                    Json::Number(maximum.clone()).to_expr(),
+                ),
+                format!(
+                    "expected number less than or equal to {}",
+                    js_number(maximum)
+                ),
+            ));
+        }
+        if !numbers.is_empty() {
+            out.push(if_(typeof_is(&input, "number"), numbers));
+        }
+        let mut arrays = vec![];
+        let length = member(&input, "length");
+        if let Some(min_items) = keywords.min_items {
+            arrays.push(fail_if(
+                bin(length.clone(), BinaryOp::Lt, num_lit(min_items)),
+                format!("expected array with at least {} items", min_items),
+            ));
+        }
+        if let Some(max_items) = keywords.max_items {
+            arrays.push(fail_if(
+                bin(length, BinaryOp::Gt, num_lit(max_items)),
+                format!("expected array with at most {} items", max_items),
+            ));
+        }
+        if !arrays.is_empty() {
+            out.push(if_(is_array(&input), arrays));
+        }
+        out.push(ret(ident("undefined")));
+        out
+    }
+
+    fn constrained_bodies(
+        &mut self,
+        inner: &Runtype,
+        constraints: &str,
+    ) -> (Vec<Stmt>, Vec<Stmt>, Vec<Stmt>) {
+        let input = input();
+        let message = call(ident(constraints), vec![input.clone()]);
+        let validate = self.validate_expr(inner, &input);
+        let validate_body = vec![ret(bin(
+            validate.clone(),
+            BinaryOp::LogicalAnd,
+            bin(message.clone(), BinaryOp::EqEqEq, ident("undefined")),
+        ))];
+        let parse_body = vec![ret(self.parse_expr(inner, &input))];
+        let report_body = vec![
+            const_("message", message),
+            if_(
+                bin(
+                    bin(ident("message"), BinaryOp::EqEqEq, ident("undefined")),
+                    BinaryOp::LogicalOr,
+                    not(validate),
+                ),
+                vec![ret(self.report_expr(inner, &input))],
+            ),
+            ret(call(
+                ident("buildError"),
+                vec![ctx(), ident("message"), input],
+            )),
+        ];
+        (validate_body, parse_body, report_body)
+    }
+
+    fn validate_body(&mut self, schema: &Runtype) -> Vec<Stmt> {
+        let input = input();
+        match &schema.kind {
+            RuntypeKind::Object {
+                vs,
+                indexed_properties,
+            } => self.object_validate(vs, indexed_properties),
+            RuntypeKind::Array(item) => self.array_validate(item),
+            RuntypeKind::AnyArrayLike => self.array_validate(&Runtype::any()),
+            RuntypeKind::Tuple {
+                prefix_items,
+                optional_items,
+                items,
+                suffix_items,
+            } => {
+                let layout = tuple_layout(prefix_items, optional_items, items, suffix_items);
+                let mut out = vec![return_false_unless(is_array(&input))];
+                if items.is_none() {
+                    let max = prefix_items.len() + optional_items.len() + suffix_items.len();
+                    out.push(if_(
+                        bin(member(&input, "length"), BinaryOp::Gt, num_lit(max)),
+                        vec![ret(bool_lit(false))],
+                    ));
+                }
+                out.extend(layout.preamble);
+                for it in layout.elements {
+                    let check = self.tuple_element_validate(&it);
+                    out.push(guarded(&it.guard, check));
+                }
+                if let Some((start, end, rest)) = layout.rest {
+                    let check = self.validate_expr(&rest, &index(&input, ident("i")));
+                    out.push(for_range(start, end, vec![return_false_unless(check)]));
+                }
+                for it in layout.suffix {
+                    out.push(self.tuple_element_validate(&it));
+                }
+                out.push(ret(bool_lit(true)));
+                out
+            }
+            RuntypeKind::Map(k, v) => {
+                let key_check = self.validate_expr(k, &ident("k"));
+                let value_check = self.validate_expr(v, &ident("v"));
+                vec![
+                    return_false_unless(instance_of(&input, ident("Map"))),
+                    for_of(
+                        &["k", "v"],
+                        input,
+                        vec![if_(
+                            bin(not(key_check), BinaryOp::LogicalOr, not(value_check)),
+                            vec![ret(bool_lit(false))],
+                        )],
+                    ),
+                    ret(bool_lit(true)),
+                ]
+            }
+            RuntypeKind::Set(v) => {
+                let check = self.validate_expr(v, &ident("v"));
+                vec![
+                    return_false_unless(instance_of(&input, ident("Set"))),
+                    for_of(&["v"], input, vec![return_false_unless(check)]),
+                    ret(bool_lit(true)),
+                ]
+            }
+            RuntypeKind::AllOf(vs) => {
+                let mut checks = vec![typeof_is(&input, "object")];
+                for it in vs {
+                    checks.push(self.validate_expr(it, &input));
+                }
+                vec![ret(join(checks, BinaryOp::LogicalAnd))]
+            }
+            RuntypeKind::AnyOf(vs) => match self.union_kind(vs) {
+                UnionKind::Consts(consts) => {
+                    let tests = consts.into_iter().map(|it| it.to_expr()).collect();
+                    let mut cases = fallthrough_cases(tests, vec![ret(bool_lit(true))]);
+                    cases.push(case(None, vec![ret(bool_lit(false))]));
+                    vec![switch(input, cases)]
+                }
+                UnionKind::Discriminated(union) => {
+                    let discriminator = member(&input, &union.discriminator);
+                    let mut cases = vec![];
+                    for (value, schema) in self.discriminated_schemas(&union) {
+                        let check = self.validate_expr(&schema, &input);
+                        cases.push(case(Some(str_lit(&value)), vec![ret(check)]));
+                    }
+                    cases.push(case(None, vec![ret(bool_lit(false))]));
+                    vec![
+                        if_(
+                            bin(
+                                typeof_is_not(&input, "object"),
+                                BinaryOp::LogicalOr,
+                                bin(input.clone(), BinaryOp::EqEqEq, null_lit()),
+                            ),
+                            vec![ret(bool_lit(false))],
+                        ),
+                        switch(discriminator, cases),
+                    ]
+                }
+                UnionKind::Plain => {
+                    let checks = vs.iter().map(|it| self.validate_expr(it, &input)).collect();
+                    vec![ret(join(checks, BinaryOp::LogicalOr))]
+                }
+            },
+            _ => vec![ret(self.validate_expr(schema, &input))],
+        }
+    }
+
+    fn parse_body(&mut self, schema: &Runtype) -> Vec<Stmt> {
+        let input = input();
+        match &schema.kind {
+            RuntypeKind::Object {
+                vs,
+                indexed_properties,
+            } => self.object_parse(vs, indexed_properties),
+            RuntypeKind::Array(item) => self.array_parse(item),
+            RuntypeKind::AnyArrayLike => self.array_parse(&Runtype::any()),
+            RuntypeKind::Tuple {
+                prefix_items,
+                optional_items,
+                items,
+                suffix_items,
+            } => {
+                let layout = tuple_layout(prefix_items, optional_items, items, suffix_items);
+                let push = |value: Expr| expr_stmt(method(&ident("acc"), "push", vec![value]));
+                let mut out = layout.preamble;
+                out.push(const_("acc", array_lit(vec![])));
+                for it in layout.elements {
+                    let value = self.tuple_element_parse(&it);
+                    out.push(guarded(&it.guard, push(value)));
+                }
+                if let Some((start, end, rest)) = layout.rest {
+                    let value = self.parse_expr(&rest, &index(&input, ident("i")));
+                    out.push(for_range(start, end, vec![push(value)]));
+                }
+                for it in layout.suffix {
+                    let value = self.tuple_element_parse(&it);
+                    out.push(push(value));
+                }
+                out.push(ret(ident("acc")));
+                out
+            }
+            RuntypeKind::Map(k, v) => {
+                let key = self.parse_expr(k, &ident("k"));
+                let value = self.parse_expr(v, &ident("v"));
+                vec![
+                    const_("acc", new_expr("Map", vec![])),
+                    for_of(
+                        &["k", "v"],
+                        input,
+                        vec![expr_stmt(method(&ident("acc"), "set", vec![key, value]))],
+                    ),
+                    ret(ident("acc")),
+                ]
+            }
+            RuntypeKind::Set(v) => {
+                let value = self.parse_expr(v, &ident("v"));
+                vec![
+                    const_("acc", new_expr("Set", vec![])),
+                    for_of(
+                        &["v"],
+                        input,
+                        vec![expr_stmt(method(&ident("acc"), "add", vec![value]))],
+                    ),
+                    ret(ident("acc")),
+                ]
+            }
+            RuntypeKind::AllOf(vs) => {
+                let spreads = vs
+                    .iter()
+                    .map(|it| spread_prop(self.parse_expr(it, &input)))
+                    .collect();
+                vec![ret(object_lit(spreads))]
+            }
+            RuntypeKind::AnyOf(vs) => {
+                if self.is_identity_parse(schema) {
+                    return vec![ret(input)];
+                }
+                match self.union_kind(vs) {
+                    UnionKind::Consts(_) => vec![ret(input)],
+                    UnionKind::Discriminated(union) => {
+                        let discriminator = member(&input, &union.discriminator);
+                        let mut cases = vec![];
+                        for (value, schema) in self.discriminated_schemas(&union) {
+                            let parsed = self.parse_expr(&schema, &input);
+                            cases.push(case(
+                                Some(str_lit(&value)),
+                                vec![ret(object_lit(vec![
+                                    spread_prop(parsed),
+                                    key_value(&union.discriminator, discriminator.clone()),
+                                ]))],
+                            ));
+                        }
+                        let message = bin(
+                            str_lit("INTERNAL ERROR: Missing parser for discriminator "),
+                            BinaryOp::Add,
+                            method(&ident("JSON"), "stringify", vec![discriminator.clone()]),
+                        );
+                        cases.push(case(
+                            None,
+                            vec![Stmt::Throw(ThrowStmt {
+                                span: DUMMY_SP,
+                                arg: Box::new(new_expr("Error", vec![message])),
+                            })],
+                        ));
+                        vec![switch(discriminator, cases)]
+                    }
+                    UnionKind::Plain => {
+                        // every matching branch is parsed and the results are merged
+                        let mut out = vec![const_("items", array_lit(vec![]))];
+                        for it in vs {
+                            let check = self.validate_expr(it, &input);
+                            let parsed = self.parse_expr(it, &input);
+                            out.push(if_(
+                                check,
+                                vec![expr_stmt(method(&ident("items"), "push", vec![parsed]))],
+                            ));
+                        }
+                        out.push(ret(call_args(
+                            ident("deepmerge"),
+                            vec![spread(ident("items"))],
+                        )));
+                        out
+                    }
+                }
+            }
+            _ => vec![ret(self.parse_expr(schema, &input))],
+        }
+    }
+
+    fn report_body(&mut self, schema: &Runtype) -> Vec<Stmt> {
+        let input = input();
+        match &schema.kind {
+            RuntypeKind::Object {
+                vs,
+                indexed_properties,
+            } => self.object_report(vs, indexed_properties),
+            RuntypeKind::Array(item) => self.array_report(item),
+            RuntypeKind::AnyArrayLike => self.array_report(&Runtype::any()),
+            RuntypeKind::Tuple {
+                prefix_items,
+                optional_items,
+                items,
+                suffix_items,
+            } => {
+                let layout = tuple_layout(prefix_items, optional_items, items, suffix_items);
+                let mut out = vec![if_(
+                    not(is_array(&input)),
+                    vec![ret(build_error("expected tuple", &input))],
+                )];
+                if items.is_none() {
+                    let max = prefix_items.len() + optional_items.len() + suffix_items.len();
+                    out.push(if_(
+                        bin(member(&input, "length"), BinaryOp::Gt, num_lit(max)),
+                        vec![ret(build_error(
+                            &format!("expected tuple with at most {} items", max),
+                            &input,
+                        ))],
+                    ));
+                }
+                out.extend(layout.preamble);
+                out.push(const_("acc", array_lit(vec![])));
+                for it in layout.elements {
+                    let report = self.tuple_element_report(&it.index, &it.schema, it.optional);
+                    out.push(guarded(&it.guard, report));
+                }
+                if let Some((start, end, rest)) = layout.rest {
+                    let report = self.tuple_element_report(&ident("i"), &rest, false);
+                    out.push(for_range(start, end, vec![report]));
+                }
+                for it in layout.suffix {
+                    out.push(self.tuple_element_report(&it.index, &it.schema, it.optional));
+                }
+                out.push(ret(ident("acc")));
+                out
+            }
+            RuntypeKind::Map(k, v) => {
+                let key_check = self.validate_expr(k, &ident("k"));
+                let key_report = self.report_expr(k, &ident("k"));
+                let value_check = self.validate_expr(v, &ident("v"));
+                let value_report = self.report_expr(v, &ident("v"));
+                let printed_key = method(&ident("JSON"), "stringify", vec![ident("k")]);
+                vec![
+                    if_(
+                        not(instance_of(&input, ident("Map"))),
+                        vec![ret(build_error("expected Map", &input))],
+                    ),
+                    const_("acc", array_lit(vec![])),
+                    for_of(
+                        &["k", "v"],
+                        input,
+                        vec![
+                            path_push(tpl(&["key(", ")"], vec![printed_key.clone()])),
+                            if_(not(key_check), vec![push_all("acc", key_report)]),
+                            path_pop(),
+                            path_push(tpl(&["value(", ")"], vec![printed_key])),
+                            if_(not(value_check), vec![push_all("acc", value_report)]),
+                            path_pop(),
+                        ],
+                    ),
+                    ret(ident("acc")),
+                ]
+            }
+            RuntypeKind::Set(v) => {
+                let check = self.validate_expr(v, &ident("v"));
+                let report = self.report_expr(v, &ident("v"));
+                let printed_item = method(&ident("JSON"), "stringify", vec![ident("v")]);
+                vec![
+                    if_(
+                        not(instance_of(&input, ident("Set"))),
+                        vec![ret(build_error("expected Set", &input))],
+                    ),
+                    const_("acc", array_lit(vec![])),
+                    for_of(
+                        &["v"],
+                        input,
+                        vec![
+                            path_push(tpl(&["item(", ")"], vec![printed_item])),
+                            if_(not(check), vec![push_all("acc", report)]),
+                            path_pop(),
+                        ],
+                    ),
+                    ret(ident("acc")),
+                ]
+            }
+            RuntypeKind::AllOf(vs) => {
+                let reports = vs
+                    .iter()
+                    .map(|it| spread(self.report_expr(it, &input)))
+                    .collect();
+                vec![ret(array_lit(reports))]
+            }
+            RuntypeKind::AnyOf(vs) => match self.union_kind(vs) {
+                UnionKind::Consts(consts) => {
+                    let values = consts.iter().map(js_json).collect::<Vec<_>>();
+                    vec![ret(build_error(
+                        &format!("expected one of {}", limited_comma_join(&values)),
+                        &input,
+                    ))]
+                }
+                UnionKind::Discriminated(union) => {
+                    let discriminator = member(&input, &union.discriminator);
+                    let schemas = self.discriminated_schemas(&union);
+                    let expected = schemas
+                        .iter()
+                        .map(|(value, _)| js_string(value))
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    let mut cases = vec![];
+                    for (value, schema) in schemas {
+                        let report = self.report_expr(&schema, &input);
+                        cases.push(case(Some(str_lit(&value)), vec![ret(report)]));
+                    }
+                    vec![
+                        if_(
+                            bin(
+                                is_nullish(&input),
+                                BinaryOp::LogicalOr,
+                                typeof_is_not(&input, "object"),
+                            ),
+                            vec![ret(build_error("expected object", &input))],
+                        ),
+                        switch(discriminator.clone(), cases),
+                        if_(
+                            is_nullish(&discriminator),
+                            vec![ret(build_error(
+                                &format!(
+                                    "expected discriminator key {}",
+                                    js_string(&union.discriminator)
+                                ),
+                                &input,
+                            ))],
+                        ),
+                        path_push(str_lit(&union.discriminator)),
+                        const_(
+                            "errors",
+                            build_error(&format!("expected one of {}", expected), &discriminator),
+                        ),
+                        path_pop(),
+                        ret(ident("errors")),
+                    ]
+                }
+                UnionKind::Plain => {
+                    // the branches report errors relative to the union
+                    let reports = vs
+                        .iter()
+                        .map(|it| arg(self.report_expr(it, &input)))
+                        .collect();
+                    let ctx_path = member(&ctx(), "path");
+                    vec![
+                        const_("path", ctx_path.clone()),
+                        assign(ctx_path.clone(), array_lit(vec![])),
+                        const_("branchErrors", array_lit(reports)),
+                        assign(ctx_path, ident("path")),
+                        ret(call(
+                            ident("unionDecodeError"),
+                            vec![ctx(), ident("branchErrors"), input],
+                        )),
+                    ]
+                }
+            },
+            _ => vec![ret(self.report_expr(schema, &input))],
+        }
+    }
+
+    fn object_validate(
+        &mut self,
+        vs: &BTreeMap<String, Optionality<Runtype>>,
+        indexed_properties: &[IndexedProperty],
+    ) -> Vec<Stmt> {
+        let input = input();
+        let mut out = vec![if_(
+            join(
+                vec![
+                    typeof_is_not(&input, "object"),
+                    bin(input.clone(), BinaryOp::EqEqEq, null_lit()),
+                    is_array(&input),
+                ],
+                BinaryOp::LogicalOr,
+            ),
+            vec![ret(bool_lit(false))],
+        )];
+        for (key, value) in vs {
+            let check = self.optional_validate(value, &member(&input, key));
+            if !is_true(&check) {
+                out.push(return_false_unless(check));
+            }
+        }
+        let keys = vs.keys().collect::<Vec<_>>();
+        let object_keys = method(&ident("Object"), "keys", vec![input.clone()]);
+        if !indexed_properties.is_empty() {
+            // a key must satisfy every index signature it matches
+            let mut body: Vec<Stmt> = skip_known_keys(&keys).into_iter().collect();
+            body.push(Stmt::Decl(Decl::Var(Box::new(var_decl(
+                VarDeclKind::Let,
+                binding("matched"),
+                bool_lit(false),
+            )))));
+            for it in indexed_properties {
+                let key_check = self.validate_expr(&it.key, &ident("k"));
+                let value_check = self.optional_validate(&it.value, &index(&input, ident("k")));
+                body.push(if_(
+                    key_check,
+                    vec![
+                        expr_stmt(Expr::Assign(AssignExpr {
+                            span: DUMMY_SP,
+                            op: AssignOp::Assign,
+                            left: AssignTarget::Simple(SimpleAssignTarget::Ident(BindingIdent {
+                                id: Ident {
+                                    span: DUMMY_SP,
+                                    ctxt: SyntaxContext::empty(),
+                                    sym: "matched".into(),
+                                    optional: false,
+                                },
+                                type_ann: None,
+                            })),
+                            right: Box::new(bool_lit(true)),
+                        })),
+                        return_false_unless(value_check),
+                    ],
+                ));
+            }
+            body.push(return_false_unless(ident("matched")));
+            out.push(for_of(&["k"], object_keys, body));
+        } else if keys.is_empty() {
+            out.push(if_(
+                bin(
+                    member(&ctx(), "disallowExtraProperties"),
+                    BinaryOp::LogicalAnd,
+                    bin(member(&object_keys, "length"), BinaryOp::Gt, num_lit(0)),
+                ),
+                vec![ret(bool_lit(false))],
+            ));
+        } else {
+            let mut body: Vec<Stmt> = skip_known_keys(&keys).into_iter().collect();
+            body.push(ret(bool_lit(false)));
+            out.push(if_(
+                member(&ctx(), "disallowExtraProperties"),
+                vec![for_of(&["k"], object_keys, body)],
+            ));
+        }
+        out.push(ret(bool_lit(true)));
+        out
+    }
+
+    fn indexed_properties_parse(&mut self, indexed_properties: &[IndexedProperty]) -> Vec<Stmt> {
+        // the first matching index signature parses the value
+        let input = input();
+        let mut out = vec![];
+        for it in indexed_properties {
+            let key_check = self.validate_expr(&it.key, &ident("k"));
+            let key = self.parse_expr(&it.key, &ident("k"));
+            let value = self.optional_parse(&it.value, &index(&input, ident("k")));
+            out.push(if_(
+                key_check,
+                vec![assign(index(&ident("acc"), key), value), continue_()],
+            ));
+        }
+        out
+    }
+
+    fn object_parse(
+        &mut self,
+        vs: &BTreeMap<String, Optionality<Runtype>>,
+        indexed_properties: &[IndexedProperty],
+    ) -> Vec<Stmt> {
+        let input = input();
+        let acc = ident("acc");
+        let object_keys = method(&ident("Object"), "keys", vec![input.clone()]);
+        let mut input_order_cases = vec![];
+        for (key, value) in vs {
+            let parsed = self.optional_parse(value, &member(&input, key));
+            input_order_cases.push(case(
+                Some(str_lit(key)),
+                vec![assign(member(&acc, key), parsed), continue_()],
+            ));
+        }
+        let indexed = self.indexed_properties_parse(indexed_properties);
+        let mut input_order = vec![];
+        if !input_order_cases.is_empty() {
+            input_order.push(switch(ident("k"), input_order_cases));
+        }
+        input_order.extend(indexed.clone());
+        let input_order = for_of(&["k"], object_keys.clone(), input_order);
+
+        // the keys are sorted by their UTF-16 code units, like `Array.prototype.sort` does
+        let mut sorted_keys = vs.keys().collect::<Vec<_>>();
+        sorted_keys.sort_by_key(|it| it.encode_utf16().collect::<Vec<_>>());
+        let mut sorted_order = vec![];
+        for key in &sorted_keys {
+            let parsed = self.optional_parse(&vs[*key], &member(&input, key));
+            sorted_order.push(if_(
+                call(ident("hasOwn"), vec![input.clone(), str_lit(key)]),
+                vec![assign(member(&acc, key), parsed)],
+            ));
+        }
+        if !indexed_properties.is_empty() {
+            let mut collect: Vec<Stmt> = skip_known_keys(&sorted_keys).into_iter().collect();
+            collect.push(expr_stmt(method(
+                &ident("extraKeys"),
+                "push",
+                vec![ident("k")],
+            )));
+            sorted_order.extend([
+                const_("extraKeys", array_lit(vec![])),
+                for_of(&["k"], object_keys, collect),
+                expr_stmt(method(&ident("extraKeys"), "sort", vec![])),
+                for_of(&["k"], ident("extraKeys"), indexed),
+            ]);
+        }
+        vec![
+            const_("acc", object_lit(vec![])),
+            if_else(
+                bin(
+                    member(&ctx(), "objectKeyOrder"),
+                    BinaryOp::EqEqEq,
+                    str_lit("input"),
+                ),
+                vec![input_order],
+                sorted_order,
+            ),
+            ret(acc),
+        ]
+    }
+
+    fn object_report(
+        &mut self,
+        vs: &BTreeMap<String, Optionality<Runtype>>,
+        indexed_properties: &[IndexedProperty],
+    ) -> Vec<Stmt> {
+        let input = input();
+        let object_keys = method(&ident("Object"), "keys", vec![input.clone()]);
+        let mut out = vec![
+            if_(
+                join(
+                    vec![
+                        typeof_is_not(&input, "object"),
+                        is_array(&input),
+                        bin(input.clone(), BinaryOp::EqEqEq, null_lit()),
+                    ],
+                    BinaryOp::LogicalOr,
+                ),
+                vec![ret(build_error("expected object", &input))],
+            ),
+            const_("acc", array_lit(vec![])),
+        ];
+        for (key, value) in vs {
+            let value_input = member(&input, key);
+            let check = self.optional_validate(value, &value_input);
+            if is_true(&check) {
+                continue;
+            }
+            let report = self.report_expr(value.inner(), &value_input);
+            out.push(if_(
+                not(check),
+                vec![path_push(str_lit(key)), push_all("acc", report), path_pop()],
+            ));
+        }
+        let keys = vs.keys().collect::<Vec<_>>();
+        if !indexed_properties.is_empty() {
+            let mut body: Vec<Stmt> = skip_known_keys(&keys).into_iter().collect();
+            let mut key_reports = vec![];
+            let mut value_reports = vec![];
+            let value_input = index(&input, ident("k"));
+            for (i, it) in indexed_properties.iter().enumerate() {
+                let matches = format!("matches{}", i);
+                let key_check = self.validate_expr(&it.key, &ident("k"));
+                body.push(const_(&matches, key_check));
+                key_reports.push(spread(self.report_expr(&it.key, &ident("k"))));
+                let value_check = self.optional_validate(&it.value, &value_input);
+                let value_report = self.report_expr(it.value.inner(), &value_input);
+                value_reports.push(if_(
+                    bin(ident(&matches), BinaryOp::LogicalAnd, not(value_check)),
+                    vec![push_all("acc", value_report)],
+                ));
+            }
+            let none_matches = join(
+                (0..indexed_properties.len())
+                    .map(|i| not(ident(&format!("matches{}", i))))
+                    .collect(),
+                BinaryOp::LogicalAnd,
+            );
+            body.push(path_push(ident("k")));
+            body.push(if_(
+                none_matches,
+                vec![expr_stmt(call_args(
+                    member(&ident("acc"), "push"),
+                    key_reports,
+                ))],
+            ));
+            body.extend(value_reports);
+            body.push(path_pop());
+            out.push(for_of(&["k"], object_keys, body));
+        } else {
+            let mut body: Vec<Stmt> = skip_known_keys(&keys).into_iter().collect();
+            body.extend([
+                path_push(ident("k")),
+                push_all(
+                    "extra",
+                    build_error("extra property", &index(&input, ident("k"))),
+                ),
+                path_pop(),
+            ]);
+            out.push(if_(
+                member(&ctx(), "disallowExtraProperties"),
+                vec![
+                    const_("extra", array_lit(vec![])),
+                    for_of(&["k"], object_keys, body),
+                    if_(
+                        bin(member(&ident("extra"), "length"), BinaryOp::Gt, num_lit(0)),
+                        vec![ret(ident("extra"))],
+                    ),
+                ],
+            ));
+        }
+        out.push(ret(ident("acc")));
+        out
+    }
+
+    fn array_validate(&mut self, item: &Runtype) -> Vec<Stmt> {
+        let input = input();
+        let check = self.validate_expr(item, &index(&input, ident("i")));
+        if is_true(&check) {
+            return vec![ret(is_array(&input))];
+        }
+        vec![
+            return_false_unless(is_array(&input)),
+            for_range(
+                num_lit(0),
+                member(&input, "length"),
+                vec![return_false_unless(check)],
+            ),
+            ret(bool_lit(true)),
+        ]
+    }
+
+    fn array_parse(&mut self, item: &Runtype) -> Vec<Stmt> {
+        let input = input();
+        if self.is_identity_parse(item) {
+            return vec![ret(method(&input, "slice", vec![]))];
+        }
+        let value = self.parse_expr(item, &index(&input, ident("i")));
+        vec![
+            const_("acc", new_expr("Array", vec![member(&input, "length")])),
+            for_range(
+                num_lit(0),
+                member(&input, "length"),
+                vec![assign(index(&ident("acc"), ident("i")), value)],
+            ),
+            ret(ident("acc")),
+        ]
+    }
+
+    fn array_report(&mut self, item: &Runtype) -> Vec<Stmt> {
+        let input = input();
+        let item_input = index(&input, ident("i"));
+        let check = self.validate_expr(item, &item_input);
+        let report = self.report_expr(item, &item_input);
+        vec![
+            if_(
+                not(is_array(&input)),
+                vec![ret(build_error("expected array", &input))],
+            ),
+            const_("acc", array_lit(vec![])),
+            for_range(
+                num_lit(0),
+                member(&input, "length"),
+                vec![if_(
+                    not(check),
+                    vec![
+                        path_push(tpl(&["[", "]"], vec![ident("i")])),
+                        push_all("acc", report),
+                        path_pop(),
+                    ],
+                )],
+            ),
+            ret(ident("acc")),
+        ]
+    }
+
+    fn tuple_element_validate(&mut self, element: &TupleElement) -> Stmt {
+        let input = index(&input(), element.index.clone());
+        let check = if element.optional {
+            self.optional_validate(&Optionality::Optional(element.schema.clone()), &input)
+        } else {
+            self.validate_expr(&element.schema, &input)
+        };
+        return_false_unless(check)
+    }
+
+    fn tuple_element_parse(&mut self, element: &TupleElement) -> Expr {
+        let input = index(&input(), element.index.clone());
+        if element.optional {
+            self.optional_parse(&Optionality::Optional(element.schema.clone()), &input)
+        } else {
+            self.parse_expr(&element.schema, &input)
+        }
+    }
+
+    fn tuple_element_report(&mut self, at: &Expr, schema: &Runtype, optional: bool) -> Stmt {
+        let input = index(&input(), at.clone());
+        let check = if optional {
+            self.optional_validate(&Optionality::Optional(schema.clone()), &input)
+        } else {
+            self.validate_expr(schema, &input)
+        };
+        let report = self.report_expr(schema, &input);
+        let path = match at {
+            Expr::Lit(Lit::Num(n)) => str_lit(&format!("[{}]", n.value)),
+            _ => tpl(&["[", "]"], vec![at.clone()]),
+        };
+        if_(
+            not(check),
+            vec![path_push(path), push_all("acc", report), path_pop()],
+        )
+    }
+
+    fn union_kind(&mut self, vs: &BTreeSet<Runtype>) -> UnionKind {
+        let flat_values = vs
+            .iter()
+            .flat_map(|it| extract_union(it, self.named_schemas))
+            .collect::<BTreeSet<_>>();
+        let dbg_ctx = DebugPrintCtx {
+            all_names: &self.all_names.iter().collect::<Vec<_>>(),
+            type_with_args_names: &mut self.type_with_args_names,
+        };
+        if let Some(consts) = union_consts(&flat_values, &dbg_ctx) {
+            return UnionKind::Consts(consts);
+        }
+        match discriminated_union(&flat_values, self.named_schemas) {
+            Some(union) => UnionKind::Discriminated(union),
+            None => UnionKind::Plain,
+        }
+    }
+
+    fn discriminated_schemas(&self, union: &DiscriminatedUnion) -> Vec<(String, Runtype)> {
+        union
+            .cases
+            .iter()
+            .map(|(value, cases)| {
+                let schema = if cases.len() == 1 {
+                    self.maybe_named_ref(&cases[0])
+                        .unwrap_or_else(|| cases[0].clone())
+                } else {
+                    Runtype::any_of(cases.clone())
+                };
+                (value.clone(), schema)
+            })
+            .collect()
+    }
+
+    fn codec(&mut self, schema: &Runtype) -> Expr {
+        // named types are compiled once, an exported name reuses their functions
+        let mut schema = schema;
+        while let RuntypeKind::Ref(to) = &schema.kind
+            && !has_constraints(schema)
+        {
+            schema = self.named_schema(to);
+        }
+        let schema = schema.clone();
+        let id = self.hoist(&schema);
+        object_lit(
+            ["validate", "parse", "report"]
+                .into_iter()
+                .map(|op| key_value(op, ident(&id.name(op))))
+                .collect(),
+        )
+    }
+
+    fn parser_factory(&mut self, decoder: &BuiltDecoder) -> Expr {
+        self.factory_hoisted = Some(Hoisted::default());
+        let codec = self.codec(&decoder.schema);
+        let functions = self
+            .factory_hoisted
+            .take()
+            .expect("should be compiling a factory")
+            .functions
+            .concat();
+        Expr::Arrow(ArrowExpr {
+            span: DUMMY_SP,
+            ctxt: SyntaxContext::empty(),
+            params: decoder.type_params.iter().map(|it| binding(it)).collect(),
+            body: Box::new(BlockStmtOrExpr::BlockStmt(BlockStmt {
+                span: DUMMY_SP,
+                ctxt: SyntaxContext::empty(),
+                stmts: functions.into_iter().chain([ret(codec)]).collect(),
+            })),
+            is_async: false,
+            is_generator: false,
+            type_params: None,
+            return_type: None,
+        })
+    }
+}
+
+enum UnionKind {
+    Consts(Vec<Json>),
+    Discriminated(DiscriminatedUnion),
+    Plain,
+}
+
+fn typeof_name(kind: &RuntypeKind) -> Option<&'static str> {
+    match kind {
+        RuntypeKind::String => Some("string"),
+        RuntypeKind::Boolean => Some("boolean"),
+        RuntypeKind::Number => Some("number"),
+        RuntypeKind::Function { .. } => Some("function"),
+        RuntypeKind::Promise(_) => Some("object"),
+        _ => None,
+    }
+}
+
+fn const_json(schema: &Runtype) -> Option<Json> {
+    match &schema.kind {
+        RuntypeKind::Const(c) => Some(c.clone().to_json()),
+        RuntypeKind::TplLitType(t) => match t.0.as_slice() {
+            [TplLitTypeItem::StringConst(c)] => Some(Json::String(c.clone())),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn formats_list(CustomFormat(first, rest): &CustomFormat) -> Vec<String> {
+    std::iter::once(first).chain(rest.iter()).cloned().collect()
+}
+
+fn leaf_validate(schema: &Runtype, input: &Expr) -> Expr {
+    if let Some(name) = typeof_name(&schema.kind) {
+        return typeof_is(input, name);
+    }
+    if let Some(value) = const_json(schema) {
+        return bin(input.clone(), BinaryOp::EqEqEq, value.to_expr());
+    }
+    match &schema.kind {
+        RuntypeKind::Any => bool_lit(true),
+        RuntypeKind::Never => bool_lit(false),
+        RuntypeKind::Null | RuntypeKind::Undefined | RuntypeKind::Void => is_nullish(input),
+        RuntypeKind::TplLitType(t) => {
+            let regex = Expr::Lit(Lit::Regex(Regex {
+                span: DUMMY_SP,
+                exp: t.anchored_regex_expr().into(),
+                flags: "".into(),
+            }));
+            bin(
+                typeof_is(input, "string"),
+                BinaryOp::LogicalAnd,
+                method(&regex, "test", vec![input.clone()]),
+            )
+        }
+        RuntypeKind::StringWithFormat(formats) => {
+            let mut checks = vec![typeof_is(input, "string")];
+            for it in formats_list(formats) {
+                checks.push(call(
+                    ident("validateStringFormat"),
+                    vec![str_lit(&it), input.clone()],
+                ));
+            }
+            join(checks, BinaryOp::LogicalAnd)
+        }
+        RuntypeKind::NumberWithFormat(formats) => {
+            let mut checks = vec![typeof_is(input, "number")];
+            for it in formats_list(formats) {
+                checks.push(call(
+                    ident("validateNumberFormat"),
+                    vec![str_lit(&it), input.clone()],
+                ));
+            }
+            join(checks, BinaryOp::LogicalAnd)
+        }
+        RuntypeKind::NumberRange(range) => {
+            let mut checks = vec![
+                typeof_is(input, "number"),
+                not(method(&ident("Number"), "isNaN", vec![input.clone()])),
+            ];
+            if range.integer {
+                checks.push(method(&ident("Number"), "isInteger", vec![input.clone()]));
+            }
+            if let Some(min) = &range.minimum {
+                let op = if min.exclusive {
+                    BinaryOp::Gt
+                } else {
+                    BinaryOp::GtEq
+                };
+                checks.push(bin(
+                    input.clone(),
+                    op,
+                    Json::Number(min.value.clone()).to_expr(),
+                ));
+            }
+            if let Some(max) = &range.maximum {
+                let op = if max.exclusive {
+                    BinaryOp::Lt
+                } else {
+                    BinaryOp::LtEq
+                };
+                checks.push(bin(
+                    input.clone(),
+                    op,
+                    Json::Number(max.value.clone()).to_expr(),
+                ));
+            }
+            join(checks, BinaryOp::LogicalAnd)
+        }
+        RuntypeKind::Date => instance_of(input, ident("Date")),
+        RuntypeKind::BigInt => typeof_is(input, "bigint"),
+        RuntypeKind::TypedArray(kind) => instance_of(input, ident(kind.js_name())),
+        _ => unreachable!("not a leaf runtype: {:?}", schema.kind),
+    }
+}
+
+fn leaf_report(schema: &Runtype, input: &Expr) -> Expr {
+    if let Some(name) = typeof_name(&schema.kind) {
+        return build_error(&format!("expected {}", name), input);
+    }
+    if let Some(value) = const_json(schema) {
+        return build_error(&format!("expected {}", js_json(&value)), input);
+    }
+    let format_error = |formatter: &str, formats: &CustomFormat| {
+        let formats = Json::Array(
+            formats_list(formats)
+                .into_iter()
+                .map(Json::String)
+                .collect(),
+        );
+        call(
+            ident("buildError"),
+            vec![
+                ctx(),
+                call(ident(formatter), vec![formats.to_expr(), input.clone()]),
+                input.clone(),
+            ],
+        )
+    };
+    let message = match &schema.kind {
+        RuntypeKind::Any => "expected any".to_string(),
+        RuntypeKind::Never => "expected never".to_string(),
+        RuntypeKind::Null | RuntypeKind::Undefined | RuntypeKind::Void => {
+            "expected nullish value".to_string()
+        }
+        RuntypeKind::TplLitType(t) => format!("expected string matching {}", t.describe()),
+        RuntypeKind::StringWithFormat(formats) => {
+            return format_error("stringFormatError", formats);
+        }
+        RuntypeKind::NumberWithFormat(formats) => {
+            return format_error("numberFormatError", formats);
+        }
+        RuntypeKind::NumberRange(range) => format!("expected {}", describe_range(range)),
+        RuntypeKind::Date => "expected Date".to_string(),
+        RuntypeKind::BigInt => "expected BigInt".to_string(),
+        RuntypeKind::TypedArray(kind) => format!("expected {}", kind.js_name()),
+        _ => unreachable!("not a leaf runtype: {:?}", schema.kind),
+    };
+    build_error(&message, input)
+}
+
+/// Negated types, like `Exclude<string, "a">`, have no runtime check to compile to.
+fn reject_negated_types(
+    named_schemas: &[NamedSchema],
+    built_parsers: &[BuiltDecoder],
+) -> Result<()> {
+    fn contains_negation(schema: &Runtype) -> bool {
+        matches!(schema.kind, RuntypeKind::StNot(_))
+            || schema.children().into_iter().any(contains_negation)
+    }
+    let negated = named_schemas
+        .iter()
+        .map(|it| &it.schema)
+        .chain(built_parsers.iter().map(|it| &it.schema))
+        .any(contains_negation);
+    if negated {
+        return Err(anyhow!(
+            "Cannot compile validators for a negated type, like `Exclude<string, \"a\">`"
+        ));
+    }
+    Ok(())
+}
+
+impl ParserExtractResult {
+    /// Compiles the parsers into standalone validation functions, see `codegen-aot.js` for the
+    /// helpers they call.
+    pub fn emit_aot_code(self) -> Result<String> {
+        let built_parsers = self.built_decoders.unwrap_or_default();
+        let named_schemas = validate_type_uniqueness(&self.validators)?;
+        reject_negated_types(&named_schemas, &built_parsers)?;
+        let mut ctx = AotContext {
+            named_schemas: &named_schemas,
+            all_names: named_schemas.iter().map(|it| it.name.clone()).collect(),
+            type_with_args_names: BTreeMap::new(),
+            parameterized: parameterized_names(&named_schemas),
+            hoisted: Hoisted::default(),
+            factory_hoisted: None,
+            constants: vec![],
+        };
+
+        let mut parsers = vec![];
+        for decoder in &built_parsers {
+            let value = if decoder.type_params.is_empty() {
+                ctx.codec(&decoder.schema)
+            } else {
+                ctx.parser_factory(decoder)
+            };
+            parsers.push(PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+                key: PropName::Str(Str {
+                    span: DUMMY_SP,
+                    value: decoder.exported_name.as_str().into(),
+                    raw: None,
+                }),
+                value: Box::new(value),
+            }))));
+        }
+
+        let stmts = ctx
+            .constants
+            .into_iter()
+            .chain([const_(
+                "RequiredClasses",
+                required_classes(&named_schemas, &built_parsers).to_expr(),
+            )])
+            .chain(ctx.hoisted.functions.concat())
+            .chain([const_("buildParsersInput", object_lit(parsers))]);
+        let mut module = Module {
+            span: DUMMY_SP,
+            body: stmts.map(ModuleItem::Stmt).collect(),
+            shebang: None,
+        };
+        // adds the parentheses that operator precedence requires
+        module.visit_mut_with(&mut fixer(None));
+        emit_module_items(module.body)
+    }
+}
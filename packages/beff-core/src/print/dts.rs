@@ -0,0 +1,474 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Component, Path};
+
+use anyhow::Result;
+
+use crate::ast::runtype::{
+    CustomFormat, DebugPrintCtx, IndexedProperty, NumberBound, NumberRange, Optionality, Runtype,
+    RuntypeKind, TplLitType, TplLitTypeItem,
+};
+use crate::parser_extractor::{BuiltDecoder, ParserExtractResult};
+use crate::{NamedSchema, RuntypeUUID, TypeAddress};
+
+use super::printer::validate_type_uniqueness;
+use super::util::{js_string, prop_name};
+
+// Types from `@beff/client` are referenced through this namespace, so they cannot clash with
+// the names of user types.
+const CLIENT_NAMESPACE: &str = "beff";
+
+struct DtsContext {
+    all_names: Vec<RuntypeUUID>,
+    type_with_args_names: BTreeMap<RuntypeUUID, String>,
+    // the factory type parameters each named type depends on, directly or through a ref
+    type_params: BTreeMap<RuntypeUUID, BTreeSet<String>>,
+    // `BeffParser`, or `CompiledParser` for the compiled backend
+    parser_type: &'static str,
+    // generic types instantiated with type parameters only, declared under their own name
+    generic_names: BTreeMap<RuntypeUUID, String>,
+    // the exported classes, imported from their file
+    class_types: BTreeMap<TypeAddress, String>,
+}
+
+fn generic_names(named_schemas: &[NamedSchema]) -> BTreeMap<RuntypeUUID, String> {
+    let all_names = named_schemas.iter().map(|it| &it.name).collect::<Vec<_>>();
+    let mut by_name: BTreeMap<String, Vec<&RuntypeUUID>> = BTreeMap::new();
+    for named_schema in named_schemas {
+        let only_type_params = named_schema
+            .name
+            .type_arguments
+            .iter()
+            .all(|it| matches!(it.kind, RuntypeKind::TypeParameter(_)));
+        if only_type_params {
+            let name = named_schema.name.ty.print_name_for_js_codegen(&all_names);
+            by_name.entry(name).or_default().push(&named_schema.name);
+        }
+    }
+    // `Paginated<T>` and `Paginated<U>`, or a type of the same name, keep the generated names
+    by_name
+        .into_iter()
+        .filter_map(|(name, uuids)| match uuids.as_slice() {
+            [single] if !single.type_arguments.is_empty() => Some(((*single).clone(), name)),
+            _ => None,
+        })
+        .collect()
+}
+
+// the import specifier of `file` from a module in `dir`, without the extension
+fn relative_specifier(dir: &str, file: &str) -> String {
+    let dir = Path::new(dir).components().collect::<Vec<_>>();
+    let file = Path::new(file).with_extension("");
+    let file = file.components().collect::<Vec<_>>();
+    let common = dir.iter().zip(&file).take_while(|(a, b)| a == b).count();
+    let mut parts = vec![];
+    for it in &dir[common..] {
+        if !matches!(it, Component::CurDir) {
+            parts.push("..".to_string());
+        }
+    }
+    if parts.is_empty() {
+        parts.push(".".to_string());
+    }
+    for it in &file[common..] {
+        parts.push(it.as_os_str().to_string_lossy().into_owned());
+    }
+    parts.join("/")
+}
+
+fn collect_type_params(
+    schema: &Runtype,
+    type_params: &BTreeMap<RuntypeUUID, BTreeSet<String>>,
+    acc: &mut BTreeSet<String>,
+) {
+    match &schema.kind {
+        RuntypeKind::TypeParameter(name) => {
+            acc.insert(name.clone());
+        }
+        RuntypeKind::Ref(to) => {
+            if let Some(params) = type_params.get(to) {
+                acc.extend(params.iter().cloned());
+            }
+        }
+        _ => {
+            for child in schema.children() {
+                collect_type_params(child, type_params, acc);
+            }
+        }
+    }
+}
+
+fn named_type_params(named_schemas: &[NamedSchema]) -> BTreeMap<RuntypeUUID, BTreeSet<String>> {
+    let mut acc: BTreeMap<RuntypeUUID, BTreeSet<String>> = BTreeMap::new();
+    loop {
+        let mut changed = false;
+        for named_schema in named_schemas {
+            let mut params = BTreeSet::new();
+            for it in named_schema
+                .name
+                .type_arguments
+                .iter()
+                .chain(std::iter::once(&named_schema.schema))
+            {
+                collect_type_params(it, &acc, &mut params);
+            }
+            if params.is_empty() {
+                continue;
+            }
+            let existing = acc.entry(named_schema.name.clone()).or_default();
+            if existing.len() != params.len() {
+                *existing = params;
+                changed = true;
+            }
+        }
+        if !changed {
+            return acc;
+        }
+    }
+}
+
+fn client_type(name: &str) -> String {
+    format!("{}.{}", CLIENT_NAMESPACE, name)
+}
+
+fn formats_type(base: &str, extends: &str, CustomFormat(first, rest): &CustomFormat) -> String {
+    let mut acc = format!("{}<{}>", client_type(base), js_string(first));
+    for it in rest {
+        acc = format!("{}<{}, {}>", client_type(extends), acc, js_string(it));
+    }
+    acc
+}
+
+// Exclusive bounds have no static counterpart and are declared as inclusive.
+fn number_range_type(range: &NumberRange) -> String {
+    let bound = |it: &Option<_>| match it {
+        Some(NumberBound { value, .. }) => value.to_f64().to_string(),
+        None => "number".to_string(),
+    };
+    let range_type = format!(
+        "{}<{}, {}>",
+        client_type("Range"),
+        bound(&range.minimum),
+        bound(&range.maximum)
+    );
+    let is_non_negative = range.maximum.is_none()
+        && range
+            .minimum
+            .as_ref()
+            .is_some_and(|it| it.value.to_f64() == 0.0);
+    match (
+        range.integer,
+        range.minimum.is_some() || range.maximum.is_some(),
+    ) {
+        (true, false) => client_type("Int"),
+        (false, false) => "number".to_string(),
+        (false, true) if is_non_negative => client_type("NonNegative"),
+        (false, true) => range_type,
+        (true, true) => format!("{} & {}", client_type("Int"), range_type),
+    }
+}
+
+fn escape_template(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace('`', "\\`")
+        .replace("${", "\\${")
+}
+
+fn template_item(item: &TplLitTypeItem) -> String {
+    match item {
+        TplLitTypeItem::String => "${string}".to_string(),
+        TplLitTypeItem::Number => "${number}".to_string(),
+        TplLitTypeItem::BigInt => "${bigint}".to_string(),
+        TplLitTypeItem::Boolean => "${boolean}".to_string(),
+        TplLitTypeItem::IntrinsicString(intrinsic) => {
+            format!("${{{}<string>}}", intrinsic.name())
+        }
+        TplLitTypeItem::StringConst(v) => escape_template(v),
+        TplLitTypeItem::OneOf(values) => {
+            let values = values
+                .iter()
+                .map(|it| template_literal_type(&TplLitType(vec![it.clone()])))
+                .collect::<Vec<_>>()
+                .join(" | ");
+            format!("${{{}}}", values)
+        }
+    }
+}
+
+fn template_literal_type(tpl: &TplLitType) -> String {
+    match tpl.0.as_slice() {
+        [TplLitTypeItem::StringConst(single)] => js_string(single),
+        items => format!("`{}`", items.iter().map(template_item).collect::<String>()),
+    }
+}
+
+impl DtsContext {
+    fn print_name(&mut self, name: &RuntypeUUID) -> String {
+        if let Some(generic) = self.generic_names.get(name) {
+            return generic.clone();
+        }
+        let mut ctx = DebugPrintCtx {
+            all_names: &self.all_names.iter().collect::<Vec<_>>(),
+            type_with_args_names: &mut self.type_with_args_names,
+        };
+        name.print_name_for_js_codegen(&mut ctx)
+    }
+
+    fn declared_name(&mut self, name: &RuntypeUUID) -> String {
+        let printed = self.print_name(name);
+        match self.type_params.get(name) {
+            Some(params) => format!(
+                "{}<{}>",
+                printed,
+                params.iter().cloned().collect::<Vec<_>>().join(", ")
+            ),
+            None => printed,
+        }
+    }
+
+    // unions, intersections and function types need parentheses inside another union or
+    // intersection
+    fn member_type(&mut self, schema: &Runtype) -> String {
+        let printed = self.ts_type(schema);
+        match &schema.kind {
+            RuntypeKind::AnyOf(_) | RuntypeKind::AllOf(_) | RuntypeKind::Function { .. } => {
+                format!("({})", printed)
+            }
+            RuntypeKind::NumberRange(_) if printed.contains(" & ") => format!("({})", printed),
+            _ => printed,
+        }
+    }
+
+    fn tuple_type(
+        &mut self,
+        prefix_items: &[Runtype],
+        optional_items: &[Runtype],
+        items: Option<&Runtype>,
+        suffix_items: &[Runtype],
+    ) -> String {
+        // a tuple names all of its elements or none of them
+        let labeled = prefix_items
+            .iter()
+            .chain(optional_items)
+            .chain(items)
+            .chain(suffix_items)
+            .all(|it| it.metadata.label.is_some());
+        let element = |it: &Runtype, prefix: &str, optional: &str, ty: String| match (
+            &it.metadata.label,
+            labeled,
+        ) {
+            (Some(label), true) => format!("{prefix}{label}{optional}: {ty}"),
+            _ => format!("{prefix}{ty}{optional}"),
+        };
+        let mut acc = vec![];
+        for it in prefix_items {
+            let ty = self.ts_type(it);
+            acc.push(element(it, "", "", ty));
+        }
+        for it in optional_items {
+            let ty = self.member_type(it);
+            acc.push(element(it, "", "?", ty));
+        }
+        if let Some(it) = items {
+            let ty = format!("Array<{}>", self.ts_type(it));
+            acc.push(element(it, "...", "", ty));
+        }
+        for it in suffix_items {
+            let ty = self.ts_type(it);
+            acc.push(element(it, "", "", ty));
+        }
+        format!("[{}]", acc.join(", "))
+    }
+
+    fn object_type(
+        &mut self,
+        vs: &BTreeMap<String, Optionality<Runtype>>,
+        indexed_properties: &[IndexedProperty],
+    ) -> String {
+        let mut parts = vec![];
+        if !vs.is_empty() || indexed_properties.is_empty() {
+            let props = vs
+                .iter()
+                .map(|(k, v)| {
+                    let optional = if v.is_required() { "" } else { "?" };
+                    format!("{}{}: {}", prop_name(k), optional, self.ts_type(v.inner()))
+                })
+                .collect::<Vec<_>>();
+            parts.push(if props.is_empty() {
+                "{}".to_string()
+            } else {
+                format!("{{ {} }}", props.join("; "))
+            });
+        }
+        // a mapped type accepts any key type, an index signature does not
+        for it in indexed_properties {
+            let optional = if it.value.is_required() { "" } else { "?" };
+            parts.push(format!(
+                "{{ [K in {}]{}: {} }}",
+                self.ts_type(&it.key),
+                optional,
+                self.ts_type(it.value.inner())
+            ));
+        }
+        parts.join(" & ")
+    }
+
+    fn ts_type(&mut self, schema: &Runtype) -> String {
+        match &schema.kind {
+            RuntypeKind::Null => "null".to_string(),
+            RuntypeKind::Undefined => "undefined".to_string(),
+            RuntypeKind::Void => "void".to_string(),
+            RuntypeKind::Boolean => "boolean".to_string(),
+            RuntypeKind::String => "string".to_string(),
+            RuntypeKind::Number => "number".to_string(),
+            RuntypeKind::Any => "any".to_string(),
+            RuntypeKind::AnyArrayLike => "Array<any>".to_string(),
+            RuntypeKind::Never => "never".to_string(),
+            // TypeScript cannot spell a negation, e.g. `Exclude<string, "a">`
+            RuntypeKind::StNot(_) => "unknown".to_string(),
+            RuntypeKind::Date => "Date".to_string(),
+            RuntypeKind::BigInt => "bigint".to_string(),
+            RuntypeKind::TypedArray(kind) => kind.js_name().to_string(),
+            // a class that is not exported cannot be named outside of its file
+            RuntypeKind::InstanceOf(class) => self
+                .class_types
+                .get(class)
+                .cloned()
+                .unwrap_or_else(|| "object".to_string()),
+            RuntypeKind::TypeParameter(name) => name.clone(),
+            RuntypeKind::StringWithFormat(formats) => {
+                formats_type("StringFormat", "StringFormatExtends", formats)
+            }
+            RuntypeKind::NumberWithFormat(formats) => {
+                formats_type("NumberFormat", "NumberFormatExtends", formats)
+            }
+            RuntypeKind::NumberRange(range) => number_range_type(range),
+            RuntypeKind::TplLitType(tpl) => template_literal_type(tpl),
+            RuntypeKind::Const(it) => it.clone().to_json().to_serde().to_string(),
+            RuntypeKind::Ref(to) => self.declared_name(to),
+            RuntypeKind::Array(it) => format!("Array<{}>", self.ts_type(it)),
+            RuntypeKind::Set(it) => format!("Set<{}>", self.ts_type(it)),
+            RuntypeKind::Map(k, v) => format!("Map<{}, {}>", self.ts_type(k), self.ts_type(v)),
+            RuntypeKind::Promise(it) => format!("Promise<{}>", self.ts_type(it)),
+            RuntypeKind::Function {
+                params,
+                return_type,
+                is_constructor,
+            } => {
+                let new = if *is_constructor { "new " } else { "" };
+                format!(
+                    "{}(...args: {}) => {}",
+                    new,
+                    self.ts_type(params),
+                    self.ts_type(return_type)
+                )
+            }
+            RuntypeKind::Tuple {
+                prefix_items,
+                optional_items,
+                items,
+                suffix_items,
+            } => self.tuple_type(prefix_items, optional_items, items.as_deref(), suffix_items),
+            RuntypeKind::Object {
+                vs,
+                indexed_properties,
+            } => self.object_type(vs, indexed_properties),
+            RuntypeKind::AnyOf(vs) => vs
+                .iter()
+                .map(|it| self.member_type(it))
+                .collect::<Vec<_>>()
+                .join(" | "),
+            RuntypeKind::AllOf(vs) => vs
+                .iter()
+                .map(|it| self.member_type(it))
+                .collect::<Vec<_>>()
+                .join(" & "),
+        }
+    }
+
+    fn parser_type(&mut self, decoder: &BuiltDecoder) -> String {
+        let parser = format!(
+            "{}<{}>",
+            client_type(self.parser_type),
+            self.ts_type(&decoder.schema)
+        );
+        if decoder.type_params.is_empty() {
+            return parser;
+        }
+        // the arguments share the names of the type parameters, as in the generated factory
+        let args = decoder
+            .type_params
+            .iter()
+            .map(|it| format!("{}: {}<{}>", it, client_type(self.parser_type), it))
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!(
+            "<{}>({}) => {}",
+            decoder.type_params.join(", "),
+            args,
+            parser
+        )
+    }
+}
+
+impl ParserExtractResult {
+    /// Declares every named type and built parser as a fully resolved TypeScript type, named as
+    /// in the generated code. Exported classes are imported relative to `output_dir`, where the
+    /// declarations are written. Parsers of the compiled backend are declared as
+    /// `CompiledParser`s.
+    pub fn emit_dts(&self, output_dir: &str, compiled: bool) -> Result<String> {
+        let named_schemas = validate_type_uniqueness(&self.validators)?;
+        let mut ctx = DtsContext {
+            all_names: named_schemas.iter().map(|it| it.name.clone()).collect(),
+            type_with_args_names: BTreeMap::new(),
+            type_params: named_type_params(&named_schemas),
+            parser_type: if compiled {
+                "CompiledParser"
+            } else {
+                "BeffParser"
+            },
+            generic_names: generic_names(&named_schemas),
+            class_types: self
+                .exported_classes
+                .iter()
+                .map(|it| {
+                    let specifier = relative_specifier(output_dir, &it.file.0);
+                    (
+                        it.clone(),
+                        format!("import({}).{}", js_string(&specifier), it.name),
+                    )
+                })
+                .collect(),
+        };
+
+        let mut acc = format!(
+            "import type * as {} from \"@beff/client\";\n\n",
+            CLIENT_NAMESPACE
+        );
+        let mut declared = BTreeSet::new();
+        for named_schema in &named_schemas {
+            declared.insert(ctx.print_name(&named_schema.name));
+            let name = ctx.declared_name(&named_schema.name);
+            let ty = ctx.ts_type(&named_schema.schema);
+            acc.push_str(&format!("export type {} = {};\n", name, ty));
+        }
+
+        // a user type may already be named `BuiltParsers`
+        let mut parsers_name = "BuiltParsers".to_string();
+        let mut suffix = 0;
+        while declared.contains(&parsers_name) {
+            suffix += 1;
+            parsers_name = format!("BuiltParsers_{}", suffix);
+        }
+        let decoders = self.built_decoders.as_deref().unwrap_or_default();
+        acc.push_str(&format!("\nexport type {} = {{\n", parsers_name));
+        for decoder in decoders {
+            acc.push_str(&format!(
+                "  {}: {};\n",
+                prop_name(&decoder.exported_name),
+                ctx.parser_type(decoder)
+            ));
+        }
+        acc.push_str("};\n");
+        Ok(acc)
+    }
+}
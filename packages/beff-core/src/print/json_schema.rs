@@ -0,0 +1,542 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use anyhow::{Result, anyhow};
+
+use crate::ast::json::Json;
+use crate::ast::runtype::{
+    CustomFormat, DebugPrintCtx, IndexedProperty, NumberBound, NumberRange, Optionality, Runtype,
+    RuntypeKind, TplLitTypeItem,
+};
+use crate::parser_extractor::ParserExtractResult;
+use crate::{NamedSchema, RuntypeUUID};
+
+use super::printer::{extract_union, parameterized_names, union_consts, validate_type_uniqueness};
+
+const JSON_SCHEMA_DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+// The schemas mirror the `schema` method of the runtime classes, except that named types are
+// referenced from a definitions container instead of being inlined, template literals are
+// matched by their regular expression and tuples always set `minItems`.
+pub(super) struct SchemaPrinter<'a> {
+    named_schemas: &'a [NamedSchema],
+    all_names: Vec<RuntypeUUID>,
+    type_with_args_names: BTreeMap<RuntypeUUID, String>,
+    // e.g. `#/$defs/`, the name of the definition is appended
+    ref_prefix: &'static str,
+    definitions: BTreeMap<String, Json>,
+    in_progress: BTreeSet<String>,
+    path: Vec<String>,
+}
+
+pub(super) fn object(vs: Vec<(&str, Json)>) -> Json {
+    Json::object(vs.into_iter().map(|(k, v)| (k.to_string(), v)).collect())
+}
+
+fn type_schema(type_name: &str) -> Json {
+    object(vec![("type", Json::String(type_name.to_string()))])
+}
+
+fn null_schema() -> Json {
+    type_schema("null")
+}
+
+fn is_null_schema(schema: &Json) -> bool {
+    matches!(schema, Json::Object(vs) if vs.get("type") == Some(&Json::String("null".to_string())))
+}
+
+fn optional_schema(inner: Json) -> Json {
+    object(vec![("anyOf", Json::Array(vec![inner, null_schema()]))])
+}
+
+/// Mirrors `removeNullUnionBranch`, the properties that accept null are not required.
+fn remove_null_union_branch(schema: &Json) -> Option<Json> {
+    let Json::Object(vs) = schema else {
+        return None;
+    };
+    let key = ["anyOf", "oneOf"]
+        .into_iter()
+        .find(|it| vs.contains_key(*it))?;
+    let Some(Json::Array(variants)) = vs.get(key) else {
+        return None;
+    };
+    let non_null = variants
+        .iter()
+        .filter(|it| !is_null_schema(it))
+        .map(|it| remove_null_union_branch(it).unwrap_or_else(|| it.clone()))
+        .collect::<Vec<_>>();
+    if non_null.len() == variants.len() || non_null.is_empty() {
+        return None;
+    }
+    if let [single] = non_null.as_slice() {
+        return Some(single.clone());
+    }
+    let mut vs = vs.clone();
+    vs.insert(key.to_string(), Json::Array(non_null));
+    Some(Json::Object(vs))
+}
+
+/// Mirrors `tryMergeAllOfObjectSchemas`, closed objects are merged into a single one.
+fn try_merge_closed_objects(schemas: &[Json]) -> Option<Json> {
+    let mut properties = BTreeMap::new();
+    let mut required = BTreeSet::new();
+    for schema in schemas {
+        let Json::Object(vs) = schema else {
+            return None;
+        };
+        let is_closed_object = vs.get("type") == Some(&Json::String("object".to_string()))
+            && vs.get("additionalProperties") == Some(&Json::Bool(false))
+            && vs.keys().all(|it| {
+                ["type", "properties", "required", "additionalProperties"].contains(&it.as_str())
+            });
+        if !is_closed_object {
+            return None;
+        }
+        if let Some(Json::Array(keys)) = vs.get("required") {
+            required.extend(keys.iter().cloned());
+        }
+        if let Some(Json::Object(props)) = vs.get("properties") {
+            for (k, v) in props {
+                match properties.get(k) {
+                    Some(existing) if existing != v => return None,
+                    _ => {
+                        properties.insert(k.clone(), v.clone());
+                    }
+                }
+            }
+        }
+    }
+    let mut acc = vec![("type", Json::String("object".to_string()))];
+    if !properties.is_empty() {
+        acc.push(("properties", Json::Object(properties)));
+    }
+    if !required.is_empty() {
+        acc.push(("required", Json::Array(required.into_iter().collect())));
+    }
+    acc.push(("additionalProperties", Json::Bool(false)));
+    Some(object(acc))
+}
+
+fn formats_schema(type_name: &str, CustomFormat(first, rest): &CustomFormat) -> Json {
+    // the `jsonSchemaFormat` of a format is only known at runtime
+    let formats = std::iter::once(first.as_str())
+        .chain(rest.iter().map(|it| it.as_str()))
+        .collect::<Vec<_>>()
+        .join(" and ");
+    object(vec![
+        ("type", Json::String(type_name.to_string())),
+        ("format", Json::String(formats)),
+    ])
+}
+
+fn number_range_schema(range: &NumberRange) -> Json {
+    let mut acc = vec![("type", Json::String("number".to_string()))];
+    if range.integer {
+        acc.push(("multipleOf", Json::parse_int(1)));
+    }
+    if let Some(NumberBound { value, exclusive }) = &range.minimum {
+        let key = if *exclusive {
+            "exclusiveMinimum"
+        } else {
+            "minimum"
+        };
+        acc.push((key, Json::Number(value.clone())));
+    }
+    if let Some(NumberBound { value, exclusive }) = &range.maximum {
+        let key = if *exclusive {
+            "exclusiveMaximum"
+        } else {
+            "maximum"
+        };
+        acc.push((key, Json::Number(value.clone())));
+    }
+    object(acc)
+}
+
+/// The keywords of the JSDoc tags, on top of the schema of the type.
+fn with_keywords(schema: Json, original: &Runtype) -> Json {
+    let Json::Object(mut vs) = schema else {
+        return schema;
+    };
+    if let Some(description) = &original.metadata.description {
+        vs.insert("description".to_string(), Json::String(description.clone()));
+    }
+    if let Some(keywords) = &original.metadata.keywords {
+        let count = |it: usize| Json::parse_int(it as i64);
+        let entries = [
+            ("format", keywords.format.clone().map(Json::String)),
+            ("default", keywords.default.clone()),
+            (
+                "deprecated",
+                keywords.deprecated.then_some(Json::Bool(true)),
+            ),
+            (
+                "examples",
+                (!keywords.examples.is_empty()).then(|| Json::Array(keywords.examples.clone())),
+            ),
+            ("minLength", keywords.min_length.map(count)),
+            ("maxLength", keywords.max_length.map(count)),
+            ("pattern", keywords.pattern.clone().map(Json::String)),
+            ("minimum", keywords.minimum.clone().map(Json::Number)),
+            ("maximum", keywords.maximum.clone().map(Json::Number)),
+            ("minItems", keywords.min_items.map(count)),
+            ("maxItems", keywords.max_items.map(count)),
+        ];
+        for (key, value) in entries {
+            if let Some(value) = value {
+                vs.insert(key.to_string(), value);
+            }
+        }
+    }
+    Json::Object(vs)
+}
+
+impl<'a> SchemaPrinter<'a> {
+    pub fn new(named_schemas: &'a [NamedSchema], ref_prefix: &'static str) -> Self {
+        let mut printer = SchemaPrinter {
+            named_schemas,
+            all_names: named_schemas.iter().map(|it| it.name.clone()).collect(),
+            type_with_args_names: BTreeMap::new(),
+            ref_prefix,
+            definitions: BTreeMap::new(),
+            in_progress: BTreeSet::new(),
+            path: vec![],
+        };
+        // named types are printed in a fixed order, so their names do not depend on the parsers
+        let parameterized = parameterized_names(named_schemas);
+        for named_schema in named_schemas {
+            if !parameterized.contains(&named_schema.name) {
+                printer.print_name(&named_schema.name);
+            }
+        }
+        printer
+    }
+
+    /// Follows references to named types.
+    pub fn resolve<'b>(&self, schema: &'b Runtype) -> &'b Runtype
+    where
+        'a: 'b,
+    {
+        match &schema.kind {
+            RuntypeKind::Ref(to) => match self.named_schemas.iter().find(|it| it.name == *to) {
+                Some(named_schema) => self.resolve(&named_schema.schema),
+                None => schema,
+            },
+            _ => schema,
+        }
+    }
+
+    /// The definitions referenced since the last call.
+    pub fn take_definitions(&mut self) -> BTreeMap<String, Json> {
+        std::mem::take(&mut self.definitions)
+    }
+
+    pub fn print_name(&mut self, name: &RuntypeUUID) -> String {
+        let mut ctx = DebugPrintCtx {
+            all_names: &self.all_names.iter().collect::<Vec<_>>(),
+            type_with_args_names: &mut self.type_with_args_names,
+        };
+        name.print_name_for_js_codegen(&mut ctx)
+    }
+
+    fn error(&self, message: &str) -> anyhow::Error {
+        anyhow!(
+            "Failed to print schema. At {}: {}",
+            self.path.join("."),
+            message
+        )
+    }
+
+    pub fn with_path<T>(&mut self, key: &str, f: impl FnOnce(&mut Self) -> T) -> T {
+        self.path.push(key.to_string());
+        let res = f(self);
+        self.path.pop();
+        res
+    }
+
+    fn reference(&mut self, to: &RuntypeUUID) -> Result<Json> {
+        let name = self.print_name(to);
+        if !self.definitions.contains_key(&name) && !self.in_progress.contains(&name) {
+            self.in_progress.insert(name.clone());
+            let named_schema = self
+                .named_schemas
+                .iter()
+                .find(|it| it.name == *to)
+                .expect("everything should be resolved by now");
+            let body = self.schema(&named_schema.schema)?;
+            self.in_progress.remove(&name);
+            self.definitions.insert(name.clone(), body);
+        }
+        Ok(object(vec![(
+            "$ref",
+            Json::String(format!("{}{}", self.ref_prefix, name)),
+        )]))
+    }
+
+    fn tuple(
+        &mut self,
+        prefix_items: &[Runtype],
+        optional_items: &[Runtype],
+        items: Option<&Runtype>,
+        suffix_items: &[Runtype],
+    ) -> Result<Json> {
+        let (prefix, rest, suffix) = self.with_path("[]", |ctx| -> Result<_> {
+            let mut prefix = vec![];
+            for it in prefix_items {
+                prefix.push(ctx.schema(it)?);
+            }
+            for it in optional_items {
+                prefix.push(optional_schema(ctx.schema(it)?));
+            }
+            let rest = items.map(|it| ctx.schema(it)).transpose()?;
+            let suffix = suffix_items
+                .iter()
+                .map(|it| ctx.schema(it))
+                .collect::<Result<Vec<_>>>()?;
+            Ok((prefix, rest, suffix))
+        })?;
+        // JSON Schema cannot place items after the rest, they are allowed anywhere after the prefix
+        let items = match rest {
+            None => Json::Bool(false),
+            Some(rest) if suffix.is_empty() => rest,
+            Some(rest) => object(vec![(
+                "anyOf",
+                Json::Array(std::iter::once(rest).chain(suffix).collect()),
+            )]),
+        };
+        let mut acc = vec![("type", Json::String("array".to_string()))];
+        if !prefix.is_empty() {
+            acc.push(("prefixItems", Json::Array(prefix)));
+        }
+        acc.push(("items", items));
+        // `prefixItems` alone accepts shorter arrays
+        let min_items = prefix_items.len() + suffix_items.len();
+        if min_items > 0 {
+            acc.push(("minItems", Json::parse_int(min_items as i64)));
+        }
+        Ok(object(acc))
+    }
+
+    fn object_schema(
+        &mut self,
+        vs: &BTreeMap<String, Optionality<Runtype>>,
+        indexed_properties: &[IndexedProperty],
+    ) -> Result<Json> {
+        let mut properties = BTreeMap::new();
+        let mut required = vec![];
+        for (k, v) in vs {
+            let raw = self.with_path(k, |ctx| ctx.schema(v.inner()))?;
+            let raw = match v {
+                Optionality::Optional(_) => optional_schema(raw),
+                Optionality::Required(_) => raw,
+            };
+            match remove_null_union_branch(&raw) {
+                Some(rewrite) => {
+                    properties.insert(k.clone(), rewrite);
+                }
+                None => {
+                    properties.insert(k.clone(), raw);
+                    required.push(Json::String(k.clone()));
+                }
+            }
+        }
+        let mut base = vec![
+            ("type", Json::String("object".to_string())),
+            ("properties", Json::Object(properties.clone())),
+        ];
+        if !required.is_empty() {
+            base.push(("required", Json::Array(required)));
+        }
+
+        let mut index_schemas = vec![];
+        for IndexedProperty { key, value } in indexed_properties {
+            let key_schema = self.with_path("[key]", |ctx| ctx.schema(key))?;
+            let value_schema = self.with_path("[value]", |ctx| ctx.schema(value.inner()))?;
+            let value_schema = match value {
+                Optionality::Optional(_) => optional_schema(value_schema),
+                Optionality::Required(_) => value_schema,
+            };
+            index_schemas.push(object(vec![
+                ("type", Json::String("object".to_string())),
+                ("additionalProperties", value_schema),
+                ("propertyNames", key_schema),
+            ]));
+        }
+
+        if index_schemas.is_empty() {
+            base.push(("additionalProperties", Json::Bool(false)));
+            return Ok(object(base));
+        }
+        if properties.is_empty()
+            && let [IndexedProperty { value, .. }] = indexed_properties
+        {
+            return Ok(match &value.inner().kind {
+                // keys of a `Record<string, never>` do not matter, it is always empty
+                RuntypeKind::Never => object(vec![
+                    ("type", Json::String("object".to_string())),
+                    ("additionalProperties", Json::Bool(false)),
+                ]),
+                RuntypeKind::Any => {
+                    let mut single = index_schemas.remove(0);
+                    if let Json::Object(vs) = &mut single {
+                        vs.insert("additionalProperties".to_string(), Json::Bool(true));
+                    }
+                    single
+                }
+                _ => index_schemas.remove(0),
+            });
+        }
+        Ok(object(vec![(
+            "allOf",
+            Json::Array(std::iter::once(object(base)).chain(index_schemas).collect()),
+        )]))
+    }
+
+    fn any_of(&mut self, vs: &BTreeSet<Runtype>) -> Result<Json> {
+        let flat_values = vs
+            .iter()
+            .flat_map(|it| extract_union(it, self.named_schemas))
+            .collect::<BTreeSet<_>>();
+        let dbg_ctx = DebugPrintCtx {
+            all_names: &self.all_names.iter().collect::<Vec<_>>(),
+            type_with_args_names: &mut self.type_with_args_names,
+        };
+        if let Some(consts) = union_consts(&flat_values, &dbg_ctx) {
+            let type_name = |it: &Json| match it {
+                Json::String(_) => Some("string"),
+                Json::Number(_) => Some("number"),
+                Json::Bool(_) => Some("boolean"),
+                _ => None,
+            };
+            let single_type = type_name(&consts[0])
+                .filter(|first| consts.iter().all(|it| type_name(it) == Some(*first)));
+            let mut acc = vec![];
+            if let Some(type_name) = single_type {
+                acc.push(("type", Json::String(type_name.to_string())));
+            }
+            acc.push(("enum", Json::Array(consts)));
+            return Ok(object(acc));
+        }
+        let schemas = vs
+            .iter()
+            .map(|it| self.schema(it))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(object(vec![("anyOf", Json::Array(schemas))]))
+    }
+
+    pub fn schema(&mut self, schema: &Runtype) -> Result<Json> {
+        let printed = match &schema.kind {
+            RuntypeKind::Null | RuntypeKind::Undefined | RuntypeKind::Void => null_schema(),
+            RuntypeKind::Boolean => type_schema("boolean"),
+            RuntypeKind::String => type_schema("string"),
+            RuntypeKind::Number => type_schema("number"),
+            RuntypeKind::Any => object(vec![]),
+            RuntypeKind::Never => object(vec![("anyOf", Json::Array(vec![]))]),
+            RuntypeKind::AnyArrayLike => object(vec![
+                ("type", Json::String("array".to_string())),
+                ("items", object(vec![])),
+            ]),
+            RuntypeKind::StringWithFormat(formats) => formats_schema("string", formats),
+            RuntypeKind::NumberWithFormat(formats) => formats_schema("number", formats),
+            RuntypeKind::NumberRange(range) => number_range_schema(range),
+            RuntypeKind::Const(it) => object(vec![("const", it.clone().to_json())]),
+            RuntypeKind::TplLitType(tpl) => match tpl.0.as_slice() {
+                [TplLitTypeItem::StringConst(it)] => {
+                    object(vec![("const", Json::String(it.clone()))])
+                }
+                _ => object(vec![
+                    ("type", Json::String("string".to_string())),
+                    ("pattern", Json::String(tpl.anchored_regex_expr())),
+                ]),
+            },
+            RuntypeKind::Ref(to) => self.reference(to)?,
+            RuntypeKind::Array(it) => {
+                let items = self.with_path("[]", |ctx| ctx.schema(it))?;
+                object(vec![
+                    ("type", Json::String("array".to_string())),
+                    ("items", items),
+                ])
+            }
+            RuntypeKind::Tuple {
+                prefix_items,
+                optional_items,
+                items,
+                suffix_items,
+            } => self.tuple(prefix_items, optional_items, items.as_deref(), suffix_items)?,
+            RuntypeKind::Object {
+                vs,
+                indexed_properties,
+            } => self.object_schema(vs, indexed_properties)?,
+            RuntypeKind::AnyOf(vs) => self.any_of(vs)?,
+            RuntypeKind::AllOf(vs) => {
+                let schemas = vs
+                    .iter()
+                    .map(|it| self.schema(it))
+                    .collect::<Result<Vec<_>>>()?;
+                match try_merge_closed_objects(&schemas) {
+                    Some(merged) => merged,
+                    None => object(vec![("allOf", Json::Array(schemas))]),
+                }
+            }
+            RuntypeKind::Date => return Err(self.error("Cannot generate JSON Schema for Date")),
+            RuntypeKind::BigInt => {
+                return Err(self.error("Cannot generate JSON Schema for BigInt"));
+            }
+            RuntypeKind::TypedArray(kind) => {
+                let message = format!("Cannot generate JSON Schema for {}", kind.js_name());
+                return Err(self.error(&message));
+            }
+            RuntypeKind::InstanceOf(class) => {
+                let message = format!("Cannot generate JSON Schema for class {}", class.name);
+                return Err(self.error(&message));
+            }
+            RuntypeKind::Map(_, _) => return Err(self.error("Cannot generate JSON Schema for Map")),
+            RuntypeKind::Set(_) => return Err(self.error("Cannot generate JSON Schema for Set")),
+            RuntypeKind::Function { .. } => {
+                return Err(self.error("Cannot generate JSON Schema for a function"));
+            }
+            RuntypeKind::Promise(_) => {
+                return Err(self.error("Cannot generate JSON Schema for Promise"));
+            }
+            RuntypeKind::TypeParameter(name) => {
+                let message = format!("Cannot generate JSON Schema for type parameter {}", name);
+                return Err(self.error(&message));
+            }
+            RuntypeKind::StNot(_) => {
+                return Err(self.error("Cannot generate JSON Schema for a negated type"));
+            }
+        };
+        Ok(with_keywords(printed, schema))
+    }
+}
+
+impl ParserExtractResult {
+    /// A JSON Schema 2020-12 document for every built parser, with the named types it uses
+    /// under `$defs`. Parser factories have no schema until their arguments are known, they
+    /// are left out.
+    pub fn emit_json_schemas(&self) -> Result<BTreeMap<String, Json>> {
+        let named_schemas = validate_type_uniqueness(&self.validators)?;
+        let mut printer = SchemaPrinter::new(&named_schemas, "#/$defs/");
+
+        let mut acc = BTreeMap::new();
+        for decoder in self.built_decoders.as_deref().unwrap_or_default() {
+            if !decoder.type_params.is_empty() {
+                continue;
+            }
+            let schema =
+                printer.with_path(&decoder.exported_name, |ctx| ctx.schema(&decoder.schema))?;
+            let definitions = printer.take_definitions();
+            let Json::Object(mut document) = schema else {
+                unreachable!("schemas are printed as objects")
+            };
+            document.insert(
+                "$schema".to_string(),
+                Json::String(JSON_SCHEMA_DIALECT.to_string()),
+            );
+            if !definitions.is_empty() {
+                document.insert("$defs".to_string(), Json::Object(definitions));
+            }
+            acc.insert(decoder.exported_name.clone(), Json::Object(document));
+        }
+        Ok(acc)
+    }
+}
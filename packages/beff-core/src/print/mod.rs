@@ -1 +1,7 @@
+pub mod aot;
+pub mod dts;
+pub mod json_schema;
+pub mod openapi;
 pub mod printer;
+pub mod source_map;
+mod util;
@@ -0,0 +1,214 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use anyhow::{Result, anyhow};
+
+use crate::ast::json::Json;
+use crate::ast::runtype::{Optionality, Runtype, RuntypeKind};
+use crate::parser_extractor::{BuiltRoute, ParserExtractResult};
+
+use super::json_schema::{SchemaPrinter, object};
+use super::printer::validate_type_uniqueness;
+
+const OPENAPI_VERSION: &str = "3.1.0";
+
+fn route_error(route: &BuiltRoute, message: &str) -> anyhow::Error {
+    anyhow!(
+        "Failed to print OpenAPI document. At {} {}: {}",
+        route.method.to_uppercase(),
+        route.path,
+        message
+    )
+}
+
+// `/users/{id}/posts/{postId}` has the parameters `id` and `postId`
+fn path_template_params(path: &str) -> BTreeSet<&str> {
+    path.split('{')
+        .skip(1)
+        .filter_map(|it| it.split_once('}').map(|(name, _)| name))
+        .collect()
+}
+
+fn json_content(schema: Json) -> Json {
+    object(vec![("application/json", object(vec![("schema", schema)]))])
+}
+
+fn object_properties<'b>(
+    printer: &SchemaPrinter<'b>,
+    schema: &'b Runtype,
+) -> Option<&'b BTreeMap<String, Optionality<Runtype>>> {
+    match &printer.resolve(schema).kind {
+        RuntypeKind::Object {
+            vs,
+            indexed_properties,
+        } if indexed_properties.is_empty() => Some(vs),
+        _ => None,
+    }
+}
+
+fn parameters<'b>(
+    printer: &mut SchemaPrinter<'b>,
+    route: &BuiltRoute,
+    part: &str,
+    schema: &'b Runtype,
+) -> Result<Vec<Json>> {
+    let location = if part == "params" { "path" } else { "query" };
+    let Some(vs) = object_properties(printer, schema) else {
+        let message = format!("{} should be an object type without index signatures", part);
+        return Err(route_error(route, &message));
+    };
+    let mut acc = vec![];
+    for (name, v) in vs {
+        let required = matches!(v, Optionality::Required(_));
+        if location == "path" && !required {
+            let message = format!("path parameter {} cannot be optional", name);
+            return Err(route_error(route, &message));
+        }
+        let schema =
+            printer.with_path(part, |ctx| ctx.with_path(name, |ctx| ctx.schema(v.inner())))?;
+        acc.push(object(vec![
+            ("name", Json::String(name.clone())),
+            ("in", Json::String(location.to_string())),
+            ("required", Json::Bool(required)),
+            ("schema", schema),
+        ]));
+    }
+    Ok(acc)
+}
+
+fn operation<'b>(printer: &mut SchemaPrinter<'b>, route: &'b BuiltRoute) -> Result<Json> {
+    let Some(parts) = object_properties(printer, &route.schema) else {
+        return Err(route_error(
+            route,
+            "a route should be an object type with params, query, body and response",
+        ));
+    };
+    if let Some(unknown) = parts
+        .keys()
+        .find(|it| !["params", "query", "body", "response"].contains(&it.as_str()))
+    {
+        let message = format!(
+            "unknown route property {}, expected params, query, body or response",
+            unknown
+        );
+        return Err(route_error(route, &message));
+    }
+
+    let template_params = path_template_params(&route.path);
+    let declared_params = match parts.get("params") {
+        Some(params) => object_properties(printer, params.inner())
+            .map(|vs| vs.keys().map(|it| it.as_str()).collect())
+            .unwrap_or_default(),
+        None => BTreeSet::new(),
+    };
+    if let Some(missing) = template_params.difference(&declared_params).next() {
+        let message = format!("path parameter {} is not declared in params", missing);
+        return Err(route_error(route, &message));
+    }
+    if let Some(extra) = declared_params.difference(&template_params).next() {
+        let message = format!("params.{} is not a parameter of the path", extra);
+        return Err(route_error(route, &message));
+    }
+
+    let route_name = format!("{} {}", route.method.to_uppercase(), route.path);
+    printer.with_path(&route_name, |printer| {
+        let mut acc = vec![];
+
+        let mut params = vec![];
+        for part in ["params", "query"] {
+            if let Some(schema) = parts.get(part) {
+                params.extend(parameters(printer, route, part, schema.inner())?);
+            }
+        }
+        if !params.is_empty() {
+            acc.push(("parameters", Json::Array(params)));
+        }
+
+        if let Some(body) = parts.get("body") {
+            let schema = printer.with_path("body", |ctx| ctx.schema(body.inner()))?;
+            acc.push((
+                "requestBody",
+                object(vec![
+                    (
+                        "required",
+                        Json::Bool(matches!(body, Optionality::Required(_))),
+                    ),
+                    ("content", json_content(schema)),
+                ]),
+            ));
+        }
+
+        let responses = match parts.get("response") {
+            Some(response) => {
+                let schema = printer.with_path("response", |ctx| ctx.schema(response.inner()))?;
+                object(vec![(
+                    "200",
+                    object(vec![
+                        ("description", Json::String("OK".to_string())),
+                        ("content", json_content(schema)),
+                    ]),
+                )])
+            }
+            None => object(vec![(
+                "204",
+                object(vec![(
+                    "description",
+                    Json::String("No Content".to_string()),
+                )]),
+            )]),
+        };
+        acc.push(("responses", responses));
+        Ok(object(acc))
+    })
+}
+
+impl ParserExtractResult {
+    /// An OpenAPI 3.1 document of the routes passed to `buildRoutes`, with the named types
+    /// they use under `components.schemas`. None if `buildRoutes` is not called.
+    pub fn emit_openapi(&self) -> Result<Option<Json>> {
+        let Some(built_routes) = &self.built_routes else {
+            return Ok(None);
+        };
+        let named_schemas = validate_type_uniqueness(&self.validators)?;
+        let mut printer = SchemaPrinter::new(&named_schemas, "#/components/schemas/");
+
+        let mut paths: BTreeMap<String, BTreeMap<String, Json>> = BTreeMap::new();
+        for route in &built_routes.routes {
+            let operation = operation(&mut printer, route)?;
+            let path_item = paths.entry(route.path.clone()).or_default();
+            if path_item.contains_key(&route.method) {
+                return Err(route_error(route, "the route is declared twice"));
+            }
+            path_item.insert(route.method.clone(), operation);
+        }
+
+        let mut info = BTreeMap::from([
+            ("title".to_string(), Json::String("API".to_string())),
+            ("version".to_string(), Json::String("0.0.0".to_string())),
+        ]);
+        for (k, v) in &built_routes.info {
+            info.insert(k.clone(), Json::String(v.clone()));
+        }
+
+        let mut document = vec![
+            ("openapi", Json::String(OPENAPI_VERSION.to_string())),
+            ("info", Json::Object(info)),
+            (
+                "paths",
+                Json::Object(
+                    paths
+                        .into_iter()
+                        .map(|(k, v)| (k, Json::Object(v)))
+                        .collect(),
+                ),
+            ),
+        ];
+        let definitions = printer.take_definitions();
+        if !definitions.is_empty() {
+            document.push((
+                "components",
+                object(vec![("schemas", Json::Object(definitions))]),
+            ));
+        }
+        Ok(Some(object(document)))
+    }
+}
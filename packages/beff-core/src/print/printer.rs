@@ -4,32 +4,44 @@ use anyhow::{Ok, Result, anyhow};
 use swc_common::DUMMY_SP;
 use swc_common::SourceMap;
 use swc_common::SyntaxContext;
+use swc_common::source_map::LineCol;
+use swc_common::{BytePos, Span};
 use swc_common::{FilePathMapping, sync::Lrc};
 use swc_ecma_ast::Module;
 use swc_ecma_ast::{
-    ArrayLit, BindingIdent, Decl, Expr, ExprOrSpread, Ident, KeyValueProp, Lit, ModuleItem,
-    NewExpr, Null, ObjectLit, Pat, Prop, PropName, PropOrSpread, Regex, Stmt, Str, VarDecl,
-    VarDeclKind, VarDeclarator,
+    ArrayLit, ArrowExpr, AssignExpr, AssignOp, BindingIdent, BlockStmt, BlockStmtOrExpr,
+    ComputedPropName, Decl, Expr, ExprOrSpread, ExprStmt, Ident, KeyValueProp, Lit, MemberExpr,
+    MemberProp, ModuleItem, NewExpr, Null, ObjectLit, Pat, Prop, PropName, PropOrSpread, Regex,
+    ReturnStmt, SimpleAssignTarget, Stmt, Str, VarDecl, VarDeclKind, VarDeclarator,
 };
 use swc_ecma_codegen::Config;
 use swc_ecma_codegen::{Emitter, text_writer::JsWriter};
 
-use crate::RuntypeUUID;
 use crate::ast::json::Json;
 use crate::ast::runtype::CustomFormat;
 use crate::ast::runtype::DebugPrintCtx;
 use crate::ast::runtype::TplLitTypeItem;
 use crate::parser_extractor::ParserExtractResult;
+use crate::print::source_map::{SourcePositions, build_source_map};
 use crate::{
     NamedSchema,
     ast::runtype::{
-        IndexedProperty, Optionality, Runtype, RuntypeConst, RuntypeKind, RuntypeMetadata,
-        TplLitType, TypedArrayKind,
+        IndexedProperty, NumberBound, NumberRange, Optionality, Runtype, RuntypeConst, RuntypeKind,
+        RuntypeMetadata, SchemaKeywords, TplLitType, TypedArrayKind,
     },
     parser_extractor::BuiltDecoder,
 };
+use crate::{RuntypeName, RuntypeUUID, SourcePosition, TypeAddress};
 
-fn emit_module_items(body: Vec<ModuleItem>) -> Result<String> {
+pub(super) fn emit_module_items(body: Vec<ModuleItem>) -> Result<String> {
+    emit_module_items_with_source_map(body, None)
+}
+
+// `srcmap` collects the spans written and where they are in the output
+fn emit_module_items_with_source_map(
+    body: Vec<ModuleItem>,
+    srcmap: Option<&mut Vec<(BytePos, LineCol)>>,
+) -> Result<String> {
     let ast = Module {
         span: DUMMY_SP,
         body,
@@ -45,7 +57,7 @@ fn emit_module_items(body: Vec<ModuleItem>) -> Result<String> {
                 cfg: Config::default(),
                 cm: cm.clone(),
                 comments: None,
-                wr: JsWriter::new(cm, "\n", &mut buf, None),
+                wr: JsWriter::new(cm, "\n", &mut buf, srcmap),
             };
 
             emitter.emit_module(&ast)?;
@@ -58,7 +70,7 @@ fn emit_module_items(body: Vec<ModuleItem>) -> Result<String> {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
-struct PrintableRuntypeKey {
+pub(super) struct PrintableRuntypeKey {
     metadata: RuntypeMetadata,
     kind: PrintableRuntypeKindKey,
 }
@@ -87,15 +99,18 @@ enum PrintableRuntypeKindKey {
     AnyArrayLike,
     StringWithFormat(CustomFormat),
     NumberWithFormat(CustomFormat),
+    NumberRange(NumberRange),
     TplLitType(TplLitType),
     Object {
         vs: BTreeMap<String, PrintableOptionalityKey>,
-        indexed_properties: Option<Box<PrintableIndexedPropertyKey>>,
+        indexed_properties: Vec<PrintableIndexedPropertyKey>,
     },
     Array(Box<PrintableRuntypeKey>),
     Tuple {
         prefix_items: Vec<PrintableRuntypeKey>,
+        optional_items: Vec<PrintableRuntypeKey>,
         items: Option<Box<PrintableRuntypeKey>>,
+        suffix_items: Vec<PrintableRuntypeKey>,
     },
     Ref(RuntypeUUID),
     AnyOf(BTreeSet<PrintableRuntypeKey>),
@@ -104,17 +119,24 @@ enum PrintableRuntypeKindKey {
     Never,
     StNot(Box<PrintableRuntypeKey>),
     Function,
+    Promise,
     Date,
     BigInt,
     TypedArray(TypedArrayKind),
+    InstanceOf(TypeAddress),
     Map(Box<PrintableRuntypeKey>, Box<PrintableRuntypeKey>),
     Set(Box<PrintableRuntypeKey>),
+    TypeParameter(String),
 }
 
 impl PrintableRuntypeKey {
-    fn from_runtype(schema: &Runtype) -> Self {
+    pub(super) fn from_runtype(schema: &Runtype) -> Self {
         Self {
-            metadata: schema.metadata.clone(),
+            // labels are not printed, labelled tuple elements share their validators
+            metadata: RuntypeMetadata {
+                label: None,
+                ..schema.metadata.clone()
+            },
             kind: PrintableRuntypeKindKey::from_kind(&schema.kind),
         }
     }
@@ -155,6 +177,7 @@ impl PrintableRuntypeKindKey {
             RuntypeKind::AnyArrayLike => Self::AnyArrayLike,
             RuntypeKind::StringWithFormat(format) => Self::StringWithFormat(format.clone()),
             RuntypeKind::NumberWithFormat(format) => Self::NumberWithFormat(format.clone()),
+            RuntypeKind::NumberRange(range) => Self::NumberRange(range.clone()),
             RuntypeKind::TplLitType(tpl_lit_type) => Self::TplLitType(tpl_lit_type.clone()),
             RuntypeKind::Object {
                 vs,
@@ -170,23 +193,34 @@ impl PrintableRuntypeKindKey {
                     })
                     .collect(),
                 indexed_properties: indexed_properties
-                    .as_ref()
-                    .map(|it| Box::new(PrintableIndexedPropertyKey::from_indexed_property(it))),
+                    .iter()
+                    .map(PrintableIndexedPropertyKey::from_indexed_property)
+                    .collect(),
             },
             RuntypeKind::Array(item) => {
                 Self::Array(Box::new(PrintableRuntypeKey::from_runtype(item)))
             }
             RuntypeKind::Tuple {
                 prefix_items,
+                optional_items,
                 items,
+                suffix_items,
             } => Self::Tuple {
                 prefix_items: prefix_items
                     .iter()
                     .map(PrintableRuntypeKey::from_runtype)
                     .collect(),
+                optional_items: optional_items
+                    .iter()
+                    .map(PrintableRuntypeKey::from_runtype)
+                    .collect(),
                 items: items
                     .as_ref()
                     .map(|it| Box::new(PrintableRuntypeKey::from_runtype(it))),
+                suffix_items: suffix_items
+                    .iter()
+                    .map(PrintableRuntypeKey::from_runtype)
+                    .collect(),
             },
             RuntypeKind::Ref(name) => Self::Ref(name.clone()),
             RuntypeKind::AnyOf(vs) => {
@@ -200,10 +234,12 @@ impl PrintableRuntypeKindKey {
             RuntypeKind::StNot(inner) => {
                 Self::StNot(Box::new(PrintableRuntypeKey::from_runtype(inner)))
             }
-            RuntypeKind::Function => Self::Function,
+            RuntypeKind::Function { .. } => Self::Function,
+            RuntypeKind::Promise(_) => Self::Promise,
             RuntypeKind::Date => Self::Date,
             RuntypeKind::BigInt => Self::BigInt,
             RuntypeKind::TypedArray(kind) => Self::TypedArray(*kind),
+            RuntypeKind::InstanceOf(class) => Self::InstanceOf(class.clone()),
             RuntypeKind::Map(key, value) => Self::Map(
                 Box::new(PrintableRuntypeKey::from_runtype(key)),
                 Box::new(PrintableRuntypeKey::from_runtype(value)),
@@ -211,6 +247,7 @@ impl PrintableRuntypeKindKey {
             RuntypeKind::Set(value) => {
                 Self::Set(Box::new(PrintableRuntypeKey::from_runtype(value)))
             }
+            RuntypeKind::TypeParameter(name) => Self::TypeParameter(name.clone()),
         }
     }
 }
@@ -220,6 +257,15 @@ struct PrintContext {
     all_names: Vec<RuntypeUUID>,
     type_with_args_names: BTreeMap<RuntypeUUID, String>,
     inlined: BTreeSet<RuntypeUUID>,
+    // named types that depend on the type parameters of a parser factory
+    parameterized: BTreeSet<RuntypeUUID>,
+    // values hoisted into the body of the parser factory being printed
+    factory_hoisted: Option<BTreeMap<PrintableRuntypeKey, (usize, Expr)>>,
+    declaration_positions: BTreeMap<RuntypeName, SourcePosition>,
+    positions: SourcePositions,
+    // the declaration being printed, the values hoisted meanwhile point to it
+    origin: Span,
+    hoisted_origins: BTreeMap<usize, Span>,
 }
 
 impl PrintContext {
@@ -232,8 +278,12 @@ impl PrintContext {
     }
 }
 
-fn const_decl(name: &str, init: Expr) -> ModuleItem {
-    ModuleItem::Stmt(Stmt::Decl(Decl::Var(
+fn const_decl(name: &str, init: Expr, span: Span) -> ModuleItem {
+    ModuleItem::Stmt(const_decl_stmt(name, init, span))
+}
+
+fn const_decl_stmt(name: &str, init: Expr, span: Span) -> Stmt {
+    Stmt::Decl(Decl::Var(
         VarDecl {
             span: DUMMY_SP,
             ctxt: SyntaxContext::empty(),
@@ -243,7 +293,7 @@ fn const_decl(name: &str, init: Expr) -> ModuleItem {
                 span: DUMMY_SP,
                 name: Pat::Ident(BindingIdent {
                     id: Ident {
-                        span: DUMMY_SP,
+                        span,
                         sym: name.into(),
                         optional: false,
                         ctxt: SyntaxContext::empty(),
@@ -255,11 +305,36 @@ fn const_decl(name: &str, init: Expr) -> ModuleItem {
             }],
         }
         .into(),
-    )))
+    ))
+}
+
+/// The classes `buildParsers` should be given, checked before building the parsers. They are
+/// keyed by name, the frontend reports two classes with the same name.
+pub(super) fn required_classes(
+    named_schemas: &[NamedSchema],
+    built_parsers: &[BuiltDecoder],
+) -> Json {
+    fn collect(schema: &Runtype, acc: &mut BTreeSet<String>) {
+        if let RuntypeKind::InstanceOf(class) = &schema.kind {
+            acc.insert(class.name.clone());
+        }
+        for it in schema.children() {
+            collect(it, acc);
+        }
+    }
+    let mut classes = BTreeSet::new();
+    for schema in named_schemas
+        .iter()
+        .map(|it| &it.schema)
+        .chain(built_parsers.iter().map(|it| &it.schema))
+    {
+        collect(schema, &mut classes);
+    }
+    Json::Array(classes.into_iter().map(Json::String).collect())
 }
 
 /// Any type with the same name must be identical
-fn validate_type_uniqueness(parser: &[NamedSchema]) -> Result<Vec<NamedSchema>> {
+pub(super) fn validate_type_uniqueness(parser: &[NamedSchema]) -> Result<Vec<NamedSchema>> {
     let mut acc: Vec<NamedSchema> = vec![];
 
     for d in parser {
@@ -310,23 +385,52 @@ fn new_runtype_class(constructor: &str, mut args: Vec<Expr>, original_runtype: &
 }
 
 fn runtype_metadata_arg(schema: &Runtype) -> Expr {
-    match schema.metadata.description.as_ref() {
-        Some(description) => Expr::Object(ObjectLit {
-            span: DUMMY_SP,
-            props: vec![PropOrSpread::Prop(
-                Prop::KeyValue(KeyValueProp {
-                    key: PropName::Str(Str {
-                        span: DUMMY_SP,
-                        value: "description".into(),
-                        raw: None,
-                    }),
-                    value: string_lit(description).into(),
-                })
-                .into(),
-            )],
-        }),
-        None => Expr::Ident(identifier("undefined")),
+    let mut vs = vec![];
+    if let Some(description) = &schema.metadata.description {
+        vs.push(("description".to_string(), Json::String(description.clone())));
     }
+    if let Some(keywords) = &schema.metadata.keywords {
+        if let Some(format) = &keywords.format {
+            vs.push(("format".to_string(), Json::String(format.clone())));
+        }
+        if let Some(default) = &keywords.default {
+            vs.push(("default".to_string(), default.clone()));
+        }
+        if keywords.deprecated {
+            vs.push(("deprecated".to_string(), Json::Bool(true)));
+        }
+        if !keywords.examples.is_empty() {
+            vs.push((
+                "examples".to_string(),
+                Json::Array(keywords.examples.clone()),
+            ));
+        }
+    }
+    if vs.is_empty() {
+        return Expr::Ident(identifier("undefined"));
+    }
+    Json::object(vs).to_expr()
+}
+
+/// Checks the keywords of the JSDoc tags on top of the validation of `inner`.
+fn constrained_runtype(inner: Expr, keywords: &SchemaKeywords) -> Expr {
+    let count = |it: usize| Json::parse_int(it as i64);
+    let vs = [
+        ("minLength", keywords.min_length.map(count)),
+        ("maxLength", keywords.max_length.map(count)),
+        ("pattern", keywords.pattern.clone().map(Json::String)),
+        ("minimum", keywords.minimum.clone().map(Json::Number)),
+        ("maximum", keywords.maximum.clone().map(Json::Number)),
+        ("minItems", keywords.min_items.map(count)),
+        ("maxItems", keywords.max_items.map(count)),
+    ]
+    .into_iter()
+    .filter_map(|(key, value)| value.map(|it| (key.to_string(), it)))
+    .collect();
+    new_runtime_class(
+        "ConstrainedRuntype",
+        vec![inner, Json::object(vs).to_expr()],
+    )
 }
 
 fn string_lit(s: &str) -> Expr {
@@ -349,6 +453,16 @@ fn typeof_runtype(t: &str, original_runtype: &Runtype) -> Expr {
 }
 
 fn ref_runtype(to: &RuntypeUUID, ctx: &mut PrintContext, original_runtype: &Runtype) -> Expr {
+    if ctx.parameterized.contains(to) {
+        return new_runtype_class(
+            "ScopedRefRuntype",
+            vec![
+                string_lit(&ctx.print_rt_name(to)),
+                Expr::Ident(identifier(FACTORY_SCOPE_NAME)),
+            ],
+            original_runtype,
+        );
+    }
     new_runtype_class(
         "RefRuntype",
         vec![
@@ -369,6 +483,35 @@ fn no_args_runtype(class_name: &str, original_runtype: &Runtype) -> Expr {
     )
 }
 
+/// The range is passed with the JSON Schema keywords it stands for.
+fn number_range_runtype(range: &NumberRange, original_runtype: &Runtype) -> Expr {
+    let mut keywords = BTreeMap::new();
+    if range.integer {
+        keywords.insert("multipleOf".to_string(), Json::parse_int(1));
+    }
+    if let Some(NumberBound { value, exclusive }) = &range.minimum {
+        let key = if *exclusive {
+            "exclusiveMinimum"
+        } else {
+            "minimum"
+        };
+        keywords.insert(key.to_string(), Json::Number(value.clone()));
+    }
+    if let Some(NumberBound { value, exclusive }) = &range.maximum {
+        let key = if *exclusive {
+            "exclusiveMaximum"
+        } else {
+            "maximum"
+        };
+        keywords.insert(key.to_string(), Json::Number(value.clone()));
+    }
+    new_runtype_class(
+        "NumberRangeRuntype",
+        vec![Json::Object(keywords).to_expr()],
+        original_runtype,
+    )
+}
+
 fn formats_runtype(
     constructor: &str,
     first: &String,
@@ -428,7 +571,7 @@ fn runtype_union_or_intersection(
     new_runtype_class(constructor, vec![arr], original_runtype)
 }
 
-fn extract_union(it: &Runtype, named_schemas: &[NamedSchema]) -> Vec<Runtype> {
+pub(super) fn extract_union(it: &Runtype, named_schemas: &[NamedSchema]) -> Vec<Runtype> {
     match &it.kind {
         RuntypeKind::AnyOf(vs) => vs
             .iter()
@@ -454,17 +597,24 @@ fn maybe_named_ref(
     named_schemas
         .iter()
         .find(|named_schema| {
-            !ctx.inlined.contains(&named_schema.name) && named_schema.schema == *schema
+            !ctx.inlined.contains(&named_schema.name)
+                && !ctx.parameterized.contains(&named_schema.name)
+                && named_schema.schema == *schema
         })
         .map(|named_schema| Runtype::ref_(named_schema.name.clone()))
 }
 
+/// A union of objects told apart by a required property holding string literals.
+pub(super) struct DiscriminatedUnion {
+    pub discriminator: String,
+    // the objects accepted for each value of the discriminator, sorted by value
+    pub cases: Vec<(String, Vec<Runtype>)>,
+}
+
 fn runtype_any_of_discriminated(
     original_runtype: &Runtype,
     flat_values_set: &BTreeSet<Runtype>,
-    discriminator: String,
-    discriminator_strings_set: BTreeSet<String>,
-    object_vs: Vec<BTreeMap<String, Optionality<Runtype>>>,
+    union: DiscriminatedUnion,
     named_schemas: &[NamedSchema],
     ctx: &mut PrintContext,
 ) -> Expr {
@@ -475,88 +625,23 @@ fn runtype_any_of_discriminated(
     };
     flat_values.sort_by_key(|it| it.debug_print(&dbg_ctx));
 
-    let mut discriminator_strings = discriminator_strings_set
-        .into_iter()
-        .collect::<Vec<String>>();
-    discriminator_strings.sort();
-
     let disc = Expr::Lit(Lit::Str(Str {
         span: DUMMY_SP,
-        value: discriminator.clone().into(),
+        value: union.discriminator.clone().into(),
         raw: None,
     }));
 
-    let schema_mapping_obj = Expr::Object(ObjectLit {
-        span: DUMMY_SP,
-        props: discriminator_strings
-            .iter()
-            .map(|current_key| {
-                let cases = object_vs
-                    .iter()
-                    .filter(|vs| {
-                        let value = vs
-                            .get(&discriminator)
-                            .expect("we already checked the discriminator exists")
-                            .inner();
-
-                        extract_union(value, named_schemas)
-                            .into_iter()
-                            .filter_map(|it| it.extract_single_string_const())
-                            .any(|it| it == *current_key)
-                    })
-                    .map(|vs| {
-                        Runtype::object(vs.iter().map(|it| (it.0.clone(), it.1.clone())).collect())
-                    })
-                    .collect::<Vec<_>>();
-                let schema = if cases.len() == 1 {
-                    maybe_named_ref(&cases[0], named_schemas, ctx)
-                        .unwrap_or_else(|| cases[0].clone())
-                } else {
-                    Runtype::any_of(cases)
-                };
-
-                PropOrSpread::Prop(
-                    Prop::KeyValue(KeyValueProp {
-                        key: PropName::Str(Str {
-                            span: DUMMY_SP,
-                            value: current_key.clone().into(),
-                            raw: None,
-                        }),
-                        value: print_runtype(&schema, named_schemas, ctx).into(),
-                    })
-                    .into(),
-                )
-            })
-            .collect(),
-    });
-
     let mapping_obj = Expr::Object(ObjectLit {
         span: DUMMY_SP,
-        props: discriminator_strings
+        props: union
+            .cases
             .iter()
-            .map(|current_key| {
-                let cases = object_vs
-                    .iter()
-                    .filter(|vs| {
-                        let value = vs
-                            .get(&discriminator)
-                            .expect("we already checked the discriminator exists")
-                            .inner();
-
-                        extract_union(value, named_schemas)
-                            .into_iter()
-                            .filter_map(|it| it.extract_single_string_const())
-                            .any(|it| it == *current_key)
-                    })
-                    .map(|vs| {
-                        Runtype::object(vs.iter().map(|it| (it.0.clone(), it.1.clone())).collect())
-                    })
-                    .collect::<Vec<_>>();
+            .map(|(current_key, cases)| {
                 let schema = if cases.len() == 1 {
                     maybe_named_ref(&cases[0], named_schemas, ctx)
                         .unwrap_or_else(|| cases[0].clone())
                 } else {
-                    Runtype::any_of(cases)
+                    Runtype::any_of(cases.clone())
                 };
 
                 PropOrSpread::Prop(
@@ -599,20 +684,50 @@ fn runtype_any_of_discriminated(
         "AnyOfDiscriminatedRuntype",
         vec![
             flat_values_schema_arr,
-            disc.clone(),
+            disc,
+            // the mapping used to validate is also the one used to print schemas
+            mapping_obj.clone(),
             mapping_obj,
-            schema_mapping_obj,
         ],
         original_runtype,
     )
 }
 
-fn maybe_runtype_any_of_discriminated(
-    original_runtype: &Runtype,
+fn discriminated_cases(
+    discriminator: &str,
+    discriminator_strings: BTreeSet<String>,
+    object_vs: &[BTreeMap<String, Optionality<Runtype>>],
+    named_schemas: &[NamedSchema],
+) -> Vec<(String, Vec<Runtype>)> {
+    discriminator_strings
+        .into_iter()
+        .map(|current_key| {
+            let cases = object_vs
+                .iter()
+                .filter(|vs| {
+                    let value = vs
+                        .get(discriminator)
+                        .expect("we already checked the discriminator exists")
+                        .inner();
+
+                    extract_union(value, named_schemas)
+                        .into_iter()
+                        .filter_map(|it| it.extract_single_string_const())
+                        .any(|it| it == current_key)
+                })
+                .map(|vs| {
+                    Runtype::object(vs.iter().map(|it| (it.0.clone(), it.1.clone())).collect())
+                })
+                .collect::<Vec<_>>();
+            (current_key, cases)
+        })
+        .collect()
+}
+
+pub(super) fn discriminated_union(
     flat_values: &BTreeSet<Runtype>,
     named_schemas: &[NamedSchema],
-    ctx: &mut PrintContext,
-) -> Option<Expr> {
+) -> Option<DiscriminatedUnion> {
     let object_vs = flat_values
         .iter()
         .map(|it| extract_object_shape(it, named_schemas))
@@ -679,15 +794,16 @@ fn maybe_runtype_any_of_discriminated(
                             })
                             .collect::<BTreeSet<_>>();
 
-                        return Some(runtype_any_of_discriminated(
-                            original_runtype,
-                            flat_values,
-                            discriminator,
+                        let cases = discriminated_cases(
+                            &discriminator,
                             discriminator_strings,
-                            object_vs,
+                            &object_vs,
                             named_schemas,
-                            ctx,
-                        ));
+                        );
+                        return Some(DiscriminatedUnion {
+                            discriminator,
+                            cases,
+                        });
                     }
                 }
             }
@@ -704,7 +820,7 @@ fn extract_object_shape(
         RuntypeKind::Object {
             vs,
             indexed_properties,
-        } if indexed_properties.is_none() => Some(vs.clone()),
+        } if indexed_properties.is_empty() => Some(vs.clone()),
         RuntypeKind::Ref(r) => named_schemas
             .iter()
             .find(|it| it.name == *r)
@@ -776,54 +892,63 @@ fn string_const_union(schema: &Runtype, named_schemas: &[NamedSchema]) -> Option
         .collect()
 }
 
-fn maybe_runtype_any_of_consts(
-    original_runtype: &Runtype,
+/// The values of a union of literals, in the order they are printed.
+pub(super) fn union_consts(
     flat_values_set: &BTreeSet<Runtype>,
-    ctx: &mut PrintContext,
-) -> Option<Expr> {
+    dbg_ctx: &DebugPrintCtx,
+) -> Option<Vec<Json>> {
     let mut flat_values = flat_values_set.iter().cloned().collect::<Vec<Runtype>>();
-    let dbg_ctx = DebugPrintCtx {
-        all_names: &ctx.all_names.iter().collect::<Vec<_>>(),
-        type_with_args_names: &mut ctx.type_with_args_names,
-    };
-
-    flat_values.sort_by_key(|it| it.debug_print(&dbg_ctx));
+    flat_values.sort_by_key(|it| it.debug_print(dbg_ctx));
 
     let all_consts = flat_values.iter().all(|it| {
         it.extract_single_string_const().is_some() || matches!(it.kind, RuntypeKind::Const(_))
     });
-    if all_consts {
-        let consts: Vec<Expr> = flat_values
+    if !all_consts {
+        return None;
+    }
+    Some(
+        flat_values
             .iter()
             .map(|it| match it.extract_single_string_const() {
-                Some(s) => Json::String(s).to_expr(),
+                Some(s) => Json::String(s),
                 None => match &it.kind {
-                    RuntypeKind::Const(c) => c.clone().to_json().to_expr(),
+                    RuntypeKind::Const(c) => c.clone().to_json(),
                     _ => unreachable!(),
                 },
             })
-            .collect();
+            .collect(),
+    )
+}
 
-        let consts = Expr::Array(ArrayLit {
-            span: DUMMY_SP,
-            elems: consts
-                .into_iter()
-                .map(|it| {
-                    Some(ExprOrSpread {
-                        spread: None,
-                        expr: it.into(),
-                    })
+fn maybe_runtype_any_of_consts(
+    original_runtype: &Runtype,
+    flat_values_set: &BTreeSet<Runtype>,
+    ctx: &mut PrintContext,
+) -> Option<Expr> {
+    let dbg_ctx = DebugPrintCtx {
+        all_names: &ctx.all_names.iter().collect::<Vec<_>>(),
+        type_with_args_names: &mut ctx.type_with_args_names,
+    };
+    let consts = union_consts(flat_values_set, &dbg_ctx)?;
+
+    let consts = Expr::Array(ArrayLit {
+        span: DUMMY_SP,
+        elems: consts
+            .into_iter()
+            .map(|it| {
+                Some(ExprOrSpread {
+                    spread: None,
+                    expr: it.to_expr().into(),
                 })
-                .collect(),
-        });
+            })
+            .collect(),
+    });
 
-        return Some(new_runtype_class(
-            "AnyOfConstsRuntype",
-            vec![consts],
-            original_runtype,
-        ));
-    }
-    None
+    Some(new_runtype_class(
+        "AnyOfConstsRuntype",
+        vec![consts],
+        original_runtype,
+    ))
 }
 
 fn hoist_name(name: usize) -> String {
@@ -832,14 +957,96 @@ fn hoist_name(name: usize) -> String {
 fn hoist_identifier(name: usize) -> Expr {
     Expr::Ident(identifier(&hoist_name(name)))
 }
+fn factory_hoist_name(name: usize) -> String {
+    format!("factory_hoist_{}", name)
+}
+fn factory_hoist_identifier(name: usize) -> Expr {
+    Expr::Ident(identifier(&factory_hoist_name(name)))
+}
+
+const FACTORY_SCOPE_NAME: &str = "scopedRuntypes";
+
+pub(super) fn mentions_type_parameter(
+    schema: &Runtype,
+    parameterized: &BTreeSet<RuntypeUUID>,
+) -> bool {
+    match &schema.kind {
+        RuntypeKind::TypeParameter(_) => true,
+        RuntypeKind::Ref(to) => parameterized.contains(to),
+        _ => schema
+            .children()
+            .into_iter()
+            .any(|it| mentions_type_parameter(it, parameterized)),
+    }
+}
+
+/// Named types instantiated with type parameters can only be built inside a parser factory
+pub(super) fn parameterized_names(named_schemas: &[NamedSchema]) -> BTreeSet<RuntypeUUID> {
+    let mut acc = BTreeSet::new();
+    loop {
+        let before = acc.len();
+        for named_schema in named_schemas {
+            if acc.contains(&named_schema.name) {
+                continue;
+            }
+            let depends = named_schema
+                .name
+                .type_arguments
+                .iter()
+                .chain(std::iter::once(&named_schema.schema))
+                .any(|it| mentions_type_parameter(it, &acc));
+            if depends {
+                acc.insert(named_schema.name.clone());
+            }
+        }
+        if acc.len() == before {
+            return acc;
+        }
+    }
+}
+
+fn collect_parameterized_refs(
+    schema: &Runtype,
+    named_schemas: &[NamedSchema],
+    parameterized: &BTreeSet<RuntypeUUID>,
+    acc: &mut BTreeSet<RuntypeUUID>,
+) {
+    if let RuntypeKind::Ref(to) = &schema.kind
+        && parameterized.contains(to)
+        && acc.insert(to.clone())
+    {
+        let named_schema = named_schemas
+            .iter()
+            .find(|it| it.name == *to)
+            .expect("everything should be resolved by now");
+        collect_parameterized_refs(&named_schema.schema, named_schemas, parameterized, acc);
+    }
+    for child in schema.children() {
+        collect_parameterized_refs(child, named_schemas, parameterized, acc);
+    }
+}
 
 fn optionality_wrapper(inner: Expr) -> Expr {
     new_runtime_class("OptionalFieldRuntype", vec![inner])
 }
 
 fn print_runtype(schema: &Runtype, named_schemas: &[NamedSchema], ctx: &mut PrintContext) -> Expr {
+    if let RuntypeKind::TypeParameter(name) = &schema.kind {
+        return Expr::Ident(identifier(name));
+    }
     let hoist_key = PrintableRuntypeKey::from_runtype(schema);
-    if let Some((var_name, _)) = ctx.hoisted.get(&hoist_key) {
+    // anything depending on a type parameter is built again on every factory call
+    let in_factory =
+        ctx.factory_hoisted.is_some() && mentions_type_parameter(schema, &ctx.parameterized);
+    if in_factory {
+        if let Some((var_name, _)) = ctx
+            .factory_hoisted
+            .as_ref()
+            .and_then(|it| it.get(&hoist_key))
+        {
+            return factory_hoist_identifier(*var_name);
+        }
+    } else if let Some((var_name, _)) = ctx.hoisted.get(&hoist_key) {
         return hoist_identifier(*var_name);
     }
 
@@ -847,7 +1054,8 @@ fn print_runtype(schema: &Runtype, named_schemas: &[NamedSchema], ctx: &mut Prin
         RuntypeKind::String => typeof_runtype("string", schema),
         RuntypeKind::Boolean => typeof_runtype("boolean", schema),
         RuntypeKind::Number => typeof_runtype("number", schema),
-        RuntypeKind::Function => typeof_runtype("function", schema),
+        RuntypeKind::Function { .. } => typeof_runtype("function", schema),
+        RuntypeKind::Promise(_) => typeof_runtype("object", schema),
         RuntypeKind::Ref(to) => ref_runtype(to, ctx, schema),
         RuntypeKind::Any => no_args_runtype("AnyRuntype", schema),
         RuntypeKind::Never => no_args_runtype("NeverRuntype", schema),
@@ -860,6 +1068,7 @@ fn print_runtype(schema: &Runtype, named_schemas: &[NamedSchema], ctx: &mut Prin
         RuntypeKind::NumberWithFormat(CustomFormat(first, rest)) => {
             formats_runtype("NumberWithFormatRuntype", first, rest, schema)
         }
+        RuntypeKind::NumberRange(range) => number_range_runtype(range, schema),
         RuntypeKind::Date => no_args_runtype("DateRuntype", schema),
         RuntypeKind::BigInt => no_args_runtype("BigIntRuntype", schema),
         RuntypeKind::TypedArray(kind) => new_runtype_class(
@@ -867,6 +1076,11 @@ fn print_runtype(schema: &Runtype, named_schemas: &[NamedSchema], ctx: &mut Prin
             vec![Json::String(kind.js_name().to_string()).to_expr()],
             schema,
         ),
+        RuntypeKind::InstanceOf(class) => new_runtype_class(
+            "InstanceOfRuntype",
+            vec![Json::String(class.name.clone()).to_expr()],
+            schema,
+        ),
         RuntypeKind::TplLitType(t) => match t.0.as_slice() {
             [TplLitTypeItem::StringConst(c)] => new_runtype_class(
                 "ConstRuntype",
@@ -878,7 +1092,7 @@ fn print_runtype(schema: &Runtype, named_schemas: &[NamedSchema], ctx: &mut Prin
                 vec![
                     Expr::Lit(Lit::Regex(Regex {
                         span: DUMMY_SP,
-                        exp: t.regex_expr().into(),
+                        exp: t.anchored_regex_expr().into(),
                         flags: "".into(),
                     })),
                     Expr::Lit(Lit::Str(Str {
@@ -925,41 +1139,57 @@ fn print_runtype(schema: &Runtype, named_schemas: &[NamedSchema], ctx: &mut Prin
 
             if let Some(consts) = maybe_runtype_any_of_consts(schema, &flat_values, ctx) {
                 consts
-            } else if let Some(discriminated) =
-                maybe_runtype_any_of_discriminated(schema, &flat_values, named_schemas, ctx)
-            {
-                discriminated
+            } else if let Some(union) = discriminated_union(&flat_values, named_schemas) {
+                runtype_any_of_discriminated(schema, &flat_values, union, named_schemas, ctx)
             } else {
                 runtype_union_or_intersection("AnyOfRuntype", vs, named_schemas, ctx, schema)
             }
         }
         RuntypeKind::Tuple {
             prefix_items,
+            optional_items,
             items,
+            suffix_items,
         } => {
-            let prefix_item_validators = prefix_items
-                .iter()
-                .map(|it| print_runtype(it, named_schemas, ctx))
-                .collect::<Vec<Expr>>();
-            let prefix_arr = Expr::Array(ArrayLit {
-                span: DUMMY_SP,
-                elems: prefix_item_validators
-                    .into_iter()
+            let mut array_of = |items: &[Runtype], optional: bool| {
+                let validators = items
+                    .iter()
                     .map(|it| {
-                        Some(ExprOrSpread {
-                            spread: None,
-                            expr: it.into(),
-                        })
+                        let validator = print_runtype(it, named_schemas, ctx);
+                        if optional {
+                            optionality_wrapper(validator)
+                        } else {
+                            validator
+                        }
                     })
-                    .collect(),
-            });
+                    .collect::<Vec<Expr>>();
+                Expr::Array(ArrayLit {
+                    span: DUMMY_SP,
+                    elems: validators
+                        .into_iter()
+                        .map(|it| {
+                            Some(ExprOrSpread {
+                                spread: None,
+                                expr: it.into(),
+                            })
+                        })
+                        .collect(),
+                })
+            };
+            let prefix_arr = array_of(prefix_items, false);
+            let optional_arr = array_of(optional_items, true);
+            let suffix_arr = array_of(suffix_items, false);
 
             let items = match items {
                 Some(item_schema) => print_runtype(item_schema, named_schemas, ctx),
                 None => Expr::Lit(Lit::Null(Null { span: DUMMY_SP })),
             };
 
-            new_runtype_class("TupleRuntype", vec![prefix_arr, items], schema)
+            let mut args = vec![prefix_arr, items];
+            if !optional_items.is_empty() || !suffix_items.is_empty() {
+                args.extend([optional_arr, suffix_arr]);
+            }
+            new_runtype_class("TupleRuntype", args, schema)
         }
         RuntypeKind::Object {
             vs,
@@ -1061,33 +1291,153 @@ fn print_runtype(schema: &Runtype, named_schemas: &[NamedSchema], ctx: &mut Prin
             new_runtype_class("NullishRuntype", vec![string_lit("undefined")], schema)
         }
         RuntypeKind::Void => new_runtype_class("NullishRuntype", vec![string_lit("void")], schema),
+        RuntypeKind::TypeParameter(_) => unreachable!("type parameters are not hoisted"),
+    };
+    let out = match &schema.metadata.keywords {
+        Some(keywords) if keywords.has_constraints() => constrained_runtype(out, keywords),
+        _ => out,
     };
 
+    if in_factory {
+        let factory_hoisted = ctx
+            .factory_hoisted
+            .as_mut()
+            .expect("should be printing a factory");
+        let new_id = factory_hoisted.len();
+        factory_hoisted.insert(hoist_key, (new_id, out.clone()));
+        return factory_hoist_identifier(new_id);
+    }
     let new_id = ctx.hoisted.len();
     ctx.hoisted.insert(hoist_key, (new_id, out.clone()));
+    ctx.hoisted_origins.insert(new_id, ctx.origin);
     hoist_identifier(new_id)
 }
 
+fn parser_factory(
+    decoder: &BuiltDecoder,
+    named_schemas: &[NamedSchema],
+    ctx: &mut PrintContext,
+) -> Expr {
+    ctx.factory_hoisted = Some(BTreeMap::new());
+    let result = print_runtype(&decoder.schema, named_schemas, ctx);
+
+    let mut scoped = BTreeSet::new();
+    collect_parameterized_refs(
+        &decoder.schema,
+        named_schemas,
+        &ctx.parameterized,
+        &mut scoped,
+    );
+    let mut scoped_exprs = vec![];
+    for name in scoped {
+        let named_schema = named_schemas
+            .iter()
+            .find(|it| it.name == name)
+            .expect("everything should be resolved by now");
+        let validator = print_runtype(&named_schema.schema, named_schemas, ctx);
+        scoped_exprs.push((ctx.print_rt_name(&name), validator));
+    }
+
+    let mut factory_hoisted = ctx
+        .factory_hoisted
+        .take()
+        .expect("should be printing a factory")
+        .into_values()
+        .collect::<Vec<_>>();
+    factory_hoisted.sort_by_key(|it| it.0);
+
+    let scope_decl = const_decl_stmt(
+        FACTORY_SCOPE_NAME,
+        Expr::Object(ObjectLit {
+            span: DUMMY_SP,
+            props: vec![],
+        }),
+        DUMMY_SP,
+    );
+    let hoisted_decls = factory_hoisted
+        .into_iter()
+        .map(|(id, expr)| const_decl_stmt(&factory_hoist_name(id), expr, DUMMY_SP));
+    let scope_assignments = scoped_exprs.into_iter().map(|(name, value)| {
+        Stmt::Expr(ExprStmt {
+            span: DUMMY_SP,
+            expr: Expr::Assign(AssignExpr {
+                span: DUMMY_SP,
+                op: AssignOp::Assign,
+                left: SimpleAssignTarget::Member(MemberExpr {
+                    span: DUMMY_SP,
+                    obj: Expr::Ident(identifier(FACTORY_SCOPE_NAME)).into(),
+                    prop: MemberProp::Computed(ComputedPropName {
+                        span: DUMMY_SP,
+                        expr: string_lit(&name).into(),
+                    }),
+                })
+                .into(),
+                right: value.into(),
+            })
+            .into(),
+        })
+    });
+    let return_stmt = Stmt::Return(ReturnStmt {
+        span: DUMMY_SP,
+        arg: Some(result.into()),
+    });
+
+    Expr::Arrow(ArrowExpr {
+        span: DUMMY_SP,
+        ctxt: SyntaxContext::empty(),
+        params: decoder
+            .type_params
+            .iter()
+            .map(|it| {
+                Pat::Ident(BindingIdent {
+                    id: identifier(it),
+                    type_ann: None,
+                })
+            })
+            .collect(),
+        body: BlockStmtOrExpr::BlockStmt(BlockStmt {
+            span: DUMMY_SP,
+            ctxt: SyntaxContext::empty(),
+            stmts: std::iter::once(scope_decl)
+                .chain(hoisted_decls)
+                .chain(scope_assignments)
+                .chain(std::iter::once(return_stmt))
+                .collect(),
+        })
+        .into(),
+        is_async: false,
+        is_generator: false,
+        type_params: None,
+        return_type: None,
+    })
+}
+
 fn build_parsers_input(
     decs: &[BuiltDecoder],
     named_schemas: &[NamedSchema],
     ctx: &mut PrintContext,
 ) -> Expr {
-    let mut validator_exprs: Vec<(String, Expr)> = vec![];
+    let mut validator_exprs: Vec<(String, Expr, Span)> = vec![];
     for decoder in decs {
-        let validator = print_runtype(&decoder.schema, named_schemas, ctx);
-        validator_exprs.push((decoder.exported_name.clone(), validator));
+        ctx.origin = ctx.positions.span(decoder.position.as_ref());
+        let validator = if decoder.type_params.is_empty() {
+            print_runtype(&decoder.schema, named_schemas, ctx)
+        } else {
+            parser_factory(decoder, named_schemas, ctx)
+        };
+        validator_exprs.push((decoder.exported_name.clone(), validator, ctx.origin));
     }
+    ctx.origin = DUMMY_SP;
 
     Expr::Object(ObjectLit {
         span: DUMMY_SP,
         props: validator_exprs
             .into_iter()
-            .map(|(key, value)| {
+            .map(|(key, value, span)| {
                 PropOrSpread::Prop(
                     Prop::KeyValue(KeyValueProp {
                         key: PropName::Str(Str {
-                            span: DUMMY_SP,
+                            span,
                             value: key.into(),
                             raw: None,
                         }),
@@ -1101,26 +1451,35 @@ fn build_parsers_input(
 }
 
 fn named_runtypes(named_schemas: &[NamedSchema], ctx: &mut PrintContext) -> Expr {
-    let mut validator_exprs: Vec<(RuntypeUUID, Expr)> = vec![];
+    let mut validator_exprs: Vec<(RuntypeUUID, Expr, Span)> = vec![];
     for named_schema in named_schemas {
+        if ctx.parameterized.contains(&named_schema.name) {
+            continue;
+        }
+        let position = ctx
+            .declaration_positions
+            .get(&named_schema.name.ty)
+            .cloned();
+        ctx.origin = ctx.positions.span(position.as_ref());
         let validator = print_runtype(&named_schema.schema, named_schemas, ctx);
-        validator_exprs.push((named_schema.name.clone(), validator));
+        validator_exprs.push((named_schema.name.clone(), validator, ctx.origin));
     }
+    ctx.origin = DUMMY_SP;
     // filter out the inlined ones
-    let validator_exprs: Vec<(RuntypeUUID, Expr)> = validator_exprs
+    let validator_exprs: Vec<(RuntypeUUID, Expr, Span)> = validator_exprs
         .into_iter()
-        .filter(|(name, _)| !ctx.inlined.contains(name))
+        .filter(|(name, _, _)| !ctx.inlined.contains(name))
         .collect();
 
     Expr::Object(ObjectLit {
         span: DUMMY_SP,
         props: validator_exprs
             .into_iter()
-            .map(|(key, value)| {
+            .map(|(key, value, span)| {
                 PropOrSpread::Prop(
                     Prop::KeyValue(KeyValueProp {
                         key: PropName::Str(Str {
-                            span: DUMMY_SP,
+                            span,
                             value: ctx.print_rt_name(&key).into(),
                             raw: None,
                         }),
@@ -1135,6 +1494,19 @@ fn named_runtypes(named_schemas: &[NamedSchema], ctx: &mut PrintContext) -> Expr
 
 impl ParserExtractResult {
     pub fn emit_code(self) -> Result<String> {
+        let (module_items, _) = self.module_items()?;
+        emit_module_items(module_items)
+    }
+
+    /// The code, and a source map from its named types and parsers to their declarations.
+    pub fn emit_code_with_source_map(self) -> Result<(String, Json)> {
+        let (module_items, positions) = self.module_items()?;
+        let mut written = vec![];
+        let code = emit_module_items_with_source_map(module_items, Some(&mut written))?;
+        Ok((code, build_source_map(&written, &positions)))
+    }
+
+    fn module_items(self) -> Result<(Vec<ModuleItem>, SourcePositions)> {
         let built_parsers = self.built_decoders.unwrap_or_default();
         let named_schemas = validate_type_uniqueness(&self.validators)?;
         let all_names = named_schemas
@@ -1146,16 +1518,30 @@ impl ParserExtractResult {
             all_names,
             type_with_args_names: BTreeMap::new(),
             inlined: BTreeSet::new(),
+            parameterized: parameterized_names(&named_schemas),
+            factory_hoisted: None,
+            declaration_positions: self.declaration_positions,
+            positions: SourcePositions::default(),
+            origin: DUMMY_SP,
+            hoisted_origins: BTreeMap::new(),
         };
 
+        let required_classes = const_decl(
+            "RequiredClasses",
+            required_classes(&named_schemas, &built_parsers).to_expr(),
+            DUMMY_SP,
+        );
+
         let build_parsers_input: ModuleItem = const_decl(
             "buildParsersInput",
             build_parsers_input(&built_parsers, &named_schemas, &mut hoisted),
+            DUMMY_SP,
         );
 
         let build_named_parsers_input = const_decl(
             "namedRuntypes",
             named_runtypes(&named_schemas, &mut hoisted),
+            DUMMY_SP,
         );
 
         let mut sorted_direct_hoisted_values = hoisted.hoisted.into_values().collect::<Vec<_>>();
@@ -1163,18 +1549,26 @@ impl ParserExtractResult {
 
         let hoisted_direct_decls: Vec<ModuleItem> = sorted_direct_hoisted_values
             .into_iter()
-            .map(|(id, expr)| const_decl(&hoist_name(id), expr))
+            .map(|(id, expr)| {
+                let origin = hoisted
+                    .hoisted_origins
+                    .get(&id)
+                    .copied()
+                    .unwrap_or(DUMMY_SP);
+                const_decl(&hoist_name(id), expr, origin)
+            })
             .collect();
 
         let module_items = hoisted_direct_decls
             .into_iter()
             .chain(vec![
                 //
+                required_classes,
                 build_named_parsers_input,
                 build_parsers_input,
             ])
             .collect();
 
-        Ok(emit_module_items(module_items)?)
+        Ok((module_items, hoisted.positions))
     }
 }
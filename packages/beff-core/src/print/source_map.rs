@@ -0,0 +1,110 @@
+use std::collections::BTreeMap;
+
+use swc_common::{BytePos, DUMMY_SP, Span, source_map::LineCol};
+
+use crate::SourcePosition;
+use crate::ast::json::Json;
+
+const BASE64_DIGITS: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// The declarations the generated code points back to. The spans handed to the code are not
+/// offsets in a file, `lo` is the id of a position, so the writer can report where each one
+/// ends up in the output.
+#[derive(Default)]
+pub(super) struct SourcePositions {
+    positions: Vec<SourcePosition>,
+    ids: BTreeMap<SourcePosition, u32>,
+}
+
+impl SourcePositions {
+    pub fn span(&mut self, position: Option<&SourcePosition>) -> Span {
+        let Some(position) = position else {
+            return DUMMY_SP;
+        };
+        let id = *self.ids.entry(position.clone()).or_insert_with(|| {
+            self.positions.push(position.clone());
+            // zero is the dummy position
+            self.positions.len() as u32
+        });
+        Span::new(BytePos(id), BytePos(id))
+    }
+
+    fn get(&self, pos: BytePos) -> Option<&SourcePosition> {
+        self.positions.get((pos.0 as usize).checked_sub(1)?)
+    }
+}
+
+fn push_vlq(acc: &mut String, value: i64) {
+    let mut rest = if value < 0 {
+        ((-value) << 1) | 1
+    } else {
+        value << 1
+    };
+    loop {
+        let mut digit = rest & 31;
+        rest >>= 5;
+        if rest > 0 {
+            digit |= 32;
+        }
+        acc.push(BASE64_DIGITS[digit as usize] as char);
+        if rest == 0 {
+            return;
+        }
+    }
+}
+
+/// A source map v3 of the code, from the positions recorded while writing it.
+pub(super) fn build_source_map(
+    written: &[(BytePos, LineCol)],
+    positions: &SourcePositions,
+) -> Json {
+    let mut segments = written
+        .iter()
+        .filter_map(|(pos, loc)| positions.get(*pos).map(|it| (loc.line, loc.col, it)))
+        .collect::<Vec<_>>();
+    segments.sort_by_key(|(line, col, _)| (*line, *col));
+    // the start and the end of a token point to the same declaration
+    segments.dedup_by(|b, a| a.0 == b.0 && a.2 == b.2);
+
+    let mut sources: BTreeMap<String, i64> = BTreeMap::new();
+    for (_, _, position) in &segments {
+        sources.insert(position.file.to_string(), 0);
+    }
+    for (idx, v) in sources.values_mut().enumerate() {
+        *v = idx as i64;
+    }
+
+    // every field but the generated column is relative to the previous segment
+    let mut mappings = String::new();
+    let (mut line, mut col, mut source, mut source_line, mut source_col) = (0, 0, 0, 0, 0);
+    for (gen_line, gen_col, position) in segments {
+        if gen_line != line {
+            for _ in line..gen_line {
+                mappings.push(';');
+            }
+            line = gen_line;
+            col = 0;
+        } else if !mappings.is_empty() && !mappings.ends_with(';') {
+            mappings.push(',');
+        }
+        let src = sources[&position.file.to_string()];
+        push_vlq(&mut mappings, gen_col as i64 - col);
+        push_vlq(&mut mappings, src - source);
+        push_vlq(&mut mappings, position.line as i64 - source_line);
+        push_vlq(&mut mappings, position.col as i64 - source_col);
+        col = gen_col as i64;
+        source = src;
+        source_line = position.line as i64;
+        source_col = position.col as i64;
+    }
+
+    Json::object(vec![
+        ("version".to_string(), Json::parse_int(3)),
+        (
+            "sources".to_string(),
+            Json::Array(sources.into_keys().map(Json::String).collect()),
+        ),
+        ("names".to_string(), Json::Array(vec![])),
+        ("mappings".to_string(), Json::String(mappings)),
+    ])
+}
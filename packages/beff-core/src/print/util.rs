@@ -0,0 +1,22 @@
+// helpers shared by the printers that write JavaScript or TypeScript source
+
+pub(super) fn js_string(s: &str) -> String {
+    serde_json::to_string(s).expect("should be possible to serialize a string")
+}
+
+pub(super) fn is_identifier(key: &str) -> bool {
+    let mut chars = key.chars();
+    chars
+        .next()
+        .is_some_and(|it| it.is_ascii_alphabetic() || it == '_' || it == '$')
+        && chars.all(|it| it.is_ascii_alphanumeric() || it == '_' || it == '$')
+}
+
+// an object key, quoted when it is not an identifier
+pub(super) fn prop_name(key: &str) -> String {
+    if is_identifier(key) {
+        key.to_string()
+    } else {
+        js_string(key)
+    }
+}
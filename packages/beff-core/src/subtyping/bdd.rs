@@ -24,7 +24,7 @@ pub struct IndexedPropertiesAtomic {
 
 pub struct MappingAtomicType {
     pub vs: BTreeMap<String, Rc<SemType>>,
-    pub indexed_properties: Option<IndexedPropertiesAtomic>,
+    pub indexed_properties: Vec<IndexedPropertiesAtomic>,
 }
 impl Default for MappingAtomicType {
     fn default() -> Self {
@@ -36,7 +36,7 @@ impl MappingAtomicType {
     pub fn new() -> Self {
         Self {
             vs: BTreeMap::new(),
-            indexed_properties: None,
+            indexed_properties: vec![],
         }
     }
 }
@@ -45,6 +45,30 @@ impl MappingAtomicType {
 pub struct ListAtomic {
     pub prefix_items: Vec<Rc<SemType>>,
     pub items: Rc<SemType>,
+    // elements after the rest, always empty when `items` is never
+    pub suffix_items: Vec<Rc<SemType>>,
+}
+
+impl ListAtomic {
+    fn admits_len(&self, len: usize) -> bool {
+        if self.items.is_never() {
+            len == self.prefix_items.len()
+        } else {
+            len >= self.prefix_items.len() + self.suffix_items.len()
+        }
+    }
+
+    /// The type of the element at `idx` of lists of length `len`, which must be admitted.
+    fn member_at(&self, idx: usize, len: usize) -> Rc<SemType> {
+        let suffix_start = len - self.suffix_items.len();
+        if idx < self.prefix_items.len() {
+            self.prefix_items[idx].clone()
+        } else if idx >= suffix_start {
+            self.suffix_items[idx - suffix_start].clone()
+        } else {
+            self.items.clone()
+        }
+    }
 }
 
 #[derive(PartialEq, Eq, Hash, Debug, Ord, PartialOrd, Clone, Copy)]
@@ -439,11 +463,108 @@ fn list_inhabited(
     }
 }
 
+fn list_conjunction_atoms(
+    conjunction: &Option<Rc<Conjunction>>,
+    builder: &SemTypeContext,
+) -> Vec<Rc<ListAtomic>> {
+    let mut acc = vec![];
+    let mut it = conjunction.clone();
+    while let Some(some_it) = it {
+        acc.push(match some_it.atom {
+            Atom::List(a) => builder.get_list_atomic(a),
+            Atom::Set(a) => builder.get_set_atomic(a),
+            _ => unreachable!(),
+        });
+        it = some_it.next.clone();
+    }
+    acc
+}
+
+// `list_inhabited` for lists of a single length, `neg` holds the element types of each
+// negated tuple of that length.
+fn fixed_length_list_inhabited(
+    members: &[Rc<SemType>],
+    neg: &[Vec<Rc<SemType>>],
+    builder: &mut SemTypeContext,
+) -> Result<bool> {
+    let Some((nt, rest)) = neg.split_first() else {
+        return Ok(true);
+    };
+    for i in 0..members.len() {
+        let d = members[i].diff(&nt[i])?;
+        if !d.is_empty(builder)? {
+            let mut s = members.to_vec();
+            s[i] = d;
+            if fixed_length_list_inhabited(&s, rest, builder)? {
+                return Ok(true);
+            }
+        }
+    }
+    Ok(false)
+}
+
+// Lists with elements after the rest cannot be handled by `list_inhabited`, so every length is
+// checked on its own. Lists longer than `max_len` have more middle elements, typed by the rest of
+// every atom, than there are negated tuples. One of them is not needed to stay out of the
+// negated tuples and can be removed, so lengths up to `max_len` are enough.
+fn list_formula_is_empty_by_length(
+    pos: &[Rc<ListAtomic>],
+    neg: &[Rc<ListAtomic>],
+    builder: &mut SemTypeContext,
+) -> Result<IsEmptyStatus> {
+    let atoms = pos.iter().chain(neg.iter());
+    let max_prefix = atoms
+        .clone()
+        .map(|it| it.prefix_items.len())
+        .max()
+        .unwrap_or(0);
+    let max_suffix = atoms.map(|it| it.suffix_items.len()).max().unwrap_or(0);
+    let max_len = max_prefix + max_suffix + neg.len() + 1;
+
+    'len: for len in 0..=max_len {
+        let mut members: Vec<Rc<SemType>> = (0..len)
+            .map(|_| Rc::new(SemTypeContext::unknown()))
+            .collect();
+        for lt in pos {
+            if !lt.admits_len(len) {
+                continue 'len;
+            }
+            for (i, m) in members.iter_mut().enumerate() {
+                *m = m.intersect(&lt.member_at(i, len))?;
+            }
+        }
+        for m in members.iter() {
+            if m.is_empty(builder)? {
+                continue 'len;
+            }
+        }
+        let neg_members = neg
+            .iter()
+            .filter(|nt| nt.admits_len(len))
+            .map(|nt| (0..len).map(|i| nt.member_at(i, len)).collect())
+            .collect::<Vec<Vec<_>>>();
+        if fixed_length_list_inhabited(&members, &neg_members, builder)? {
+            return Ok(IsEmptyStatus::NotEmpty);
+        }
+    }
+    Ok(IsEmptyStatus::IsEmpty)
+}
+
 fn list_formula_is_empty(
     pos: &Option<Rc<Conjunction>>,
     neg: &Option<Rc<Conjunction>>,
     builder: &mut SemTypeContext,
 ) -> Result<IsEmptyStatus> {
+    let pos_atoms = list_conjunction_atoms(pos, builder);
+    let neg_atoms = list_conjunction_atoms(neg, builder);
+    if pos_atoms
+        .iter()
+        .chain(neg_atoms.iter())
+        .any(|it| !it.suffix_items.is_empty())
+    {
+        return list_formula_is_empty_by_length(&pos_atoms, &neg_atoms, builder);
+    }
+
     let mut prefix_items = vec![];
     let mut items = Rc::new(SemTypeContext::unknown());
 
@@ -532,7 +653,7 @@ pub fn list_is_empty(bdd: &Rc<Bdd>, builder: &mut SemTypeContext) -> Result<IsEm
 }
 
 fn bdd_mapping_member_type_inner(
-    ctx: &SemTypeContext,
+    ctx: &mut SemTypeContext,
     b: Rc<Bdd>,
     key: MappingStrKey,
     accum: Rc<SemType>,
@@ -551,7 +672,7 @@ fn bdd_mapping_member_type_inner(
                 Atom::Map(a) => ctx.get_map_atomic(*a),
                 _ => unreachable!(),
             };
-            let a = mapping_member_type_inner(b_atom_type.clone(), key.clone())?;
+            let a = mapping_member_type_inner(ctx, b_atom_type.clone(), key.clone())?;
             let a = a.intersect(&accum)?;
             let a = bdd_mapping_member_type_inner(ctx, left.clone(), key.clone(), a.clone())?;
 
@@ -570,6 +691,7 @@ enum MappingStrKey {
 }
 
 fn mapping_atomic_applicable_member_types_inner(
+    ctx: &mut SemTypeContext,
     atomic: Rc<MappingAtomicType>,
     key: MappingStrKey,
 ) -> anyhow::Result<Vec<Rc<SemType>>> {
@@ -579,38 +701,38 @@ fn mapping_atomic_applicable_member_types_inner(
                 return Ok(vec![]);
             }
             let mut member_types = vec![];
-            for (k, ty) in atomic.vs.iter() {
-                let mut found = false;
-
-                for l in &values {
-                    if l == k {
-                        found = true;
-                        break;
+            for l in &values {
+                if let Some(ty) = atomic.vs.get(l) {
+                    member_types.push(ty.clone());
+                    continue;
+                }
+                // a key matched by several index signatures must satisfy all of them
+                let l_st = Rc::new(SemTypeContext::string_const(StringLitOrFormat::Tpl(
+                    TplLitType(vec![TplLitTypeItem::StringConst(l.clone())]),
+                )));
+                let mut acc: Option<Rc<SemType>> = None;
+                for v in &atomic.indexed_properties {
+                    if l_st.is_subtype(&v.key, ctx)? {
+                        acc = Some(match acc {
+                            Some(prev) => prev.intersect(&v.value)?,
+                            None => v.value.clone(),
+                        });
                     }
                 }
-
-                if found {
-                    member_types.push(ty.clone());
+                if let Some(ty) = acc {
+                    member_types.push(ty);
                 }
             }
 
-            let is_subtype = member_types.len() == atomic.vs.len();
-            if !is_subtype
-                && let Some(v) = &atomic.indexed_properties
-                && v.key.is_all_strings()
-            {
-                member_types.push(v.value.clone());
-            }
-
             Ok(member_types)
         }
         MappingStrKey::True => {
             let mut vs: Vec<Rc<SemType>> = atomic.vs.values().cloned().collect();
 
-            if let Some(v) = &atomic.indexed_properties
-                && v.key.is_all_strings()
-            {
-                vs.push(v.value.clone());
+            for v in &atomic.indexed_properties {
+                if v.key.is_all_strings() {
+                    vs.push(v.value.clone());
+                }
             }
             Ok(vs)
         }
@@ -618,12 +740,13 @@ fn mapping_atomic_applicable_member_types_inner(
 }
 
 fn mapping_member_type_inner(
+    ctx: &mut SemTypeContext,
     atomic: Rc<MappingAtomicType>,
     key: MappingStrKey,
 ) -> anyhow::Result<Rc<SemType>> {
     let mut member_type: Option<Rc<SemType>> = None;
 
-    for ty in mapping_atomic_applicable_member_types_inner(atomic, key)? {
+    for ty in mapping_atomic_applicable_member_types_inner(ctx, atomic, key)? {
         match member_type {
             Some(mt) => {
                 member_type = Some(mt.union(&ty)?);
@@ -661,11 +784,15 @@ fn bdd_mapped_record_member_type_inner_val(
                 Atom::Map(a) => ctx.get_map_atomic(*a),
                 _ => unreachable!(),
             };
-            let mut found = None;
-            if let Some(p) = &b_atom_type.indexed_properties {
+            // a key matched by several index signatures must satisfy all of them
+            let mut found: Option<Rc<SemType>> = None;
+            for p in &b_atom_type.indexed_properties {
                 let key_is_subtype = idx_st.is_subtype(&p.key, ctx)?;
                 if key_is_subtype {
-                    found = Some(p);
+                    found = Some(match found {
+                        Some(acc) => acc.intersect(&p.value)?,
+                        None => p.value.clone(),
+                    });
                 }
             }
             // if the key type does not intersect the key, then skip this branch
@@ -673,7 +800,7 @@ fn bdd_mapped_record_member_type_inner_val(
                 None => {
                     return Ok(SemTypeContext::never().into());
                 }
-                Some(p) => p.value.clone(),
+                Some(v) => v,
             };
             let a = a.intersect(&accum)?;
             let a = bdd_mapped_record_member_type_inner_val(ctx, left.clone(), idx_st.clone(), a)?;
@@ -731,7 +858,8 @@ pub fn mapping_indexed_access(
                         let mut all_string_const = true;
                         for v in values {
                             match v {
-                                StringLitOrFormat::Format(_custom_format) => {
+                                StringLitOrFormat::Format(_)
+                                | StringLitOrFormat::Refined { .. } => {
                                     all_string_const = false;
                                 }
                                 StringLitOrFormat::Tpl(tpl_lit_type) => {
@@ -817,6 +945,7 @@ fn int_subtype_max(allowed: bool, values: &Vec<N>) -> i64 {
 fn list_atomic_member_type_at_inner(
     prefix_items: &[Rc<SemType>],
     items: &Rc<SemType>,
+    suffix_items: &[Rc<SemType>],
     key: ListNumberKey,
 ) -> Result<Rc<SemType>> {
     match key {
@@ -831,14 +960,21 @@ fn list_atomic_member_type_at_inner(
                     }
                 }
             }
-            if init_len == 0 || int_subtype_max(allowed, &values) > (init_len as i64) - 1 {
+            let max = int_subtype_max(allowed, &values);
+            if init_len == 0 || max > (init_len as i64) - 1 {
                 m = m.union(items)?;
             }
+            // the j-th element after the rest can be at any index from `init_len + j`
+            for (j, v) in suffix_items.iter().enumerate() {
+                if max >= (init_len + j) as i64 {
+                    m = m.union(v)?;
+                }
+            }
             Ok(m)
         }
         ListNumberKey::True => {
             let mut m = items.clone();
-            for it in prefix_items.iter() {
+            for it in prefix_items.iter().chain(suffix_items.iter()) {
                 m = m.union(it)?;
             }
             Ok(m)
@@ -850,7 +986,12 @@ fn list_atomic_member_type_inner(
     atomic: Rc<ListAtomic>,
     key: ListNumberKey,
 ) -> Result<Rc<SemType>> {
-    list_atomic_member_type_at_inner(&atomic.prefix_items, &atomic.items, key)
+    list_atomic_member_type_at_inner(
+        &atomic.prefix_items,
+        &atomic.items,
+        &atomic.suffix_items,
+        key,
+    )
 }
 
 fn list_member_type_inner_val(atomic: Rc<ListAtomic>, key: ListNumberKey) -> Result<Rc<SemType>> {
@@ -921,6 +1062,9 @@ pub fn list_indexed_access(
                                 rest.join(",")
                             )
                         }
+                        NumberRepresentationOrFormat::Range(range) => {
+                            bail!("range cannot be used as list index: {}", range.describe())
+                        }
                     }
                 }
 
@@ -998,7 +1142,7 @@ pub fn keyof(ctx: &mut SemTypeContext, st: Rc<SemType>) -> anyhow::Result<Rc<Sem
                                 )));
                             atom_keys = atom_keys.union(&key_ty)?;
                         }
-                        if let Some(indexed) = &a.indexed_properties {
+                        for indexed in &a.indexed_properties {
                             atom_keys = atom_keys.union(&indexed.key)?;
                         }
                         conj_keys = conj_keys.union(&atom_keys)?;
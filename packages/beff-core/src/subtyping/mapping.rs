@@ -37,23 +37,21 @@ pub fn intersect_mapping(
         acc.push((name.to_string(), t))
     }
 
-    let mut indexed_properties_acc = None;
-
-    match (&m1.indexed_properties, &m2.indexed_properties) {
-        (Some(p1), Some(p2)) => {
-            let keys_are_same_type = p1.key.is_same_type(&p2.key, ctx)?;
-            if !keys_are_same_type {
-                bail!("intersection of indexed properties of different key types is not supported");
+    // a key must satisfy every index signature it matches,
+    // so the signatures of both sides are kept side by side
+    let mut indexed_properties_acc: Vec<IndexedPropertiesAtomic> = m1.indexed_properties.clone();
+    for p2 in &m2.indexed_properties {
+        let mut merged = false;
+        for p1 in indexed_properties_acc.iter_mut() {
+            if p1.key.is_same_type(&p2.key, ctx)? {
+                p1.value = p1.value.intersect(&p2.value)?;
+                merged = true;
+                break;
             }
-            indexed_properties_acc = Some(IndexedPropertiesAtomic {
-                key: p1.key.clone(),
-                value: p1.value.intersect(&p2.value)?,
-            });
         }
-        (None, Some(p)) | (Some(p), None) => {
-            indexed_properties_acc = Some(p.clone());
+        if !merged {
+            indexed_properties_acc.push(p2.clone());
         }
-        (None, None) => {}
     }
 
     Ok(Some(Rc::new(MappingAtomicType {
@@ -82,7 +80,9 @@ fn is_finite_string_set(ty: &Rc<SemType>) -> bool {
             }
             for val in values {
                 match val {
-                    StringLitOrFormat::Format(_) => return false,
+                    StringLitOrFormat::Format(_) | StringLitOrFormat::Refined { .. } => {
+                        return false;
+                    }
                     StringLitOrFormat::Tpl(tpl) => {
                         for item in &tpl.0 {
                             if !is_finite_tpl_item(item) {
@@ -169,6 +169,37 @@ fn mapping_atomic_type_is_empty(
     check_mapping_empty(atom, &neg_mappings, ctx, is_map)
 }
 
+// The value of key `k` according to the index signatures of `m`.
+// A key matched by several signatures must satisfy all of them.
+// Returns `None` if no signature matches `k`.
+fn get_index_value_of_key(
+    m: &MappingAtomicType,
+    k: &str,
+    ctx: &mut SemTypeContext,
+) -> Result<Option<Rc<SemType>>> {
+    let k_type = Rc::new(SemTypeContext::string_const(StringLitOrFormat::Tpl(
+        TplLitType(vec![TplLitTypeItem::StringConst(k.to_string())]),
+    )));
+
+    let mut acc: Option<Rc<SemType>> = None;
+    let mut is_required = false;
+    for idx in &m.indexed_properties {
+        if k_type.is_subtype(&idx.key, ctx)? {
+            is_required |= is_finite_string_set(&idx.key);
+            acc = Some(match acc {
+                Some(prev) => prev.intersect(&idx.value)?,
+                None => idx.value.clone(),
+            });
+        }
+    }
+
+    match acc {
+        Some(v) if is_required => Ok(Some(v)),
+        Some(v) => Ok(Some(SemTypeContext::make_optional(v)?)),
+        None => Ok(None),
+    }
+}
+
 // Used for the positive side (`pos`).
 // We treat `pos` as a specific type definition (Closed).
 // If a key is not present in `pos` (and not covered by an index signature),
@@ -183,21 +214,10 @@ fn get_value_exact(
         return Ok(v.clone());
     }
 
-    if let Some(idx) = &m.indexed_properties {
-        let k_type = Rc::new(SemTypeContext::string_const(StringLitOrFormat::Tpl(
-            TplLitType(vec![TplLitTypeItem::StringConst(k.to_string())]),
-        )));
-
-        if k_type.is_subtype(&idx.key, ctx)? {
-            if is_finite_string_set(&idx.key) {
-                return Ok(idx.value.clone());
-            } else {
-                return SemTypeContext::make_optional(idx.value.clone());
-            }
-        }
+    match get_index_value_of_key(m, k, ctx)? {
+        Some(v) => Ok(v),
+        None => Ok(Rc::new(SemTypeContext::optional_prop())),
     }
-
-    Ok(Rc::new(SemTypeContext::optional_prop()))
 }
 
 // Used for the negative side (`neg`).
@@ -209,86 +229,54 @@ fn get_value_open(m: &MappingAtomicType, k: &str, ctx: &mut SemTypeContext) -> R
         return Ok(v.clone());
     }
 
-    if let Some(idx) = &m.indexed_properties {
-        let k_type = Rc::new(SemTypeContext::string_const(StringLitOrFormat::Tpl(
-            TplLitType(vec![TplLitTypeItem::StringConst(k.to_string())]),
-        )));
-
-        if k_type.is_subtype(&idx.key, ctx)? {
-            if is_finite_string_set(&idx.key) {
-                return Ok(idx.value.clone());
-            } else {
-                return SemTypeContext::make_optional(idx.value.clone());
-            }
-        }
+    match get_index_value_of_key(m, k, ctx)? {
+        Some(v) => Ok(v),
+        None => Ok(Rc::new(SemTypeContext::unknown())),
     }
-
-    Ok(Rc::new(SemTypeContext::unknown()))
 }
 
-// Used for `pos`.
-// Returns the index signature value of `pos`.
-// If `pos` has no index signature, it implies no extra properties are present (they are `void`).
-fn get_index_value_exact(m: &MappingAtomicType) -> Rc<SemType> {
-    if let Some(idx) = &m.indexed_properties {
-        idx.value.clone()
-    } else {
-        Rc::new(SemTypeContext::optional_prop())
+// Splits the index signatures of `m` into regions with disjoint key types.
+// Overlapping signatures produce a region for each combination of signatures,
+// whose value is the intersection of the values of the signatures it belongs to.
+// For example `[k: string]: A` and `[k: `data-${string}`]: B` produce
+// `data-${string}` with `A & B` and `string \ data-${string}` with `A`.
+fn get_index_regions(
+    m: &MappingAtomicType,
+    ctx: &mut SemTypeContext,
+) -> Result<Vec<IndexedPropertiesAtomic>> {
+    let sigs = &m.indexed_properties;
+    if sigs.len() <= 1 {
+        return Ok(sigs.clone());
     }
-}
-
-// Used for `neg`.
-// Returns the index signature value of `neg`.
-// If `neg` has no index signature, it implies no constraint on extra properties (`unknown`).
-fn get_index_value_open(m: &MappingAtomicType) -> Rc<SemType> {
-    if let Some(idx) = &m.indexed_properties {
-        idx.value.clone()
-    } else {
-        Rc::new(SemTypeContext::unknown())
+    if sigs.len() > 8 {
+        bail!("too many overlapping index signatures");
     }
-}
-
-// Used for `pos`.
-// The domain of keys covered by `pos`'s index signature.
-// If none, it's `never` (no keys covered).
-fn get_key_type_exact(m: &MappingAtomicType) -> Rc<SemType> {
-    if let Some(idx) = &m.indexed_properties {
-        idx.key.clone()
-    } else {
-        Rc::new(SemTypeContext::never())
-    }
-}
-
-// Used for `neg`.
-// The domain of keys covered by `neg`'s index signature.
-// If none, it defaults to `string` (all keys) but with `unknown` value
-// (see `get_index_value_open`), effectively implementing the "Open" nature of structural types.
-fn get_key_type_open(m: &MappingAtomicType) -> Rc<SemType> {
-    if let Some(idx) = &m.indexed_properties {
-        idx.key.clone()
-    } else {
-        Rc::new(SemTypeContext::string())
+    let mut acc = vec![];
+    for mask in 1..(1usize << sigs.len()) {
+        let mut key: Rc<SemType> = Rc::new(SemTypeContext::unknown());
+        let mut value: Rc<SemType> = Rc::new(SemTypeContext::unknown());
+        for (i, sig) in sigs.iter().enumerate() {
+            if mask & (1 << i) != 0 {
+                key = key.intersect(&sig.key)?;
+                value = value.intersect(&sig.value)?;
+            } else {
+                key = key.diff(&sig.key)?;
+            }
+        }
+        if !key.is_empty(ctx)? {
+            acc.push(IndexedPropertiesAtomic { key, value });
+        }
     }
+    Ok(acc)
 }
 
-// Determines the constraint imposed by `neg`'s index signature on the "rest" of `pos`.
-// If `pos`'s index keys are covered by `neg`'s index keys, then `neg` imposes its index value constraint.
-// Crucially, since index signatures in `neg` represent "if a key exists, it must match X",
-// but don't enforce existence, the effective constraint is `X | void` (optional).
-fn get_effective_index_value(
-    pos: &MappingAtomicType,
-    neg: &MappingAtomicType,
-    ctx: &mut SemTypeContext,
-) -> Result<Rc<SemType>> {
-    let pos_key = get_key_type_exact(pos);
-    let neg_key = get_key_type_open(neg);
-
-    if pos_key.is_subtype(&neg_key, ctx)? {
-        let val = get_index_value_open(neg);
-        SemTypeContext::make_optional(val)
-    } else {
-        Ok(Rc::new(SemTypeContext::unknown()))
+// The domain of keys covered by the index signatures of `m`.
+fn get_index_keys(m: &MappingAtomicType) -> Result<Rc<SemType>> {
+    let mut acc: Rc<SemType> = Rc::new(SemTypeContext::never());
+    for idx in &m.indexed_properties {
+        acc = acc.union(&idx.key)?;
     }
+    Ok(acc)
 }
 
 // Checks if `pos` is a subtype of the union of `negs`.
@@ -324,7 +312,7 @@ fn check_mapping_empty(
             return Ok(true);
         }
     }
-    if let Some(idx) = &pos.indexed_properties {
+    for idx in &pos.indexed_properties {
         if idx.key.is_empty(ctx)? {
             return Ok(true);
         }
@@ -355,12 +343,12 @@ fn check_mapping_empty(
     }
 
     // Add keys from finite index signatures in neg
-    if let Some(idx) = &current_neg.indexed_properties
-        && is_finite_string_set(&idx.key)
-    {
-        let keys = extract_keys_from_type(&idx.key);
-        for k in keys {
-            all_keys.insert(k);
+    for idx in &current_neg.indexed_properties {
+        if is_finite_string_set(&idx.key) {
+            let keys = extract_keys_from_type(&idx.key);
+            for k in keys {
+                all_keys.insert(k);
+            }
         }
     }
 
@@ -387,8 +375,12 @@ fn check_mapping_empty(
     if is_map {
         // For Map types, we must ensure that all keys allowed by the positive type's index signature
         // are covered by the negative type's index signature.
-        let pos_key = get_key_type_exact(&pos);
-        let neg_key = get_key_type_open(current_neg);
+        let pos_key = get_index_keys(&pos)?;
+        let neg_key = if current_neg.indexed_properties.is_empty() {
+            Rc::new(SemTypeContext::string())
+        } else {
+            get_index_keys(current_neg)?
+        };
 
         let diff_keys = pos_key.diff(&neg_key)?;
         if !diff_keys.is_empty(ctx)? {
@@ -396,27 +388,47 @@ fn check_mapping_empty(
         }
     }
 
-    let v_p_idx = get_index_value_exact(&pos);
-    let v_n_idx = get_effective_index_value(&pos, current_neg, ctx)?;
-
-    let diff_idx = v_p_idx.diff(&v_n_idx)?;
-    if !diff_idx.is_empty(ctx)? {
-        let mut new_pos = (*pos).clone();
-        // Update indexed_properties value
-        // We need to preserve the key type of pos
-        let key_type = if let Some(idx) = &pos.indexed_properties {
-            idx.key.clone()
-        } else {
-            Rc::new(SemTypeContext::unknown())
-        };
+    // Keys of `pos` that no signature of `neg` matches are unconstrained by `neg`.
+    // For the ones that are matched, `neg` requires `X | void`, since index signatures
+    // say "if a key exists, it must match X" but don't enforce existence.
+    // Each overlap of a `pos` region with a `neg` region is its own dimension.
+    let pos_regions = get_index_regions(&pos, ctx)?;
+    let neg_regions = get_index_regions(current_neg, ctx)?;
+    for (i, p) in pos_regions.iter().enumerate() {
+        for n in &neg_regions {
+            let overlap = p.key.intersect(&n.key)?;
+            if overlap.is_empty(ctx)? {
+                continue;
+            }
+            let v_n_idx = SemTypeContext::make_optional(n.value.clone())?;
+            let diff_idx = p.value.diff(&v_n_idx)?;
+            if diff_idx.is_empty(ctx)? {
+                continue;
+            }
 
-        new_pos.indexed_properties = Some(IndexedPropertiesAtomic {
-            key: key_type,
-            value: diff_idx,
-        });
+            let mut new_pos = (*pos).clone();
+            let mut indexed_properties = vec![];
+            for (j, it) in pos_regions.iter().enumerate() {
+                if i != j {
+                    indexed_properties.push(it.clone());
+                }
+            }
+            let rest = p.key.diff(&overlap)?;
+            if !rest.is_empty(ctx)? {
+                indexed_properties.push(IndexedPropertiesAtomic {
+                    key: rest,
+                    value: p.value.clone(),
+                });
+            }
+            indexed_properties.push(IndexedPropertiesAtomic {
+                key: overlap,
+                value: diff_idx,
+            });
+            new_pos.indexed_properties = indexed_properties;
 
-        if !check_mapping_empty(Rc::new(new_pos), rest_negs, ctx, is_map)? {
-            return Ok(false);
+            if !check_mapping_empty(Rc::new(new_pos), rest_negs, ctx, is_map)? {
+                return Ok(false);
+            }
         }
     }
 
@@ -461,10 +473,8 @@ mod tests {
         let idx = ctx.mapping_definitions.len();
         ctx.mapping_definitions.push(Some(
             MappingAtomicType {
-                vs: vec![("a".to_string(), st_string.into())]
-                    .into_iter()
-                    .collect(),
-                indexed_properties: None,
+                vs: vec![("a".to_string(), st_string)].into_iter().collect(),
+                indexed_properties: vec![],
             }
             .into(),
         ));
@@ -1,8 +1,8 @@
 use self::bdd::ListAtomic;
-use self::semtype::{ComplexSemType, SemType, SemTypeContext, SemTypeOps};
+use self::semtype::{SemType, SemTypeContext, SemTypeOps};
 use self::subtype::StringLitOrFormat;
 use crate::RuntypeUUID;
-use crate::ast::runtype::{CustomFormat, Optionality, RuntypeConst, RuntypeKind};
+use crate::ast::runtype::{CustomFormat, IndexedProperty, Optionality, RuntypeConst, RuntypeKind};
 use crate::subtyping::bdd::{IndexedPropertiesAtomic, MappingAtomicType};
 use crate::subtyping::subtype::NumberRepresentationOrFormat;
 use crate::{NamedSchema, ast::runtype::Runtype};
@@ -31,7 +31,12 @@ impl IsEmptyStatus {
 
 struct ToSemTypeConverter<'a> {
     validators: &'a [&'a NamedSchema],
+    // references being expanded since the last type constructor,
+    // seeing one of them again means the recursion is not guarded by a constructor
     seen_refs: BTreeSet<RuntypeUUID>,
+    // type constructors being converted or already converted,
+    // recursion through them ends at the atom allocated for the constructor
+    constructor_memo: BTreeMap<Runtype, Rc<SemType>>,
 }
 
 impl<'a> ToSemTypeConverter<'a> {
@@ -39,9 +44,93 @@ impl<'a> ToSemTypeConverter<'a> {
         Self {
             validators,
             seen_refs: BTreeSet::new(),
+            constructor_memo: BTreeMap::new(),
         }
     }
 
+    fn enter_constructor(&mut self, schema: &Runtype, atom: Rc<SemType>) -> BTreeSet<RuntypeUUID> {
+        self.constructor_memo.insert(schema.clone(), atom);
+        std::mem::take(&mut self.seen_refs)
+    }
+
+    fn convert_all(
+        &mut self,
+        vs: &[Runtype],
+        builder: &mut SemTypeContext,
+    ) -> Result<Vec<Rc<SemType>>> {
+        vs.iter()
+            .map(|v| self.convert_to_sem_type(v, builder))
+            .collect()
+    }
+
+    /// The lists of a tuple, the first ones without the rest and some of the optional elements.
+    fn convert_tuple_atomics(
+        &mut self,
+        prefix_items: &[Runtype],
+        optional_items: &[Runtype],
+        items: Option<&Runtype>,
+        suffix_items: &[Runtype],
+        builder: &mut SemTypeContext,
+    ) -> Result<Vec<ListAtomic>> {
+        let prefix_items = self.convert_all(prefix_items, builder)?;
+        let optional_items = self.convert_all(optional_items, builder)?;
+        let items = match items {
+            Some(items) => self.convert_to_sem_type(items, builder)?,
+            // todo: should be unknown?
+            None => SemTypeContext::never().into(),
+        };
+        let suffix_items = self.convert_all(suffix_items, builder)?;
+
+        let mut acc = vec![];
+        for present in 0..optional_items.len() {
+            acc.push(ListAtomic {
+                prefix_items: [&prefix_items[..], &optional_items[..present]].concat(),
+                items: SemTypeContext::never().into(),
+                suffix_items: vec![],
+            });
+        }
+        acc.push(ListAtomic {
+            prefix_items: [prefix_items, optional_items].concat(),
+            items,
+            suffix_items,
+        });
+        Ok(acc)
+    }
+
+    fn convert_mapping_atomic(
+        &mut self,
+        vs: &BTreeMap<String, Optionality<Runtype>>,
+        indexed_properties: &[IndexedProperty],
+        builder: &mut SemTypeContext,
+    ) -> Result<MappingAtomicType> {
+        let vs = vs
+            .iter()
+            .map(|(k, v)| match v {
+                Optionality::Optional(v) => self
+                    .convert_to_sem_type(v, builder)
+                    .and_then(|v| SemTypeContext::make_optional(v).map(|v| (k.clone(), v))),
+                Optionality::Required(v) => {
+                    self.convert_to_sem_type(v, builder).map(|v| (k.clone(), v))
+                }
+            })
+            .collect::<Result<_>>()?;
+        let mut indexed_props_acc = vec![];
+        for it in indexed_properties {
+            let v = match &it.value {
+                Optionality::Optional(v) => self
+                    .convert_to_sem_type(v, builder)
+                    .and_then(SemTypeContext::make_optional)?,
+                Optionality::Required(v) => self.convert_to_sem_type(v, builder)?,
+            };
+            let k = self.convert_to_sem_type(&it.key, builder)?;
+            indexed_props_acc.push(IndexedPropertiesAtomic { key: k, value: v });
+        }
+        Ok(MappingAtomicType {
+            vs,
+            indexed_properties: indexed_props_acc,
+        })
+    }
+
     fn get_reference(&self, name: &RuntypeUUID) -> Result<&Runtype> {
         for validator in self.validators {
             if &validator.name == name {
@@ -56,6 +145,9 @@ impl<'a> ToSemTypeConverter<'a> {
         schema: &Runtype,
         builder: &mut SemTypeContext,
     ) -> Result<Rc<SemType>> {
+        if let Some(ty) = self.constructor_memo.get(schema) {
+            return Ok(ty.clone());
+        }
         match &schema.kind {
             RuntypeKind::Ref(name) => {
                 let schema = self.get_reference(name)?.clone();
@@ -63,12 +155,15 @@ impl<'a> ToSemTypeConverter<'a> {
                 // handle recursive types
                 if let RuntypeKind::Tuple {
                     prefix_items,
+                    optional_items,
                     items,
+                    suffix_items,
                 } = &schema.kind
+                    && optional_items.is_empty()
                 {
                     match builder.list_runtype_ref_memo.get(name) {
                         Some(idx) => {
-                            let ty = Rc::new(SemTypeContext::mapping_definition_from_idx(*idx));
+                            let ty = Rc::new(SemTypeContext::list_definition_from_idx(*idx));
                             return Ok(ty);
                         }
                         None => {
@@ -76,20 +171,14 @@ impl<'a> ToSemTypeConverter<'a> {
                             builder.list_runtype_ref_memo.insert(name.clone(), idx);
                             builder.list_definitions.push(None);
 
-                            let items = match items {
-                                Some(items) => Some(self.convert_to_sem_type(items, builder)?),
-                                None => None,
-                            };
-                            let prefix_items: Vec<Rc<ComplexSemType>> = prefix_items
-                                .iter()
-                                .map(|v| self.convert_to_sem_type(v, builder))
-                                .collect::<Result<_>>()?;
-
-                            builder.list_definitions[idx] = Some(Rc::new(ListAtomic {
+                            let mut atomics = self.convert_tuple_atomics(
                                 prefix_items,
-                                // todo: should be unknown?
-                                items: items.unwrap_or(SemTypeContext::never().into()),
-                            }));
+                                optional_items,
+                                items.as_deref(),
+                                suffix_items,
+                                builder,
+                            )?;
+                            builder.list_definitions[idx] = atomics.pop().map(Rc::new);
 
                             let ty = Rc::new(SemTypeContext::list_definition_from_idx(idx));
 
@@ -112,38 +201,9 @@ impl<'a> ToSemTypeConverter<'a> {
                             let idx = builder.mapping_definitions.len();
                             builder.mapping_runtype_ref_memo.insert(name.clone(), idx);
                             builder.mapping_definitions.push(None);
-                            let vs: BTreeMap<String, Rc<SemType>> = vs
-                                .iter()
-                                .map(|(k, v)| match v {
-                                    Optionality::Optional(v) => {
-                                        self.convert_to_sem_type(v, builder).and_then(|v| {
-                                            SemTypeContext::make_optional(v).map(|v| (k.clone(), v))
-                                        })
-                                    }
-                                    Optionality::Required(v) => {
-                                        self.convert_to_sem_type(v, builder).map(|v| (k.clone(), v))
-                                    }
-                                })
-                                .collect::<Result<_>>()?;
-                            let mut indexed_props_acc = None;
-                            if let Some(it) = indexed_properties {
-                                let v = match &it.value {
-                                    Optionality::Optional(v) => self
-                                        .convert_to_sem_type(v, builder)
-                                        .and_then(SemTypeContext::make_optional)?,
-                                    Optionality::Required(v) => {
-                                        self.convert_to_sem_type(v, builder)?
-                                    }
-                                };
-                                let k = self.convert_to_sem_type(&it.key, builder)?;
-                                let t = IndexedPropertiesAtomic { key: k, value: v };
-                                indexed_props_acc = Some(t);
-                            }
-
-                            builder.mapping_definitions[idx] = Some(Rc::new(MappingAtomicType {
-                                vs,
-                                indexed_properties: indexed_props_acc,
-                            }));
+                            let atomic =
+                                self.convert_mapping_atomic(vs, indexed_properties, builder)?;
+                            builder.mapping_definitions[idx] = Some(Rc::new(atomic));
                             let ty = Rc::new(SemTypeContext::mapping_definition_from_idx(idx));
                             return Ok(ty);
                         }
@@ -166,10 +226,10 @@ impl<'a> ToSemTypeConverter<'a> {
 
                             builder.map_definitions[idx] = Some(Rc::new(MappingAtomicType {
                                 vs: BTreeMap::new(),
-                                indexed_properties: Some(IndexedPropertiesAtomic {
+                                indexed_properties: vec![IndexedPropertiesAtomic {
                                     key: k,
                                     value: v,
-                                }),
+                                }],
                             }));
                             let ty = Rc::new(SemTypeContext::map_definition_from_idx(idx));
                             return Ok(ty);
@@ -193,6 +253,7 @@ impl<'a> ToSemTypeConverter<'a> {
                             builder.set_definitions[idx] = Some(Rc::new(ListAtomic {
                                 prefix_items: vec![],
                                 items: v,
+                                suffix_items: vec![],
                             }));
                             let ty = Rc::new(SemTypeContext::set_definition_from_idx(idx));
                             return Ok(ty);
@@ -243,6 +304,12 @@ impl<'a> ToSemTypeConverter<'a> {
                 )))
                 .into(),
             ),
+            RuntypeKind::NumberRange(range) => {
+                match NumberRepresentationOrFormat::from_range(range) {
+                    Some(value) => Ok(SemTypeContext::number_const(value).into()),
+                    None => Ok(SemTypeContext::never().into()),
+                }
+            }
             RuntypeKind::TplLitType(tpl) => {
                 Ok(SemTypeContext::string_const(StringLitOrFormat::Tpl(tpl.clone())).into())
             }
@@ -250,58 +317,86 @@ impl<'a> ToSemTypeConverter<'a> {
                 vs,
                 indexed_properties,
             } => {
-                let vs = vs
-                    .iter()
-                    .map(|(k, v)| match v {
-                        Optionality::Optional(v) => self
-                            .convert_to_sem_type(v, builder)
-                            .and_then(|v| SemTypeContext::make_optional(v).map(|v| (k.clone(), v))),
-                        Optionality::Required(v) => {
-                            self.convert_to_sem_type(v, builder).map(|v| (k.clone(), v))
-                        }
-                    })
-                    .collect::<Result<_>>()?;
-                let mut indexed_props_acc = None;
-                if let Some(it) = indexed_properties {
-                    let v = match &it.value {
-                        Optionality::Optional(v) => self
-                            .convert_to_sem_type(v, builder)
-                            .and_then(SemTypeContext::make_optional)?,
-                        Optionality::Required(v) => self.convert_to_sem_type(v, builder)?,
-                    };
-                    let k = self.convert_to_sem_type(&it.key, builder)?;
-                    let t = IndexedPropertiesAtomic { key: k, value: v };
-                    indexed_props_acc = Some(t);
-                }
-
-                Ok(builder.mapping_definition(vs, indexed_props_acc).into())
+                let idx = builder.mapping_definitions.len();
+                builder.mapping_definitions.push(None);
+                let ty = Rc::new(SemTypeContext::mapping_definition_from_idx(idx));
+                let seen = self.enter_constructor(schema, ty.clone());
+                let atomic = self.convert_mapping_atomic(vs, indexed_properties, builder);
+                self.seen_refs = seen;
+                builder.mapping_definitions[idx] = Some(Rc::new(atomic?));
+                Ok(ty)
             }
             RuntypeKind::Array(items) => {
-                let items = self.convert_to_sem_type(items, builder)?;
-                Ok(builder.array(items).into())
+                let idx = builder.list_definitions.len();
+                builder.list_definitions.push(None);
+                let ty = Rc::new(SemTypeContext::list_definition_from_idx(idx));
+                let seen = self.enter_constructor(schema, ty.clone());
+                let items = self.convert_to_sem_type(items, builder);
+                self.seen_refs = seen;
+                builder.list_definitions[idx] = Some(Rc::new(ListAtomic {
+                    prefix_items: vec![],
+                    items: items?,
+                    suffix_items: vec![],
+                }));
+                Ok(ty)
             }
             RuntypeKind::Tuple {
                 prefix_items,
+                optional_items,
                 items,
+                suffix_items,
             } => {
-                let items = match items {
-                    Some(items) => Some(self.convert_to_sem_type(items, builder)?),
-                    None => None,
-                };
-                let prefix_items: Vec<Rc<ComplexSemType>> = prefix_items
-                    .iter()
-                    .map(|v| self.convert_to_sem_type(v, builder))
-                    .collect::<Result<_>>()?;
-                Ok(builder.tuple(prefix_items, items).into())
+                // one list per number of optional elements that are present
+                let first_idx = builder.list_definitions.len();
+                let count = optional_items.len() + 1;
+                let mut ty = Rc::new(SemTypeContext::never());
+                for idx in first_idx..first_idx + count {
+                    builder.list_definitions.push(None);
+                    ty = ty.union(&Rc::new(SemTypeContext::list_definition_from_idx(idx)))?;
+                }
+                let seen = self.enter_constructor(schema, ty.clone());
+                let atomics = self.convert_tuple_atomics(
+                    prefix_items,
+                    optional_items,
+                    items.as_deref(),
+                    suffix_items,
+                    builder,
+                );
+                self.seen_refs = seen;
+                for (i, atomic) in atomics?.into_iter().enumerate() {
+                    builder.list_definitions[first_idx + i] = Some(Rc::new(atomic));
+                }
+                Ok(ty)
             }
             RuntypeKind::Map(k, v) => {
-                let k = self.convert_to_sem_type(k, builder)?;
-                let v = self.convert_to_sem_type(v, builder)?;
-                Ok(builder.map(k, v).into())
+                let idx = builder.map_definitions.len();
+                builder.map_definitions.push(None);
+                let ty = Rc::new(SemTypeContext::map_definition_from_idx(idx));
+                let seen = self.enter_constructor(schema, ty.clone());
+                let kv = self
+                    .convert_to_sem_type(k, builder)
+                    .and_then(|k| Ok((k, self.convert_to_sem_type(v, builder)?)));
+                self.seen_refs = seen;
+                let (k, v) = kv?;
+                builder.map_definitions[idx] = Some(Rc::new(MappingAtomicType {
+                    vs: BTreeMap::new(),
+                    indexed_properties: vec![IndexedPropertiesAtomic { key: k, value: v }],
+                }));
+                Ok(ty)
             }
             RuntypeKind::Set(v) => {
-                let v = self.convert_to_sem_type(v, builder)?;
-                Ok(builder.set(v).into())
+                let idx = builder.set_definitions.len();
+                builder.set_definitions.push(None);
+                let ty = Rc::new(SemTypeContext::set_definition_from_idx(idx));
+                let seen = self.enter_constructor(schema, ty.clone());
+                let v = self.convert_to_sem_type(v, builder);
+                self.seen_refs = seen;
+                builder.set_definitions[idx] = Some(Rc::new(ListAtomic {
+                    prefix_items: vec![],
+                    items: v?,
+                    suffix_items: vec![],
+                }));
+                Ok(ty)
             }
             RuntypeKind::Const(cons) => match cons {
                 RuntypeConst::Bool(b) => Ok(SemTypeContext::boolean_const(*b).into()),
@@ -321,9 +416,18 @@ impl<'a> ToSemTypeConverter<'a> {
                 let chd = self.convert_to_sem_type(it, builder)?;
                 Ok(chd.complement()?)
             }
-            RuntypeKind::Function => {
+            RuntypeKind::Function { .. } => {
                 bail!("function runtype cannot be converted to semtype")
             }
+            RuntypeKind::Promise(_) => {
+                bail!("promise runtype cannot be converted to semtype")
+            }
+            RuntypeKind::InstanceOf(_) => {
+                bail!("instanceof runtype cannot be converted to semtype")
+            }
+            RuntypeKind::TypeParameter(_) => {
+                bail!("type parameter cannot be converted to semtype")
+            }
             RuntypeKind::Undefined => Ok(SemTypeContext::undefined().into()),
             RuntypeKind::Void => Ok(SemTypeContext::void().into()),
         }
@@ -464,7 +464,7 @@ impl SemTypeContext {
     pub fn mapping_definition(
         &mut self,
         vs: BTreeMap<String, Rc<SemType>>,
-        indexed_properties: Option<IndexedPropertiesAtomic>,
+        indexed_properties: Vec<IndexedPropertiesAtomic>,
     ) -> SemType {
         let idx = self.mapping_definitions.len();
         self.mapping_definitions.push(Some(
@@ -494,6 +494,7 @@ impl SemTypeContext {
         let atom = ListAtomic {
             prefix_items: vec![],
             items: v,
+            suffix_items: vec![],
         };
         self.list_definition(Rc::new(atom))
     }
@@ -502,6 +503,7 @@ impl SemTypeContext {
             prefix_items,
             // todo: should be unknown?
             items: items.unwrap_or(Self::never().into()),
+            suffix_items: vec![],
         };
         self.list_definition(Rc::new(atom))
     }
@@ -516,7 +518,7 @@ impl SemTypeContext {
     pub fn map_definition(
         &mut self,
         vs: BTreeMap<String, Rc<SemType>>,
-        indexed_properties: Option<IndexedPropertiesAtomic>,
+        indexed_properties: Vec<IndexedPropertiesAtomic>,
     ) -> SemType {
         let idx = self.map_definitions.len();
         self.map_definitions.push(Some(
@@ -547,7 +549,7 @@ impl SemTypeContext {
     pub fn map(&mut self, k: Rc<SemType>, v: Rc<SemType>) -> SemType {
         let atom = MappingAtomicType {
             vs: BTreeMap::new(),
-            indexed_properties: Some(IndexedPropertiesAtomic { key: k, value: v }),
+            indexed_properties: vec![IndexedPropertiesAtomic { key: k, value: v }],
         };
         self.map_definition(atom.vs, atom.indexed_properties)
     }
@@ -556,6 +558,7 @@ impl SemTypeContext {
         let atom = ListAtomic {
             prefix_items: vec![],
             items: v,
+            suffix_items: vec![],
         };
         self.set_definition(Rc::new(atom))
     }
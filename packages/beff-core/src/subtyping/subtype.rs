@@ -1,14 +1,17 @@
 use crate::{
     ast::{
         json::N,
-        runtype::{CustomFormat, TplLitType, TypedArrayKind},
+        runtype::{
+            CustomFormat, NumberBound, NumberRange, StringIntrinsic, TplLitType, TplLitTypeItem,
+            TypedArrayKind,
+        },
     },
     subtyping::{
         IsEmptyStatus,
         dnf::{dnf_map_is_empty, dnf_mapping_is_empty},
     },
 };
-use anyhow::{Result, bail};
+use anyhow::Result;
 use std::{collections::BTreeSet, rc::Rc};
 
 use super::{
@@ -101,6 +104,21 @@ impl SubType {
             }
             return SubType::True(SubTypeTag::String);
         }
+        // templates can match every string, e.g. `${string}` or `a${string}` | `${string}`
+        let templates = values
+            .iter()
+            .filter(|it| !matches!(it, StringLitOrFormat::Format(_)))
+            .collect::<Vec<_>>();
+        if templates
+            .iter()
+            .any(|it| it.single_string_const().is_none())
+            && StringLitOrFormat::tpl_cover_every_string(&templates)
+        {
+            if allowed {
+                return SubType::True(SubTypeTag::String);
+            }
+            return SubType::False(SubTypeTag::String);
+        }
         SubType::Proper(ProperSubtype::String { allowed, values }.into())
     }
     fn typed_array_subtype(allowed: bool, values: Vec<TypedArrayKind>) -> SubType {
@@ -147,33 +165,449 @@ impl CustomFormat {
     }
 }
 
-impl TplLitType {
-    fn is_subtype(&self, other: &TplLitType) -> Result<bool> {
-        match (self.0.as_slice(), other.0.as_slice()) {
-            ([a], [b]) => Ok(a == b),
-            _ => bail!(format!(
-                "only single-item TplLitType subtype checks are supported",
-            )),
+/// A set of chars of the template literal automata: the chars in `ranges`, or the chars
+/// outside of them when `negated`.
+#[derive(Clone, Debug)]
+struct CharClass {
+    ranges: Vec<(char, char)>,
+    negated: bool,
+}
+
+impl CharClass {
+    fn contains(&self, c: char) -> bool {
+        self.ranges.iter().any(|(lo, hi)| (*lo..=*hi).contains(&c)) != self.negated
+    }
+}
+
+/// The regular expression of a template literal item, mirroring `TplLitTypeItem::regex_expr`.
+#[derive(Clone, Debug)]
+enum TplRegex {
+    Class(CharClass),
+    Seq(Vec<TplRegex>),
+    Alt(Vec<TplRegex>),
+    Star(Box<TplRegex>),
+}
+
+impl TplRegex {
+    fn chars(ranges: &[(char, char)]) -> TplRegex {
+        TplRegex::Class(CharClass {
+            ranges: ranges.to_vec(),
+            negated: false,
+        })
+    }
+    fn chars_except(ranges: &[(char, char)]) -> TplRegex {
+        TplRegex::Class(CharClass {
+            ranges: ranges.to_vec(),
+            negated: true,
+        })
+    }
+    fn literal(s: &str) -> TplRegex {
+        TplRegex::Seq(s.chars().map(|c| TplRegex::chars(&[(c, c)])).collect())
+    }
+    fn optional(self) -> TplRegex {
+        TplRegex::Alt(vec![self, TplRegex::Seq(vec![])])
+    }
+    fn many(self) -> TplRegex {
+        TplRegex::Star(Box::new(self))
+    }
+    fn at_least_one(self) -> TplRegex {
+        TplRegex::Seq(vec![self.clone(), self.many()])
+    }
+
+    fn of_item(item: &TplLitTypeItem) -> TplRegex {
+        let digit = || TplRegex::chars(&[('0', '9')]);
+        let sign = || TplRegex::chars(&[('+', '+'), ('-', '-')]).optional();
+        let radix_integer = |x: char, digits: &[(char, char)]| {
+            let upper = x.to_ascii_uppercase();
+            TplRegex::Seq(vec![
+                TplRegex::literal("0"),
+                TplRegex::chars(&[(x, x), (upper, upper)]),
+                TplRegex::chars(digits).at_least_one(),
+            ])
+        };
+        let radix_integers = || {
+            vec![
+                radix_integer('x', &[('0', '9'), ('a', 'f'), ('A', 'F')]),
+                radix_integer('o', &[('0', '7')]),
+                radix_integer('b', &[('0', '1')]),
+            ]
+        };
+        match item {
+            TplLitTypeItem::String => TplRegex::chars_except(&[]).many(),
+            TplLitTypeItem::IntrinsicString(intrinsic) => match intrinsic {
+                StringIntrinsic::Uppercase => TplRegex::chars_except(&[('a', 'z')]).many(),
+                StringIntrinsic::Lowercase => TplRegex::chars_except(&[('A', 'Z')]).many(),
+                StringIntrinsic::Capitalize => TplRegex::Seq(vec![
+                    TplRegex::chars_except(&[('a', 'z')]),
+                    TplRegex::chars_except(&[]).many(),
+                ])
+                .optional(),
+                StringIntrinsic::Uncapitalize => TplRegex::Seq(vec![
+                    TplRegex::chars_except(&[('A', 'Z')]),
+                    TplRegex::chars_except(&[]).many(),
+                ])
+                .optional(),
+            },
+            TplLitTypeItem::Number => {
+                let mantissa = TplRegex::Alt(vec![
+                    TplRegex::Seq(vec![
+                        digit().at_least_one(),
+                        TplRegex::Seq(vec![TplRegex::literal("."), digit().many()]).optional(),
+                    ]),
+                    TplRegex::Seq(vec![TplRegex::literal("."), digit().at_least_one()]),
+                ]);
+                let exponent = TplRegex::Seq(vec![
+                    TplRegex::chars(&[('e', 'e'), ('E', 'E')]),
+                    sign(),
+                    digit().at_least_one(),
+                ]);
+                let mut alternatives = vec![
+                    TplRegex::Seq(vec![sign(), mantissa, exponent.optional()]),
+                    TplRegex::Seq(vec![sign(), TplRegex::literal("Infinity")]),
+                ];
+                alternatives.extend(radix_integers());
+                TplRegex::Alt(alternatives)
+            }
+            TplLitTypeItem::BigInt => {
+                let mut alternatives = vec![TplRegex::Seq(vec![
+                    TplRegex::literal("-").optional(),
+                    digit().at_least_one(),
+                ])];
+                alternatives.extend(radix_integers());
+                TplRegex::Alt(alternatives)
+            }
+            TplLitTypeItem::Boolean => {
+                TplRegex::Alt(vec![TplRegex::literal("true"), TplRegex::literal("false")])
+            }
+            TplLitTypeItem::StringConst(s) => TplRegex::literal(s),
+            TplLitTypeItem::OneOf(vs) => TplRegex::Alt(vs.iter().map(TplRegex::of_item).collect()),
         }
     }
 }
 
+/// A nondeterministic automaton accepting the strings matched by a template literal type.
+struct TplNfa {
+    transitions: Vec<Vec<(CharClass, usize)>>,
+    epsilons: Vec<Vec<usize>>,
+    start: usize,
+    accept: usize,
+}
+
+impl TplNfa {
+    fn new(tpl: &TplLitType) -> TplNfa {
+        let mut nfa = TplNfa {
+            transitions: vec![],
+            epsilons: vec![],
+            start: 0,
+            accept: 0,
+        };
+        nfa.start = nfa.add_state();
+        nfa.accept = nfa.add_state();
+        let regex = TplRegex::Seq(tpl.0.iter().map(TplRegex::of_item).collect());
+        nfa.compile(&regex, nfa.start, nfa.accept);
+        nfa
+    }
+
+    fn add_state(&mut self) -> usize {
+        self.transitions.push(vec![]);
+        self.epsilons.push(vec![]);
+        self.transitions.len() - 1
+    }
+
+    fn compile(&mut self, regex: &TplRegex, from: usize, to: usize) {
+        match regex {
+            TplRegex::Class(class) => self.transitions[from].push((class.clone(), to)),
+            TplRegex::Seq(items) => {
+                let mut current = from;
+                for item in items {
+                    let next = self.add_state();
+                    self.compile(item, current, next);
+                    current = next;
+                }
+                self.epsilons[current].push(to);
+            }
+            TplRegex::Alt(items) => {
+                for item in items {
+                    self.compile(item, from, to);
+                }
+            }
+            TplRegex::Star(inner) => {
+                let hub = self.add_state();
+                self.epsilons[from].push(hub);
+                self.compile(inner, hub, hub);
+                self.epsilons[hub].push(to);
+            }
+        }
+    }
+
+    fn closure(&self, states: impl IntoIterator<Item = usize>) -> BTreeSet<usize> {
+        let mut acc = BTreeSet::new();
+        let mut stack = states.into_iter().collect::<Vec<_>>();
+        while let Some(state) = stack.pop() {
+            if acc.insert(state) {
+                stack.extend(self.epsilons[state].iter().copied());
+            }
+        }
+        acc
+    }
+
+    fn step(&self, states: &BTreeSet<usize>, c: char) -> BTreeSet<usize> {
+        self.closure(states.iter().flat_map(|state| {
+            self.transitions[*state]
+                .iter()
+                .filter(|(class, _)| class.contains(c))
+                .map(|(_, target)| *target)
+        }))
+    }
+
+    /// The chars where the classes of the automaton start or stop matching.
+    fn boundaries(&self) -> impl Iterator<Item = char> + '_ {
+        self.transitions
+            .iter()
+            .flatten()
+            .flat_map(|(class, _)| class.ranges.iter())
+            .flat_map(|(lo, hi)| {
+                // skip the surrogates, they are not chars
+                let after_hi = char::from_u32(*hi as u32 + 1).or_else(|| char::from_u32(0xE000));
+                std::iter::once(*lo).chain(after_hi)
+            })
+    }
+}
+
+/// Whether some string drives the automata, run side by side, to states whose acceptance
+/// satisfies `accepts`.
+fn tpl_automata_find(automata: &[TplNfa], accepts: impl Fn(&[bool]) -> bool) -> bool {
+    // the chars between two consecutive boundaries are matched by the same classes,
+    // so one representative of each range is enough
+    let mut alphabet = BTreeSet::from(['\0']);
+    for nfa in automata {
+        alphabet.extend(nfa.boundaries());
+    }
+    let start: Vec<BTreeSet<usize>> = automata
+        .iter()
+        .map(|nfa| nfa.closure([nfa.start]))
+        .collect();
+    let mut seen = BTreeSet::new();
+    let mut stack = vec![start];
+    while let Some(config) = stack.pop() {
+        if seen.contains(&config) {
+            continue;
+        }
+        let accepting = automata
+            .iter()
+            .zip(&config)
+            .map(|(nfa, states)| states.contains(&nfa.accept))
+            .collect::<Vec<_>>();
+        if accepts(&accepting) {
+            return true;
+        }
+        for c in &alphabet {
+            stack.push(
+                automata
+                    .iter()
+                    .zip(&config)
+                    .map(|(nfa, states)| nfa.step(states, *c))
+                    .collect(),
+            );
+        }
+        seen.insert(config);
+    }
+    false
+}
+
 #[derive(PartialEq, Eq, Hash, Debug, Ord, PartialOrd, Clone)]
 pub enum StringLitOrFormat {
     Format(CustomFormat),
     Tpl(TplLitType),
+    /// The strings matched by every template of `all_of` and by none of `none_of`, produced
+    /// by intersections and differences of templates that overlap without one containing the other.
+    Refined {
+        all_of: BTreeSet<TplLitType>,
+        none_of: BTreeSet<TplLitType>,
+    },
+}
+
+/// The templates a string must match and the templates it must not match.
+type TplConstraints<'a> = (Vec<&'a TplLitType>, Vec<&'a TplLitType>);
+
+impl StringLitOrFormat {
+    fn refined(all_of: BTreeSet<TplLitType>, none_of: BTreeSet<TplLitType>) -> StringLitOrFormat {
+        if none_of.is_empty() && all_of.len() == 1 {
+            let tpl = all_of.into_iter().next().expect("we just checked len");
+            return StringLitOrFormat::Tpl(tpl);
+        }
+        StringLitOrFormat::Refined { all_of, none_of }
+    }
+
+    fn tpl_constraints(&self) -> Option<TplConstraints<'_>> {
+        match self {
+            StringLitOrFormat::Format(_) => None,
+            StringLitOrFormat::Tpl(tpl) => Some((vec![tpl], vec![])),
+            StringLitOrFormat::Refined { all_of, none_of } => {
+                Some((all_of.iter().collect(), none_of.iter().collect()))
+            }
+        }
+    }
+
+    fn single_string_const(&self) -> Option<&str> {
+        match self {
+            StringLitOrFormat::Tpl(TplLitType(items)) => match items.as_slice() {
+                [TplLitTypeItem::StringConst(s)] => Some(s),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Whether some string satisfies `accepts`, given whether each value matches it.
+    /// Formats are opaque and match no string here, the callers handle them.
+    fn tpl_find(values: &[&StringLitOrFormat], accepts: impl Fn(&[bool]) -> bool) -> bool {
+        let mut automata = vec![];
+        let mut sizes = vec![];
+        for value in values {
+            let (all_of, none_of) = value.tpl_constraints().unwrap_or_default();
+            sizes.push((all_of.len(), none_of.len()));
+            automata.extend(
+                all_of
+                    .iter()
+                    .chain(none_of.iter())
+                    .map(|it| TplNfa::new(it)),
+            );
+        }
+        tpl_automata_find(&automata, |accepting| {
+            let mut offset = 0;
+            let matched = sizes
+                .iter()
+                .map(|(all_of, none_of)| {
+                    let (all, none) =
+                        accepting[offset..offset + all_of + none_of].split_at(*all_of);
+                    offset += all_of + none_of;
+                    !all.is_empty() && all.iter().all(|it| *it) && !none.iter().any(|it| *it)
+                })
+                .collect::<Vec<_>>();
+            accepts(&matched)
+        })
+    }
+
+    fn tpl_matches(&self, s: &str) -> bool {
+        match self.tpl_constraints() {
+            Some((all_of, none_of)) => {
+                all_of.iter().all(|it| it.matches(s)) && !none_of.iter().any(|it| it.matches(s))
+            }
+            None => false,
+        }
+    }
+
+    fn tpl_is_empty(&self) -> bool {
+        !Self::tpl_find(&[self], |matched| matched[0])
+    }
+
+    fn tpl_overlaps(&self, other: &StringLitOrFormat) -> bool {
+        Self::tpl_find(&[self, other], |matched| matched[0] && matched[1])
+    }
+
+    /// Whether the values together match every string.
+    fn tpl_cover_every_string(values: &[&StringLitOrFormat]) -> bool {
+        !Self::tpl_find(values, |matched| !matched.iter().any(|it| *it))
+    }
 }
 
-trait SubtypeCheck {
+trait SubtypeCheck: Clone {
     fn is_subtype(&self, other: &Self) -> Result<bool>;
+
+    /// The values in both, as a union. By default values that do not contain one another are disjoint.
+    fn meet(&self, other: &Self) -> Result<Vec<Self>> {
+        meet_by_inclusion(self, other)
+    }
+
+    /// The values in `self` but not in `other`, as a union.
+    fn minus(&self, other: &Self) -> Result<Vec<Self>> {
+        minus_by_inclusion(self, other)
+    }
+}
+
+fn meet_by_inclusion<K: SubtypeCheck>(a: &K, b: &K) -> Result<Vec<K>> {
+    if a.is_subtype(b)? {
+        Ok(vec![a.clone()])
+    } else if b.is_subtype(a)? {
+        Ok(vec![b.clone()])
+    } else {
+        Ok(vec![])
+    }
+}
+
+fn minus_by_inclusion<K: SubtypeCheck>(a: &K, b: &K) -> Result<Vec<K>> {
+    if a.is_subtype(b)? {
+        Ok(vec![])
+    } else {
+        Ok(vec![a.clone()])
+    }
 }
 
 impl SubtypeCheck for StringLitOrFormat {
     fn is_subtype(&self, other: &StringLitOrFormat) -> Result<bool> {
+        if self == other {
+            return Ok(true);
+        }
         match (self, other) {
             (StringLitOrFormat::Format(a), StringLitOrFormat::Format(b)) => Ok(a.is_subtype(b)),
-            (StringLitOrFormat::Tpl(a), StringLitOrFormat::Tpl(b)) => a.is_subtype(b),
-            _ => Ok(self == other),
+            // formats are opaque strings, only contained in templates matching every string
+            (StringLitOrFormat::Format(_), _) => Ok(Self::tpl_cover_every_string(&[other])),
+            (_, StringLitOrFormat::Format(_)) => Ok(self.tpl_is_empty()),
+            _ => match self.single_string_const() {
+                Some(s) => Ok(other.tpl_matches(s)),
+                None => Ok(!Self::tpl_find(&[self, other], |matched| {
+                    matched[0] && !matched[1]
+                })),
+            },
+        }
+    }
+
+    fn meet(&self, other: &StringLitOrFormat) -> Result<Vec<StringLitOrFormat>> {
+        if self.is_subtype(other)? {
+            return Ok(vec![self.clone()]);
+        }
+        if other.is_subtype(self)? {
+            return Ok(vec![other.clone()]);
+        }
+        match (self.tpl_constraints(), other.tpl_constraints()) {
+            (Some((all_of, none_of)), Some((other_all_of, other_none_of)))
+                if self.tpl_overlaps(other) =>
+            {
+                Ok(vec![Self::refined(
+                    all_of.into_iter().chain(other_all_of).cloned().collect(),
+                    none_of.into_iter().chain(other_none_of).cloned().collect(),
+                )])
+            }
+            _ => Ok(vec![]),
+        }
+    }
+
+    fn minus(&self, other: &StringLitOrFormat) -> Result<Vec<StringLitOrFormat>> {
+        if self.is_subtype(other)? {
+            return Ok(vec![]);
+        }
+        match (self.tpl_constraints(), other.tpl_constraints()) {
+            (Some((all_of, none_of)), Some((other_all_of, other_none_of)))
+                if self.tpl_overlaps(other) =>
+            {
+                // a - (p1 & p2 - n1 - n2) = (a - p1) | (a - p2) | (a & n1) | (a & n2)
+                let mut acc = vec![];
+                for excluded in other_all_of {
+                    let it = Self::refined(
+                        all_of.iter().copied().cloned().collect(),
+                        none_of.iter().copied().chain([excluded]).cloned().collect(),
+                    );
+                    if !it.tpl_is_empty() {
+                        acc.push(it);
+                    }
+                }
+                for included in other_none_of {
+                    acc.extend(self.meet(&StringLitOrFormat::Tpl(included.clone()))?);
+                }
+                Ok(acc)
+            }
+            _ => Ok(vec![self.clone()]),
         }
     }
 }
@@ -182,6 +616,122 @@ impl SubtypeCheck for StringLitOrFormat {
 pub enum NumberRepresentationOrFormat {
     Lit(NumberRepresentation),
     Format(CustomFormat),
+    /// Always in the form built by `NumberRepresentationOrFormat::range`.
+    Range(NumberRange),
+}
+
+/// A range bound as its value and whether it is exclusive.
+type Bound = (f64, bool);
+
+fn range_bounds(range: &NumberRange) -> (Option<Bound>, Option<Bound>) {
+    let bound = |it: &NumberBound| (it.value.to_f64(), it.exclusive);
+    (
+        range.minimum.as_ref().map(bound),
+        range.maximum.as_ref().map(bound),
+    )
+}
+
+fn tighter_minimum(a: Option<Bound>, b: Option<Bound>) -> Option<Bound> {
+    match (a, b) {
+        (None, it) | (it, None) => it,
+        (Some(a), Some(b)) if a.0 == b.0 => Some((a.0, a.1 || b.1)),
+        (Some(a), Some(b)) => Some(if a.0 > b.0 { a } else { b }),
+    }
+}
+
+fn tighter_maximum(a: Option<Bound>, b: Option<Bound>) -> Option<Bound> {
+    match (a, b) {
+        (None, it) | (it, None) => it,
+        (Some(a), Some(b)) if a.0 == b.0 => Some((a.0, a.1 || b.1)),
+        (Some(a), Some(b)) => Some(if a.0 < b.0 { a } else { b }),
+    }
+}
+
+/// Whether the minimum `outer` lets through every number the minimum `inner` lets through.
+fn minimum_contains(outer: Option<Bound>, inner: Option<Bound>) -> bool {
+    match (outer, inner) {
+        (None, _) => true,
+        (Some(_), None) => false,
+        (Some(outer), Some(inner)) => {
+            outer.0 < inner.0 || (outer.0 == inner.0 && (!outer.1 || inner.1))
+        }
+    }
+}
+
+fn maximum_contains(outer: Option<Bound>, inner: Option<Bound>) -> bool {
+    match (outer, inner) {
+        (None, _) => true,
+        (Some(_), None) => false,
+        (Some(outer), Some(inner)) => {
+            outer.0 > inner.0 || (outer.0 == inner.0 && (!outer.1 || inner.1))
+        }
+    }
+}
+
+impl NumberRepresentationOrFormat {
+    /// The numbers between the bounds, in a canonical form: integer ranges have inclusive integer
+    /// bounds, a range of a single number is that literal, and an empty range is `None`.
+    fn range(integer: bool, minimum: Option<Bound>, maximum: Option<Bound>) -> Option<Self> {
+        let (minimum, maximum) = if integer {
+            (
+                minimum.map(|(v, exclusive)| {
+                    (if exclusive { v.floor() + 1.0 } else { v.ceil() }, false)
+                }),
+                maximum.map(|(v, exclusive)| {
+                    (if exclusive { v.ceil() - 1.0 } else { v.floor() }, false)
+                }),
+            )
+        } else {
+            (minimum, maximum)
+        };
+        if let (Some((lo, lo_exclusive)), Some((hi, hi_exclusive))) = (minimum, maximum) {
+            if lo > hi || (lo == hi && (lo_exclusive || hi_exclusive)) {
+                return None;
+            }
+            if lo == hi {
+                return Some(NumberRepresentationOrFormat::Lit(N::parse_f64(lo)));
+            }
+        }
+        let bound = |(v, exclusive): Bound| NumberBound {
+            value: N::parse_f64(v),
+            exclusive,
+        };
+        Some(NumberRepresentationOrFormat::Range(NumberRange {
+            integer,
+            minimum: minimum.map(bound),
+            maximum: maximum.map(bound),
+        }))
+    }
+
+    pub fn from_range(range: &NumberRange) -> Option<Self> {
+        let (minimum, maximum) = range_bounds(range);
+        Self::range(range.integer, minimum, maximum)
+    }
+
+    /// The numbers of `range` outside of the numbers between `minimum` and `maximum`.
+    fn range_outside(
+        range: &NumberRange,
+        minimum: Option<Bound>,
+        maximum: Option<Bound>,
+    ) -> Vec<Self> {
+        let (range_minimum, range_maximum) = range_bounds(range);
+        let mut acc = vec![];
+        if let Some((v, exclusive)) = minimum {
+            acc.extend(Self::range(
+                range.integer,
+                range_minimum,
+                tighter_maximum(range_maximum, Some((v, !exclusive))),
+            ));
+        }
+        if let Some((v, exclusive)) = maximum {
+            acc.extend(Self::range(
+                range.integer,
+                tighter_minimum(range_minimum, Some((v, !exclusive))),
+                range_maximum,
+            ));
+        }
+        acc
+    }
 }
 
 impl SubtypeCheck for NumberRepresentationOrFormat {
@@ -190,9 +740,72 @@ impl SubtypeCheck for NumberRepresentationOrFormat {
             (NumberRepresentationOrFormat::Format(a), NumberRepresentationOrFormat::Format(b)) => {
                 Ok(a.is_subtype(b))
             }
+            (NumberRepresentationOrFormat::Lit(n), NumberRepresentationOrFormat::Range(range)) => {
+                Ok(range.contains(n.to_f64()))
+            }
+            (NumberRepresentationOrFormat::Range(a), NumberRepresentationOrFormat::Range(b)) => {
+                // a range that is not a single number holds some non-integer, unless it is integer
+                if b.integer && !a.integer {
+                    return Ok(false);
+                }
+                let (a_minimum, a_maximum) = range_bounds(a);
+                let (b_minimum, b_maximum) = range_bounds(b);
+                Ok(
+                    minimum_contains(b_minimum, a_minimum)
+                        && maximum_contains(b_maximum, a_maximum),
+                )
+            }
             _ => Ok(self == other),
         }
     }
+
+    fn meet(
+        &self,
+        other: &NumberRepresentationOrFormat,
+    ) -> Result<Vec<NumberRepresentationOrFormat>> {
+        match (self, other) {
+            (NumberRepresentationOrFormat::Range(a), NumberRepresentationOrFormat::Range(b)) => {
+                let (a_minimum, a_maximum) = range_bounds(a);
+                let (b_minimum, b_maximum) = range_bounds(b);
+                Ok(Self::range(
+                    a.integer || b.integer,
+                    tighter_minimum(a_minimum, b_minimum),
+                    tighter_maximum(a_maximum, b_maximum),
+                )
+                .into_iter()
+                .collect())
+            }
+            _ => meet_by_inclusion(self, other),
+        }
+    }
+
+    fn minus(
+        &self,
+        other: &NumberRepresentationOrFormat,
+    ) -> Result<Vec<NumberRepresentationOrFormat>> {
+        if self.is_subtype(other)? {
+            return Ok(vec![]);
+        }
+        match (self, other) {
+            // the numbers of a range that are not integers are not a range, the range is kept whole
+            (NumberRepresentationOrFormat::Range(a), NumberRepresentationOrFormat::Range(b))
+                if a.integer || !b.integer =>
+            {
+                let (b_minimum, b_maximum) = range_bounds(b);
+                if self.meet(other)?.is_empty() {
+                    return Ok(vec![self.clone()]);
+                }
+                Ok(Self::range_outside(a, b_minimum, b_maximum))
+            }
+            (NumberRepresentationOrFormat::Range(a), NumberRepresentationOrFormat::Lit(n))
+                if a.contains(n.to_f64()) =>
+            {
+                let point = Some((n.to_f64(), false));
+                Ok(Self::range_outside(a, point, point))
+            }
+            _ => minus_by_inclusion(self, other),
+        }
+    }
 }
 
 #[derive(PartialEq, Eq, Hash, Debug, Ord, PartialOrd, Clone, Copy)]
@@ -281,23 +894,14 @@ fn sub_vec_intersect<K: SubtypeCheck + Clone + PartialEq + Ord>(
 ) -> Result<Vec<K>> {
     let mut acc = vec![];
 
-    // For each item in v1, check if there's a compatible item in v2
+    // For each item in v1, collect what it has in common with each item in v2
     for v1_item in v1 {
         for v2_item in v2 {
-            if v1_item == v2_item {
-                // Exact match
-                acc.push(v1_item.clone());
-            } else if v1_item.is_subtype(v2_item)? {
-                // v1_item is more specific than v2_item, so v1_item is in the intersection
-                acc.push(v1_item.clone());
-            } else if v2_item.is_subtype(v1_item)? {
-                // v2_item is more specific than v1_item, so v2_item is in the intersection
-                acc.push(v2_item.clone());
-            }
+            acc.extend(v1_item.meet(v2_item)?);
         }
     }
 
-    // Remove duplicates and keep only the most specific items
+    // The items are a union: remove duplicates and items contained in other items
     let mut deduped_acc = vec![];
     for item in acc {
         let mut should_add = true;
@@ -309,16 +913,16 @@ fn sub_vec_intersect<K: SubtypeCheck + Clone + PartialEq + Ord>(
                 should_add = false;
                 break;
             } else if item.is_subtype(existing_item)? {
-                // item is more specific, remove existing_item
-                indices_to_remove.push(idx);
-            } else if existing_item.is_subtype(&item)? {
-                // existing_item is more specific, don't add item
+                // existing_item already contains item, don't add item
                 should_add = false;
                 break;
+            } else if existing_item.is_subtype(&item)? {
+                // item contains existing_item, remove existing_item
+                indices_to_remove.push(idx);
             }
         }
 
-        // Remove items that are less specific than the current item
+        // Remove items contained in the current item
         for &idx in indices_to_remove.iter().rev() {
             deduped_acc.remove(idx);
         }
@@ -333,16 +937,21 @@ fn sub_vec_intersect<K: SubtypeCheck + Clone + PartialEq + Ord>(
 fn sub_vec_diff<K: SubtypeCheck + Clone + Ord>(v1: &[K], v2: &[K]) -> Result<Vec<K>> {
     let mut acc = vec![];
 
-    'outer: for v1_item in v1 {
+    for v1_item in v1 {
+        // remove each item of v2 from what is left of v1_item
+        let mut remaining = vec![v1_item.clone()];
         for v2_item in v2 {
-            if v1_item.is_subtype(v2_item)? {
-                continue 'outer;
+            let mut next = vec![];
+            for it in &remaining {
+                next.extend(it.minus(v2_item)?);
             }
+            remaining = next;
         }
-        acc.push(v1_item.clone());
+        acc.extend(remaining);
     }
 
     acc.sort();
+    acc.dedup();
     Ok(acc)
 }
 
@@ -883,31 +1492,111 @@ mod tests {
         assert!(!literal_a.is_subtype(&literal_b).unwrap());
     }
 
-    // #[test]
-    // fn test_string_template_types() {
-    //     use crate::ast::runtype::TplLitTypeItem;
-
-    //     let tpl1 = StringLitOrFormat::Tpl(TplLitType(vec![
-    //         TplLitTypeItem::StringConst("hello_".into()),
-    //         TplLitTypeItem::String,
-    //     ]));
-    //     let tpl2 = StringLitOrFormat::Tpl(TplLitType(vec![
-    //         TplLitTypeItem::StringConst("hello_".into()),
-    //         TplLitTypeItem::String,
-    //     ]));
-    //     let tpl3 = StringLitOrFormat::Tpl(TplLitType(vec![
-    //         TplLitTypeItem::StringConst("hi_".into()),
-    //         TplLitTypeItem::String,
-    //     ]));
-
-    //     // Identical template literals should be subtypes
-    //     assert!(tpl1.is_subtype(&tpl2).unwrap());
-    //     assert!(tpl2.is_subtype(&tpl1).unwrap());
-
-    //     // Different template literals should not be subtypes
-    //     assert!(!tpl1.is_subtype(&tpl3).unwrap());
-    //     assert!(!tpl3.is_subtype(&tpl1).unwrap());
-    // }
+    #[test]
+    fn test_string_template_types() {
+        let tpl1 = StringLitOrFormat::Tpl(TplLitType(vec![
+            TplLitTypeItem::StringConst("hello_".into()),
+            TplLitTypeItem::String,
+        ]));
+        let tpl2 = StringLitOrFormat::Tpl(TplLitType(vec![
+            TplLitTypeItem::StringConst("hello_".into()),
+            TplLitTypeItem::String,
+        ]));
+        let tpl3 = StringLitOrFormat::Tpl(TplLitType(vec![
+            TplLitTypeItem::StringConst("hi_".into()),
+            TplLitTypeItem::String,
+        ]));
+
+        // Identical template literals should be subtypes
+        assert!(tpl1.is_subtype(&tpl2).unwrap());
+        assert!(tpl2.is_subtype(&tpl1).unwrap());
+
+        // Different template literals should not be subtypes
+        assert!(!tpl1.is_subtype(&tpl3).unwrap());
+        assert!(!tpl3.is_subtype(&tpl1).unwrap());
+    }
+
+    #[test]
+    fn test_multi_part_template_inclusion() {
+        let tpl = |items: Vec<TplLitTypeItem>| StringLitOrFormat::Tpl(TplLitType(items));
+        let lit = |s: &str| TplLitTypeItem::StringConst(s.into());
+        let user_key = tpl(vec![lit("user_"), TplLitTypeItem::String]);
+        let pair = tpl(vec![
+            TplLitTypeItem::String,
+            lit("_"),
+            TplLitTypeItem::String,
+        ]);
+        let user_number = tpl(vec![lit("user_"), TplLitTypeItem::Number]);
+        let any_string = tpl(vec![TplLitTypeItem::String]);
+        let upper = tpl(vec![TplLitTypeItem::IntrinsicString(
+            StringIntrinsic::Uppercase,
+        )]);
+
+        assert!(user_key.is_subtype(&pair).unwrap());
+        assert!(!pair.is_subtype(&user_key).unwrap());
+        assert!(user_number.is_subtype(&user_key).unwrap());
+        assert!(tpl(vec![lit("user_42")]).is_subtype(&user_number).unwrap());
+        assert!(!tpl(vec![lit("user_x")]).is_subtype(&user_number).unwrap());
+        assert!(user_number.is_subtype(&any_string).unwrap());
+        let upper_suffix = TplLitTypeItem::IntrinsicString(StringIntrinsic::Uppercase);
+        assert!(
+            tpl(vec![lit("A_"), upper_suffix])
+                .is_subtype(&upper)
+                .unwrap()
+        );
+        // `${number}` matches "Infinity"
+        assert!(
+            !tpl(vec![lit("A_"), TplLitTypeItem::Number])
+                .is_subtype(&upper)
+                .unwrap()
+        );
+        assert!(!user_key.is_subtype(&upper).unwrap());
+
+        // formats are only contained in templates matching every string
+        let format = StringLitOrFormat::Format(CustomFormat("id".into(), vec![]));
+        assert!(format.is_subtype(&any_string).unwrap());
+        assert!(!format.is_subtype(&user_key).unwrap());
+        assert!(!user_key.is_subtype(&format).unwrap());
+    }
+
+    #[test]
+    fn test_overlapping_template_operations() {
+        let tpl = |items: Vec<TplLitTypeItem>| StringLitOrFormat::Tpl(TplLitType(items));
+        let lit = |s: &str| TplLitTypeItem::StringConst(s.into());
+        let starts_a = tpl(vec![lit("a"), TplLitTypeItem::String]);
+        let ends_b = tpl(vec![TplLitTypeItem::String, lit("b")]);
+
+        let both = sub_vec_intersect(
+            std::slice::from_ref(&starts_a),
+            std::slice::from_ref(&ends_b),
+        )
+        .unwrap();
+        assert_eq!(both.len(), 1);
+        assert!(tpl(vec![lit("axb")]).is_subtype(&both[0]).unwrap());
+        assert!(!tpl(vec![lit("ax")]).is_subtype(&both[0]).unwrap());
+        assert!(both[0].is_subtype(&starts_a).unwrap());
+        assert!(both[0].is_subtype(&ends_b).unwrap());
+
+        let only_a = sub_vec_diff(
+            std::slice::from_ref(&starts_a),
+            std::slice::from_ref(&ends_b),
+        )
+        .unwrap();
+        assert_eq!(only_a.len(), 1);
+        assert!(tpl(vec![lit("ax")]).is_subtype(&only_a[0]).unwrap());
+        assert!(!tpl(vec![lit("axb")]).is_subtype(&only_a[0]).unwrap());
+        assert!(
+            sub_vec_intersect(&only_a, std::slice::from_ref(&ends_b))
+                .unwrap()
+                .is_empty()
+        );
+
+        // what is left of starts_a after removing only_a is both
+        let rest = sub_vec_diff(&[starts_a], &only_a).unwrap();
+        assert_eq!(rest.len(), 1);
+        assert!(rest[0].is_subtype(&both[0]).unwrap());
+        assert!(both[0].is_subtype(&rest[0]).unwrap());
+    }
 
     #[test]
     fn test_string_format_edge_cases() {
@@ -964,6 +1653,66 @@ mod tests {
         assert!(!literal_42.is_subtype(&literal_100).unwrap());
     }
 
+    #[test]
+    fn test_number_range_operations() {
+        use crate::ast::json::N;
+
+        let range = |integer, minimum: Option<f64>, maximum: Option<f64>| {
+            NumberRepresentationOrFormat::range(
+                integer,
+                minimum.map(|it| (it, false)),
+                maximum.map(|it| (it, false)),
+            )
+            .unwrap()
+        };
+        let int = range(true, None, None);
+        let non_negative = range(false, Some(0.0), None);
+        let percent = range(false, Some(0.0), Some(100.0));
+        let digit = range(true, Some(0.0), Some(9.0));
+
+        assert!(digit.is_subtype(&int).unwrap());
+        assert!(digit.is_subtype(&percent).unwrap());
+        assert!(percent.is_subtype(&non_negative).unwrap());
+        assert!(!percent.is_subtype(&int).unwrap());
+        assert!(!non_negative.is_subtype(&percent).unwrap());
+
+        // literal membership
+        let lit = |it: f64| NumberRepresentationOrFormat::Lit(N::parse_f64(it));
+        assert!(lit(7.0).is_subtype(&digit).unwrap());
+        assert!(!lit(7.5).is_subtype(&digit).unwrap());
+        assert!(lit(7.5).is_subtype(&percent).unwrap());
+
+        // integer bounds are normalized, a single integer is a literal
+        assert_eq!(
+            int.meet(&range(false, Some(-0.5), Some(9.5))).unwrap(),
+            vec![digit.clone()]
+        );
+        assert_eq!(
+            int.meet(&range(false, Some(2.5), Some(3.5))).unwrap(),
+            vec![lit(3.0)]
+        );
+        assert!(
+            int.meet(&range(false, Some(2.2), Some(2.8)))
+                .unwrap()
+                .is_empty()
+        );
+
+        // removing a point splits the range
+        assert_eq!(
+            int.minus(&lit(0.0)).unwrap(),
+            vec![range(true, None, Some(-1.0)), range(true, Some(1.0), None)]
+        );
+        assert_eq!(
+            non_negative.minus(&lit(0.0)).unwrap(),
+            vec![NumberRepresentationOrFormat::range(false, Some((0.0, true)), None).unwrap()]
+        );
+        assert_eq!(
+            digit.minus(&range(false, Some(5.0), None)).unwrap(),
+            vec![range(true, Some(0.0), Some(4.0))]
+        );
+        assert!(digit.minus(&percent).unwrap().is_empty());
+    }
+
     #[test]
     fn test_complex_string_operations() {
         let base = StringLitOrFormat::Format(CustomFormat("entity".into(), vec![]));
@@ -989,9 +1738,9 @@ mod tests {
 
         // Test intersection operations
         let intersect_1_2 = sub_vec_intersect(&v1, &v2).unwrap();
-        // The intersection should contain the write element, which is more specific than read
-        // write (["read", "write"]) is a subtype of read (["read"]), so write is in the intersection
-        assert!(intersect_1_2.contains(&write));
+        // v1 is base, whose intersection with v2 is read
+        // write (["read", "write"]) is a subtype of read (["read"]), so it is not listed separately
+        assert!(intersect_1_2.contains(&read));
         assert_eq!(intersect_1_2.len(), 1); // Only one element should be in the intersection
 
         // Test difference operations
@@ -7,7 +7,7 @@ use crate::{
     NamedSchema, RuntypeName, RuntypeUUID,
     ast::runtype::{
         CustomFormat, IndexedProperty, Optionality, Runtype, RuntypeConst, RuntypeKind,
-        TplLitTypeItem, TypedArrayKind,
+        TypedArrayKind,
     },
     subtyping::{
         bdd::MappingAtomicType,
@@ -61,9 +61,9 @@ impl<'a, 'b> SchemerContext<'a, 'b> {
             acc.push((k.clone(), ty));
         }
 
-        let mut indexed_properties_acc = None;
+        let mut indexed_properties_acc = vec![];
 
-        if let Some(it) = &mt.indexed_properties {
+        for it in &mt.indexed_properties {
             let k = self.convert_to_schema(&it.key, None)?;
             let schema = self.convert_to_schema(&it.value, None)?;
             let ty = if it.value.has_optional() {
@@ -71,7 +71,7 @@ impl<'a, 'b> SchemerContext<'a, 'b> {
             } else {
                 schema.required()
             };
-            indexed_properties_acc = Some(Box::new(IndexedProperty { key: k, value: ty }));
+            indexed_properties_acc.push(IndexedProperty { key: k, value: ty });
         }
 
         Ok(Runtype::new(RuntypeKind::Object {
@@ -117,7 +117,7 @@ impl<'a, 'b> SchemerContext<'a, 'b> {
     }
 
     fn map_atom_schema(&mut self, mt: &Rc<MappingAtomicType>) -> anyhow::Result<Runtype> {
-        if let Some(it) = &mt.indexed_properties {
+        if let Some(it) = mt.indexed_properties.first() {
             let k = self.convert_to_schema(&it.key, None)?;
             let v = self.convert_to_schema(&it.value, None)?;
             return Ok(Runtype::map(Box::new(k), Box::new(v)));
@@ -164,7 +164,7 @@ impl<'a, 'b> SchemerContext<'a, 'b> {
     }
 
     fn list_atom_schema(&mut self, mt: &Rc<ListAtomic>) -> anyhow::Result<Runtype> {
-        if mt.prefix_items.is_empty() {
+        if mt.prefix_items.is_empty() && mt.suffix_items.is_empty() {
             if mt.items.is_any() {
                 return Ok(Runtype::any_array_like());
             }
@@ -184,7 +184,17 @@ impl<'a, 'b> SchemerContext<'a, 'b> {
         } else {
             Some(Box::new(self.convert_to_schema(&mt.items, None)?))
         };
-        Ok(Runtype::tuple(prefix_items, items))
+        let suffix_items = mt
+            .suffix_items
+            .iter()
+            .map(|it| self.convert_to_schema(it, None))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        Ok(Runtype::variadic_tuple(
+            prefix_items,
+            vec![],
+            items,
+            suffix_items,
+        ))
     }
 
     fn list_conjunction_to_schema(&mut self, clause: &Conjunction) -> anyhow::Result<Runtype> {
@@ -348,6 +358,12 @@ impl<'a, 'b> SchemerContext<'a, 'b> {
                                     !allowed,
                                 ));
                             }
+                            NumberRepresentationOrFormat::Range(range) => {
+                                acc.insert(maybe_not(
+                                    Runtype::number_range(range.clone()),
+                                    !allowed,
+                                ));
+                            }
                         }
                     }
                 }
@@ -364,17 +380,20 @@ impl<'a, 'b> SchemerContext<'a, 'b> {
                                 ));
                             }
                             StringLitOrFormat::Tpl(items) => {
-                                //
-                                match items.0.first() {
-                                    Some(TplLitTypeItem::StringConst(c)) => acc.insert(maybe_not(
-                                        Runtype::single_string_const(c),
-                                        !allowed,
-                                    )),
-                                    _ => acc.insert(maybe_not(
-                                        Runtype::tpl_lit_type(items.clone()),
-                                        !allowed,
-                                    )),
-                                };
+                                acc.insert(maybe_not(
+                                    Runtype::tpl_lit_type(items.clone()),
+                                    !allowed,
+                                ));
+                            }
+                            StringLitOrFormat::Refined { all_of, none_of } => {
+                                let refined = all_of
+                                    .iter()
+                                    .map(|it| Runtype::tpl_lit_type(it.clone()))
+                                    .chain(none_of.iter().map(|it| {
+                                        Runtype::st_not(Box::new(Runtype::tpl_lit_type(it.clone())))
+                                    }))
+                                    .collect();
+                                acc.insert(maybe_not(Runtype::all_of(refined), !allowed));
                             }
                         }
                     }
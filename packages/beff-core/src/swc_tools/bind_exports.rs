@@ -1,13 +1,17 @@
 use crate::Anchor;
 use crate::BffFileName;
+use crate::BffModuleData;
 use crate::ImportReference;
 use crate::ParsedModule;
 use crate::ParsedModuleLocals;
 use crate::SymbolsExportsModule;
 use crate::UnresolvedExport;
+use crate::swc_tools::InterfaceAugmentation;
 use crate::swc_tools::SymbolExport;
 use crate::swc_tools::SymbolExportDefault;
 use crate::swc_tools::bind_locals::ParserOfModuleLocals;
+use crate::swc_tools::bind_locals::class_decl_to_expr;
+use crate::swc_tools::bind_locals::fn_decl_to_expr;
 use crate::swc_tools::parse::parse_with_swc;
 use anyhow::Result;
 use std::collections::HashMap;
@@ -22,6 +26,7 @@ use swc_ecma_ast::ExportDefaultExpr;
 use swc_ecma_ast::ExportNamedSpecifier;
 use swc_ecma_ast::ExportNamespaceSpecifier;
 use swc_ecma_ast::ExportSpecifier;
+use swc_ecma_ast::Expr;
 use swc_ecma_ast::Ident;
 use swc_ecma_ast::ImportDefaultSpecifier;
 use swc_ecma_ast::ImportStarAsSpecifier;
@@ -30,7 +35,14 @@ use swc_ecma_ast::NamedExport;
 use swc_ecma_ast::Pat;
 use swc_ecma_ast::TsEnumDecl;
 use swc_ecma_ast::{ImportDecl, ImportNamedSpecifier, ImportSpecifier, TsInterfaceDecl};
+use swc_ecma_ast::{
+    Module, ModuleDecl, ModuleItem, Stmt, TsExportAssignment, TsModuleDecl, TsModuleName,
+    TsNamespaceBody,
+};
 use swc_ecma_visit::Visit;
+use swc_node_comments::SwcComments;
+
+const CLIENT_MODULE: &str = "@beff/client";
 
 pub trait FsModuleResolver {
     fn resolve_import(
@@ -45,6 +57,9 @@ pub struct ImportsVisitor<'a, R: FsModuleResolver> {
     pub symbol_exports: SymbolsExportsModule,
     pub current_file: BffFileName,
     pub unresolved_exports: Vec<UnresolvedExport>,
+    pub export_assignment: Option<String>,
+    pub dependencies: Vec<BffFileName>,
+    pub augmentations: Vec<InterfaceAugmentation>,
 }
 impl<'a, R: FsModuleResolver> ImportsVisitor<'a, R> {
     pub fn from_file(current_file: BffFileName, resolver: &'a mut R) -> ImportsVisitor<'a, R> {
@@ -53,12 +68,22 @@ impl<'a, R: FsModuleResolver> ImportsVisitor<'a, R> {
             symbol_exports: SymbolsExportsModule::new(),
             current_file,
             unresolved_exports: Vec::new(),
+            export_assignment: None,
+            dependencies: Vec::new(),
+            augmentations: Vec::new(),
             resolver,
         }
     }
     fn resolve_import(&mut self, module_specifier: &str) -> Option<BffFileName> {
-        self.resolver
-            .resolve_import(self.current_file.clone(), module_specifier)
+        let resolved = self
+            .resolver
+            .resolve_import(self.current_file.clone(), module_specifier);
+        if let Some(file) = &resolved
+            && !self.dependencies.contains(file)
+        {
+            self.dependencies.push(file.clone());
+        }
+        resolved
     }
 
     fn insert_import_named(&mut self, local: &Ident, module_specifier: &str, orig: &str) {
@@ -114,6 +139,47 @@ impl<R: FsModuleResolver> Visit for ImportsVisitor<'_, R> {
         );
     }
 
+    fn visit_ts_export_assignment(&mut self, n: &TsExportAssignment) {
+        // `export = X` is what both default and star imports of the module see
+        if let Expr::Ident(id) = &*n.expr {
+            self.export_assignment = Some(id.sym.to_string());
+        }
+        self.symbol_exports.set_default_export(
+            SymbolExportDefault::Expr {
+                export_expr: Rc::new(*n.expr.clone()),
+                anchor: Anchor::new(self.current_file.clone(), n.span),
+            }
+            .into(),
+        );
+    }
+
+    fn visit_ts_module_decl(&mut self, n: &TsModuleDecl) {
+        // namespace bodies are bound as their own scopes, `declare module "./x" {}`
+        // augments the interfaces of another file
+        let (TsModuleName::Str(specifier), Some(TsNamespaceBody::TsModuleBlock(block))) =
+            (&n.id, &n.body)
+        else {
+            return;
+        };
+        let Some(target_file) = self.resolve_import(&specifier.value.to_string_lossy()) else {
+            return;
+        };
+        for item in &block.body {
+            let decl = match item {
+                ModuleItem::Stmt(Stmt::Decl(Decl::TsInterface(decl)))
+                | ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+                    decl: Decl::TsInterface(decl),
+                    ..
+                })) => decl,
+                _ => continue,
+            };
+            self.augmentations.push(InterfaceAugmentation {
+                target_file: target_file.clone(),
+                decl: Rc::new(*decl.clone()),
+            });
+        }
+    }
+
     fn visit_export_decl(&mut self, n: &ExportDecl) {
         match &n.decl {
             Decl::TsInterface(n) => {
@@ -183,7 +249,42 @@ impl<R: FsModuleResolver> Visit for ImportsVisitor<'_, R> {
                 }
             }
 
-            Decl::TsModule(_) | Decl::Using(_) | Decl::Class(_) | Decl::Fn(_) => {}
+            Decl::Fn(fn_decl) => {
+                let name = fn_decl.ident.sym.to_string();
+                if fn_decl.function.body.is_some()
+                    && self.symbol_exports.named_values.contains_key(&name)
+                {
+                    return;
+                }
+                let export = Rc::new(SymbolExport::ValueExpr {
+                    expr: Rc::new(fn_decl_to_expr(fn_decl)),
+                    name: name.clone(),
+                    span: fn_decl.ident.span,
+                    original_file: self.current_file.clone(),
+                });
+                self.symbol_exports.insert_value(name, export);
+            }
+
+            Decl::Class(class_decl) => {
+                let name = class_decl.ident.sym.to_string();
+                self.symbol_exports.insert_type(
+                    name.clone(),
+                    Rc::new(SymbolExport::ClassDecl {
+                        decl: Rc::new(class_decl.clone()),
+                        original_file: self.current_file.clone(),
+                        span: class_decl.class.span,
+                    }),
+                );
+                let export = Rc::new(SymbolExport::ValueExpr {
+                    expr: Rc::new(class_decl_to_expr(class_decl)),
+                    name: name.clone(),
+                    span: class_decl.ident.span,
+                    original_file: self.current_file.clone(),
+                });
+                self.symbol_exports.insert_value(name, export);
+            }
+
+            Decl::TsModule(_) | Decl::Using(_) => {}
         }
     }
 
@@ -274,6 +375,15 @@ impl<R: FsModuleResolver> Visit for ImportsVisitor<'_, R> {
     }
     fn visit_import_decl(&mut self, node: &ImportDecl) {
         let module_specifier = node.src.value.to_string_lossy();
+        // the types of the client are built-ins, they are known by name
+        if module_specifier == CLIENT_MODULE || module_specifier.starts_with("@beff/client/") {
+            return;
+        }
+
+        if node.specifiers.is_empty() {
+            // `import "./x"` only matters for the augmentations `./x` declares
+            self.resolve_import(&module_specifier);
+        }
 
         for x in &node.specifiers {
             match x {
@@ -311,8 +421,108 @@ pub fn parse_and_bind<R: FsModuleResolver>(
         content.to_owned(),
     );
     let (module, comments) = parse_with_swc(&source_file, cm, file_name)?;
+    let scope = BffFileName::new(module.fm.name.to_string());
+    Ok(bind_module(resolver, module, comments, scope, None))
+}
+
+struct NamespaceDecl {
+    name: String,
+    exported: bool,
+    anchor_span: swc_common::Span,
+    body: Vec<ModuleItem>,
+}
+
+// declarations of an ambient namespace are exported unless it has an explicit export list
+fn implicitly_exported(items: Vec<ModuleItem>) -> Vec<ModuleItem> {
+    let has_export_list = items
+        .iter()
+        .any(|it| matches!(it, ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(_))));
+    if has_export_list {
+        return items;
+    }
+    items
+        .into_iter()
+        .map(|it| match it {
+            ModuleItem::Stmt(Stmt::Decl(decl)) => {
+                let decl = match decl {
+                    Decl::TsModule(mut m) => {
+                        m.declare = true;
+                        Decl::TsModule(m)
+                    }
+                    decl => decl,
+                };
+                ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+                    span: decl.span(),
+                    decl,
+                }))
+            }
+            it => it,
+        })
+        .collect()
+}
+
+fn namespace_body_items(body: &TsNamespaceBody, ambient: bool) -> Vec<ModuleItem> {
+    match body {
+        TsNamespaceBody::TsModuleBlock(block) if ambient => implicitly_exported(block.body.clone()),
+        TsNamespaceBody::TsModuleBlock(block) => block.body.clone(),
+        // `namespace A.B {}` is `namespace A { export namespace B {} }`
+        TsNamespaceBody::TsNamespaceDecl(decl) => {
+            vec![ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+                span: decl.span,
+                decl: Decl::TsModule(Box::new(TsModuleDecl {
+                    span: decl.span,
+                    declare: ambient || decl.declare,
+                    global: decl.global,
+                    namespace: true,
+                    id: TsModuleName::Ident(decl.id.clone()),
+                    body: Some(*decl.body.clone()),
+                })),
+            }))]
+        }
+    }
+}
+
+// declarations of the same namespace are merged into one scope
+fn collect_namespaces(items: &[ModuleItem], in_declaration_file: bool) -> Vec<NamespaceDecl> {
+    let mut acc: Vec<NamespaceDecl> = vec![];
+    for item in items {
+        let (decl, exported) = match item {
+            ModuleItem::Stmt(Stmt::Decl(Decl::TsModule(decl))) => (decl, false),
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+                decl: Decl::TsModule(decl),
+                ..
+            })) => (decl, true),
+            _ => continue,
+        };
+        let (TsModuleName::Ident(id), Some(body)) = (&decl.id, &decl.body) else {
+            continue;
+        };
+        let name = id.sym.to_string();
+        let items = namespace_body_items(body, in_declaration_file || decl.declare);
+        match acc.iter_mut().find(|it| it.name == name) {
+            Some(existing) => {
+                existing.exported |= exported;
+                existing.body.extend(items);
+            }
+            None => acc.push(NamespaceDecl {
+                name,
+                exported,
+                anchor_span: id.span,
+                body: items,
+            }),
+        }
+    }
+    acc
+}
 
-    let mut v = ImportsVisitor::from_file(BffFileName::new(module.fm.name.to_string()), resolver);
+fn bind_module<R: FsModuleResolver>(
+    resolver: &mut R,
+    module: BffModuleData,
+    comments: SwcComments,
+    scope: BffFileName,
+    parent_scope: Option<BffFileName>,
+) -> Rc<ParsedModule> {
+    let mut v = ImportsVisitor::from_file(scope.clone(), resolver);
     v.visit_module(&module.module);
 
     let mut locals = ParserOfModuleLocals {
@@ -322,7 +532,70 @@ pub fn parse_and_bind<R: FsModuleResolver>(
     locals.visit_module_item_list(&module.module.body);
 
     let mut symbol_exports = v.symbol_exports;
-    for unresolved in v.unresolved_exports {
+    let export_assignment = v.export_assignment;
+    let dependencies = v.dependencies;
+    let augmentations = v.augmentations;
+    let imports = v.imports;
+    let unresolved_exports = v.unresolved_exports;
+    let resolver = v.resolver;
+
+    let mut namespaces = HashMap::new();
+    let in_declaration_file = module.bff_fname.to_string().ends_with(".d.ts");
+    for ns in collect_namespaces(&module.module.body, in_declaration_file) {
+        let ns_scope = BffFileName::new(format!("{}::{}", scope, ns.name));
+        let ns_module = BffModuleData {
+            bff_fname: module.bff_fname.clone(),
+            fm: module.fm.clone(),
+            source_map: module.source_map.clone(),
+            module: Module {
+                span: module.module.span,
+                body: ns.body,
+                shebang: None,
+            },
+        };
+        let bound = bind_module(
+            resolver,
+            ns_module,
+            comments.clone(),
+            ns_scope.clone(),
+            Some(scope.clone()),
+        );
+        if ns.exported {
+            symbol_exports.insert_unknown(
+                ns.name.clone(),
+                Rc::new(SymbolExport::StarOfOtherFile {
+                    reference: Rc::new(ImportReference::Star {
+                        file_name: ns_scope.clone(),
+                        import_statement_anchor: Anchor::new(scope.clone(), ns.anchor_span),
+                    }),
+                }),
+            );
+        }
+        if export_assignment.as_ref() == Some(&ns.name) {
+            let SymbolsExportsModule {
+                named_types,
+                named_values,
+                named_unknown,
+                ..
+            } = &bound.symbol_exports;
+            for (k, v) in named_types {
+                symbol_exports.insert_type(k.clone(), v.clone());
+            }
+            for (k, v) in named_values {
+                symbol_exports.insert_value(k.clone(), v.clone());
+            }
+            for (k, v) in named_unknown {
+                symbol_exports.insert_unknown(k.clone(), v.clone());
+            }
+        }
+        locals.content.namespaces.insert(ns.name, ns_scope.clone());
+        for (k, v) in &bound.namespaces {
+            namespaces.insert(k.clone(), v.clone());
+        }
+        namespaces.insert(ns_scope, bound);
+    }
+
+    for unresolved in unresolved_exports {
         let renamed = unresolved.renamed;
         let js_word = unresolved.name.clone();
         let k = unresolved.name.to_string();
@@ -331,7 +604,7 @@ pub fn parse_and_bind<R: FsModuleResolver>(
                 renamed.to_string(),
                 Rc::new(SymbolExport::TsType {
                     decl: ts_type.clone(),
-                    original_file: file_name.clone(),
+                    original_file: scope.clone(),
                     name: k,
                     span: ts_type.span,
                 }),
@@ -344,24 +617,63 @@ pub fn parse_and_bind<R: FsModuleResolver>(
                 renamed.to_string(),
                 Rc::new(SymbolExport::TsEnumDecl {
                     decl: enum_.clone(),
-                    original_file: file_name.clone(),
+                    original_file: scope.clone(),
                 }),
             );
             continue;
         }
 
-        if let Some(intf) = locals.content.interfaces.get(&k) {
+        if let Some(intf) = locals.content.interfaces.get(&k).and_then(|it| it.first()) {
             symbol_exports.insert_type(
                 renamed.to_string(),
                 Rc::new(SymbolExport::TsInterfaceDecl {
                     decl: intf.clone(),
-                    original_file: file_name.clone(),
+                    original_file: scope.clone(),
                     span: intf.span,
                 }),
             );
             continue;
         }
 
+        if let Some(class) = locals.content.classes.get(&k) {
+            symbol_exports.insert_type(
+                renamed.to_string(),
+                Rc::new(SymbolExport::ClassDecl {
+                    decl: class.clone(),
+                    original_file: scope.clone(),
+                    span: class.class.span,
+                }),
+            );
+            // the default export slot holds a single symbol, it resolves to the value too
+            if renamed != "default"
+                && let Some(v) = locals.content.exprs.get(&k)
+            {
+                symbol_exports.insert_value(
+                    renamed.to_string(),
+                    Rc::new(SymbolExport::ValueExpr {
+                        expr: v.clone(),
+                        name: js_word.clone(),
+                        span: v.span(),
+                        original_file: scope.clone(),
+                    }),
+                );
+            }
+            continue;
+        }
+
+        if let Some(ns_scope) = locals.content.namespaces.get(&k) {
+            symbol_exports.insert_unknown(
+                renamed.to_string(),
+                Rc::new(SymbolExport::StarOfOtherFile {
+                    reference: Rc::new(ImportReference::Star {
+                        file_name: ns_scope.clone(),
+                        import_statement_anchor: Anchor::new(scope.clone(), unresolved.span),
+                    }),
+                }),
+            );
+            continue;
+        }
+
         if let Some(v) = locals.content.exprs.get(&k) {
             symbol_exports.insert_value(
                 renamed.to_string(),
@@ -369,7 +681,7 @@ pub fn parse_and_bind<R: FsModuleResolver>(
                     expr: v.clone(),
                     name: js_word.clone(),
                     span: v.span(),
-                    original_file: file_name.clone(),
+                    original_file: scope.clone(),
                 }),
             );
             continue;
@@ -382,13 +694,13 @@ pub fn parse_and_bind<R: FsModuleResolver>(
                     ty: v.clone(),
                     name: js_word.clone(),
                     span: v.span(),
-                    original_file: file_name.clone(),
+                    original_file: scope.clone(),
                 }),
             );
             continue;
         }
 
-        if let Some(import) = v.imports.get(&k) {
+        if let Some(import) = imports.get(&k) {
             match &**import {
                 ImportReference::Named {
                     original_name: orig,
@@ -418,12 +730,15 @@ pub fn parse_and_bind<R: FsModuleResolver>(
         }
     }
 
-    let f = Rc::new(ParsedModule {
+    Rc::new(ParsedModule {
         module,
         symbol_exports,
-        imports: v.imports,
+        imports,
         comments,
         locals: locals.content,
-    });
-    Ok(f)
+        namespaces,
+        parent_scope,
+        dependencies,
+        augmentations,
+    })
 }
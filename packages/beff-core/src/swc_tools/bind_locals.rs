@@ -1,15 +1,21 @@
 use std::{collections::HashMap, rc::Rc};
 
 use swc_ecma_ast::{
-    Decl, Expr, ModuleItem, Pat, Stmt, TsEnumDecl, TsInterfaceDecl, TsType, TsTypeAliasDecl,
+    ClassDecl, ClassExpr, Decl, Expr, FnDecl, FnExpr, ModuleItem, Pat, Stmt, TsEnumDecl,
+    TsInterfaceDecl, TsModuleDecl, TsType, TsTypeAliasDecl,
 };
+
+use crate::BffFileName;
 use swc_ecma_visit::Visit;
 
 #[derive(Debug)]
 pub struct ParsedModuleLocals {
     pub type_aliases: HashMap<String, Rc<TsTypeAliasDecl>>,
-    pub interfaces: HashMap<String, Rc<TsInterfaceDecl>>,
+    // every declaration of an interface name, in source order, they are merged
+    pub interfaces: HashMap<String, Vec<Rc<TsInterfaceDecl>>>,
     pub enums: HashMap<String, Rc<TsEnumDecl>>,
+    pub classes: HashMap<String, Rc<ClassDecl>>,
+    pub namespaces: HashMap<String, BffFileName>,
 
     pub exprs: HashMap<String, Rc<Expr>>,
     pub exprs_decls: HashMap<String, Rc<TsType>>,
@@ -20,6 +26,8 @@ impl ParsedModuleLocals {
             type_aliases: HashMap::new(),
             interfaces: HashMap::new(),
             enums: HashMap::new(),
+            classes: HashMap::new(),
+            namespaces: HashMap::new(),
             exprs: HashMap::new(),
             exprs_decls: HashMap::new(),
         }
@@ -31,6 +39,19 @@ impl Default for ParsedModuleLocals {
         Self::new()
     }
 }
+pub fn fn_decl_to_expr(fn_decl: &FnDecl) -> Expr {
+    Expr::Fn(FnExpr {
+        ident: Some(fn_decl.ident.clone()),
+        function: fn_decl.function.clone(),
+    })
+}
+pub fn class_decl_to_expr(class_decl: &ClassDecl) -> Expr {
+    Expr::Class(ClassExpr {
+        ident: Some(class_decl.ident.clone()),
+        class: class_decl.class.clone(),
+    })
+}
+
 pub struct ParserOfModuleLocals {
     pub content: ParsedModuleLocals,
 }
@@ -51,8 +72,10 @@ impl ParserOfModuleLocals {
             match it {
                 ModuleItem::Stmt(Stmt::Decl(decl)) => {
                     // add expr to self.content
-                    if let Decl::Var(var_decl) = decl {
-                        self.handle_var_decl(var_decl);
+                    match decl {
+                        Decl::Var(var_decl) => self.handle_var_decl(var_decl),
+                        Decl::Fn(fn_decl) => self.handle_fn_decl(fn_decl),
+                        _ => {}
                     }
                 }
                 ModuleItem::ModuleDecl(_) => {}
@@ -77,6 +100,25 @@ impl ParserOfModuleLocals {
             }
         }
     }
+    fn handle_fn_decl(&mut self, fn_decl: &FnDecl) {
+        let name = fn_decl.ident.sym.to_string();
+        // the implementation of an overloaded function is not visible to callers,
+        // the last overload signature is the one types see
+        if fn_decl.function.body.is_some() && self.content.exprs.contains_key(&name) {
+            return;
+        }
+        self.content
+            .exprs
+            .insert(name, Rc::new(fn_decl_to_expr(fn_decl)));
+    }
+    fn handle_class_decl(&mut self, n: &ClassDecl) {
+        // a class is both a type (its instances) and a value (its constructor)
+        let name = n.ident.sym.to_string();
+        self.content
+            .exprs
+            .insert(name.clone(), Rc::new(class_decl_to_expr(n)));
+        self.content.classes.insert(name, Rc::new(n.clone()));
+    }
     fn handle_type_alias(&mut self, n: &TsTypeAliasDecl) {
         let TsTypeAliasDecl { id, .. } = n;
         self.content
@@ -93,7 +135,9 @@ impl ParserOfModuleLocals {
         let TsInterfaceDecl { id, .. } = n;
         self.content
             .interfaces
-            .insert(id.sym.to_string(), Rc::new(n.clone()));
+            .entry(id.sym.to_string())
+            .or_default()
+            .push(Rc::new(n.clone()));
     }
 }
 
@@ -107,6 +151,12 @@ impl Visit for ParserOfModuleLocals {
     fn visit_ts_enum_decl(&mut self, n: &swc_ecma_ast::TsEnumDecl) {
         self.habdle_enum_decl(n);
     }
+    fn visit_ts_module_decl(&mut self, _n: &TsModuleDecl) {
+        // namespace bodies are bound as their own scopes
+    }
+    fn visit_class_decl(&mut self, n: &ClassDecl) {
+        self.handle_class_decl(n);
+    }
     fn visit_export_decl(&mut self, n: &swc_ecma_ast::ExportDecl) {
         match &n.decl {
             Decl::TsInterface(ts_interface_decl) => {
@@ -121,6 +171,12 @@ impl Visit for ParserOfModuleLocals {
             Decl::Var(ts_var_decl) => {
                 self.handle_var_decl(ts_var_decl);
             }
+            Decl::Fn(fn_decl) => {
+                self.handle_fn_decl(fn_decl);
+            }
+            Decl::Class(class_decl) => {
+                self.handle_class_decl(class_decl);
+            }
             _ => {}
         }
     }
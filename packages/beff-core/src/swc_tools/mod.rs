@@ -1,7 +1,7 @@
 use std::{collections::HashMap, rc::Rc};
 
 use swc_common::Span;
-use swc_ecma_ast::{Expr, TsEnumDecl, TsInterfaceDecl, TsType, TsTypeAliasDecl};
+use swc_ecma_ast::{ClassDecl, Expr, TsEnumDecl, TsInterfaceDecl, TsType, TsTypeAliasDecl};
 
 use crate::{Anchor, BffFileName, FileManager};
 
@@ -26,6 +26,11 @@ pub enum SymbolExport {
         decl: Rc<TsEnumDecl>,
         original_file: BffFileName,
     },
+    ClassDecl {
+        decl: Rc<ClassDecl>,
+        original_file: BffFileName,
+        span: Span,
+    },
     ValueExpr {
         expr: Rc<Expr>,
         name: String,
@@ -47,6 +52,13 @@ pub enum SymbolExport {
     },
 }
 
+// an interface declared inside `declare module "./x" {}`, it merges into `./x`
+#[derive(Debug, Clone)]
+pub struct InterfaceAugmentation {
+    pub target_file: BffFileName,
+    pub decl: Rc<TsInterfaceDecl>,
+}
+
 #[derive(Debug, Clone)]
 pub enum ImportReference {
     Named {
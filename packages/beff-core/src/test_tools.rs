@@ -1,5 +1,6 @@
 use crate::{
     BeffUserSettings, BffFileName, EntryPoints, FileManager, ParsedModule,
+    ast::json::Json,
     diag::{DiagnosticInformation, Location},
     parser_extractor::ParserExtractResult,
     swc_tools::bind_exports::{FsModuleResolver, parse_and_bind},
@@ -73,7 +74,10 @@ fn parse_modules(fs: &[(&str, &str)]) -> BTreeMap<BffFileName, Rc<ParsedModule>>
     }
     map
 }
-fn extract_types_with_settings(fs: &[(&str, &str)], settings: BeffUserSettings) -> ParserExtractResult {
+fn extract_types_with_settings(
+    fs: &[(&str, &str)],
+    settings: BeffUserSettings,
+) -> ParserExtractResult {
     let mut man = TestFileManager {
         fs: parse_modules(fs),
     };
@@ -140,6 +144,159 @@ pub fn print_cgen_multifile(sources: &[(&str, &str)]) -> String {
     out.push_str(&code);
     out
 }
+fn decode_vlq(segment: &str) -> Vec<i64> {
+    let digits = "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut acc = vec![];
+    let (mut value, mut shift) = (0, 0);
+    for c in segment.chars() {
+        let digit = digits.find(c).expect("should be a base64 digit") as i64;
+        value += (digit & 31) << shift;
+        shift += 5;
+        if digit & 32 == 0 {
+            acc.push(if value & 1 == 1 {
+                -(value >> 1)
+            } else {
+                value >> 1
+            });
+            (value, shift) = (0, 0);
+        }
+    }
+    acc
+}
+// one line per mapping, the generated token and the line of the declaration it points to
+pub fn print_source_map_multifile(sources: &[(&str, &str)]) -> String {
+    let p = extract_types(sources);
+    let errors = &p.errors;
+
+    if !errors.is_empty() {
+        panic!("errors: {:?}", errors);
+    }
+
+    let (code, map) = p
+        .emit_code_with_source_map()
+        .expect("should be able to emit module");
+    let map = map.to_serde();
+    let files = map["sources"]
+        .as_array()
+        .expect("should have sources")
+        .iter()
+        .map(|it| it.as_str().expect("should be a file name"))
+        .collect::<Vec<_>>();
+    let mappings = map["mappings"].as_str().expect("should have mappings");
+
+    let mut out = String::new();
+    let mut fields = [0; 4];
+    for (line, (generated, segments)) in code.lines().zip(mappings.split(';')).enumerate() {
+        fields[0] = 0;
+        for segment in segments.split(',').filter(|it| !it.is_empty()) {
+            for (field, delta) in fields.iter_mut().zip(decode_vlq(segment)) {
+                *field += delta;
+            }
+            let token = generated[fields[0] as usize..]
+                .split([':', ' '])
+                .next()
+                .unwrap_or_default();
+            let file = files[fields[1] as usize];
+            let (_, content) = sources
+                .iter()
+                .find(|(name, _)| *name == file)
+                .expect("should be one of the sources");
+            let declaration = content.lines().nth(fields[2] as usize).unwrap_or_default();
+            out.push_str(&format!(
+                "{}:{} {} -> {}:{}:{} {}\n",
+                line + 1,
+                fields[0] + 1,
+                token,
+                file,
+                fields[2] + 1,
+                fields[3] + 1,
+                declaration.trim()
+            ));
+        }
+    }
+    out
+}
+pub fn print_aot(from: &str) -> String {
+    let p = extract_types(&[("entry.ts", from)]);
+    let errors = &p.errors;
+
+    if !errors.is_empty() {
+        panic!("errors: {:?}", errors);
+    }
+
+    p.emit_aot_code()
+        .expect("should be able to emit compiled validators")
+}
+pub fn print_aot_error(from: &str) -> String {
+    let p = extract_types(&[("entry.ts", from)]);
+    let errors = &p.errors;
+
+    if !errors.is_empty() {
+        panic!("errors: {:?}", errors);
+    }
+
+    p.emit_aot_code()
+        .expect_err("should fail to compile validators")
+        .to_string()
+}
+pub fn print_dts(from: &str) -> String {
+    print_dts_with_backend(from, false)
+}
+pub fn print_compiled_dts(from: &str) -> String {
+    print_dts_with_backend(from, true)
+}
+fn print_dts_with_backend(from: &str, compiled: bool) -> String {
+    let p = extract_types(&[("entry.ts", from)]);
+    let errors = &p.errors;
+
+    if !errors.is_empty() {
+        panic!("errors: {:?}", errors);
+    }
+
+    // the declarations are written to `generated/`, next to the sources
+    p.emit_dts("generated", compiled)
+        .expect("should be able to emit type declarations")
+}
+pub fn print_json_schema(from: &str) -> String {
+    let p = extract_types(&[("entry.ts", from)]);
+    let errors = &p.errors;
+
+    if !errors.is_empty() {
+        panic!("errors: {:?}", errors);
+    }
+
+    let schemas = p
+        .emit_json_schemas()
+        .expect("should be able to emit json schemas");
+    let schemas = Json::Object(schemas.into_iter().collect());
+    serde_json::to_string_pretty(&schemas.to_serde()).expect("should be able to serialize")
+}
+pub fn print_json_schema_error(from: &str) -> String {
+    let p = extract_types(&[("entry.ts", from)]);
+    let errors = &p.errors;
+
+    if !errors.is_empty() {
+        panic!("errors: {:?}", errors);
+    }
+
+    p.emit_json_schemas()
+        .expect_err("should fail to emit json schemas")
+        .to_string()
+}
+pub fn print_openapi(from: &str) -> String {
+    let p = extract_types(&[("entry.ts", from)]);
+    let errors = &p.errors;
+
+    if !errors.is_empty() {
+        panic!("errors: {:?}", errors);
+    }
+
+    let document = p
+        .emit_openapi()
+        .expect("should be able to emit the openapi document")
+        .expect("should call buildRoutes");
+    serde_json::to_string_pretty(&document.to_serde()).expect("should be able to serialize")
+}
 pub fn failure(from: &str) -> String {
     let sources = [("entry.ts", from)];
     failure_multifile(&sources)
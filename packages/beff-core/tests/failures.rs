@@ -26,12 +26,12 @@ mod tests {
     parse.buildParsers<{ UserId: UserId }>();
   "#;
         insta::assert_snapshot!(failure(from),@r"
-        Error: Type query args are not supported
+        Error: Cannot resolve value 'entry.ts::User'
            ╭─[entry.ts:3:24]
            │
          3 │         type UserId = typeof User<string>;
            │                       ─────────┬─────────  
-           │                                ╰─────────── Type query args are not supported
+           │                                ╰─────────── Cannot resolve value 'entry.ts::User'
         ───╯
         ");
     }
@@ -317,15 +317,15 @@ mod tests {
                     parse.buildParsers<{ T: T }>();
                 "#
             )
-        ]), @r#"
-        Error: Type query args are not supported
-           ╭─[entry.ts:2:38]
+        ]), @r"
+        Error: Cannot resolve value 't.ts::User'
+           ╭─[t.ts:3:22]
            │
-         2 │                     export type T = typeof import("./t")<string>;
-           │                                     ──────────────┬─────────────  
-           │                                                   ╰─────────────── Type query args are not supported
+         3 │                     export default User;
+           │                     ──────────┬─────────  
+           │                               ╰─────────── Cannot resolve value 't.ts::User'
         ───╯
-        "#);
+        ");
     }
 
     #[test]
@@ -917,39 +917,6 @@ mod tests {
         "#);
     }
 
-    #[test]
-    fn class_is_not_supported() {
-        let from = r#"
-        class C {}
-    parse.buildParsers<{ C: C }>();
-  "#;
-        insta::assert_snapshot!(failure(from),@r"
-        Error: Cannot resolve type 'entry.ts::C'
-           ╭─[entry.ts:3:30]
-           │
-         3 │     parse.buildParsers<{ C: C }>();
-           │                             ┬  
-           │                             ╰── Cannot resolve type 'entry.ts::C'
-        ───╯
-        ");
-    }
-    #[test]
-    fn class_is_not_supported2() {
-        let from = r#"
-        class C {}
-    parse.buildParsers<{ C: typeof C }>();
-  "#;
-        insta::assert_snapshot!(failure(from),@r"
-        Error: Cannot resolve value 'entry.ts::C'
-           ╭─[entry.ts:3:30]
-           │
-         3 │     parse.buildParsers<{ C: typeof C }>();
-           │                             ────┬───  
-           │                                 ╰───── Cannot resolve value 'entry.ts::C'
-        ───╯
-        ");
-    }
-
     #[test]
     fn qualified_value_as_type() {
         insta::assert_snapshot!(failure_multifile(&[
@@ -1558,4 +1525,326 @@ mod tests {
         ───╯
         ");
     }
+
+    #[test]
+    fn string_intrinsic_on_number() {
+        let from = r#"
+    type X = Uppercase<number>;
+    parse.buildParsers<{ X: X }>();
+  "#;
+        insta::assert_snapshot!(failure(from), @r"
+        Error: Uppercase can only be applied to string types
+           ╭─[entry.ts:2:15]
+           │
+         2 │     type X = Uppercase<number>;
+           │              ────────┬────────  
+           │                      ╰────────── Uppercase can only be applied to string types
+        ───╯
+        ");
+    }
+    #[test]
+    fn return_type_on_non_function() {
+        let from = r#"
+    type X = ReturnType<string>;
+    parse.buildParsers<{ X: X }>();
+  "#;
+        insta::assert_snapshot!(failure(from), @r"
+        Error: ReturnType can only be applied to function types
+           ╭─[entry.ts:2:15]
+           │
+         2 │     type X = ReturnType<string>;
+           │              ─────────┬────────  
+           │                       ╰────────── ReturnType can only be applied to function types
+        ───╯
+        ");
+    }
+    #[test]
+    fn recursive_generic_with_different_arguments() {
+        let from = r#"
+    type Nested<T> = { item: T; next: Nested<T[]> | null };
+    parse.buildParsers<{ X: Nested<string> }>();
+  "#;
+        insta::assert_snapshot!(failure(from), @r"
+        Error: Recursive generic types must refer to themselves with the same type arguments
+           ╭─[entry.ts:2:40]
+           │
+         2 │     type Nested<T> = { item: T; next: Nested<T[]> | null };
+           │                                       ─────┬─────  
+           │                                            ╰─────── Recursive generic types must refer to themselves with the same type arguments
+        ───╯
+        ");
+    }
+    #[test]
+    fn instance_of_non_class() {
+        let from = r#"
+    interface User { name: string }
+    type X = InstanceOf<User>;
+    parse.buildParsers<{ X: X }>();
+  "#;
+        insta::assert_snapshot!(failure(from), @r"
+        Error: InstanceOf can only be applied to a class
+           ╭─[entry.ts:3:15]
+           │
+         3 │     type X = InstanceOf<User>;
+           │              ────────┬───────  
+           │                      ╰───────── InstanceOf can only be applied to a class
+        ───╯
+        ");
+    }
+    #[test]
+    fn instance_of_classes_with_the_same_name() {
+        insta::assert_snapshot!(failure_multifile(&[
+            ("a.ts", "export class User { name!: string; }"),
+            ("b.ts", "export class User { id!: string; }"),
+            (
+                "entry.ts",
+                r#"
+    import { User as A } from "./a";
+    import { User as B } from "./b";
+    parse.buildParsers<{ A: InstanceOf<A>, B: InstanceOf<B> }>();
+  "#
+            ),
+        ]), @r"
+        Error: Another class named 'User' is used with InstanceOf, classes are passed to buildParsers by name
+           ╭─[entry.ts:4:48]
+           │
+         4 │     parse.buildParsers<{ A: InstanceOf<A>, B: InstanceOf<B> }>();
+           │                                               ──────┬──────  
+           │                                                     ╰──────── Another class named 'User' is used with InstanceOf, classes are passed to buildParsers by name
+        ───╯
+        ");
+    }
+    #[test]
+    fn class_field_without_type() {
+        let from = r#"
+    class User { name; }
+    parse.buildParsers<{ X: User }>();
+  "#;
+        insta::assert_snapshot!(failure(from), @r"
+        Error: Property should have a type annotation
+           ╭─[entry.ts:2:19]
+           │
+         2 │     class User { name; }
+           │                  ──┬──  
+           │                    ╰──── Property should have a type annotation
+        ───╯
+        ");
+    }
+    #[test]
+    fn namespace_member_not_exported() {
+        let from = r#"
+    namespace Api {
+      type Hidden = string;
+    }
+    parse.buildParsers<{ X: Api.Hidden }>();
+  "#;
+        insta::assert_snapshot!(failure(from), @r"
+        Error: Cannot resolve type 'entry.ts::Api::Hidden'
+           ╭─[entry.ts:5:30]
+           │
+         5 │     parse.buildParsers<{ X: Api.Hidden }>();
+           │                             ─────┬────  
+           │                                  ╰────── Cannot resolve type 'entry.ts::Api::Hidden'
+        ───╯
+        ");
+    }
+    #[test]
+    fn parser_factory_param_is_not_a_parser() {
+        let from = r#"
+    type Box<T> = { value: T };
+    parse.buildParsers<{ Box<T>(value: T): BeffParser<Box<T>> }>();
+  "#;
+        insta::assert_snapshot!(failure(from), @r"
+        Error: Parser factory parameters should be a BeffParser of one of its type parameters
+           ╭─[entry.ts:3:34]
+           │
+         3 │     parse.buildParsers<{ Box<T>(value: T): BeffParser<Box<T>> }>();
+           │                                 ────┬───  
+           │                                     ╰───── Parser factory parameters should be a BeffParser of one of its type parameters
+        ───╯
+        ");
+    }
+    #[test]
+    fn parser_factory_unused_type_param() {
+        let from = r#"
+    type Box<T> = { value: T };
+    parse.buildParsers<{ Box<T, U>(value: BeffParser<T>): BeffParser<Box<T>> }>();
+  "#;
+        insta::assert_snapshot!(failure(from), @r"
+        Error: Type parameter U should be the type of a parser factory parameter
+           ╭─[entry.ts:3:27]
+           │
+         3 │     parse.buildParsers<{ Box<T, U>(value: BeffParser<T>): BeffParser<Box<T>> }>();
+           │                          ─────────────────────────┬─────────────────────────  
+           │                                                   ╰─────────────────────────── Type parameter U should be the type of a parser factory parameter
+        ───╯
+        ");
+    }
+    #[test]
+    fn enum_member_after_string_member() {
+        let from = r#"
+    enum E { A = "a", B }
+    parse.buildParsers<{ E: E }>();
+  "#;
+        insta::assert_snapshot!(failure(from), @r#"
+        Error: Enum member must have an initializer
+           ╭─[entry.ts:2:24]
+           │
+         2 │     enum E { A = "a", B }
+           │                       ┬  
+           │                       ╰── Enum member must have an initializer
+        ───╯
+        "#);
+    }
+
+    #[test]
+    fn jsdoc_tag_not_a_count() {
+        insta::assert_snapshot!(failure(r#"
+        export type User = {
+            /** @minLength three */
+            name: string,
+        };
+        parse.buildParsers<{ User: User }>();
+        "#), @r"
+        Error: JSDoc tag @minLength must be a non-negative integer
+           ╭─[entry.ts:4:14]
+           │
+         4 │             name: string,
+           │             ──────┬──────  
+           │                   ╰──────── JSDoc tag @minLength must be a non-negative integer
+        ───╯
+        ");
+    }
+
+    #[test]
+    fn tuple_required_element_after_optional() {
+        insta::assert_snapshot!(failure(r#"
+        export type T = [a?: string, b: number];
+        parse.buildParsers<{ T: T }>();
+        "#), @r"
+        Error: A required tuple element cannot follow an optional element
+           ╭─[entry.ts:2:39]
+           │
+         2 │         export type T = [a?: string, b: number];
+           │                                      ────┬────  
+           │                                          ╰────── A required tuple element cannot follow an optional element
+        ───╯
+        ");
+    }
+
+    #[test]
+    fn interface_merged_property_conflict() {
+        insta::assert_snapshot!(failure(r#"
+        interface User { id: string }
+        interface User { id: number }
+        parse.buildParsers<{ User: User }>();
+        "#), @r"
+        Error: Subsequent declarations of property 'id' must have the same type
+           ╭─[entry.ts:3:10]
+           │
+         3 │         interface User { id: number }
+           │         ──────────────┬──────────────  
+           │                       ╰──────────────── Subsequent declarations of property 'id' must have the same type
+        ───╯
+        ");
+    }
+
+    #[test]
+    fn interface_override_not_assignable_to_base() {
+        insta::assert_snapshot!(failure(r#"
+        interface Wide { x: string | number }
+        interface Bad extends Wide { x: boolean }
+        parse.buildParsers<{ Bad: Bad }>();
+        "#), @r"
+        Error: Property 'x' is not assignable to the same property in the base type
+           ╭─[entry.ts:3:10]
+           │
+         3 │         interface Bad extends Wide { x: boolean }
+           │         ────────────────────┬────────────────────  
+           │                             ╰────────────────────── Property 'x' is not assignable to the same property in the base type
+        ───╯
+        ");
+    }
+
+    #[test]
+    fn instantiation_expression_on_non_generic_function() {
+        insta::assert_snapshot!(failure(r#"
+        const f = (value: string) => value;
+        type T = ReturnType<typeof f<number>>;
+        parse.buildParsers<{ T: T }>();
+        "#), @r"
+        Error: Type argument count mismatch
+           ╭─[entry.ts:3:30]
+           │
+         3 │         type T = ReturnType<typeof f<number>>;
+           │                             ────────┬───────  
+           │                                     ╰───────── Type argument count mismatch
+        ───╯
+        ");
+    }
+
+    #[test]
+    fn invalid_route_key() {
+        insta::assert_snapshot!(failure(r#"
+            parse.buildRoutes<{ "FETCH /users": { response: string } }>();
+        "#), @r#"
+        Error: Route key 'FETCH /users' should be a method and a path, like "GET /users/{id}"
+           ╭─[entry.ts:2:34]
+           │
+         2 │             parse.buildRoutes<{ "FETCH /users": { response: string } }>();
+           │                                 ──────────────────┬─────────────────  
+           │                                                   ╰─────────────────── Route key 'FETCH /users' should be a method and a path, like "GET /users/{id}"
+        ───╯
+        "#);
+    }
+
+    #[test]
+    fn tpl_lit_too_complex() {
+        insta::assert_snapshot!(failure(r#"
+        type P = `a${string}` | "b";
+        type T = `${P}${P}${P}${P}${P}${P}${P}${P}${P}${P}${P}${P}${P}${P}${P}${P}${P}`;
+        parse.buildParsers<{ T: T }>();
+        "#), @r"
+        Error: Template literal type produces a union type that is too complex to represent
+           ╭─[entry.ts:3:19]
+           │
+         3 │         type T = `${P}${P}${P}${P}${P}${P}${P}${P}${P}${P}${P}${P}${P}${P}${P}${P}${P}`;
+           │                  ───────────────────────────────────┬──────────────────────────────────  
+           │                                                     ╰──────────────────────────────────── Template literal type produces a union type that is too complex to represent
+        ───╯
+        ");
+    }
+
+    #[test]
+    fn infer_in_unsupported_position() {
+        insta::assert_snapshot!(failure(r#"
+        type Unwrap<T> = T extends Promise<infer U> ? U : never;
+        parse.buildParsers<{ T: Unwrap<Promise<string>> }>();
+        "#), @r"
+        Error: Cannot infer a type from this position of the extends clause
+           ╭─[entry.ts:2:37]
+           │
+         2 │         type Unwrap<T> = T extends Promise<infer U> ? U : never;
+           │                                    ────────┬───────  
+           │                                            ╰───────── Cannot infer a type from this position of the extends clause
+        ───╯
+        ");
+    }
+
+    #[test]
+    fn mapped_type_key_remapping_too_complex() {
+        insta::assert_snapshot!(failure(r#"
+        type D = "0" | "1" | "2" | "3" | "4" | "5" | "6" | "7" | "8" | "9";
+        type Codes<T> = { [K in keyof T as `${K}${D}${D}${D}${D}${D}${D}`]: T[K] };
+        parse.buildParsers<{ B: Codes<{ a: string }> }>();
+        "#), @r"
+        Error: Template literal type produces a union type that is too complex to represent
+           ╭─[entry.ts:3:26]
+           │
+         3 │         type Codes<T> = { [K in keyof T as `${K}${D}${D}${D}${D}${D}${D}`]: T[K] };
+           │                         ─────────────────────────────┬────────────────────────────  
+           │                                                      ╰────────────────────────────── Template literal type produces a union type that is too complex to represent
+        ───╯
+        ");
+    }
 }
@@ -95,7 +95,7 @@ mod tests {
 
         type Identified__string__ = { "id": string, "next": (null | Identified__string__) };
 
-        type UserWithData_____x___boolean____ = ({ "data": { "x": boolean }, "next": (null | UserWithData_____x___boolean____) } & Dated__Date__ & Identified__string__);
+        type UserWithData_____x___boolean____ = { "createdAt": Date, "data": { "x": boolean }, "id": string, "next": (null | UserWithData_____x___boolean____), "updatedAt": Date };
 
 
         type BuiltParsers = {
@@ -131,6 +131,153 @@ mod tests {
         ");
     }
 
+    #[test]
+    fn interface_declaration_merging() {
+        let from = r#"
+        /** A user */
+        export interface User {
+            id: string;
+        }
+        export interface User {
+            id: string;
+            name: string;
+        }
+        interface Page<T> {
+            items: T[];
+        }
+        interface Page<T> {
+            total: number;
+        }
+        parse.buildParsers<{ User: User, Page: Page<User> }>();
+      "#;
+        insta::assert_snapshot!(print_types(from), @r#"
+        type Page__User__ = { "items": Array<User>, "total": number };
+
+        type User = { "id": string, "name": string };
+
+
+        type BuiltParsers = {
+          Page: Page__User__,
+          User: User,
+        }
+        "#);
+    }
+
+    #[test]
+    fn interface_module_augmentation() {
+        insta::assert_snapshot!(print_types_multifile(&[
+            (
+                "request.ts",
+                r#"
+                    export interface Request {
+                        path: string;
+                    }
+                "#,
+            ),
+            (
+                "session.ts",
+                r#"
+                    export type Session = { token: string };
+                "#,
+            ),
+            (
+                "augment.ts",
+                r#"
+                    import { Session } from "./session";
+                    declare module "./request" {
+                        interface Request {
+                            session: Session;
+                        }
+                    }
+                "#,
+            ),
+            (
+                "entry.ts",
+                r#"
+                    import { Request } from "./request";
+                    import "./augment";
+                    parse.buildParsers<{ Request: Request }>();
+                "#
+            )
+        ]), @r#"
+        type Request = { "path": string, "session": Session };
+
+        type Session = { "token": string };
+
+
+        type BuiltParsers = {
+          Request: Request,
+        }
+        "#);
+    }
+
+    #[test]
+    fn instantiation_expressions_and_generic_import_types() {
+        insta::assert_snapshot!(print_types_multifile(&[
+            (
+                "helpers.ts",
+                r#"
+                    export const makeParser = <T>(value: T): { value: T, list: T[] } => ({ value, list: [value] });
+                    export function wrap<T extends object>(data: T): { data: T } {
+                        return { data };
+                    }
+                    export type Box<T> = { inner: T };
+                "#,
+            ),
+            (
+                "entry.ts",
+                r#"
+                    import { makeParser, wrap } from "./helpers";
+                    type User = { id: string };
+                    type Made = ReturnType<typeof makeParser<User>>;
+                    type Wrapped = ReturnType<typeof wrap<User>>;
+                    type Imported = import("./helpers").Box<User>;
+                    type ImportedFn = Parameters<typeof import("./helpers").wrap<User[]>>;
+                    parse.buildParsers<{ Made: Made, Wrapped: Wrapped, Imported: Imported, ImportedFn: ImportedFn }>();
+                "#
+            )
+        ]), @r#"
+        type Imported = Box__User__;
+
+        type ImportedFn = [Array<User>];
+
+        type Made = { "list": Array<User>, "value": User };
+
+        type User = { "id": string };
+
+        type Wrapped = { "data": User };
+
+        type Box__User__ = { "inner": User };
+
+
+        type BuiltParsers = {
+          Imported: Imported,
+          ImportedFn: ImportedFn,
+          Made: Made,
+          Wrapped: Wrapped,
+        }
+        "#);
+    }
+
+    #[test]
+    fn instantiation_expression_in_value() {
+        insta::assert_snapshot!(print_types(
+            r#"
+                const makeParser = <T>(value: T): { value: T } => ({ value });
+                const makeNumber = makeParser<number>;
+                type Made = ReturnType<typeof makeNumber>;
+                parse.buildParsers<{ Made: Made }>();
+            "#
+        ), @r#"
+        type Made = { "value": number };
+
+
+        type BuiltParsers = {
+          Made: Made,
+        }
+        "#);
+    }
+
     #[test]
     fn export_default_value() {
         insta::assert_snapshot!(print_types_multifile(&[
@@ -267,6 +414,21 @@ mod tests {
         ");
     }
 
+    #[test]
+    fn tuple_spread_optional_before_required() {
+        insta::assert_snapshot!(print_types(r#"
+            export type T = [...[x: number, y?: number], string];
+            parse.buildParsers<{ T: T }>();
+        "#), @r"
+        type T = [x: number, y: (undefined | number), string];
+
+
+        type BuiltParsers = {
+          T: T,
+        }
+        ");
+    }
+
     #[test]
     fn import_type_qualifier() {
         insta::assert_snapshot!(print_types_multifile(&[
@@ -530,6 +692,7 @@ mod tests {
         "#), @r#"
         const direct_hoist_0 = new RefRuntype(undefined, "T");
         const direct_hoist_1 = new TypeofRuntype(undefined, "string");
+        const RequiredClasses = [];
         const namedRuntypes = {
             "T": direct_hoist_1
         };
@@ -1186,6 +1349,7 @@ mod tests {
         "#), @r#"
         const direct_hoist_0 = new RefRuntype(undefined, "Alias");
         const direct_hoist_1 = new TypedArrayRuntype(undefined, "Uint8Array");
+        const RequiredClasses = [];
         const namedRuntypes = {
             "Alias": direct_hoist_1
         };
@@ -1407,4 +1571,292 @@ mod tests {
         }
         ");
     }
+
+    #[test]
+    fn class_instance_fields() {
+        let from = r#"
+    class Base {
+      id!: string;
+      private secret = 1;
+      static count = 0;
+    }
+    class CreateUserDto extends Base {
+      /** the display name */
+      name!: string;
+      age?: number;
+      isActive = false;
+      #hidden = 1;
+      constructor(public readonly role: "admin" | "user", private token: string, count?: number) {
+        super();
+      }
+      greet(): string {
+        return "hi";
+      }
+      get upper(): string {
+        return this.name;
+      }
+    }
+    class Box<T> {
+      value!: T;
+      constructor(value: T) {}
+    }
+    type Args = ConstructorParameters<typeof CreateUserDto>;
+    type BoxArgs = ConstructorParameters<typeof Box>;
+    parse.buildParsers<{ CreateUserDto: CreateUserDto, Box: Box<number>, Args: Args, BoxArgs: BoxArgs }>();
+  "#;
+        insta::assert_snapshot!(print_types(from), @r#"
+        type Args = [("admin" | "user"), string, (undefined | number)?];
+
+        type Base = { "id": string };
+
+        type Box__number__ = { "value": number };
+
+        type BoxArgs = [any];
+
+        type CreateUserDto = { "age"?: number, "id": string, "isActive": boolean, "name": string, "role": ("admin" | "user") };
+
+
+        type BuiltParsers = {
+          Args: Args,
+          Box: Box__number__,
+          BoxArgs: BoxArgs,
+          CreateUserDto: CreateUserDto,
+        }
+        "#);
+    }
+
+    #[test]
+    fn class_imported() {
+        insta::assert_snapshot!(print_types_multifile(&[
+            (
+                "t.ts",
+                r#"
+                    export class A { a!: string }
+                    class B { constructor(public b: number) {} }
+                    export { B };
+                "#,
+            ),
+            (
+                "entry.ts",
+                r#"
+                    import { A, B } from "./t";
+                    type Args = ConstructorParameters<typeof B>;
+                    parse.buildParsers<{ A: A, B: B, Args: Args }>();
+                "#
+            )
+        ]), @r#"
+        type Args = [number];
+
+        type A = { "a": string };
+
+        type B = { "b": number };
+
+
+        type BuiltParsers = {
+          A: A,
+          Args: Args,
+          B: B,
+        }
+        "#);
+    }
+
+    #[test]
+    fn namespace_qualified_access() {
+        let from = r#"
+    type Id = string;
+    namespace Api {
+      export type User = { id: Id, role: Role };
+      type Role = "admin" | "user";
+      export namespace Inner {
+        export type Post = { author: User };
+      }
+    }
+    namespace A.B {
+      export type C = number;
+    }
+    parse.buildParsers<{ User: Api.User, Post: Api.Inner.Post, C: A.B.C }>();
+  "#;
+        insta::assert_snapshot!(print_types(from), @r#"
+        type Id = string;
+
+        type C = number;
+
+        type Role = ("admin" | "user");
+
+        type User = { "id": Id, "role": Role };
+
+        type Post = { "author": User };
+
+
+        type BuiltParsers = {
+          C: C,
+          Post: Post,
+          User: User,
+        }
+        "#);
+    }
+
+    #[test]
+    fn namespace_exported_from_other_file() {
+        insta::assert_snapshot!(print_types_multifile(&[
+            (
+                "t.ts",
+                r#"
+                    export declare namespace Shapes {
+                      type Circle = { radius: number };
+                      type Square = { side: number };
+                    }
+                "#,
+            ),
+            (
+                "entry.ts",
+                r#"
+                    import { Shapes } from "./t";
+                    parse.buildParsers<{ Circle: Shapes.Circle, Square: Shapes.Square }>();
+                "#
+            )
+        ]), @r#"
+        type Circle = { "radius": number };
+
+        type Square = { "side": number };
+
+
+        type BuiltParsers = {
+          Circle: Circle,
+          Square: Square,
+        }
+        "#);
+    }
+
+    #[test]
+    fn export_assignment_namespace() {
+        insta::assert_snapshot!(print_types_multifile(&[
+            (
+                "lib.d.ts",
+                r#"
+                    declare namespace lib {
+                      interface Options { verbose: boolean }
+                    }
+                    export = lib;
+                "#,
+            ),
+            (
+                "entry.ts",
+                r#"
+                    import * as star from "./lib.d";
+                    import def from "./lib.d";
+                    import { Options } from "./lib.d";
+                    parse.buildParsers<{ A: star.Options, B: def.Options, C: Options }>();
+                "#
+            )
+        ]), @r#"
+        type Options = { "verbose": boolean };
+
+
+        type BuiltParsers = {
+          A: Options,
+          B: Options,
+          C: Options,
+        }
+        "#);
+    }
+
+    #[test]
+    fn generic_parser_factory() {
+        let from = r#"
+    type Pair<A, B> = { first: A; second: B };
+    parse.buildParsers<{
+      Pair<A, B>(second: BeffParser<B>, first: BeffParser<A>): BeffParser<Pair<A, B[]>>;
+    }>();
+  "#;
+        insta::assert_snapshot!(print_types(from), @r#"
+        type Pair__A_Array_B___ = { "first": A, "second": Array<B> };
+
+
+        type BuiltParsers = {
+          Pair: <B, A>(B, A) => Pair__A_Array_B___,
+        }
+        "#);
+    }
+
+    #[test]
+    fn enum_auto_numbered_and_computed() {
+        let from = r#"
+    enum Status { Active, Inactive, Deleted = 10, Archived }
+    const enum Flags { None = 0, Read = 1 << 0, Write = 1 << 1, ReadWrite = Read | Write, All = ~(~0 << 3) }
+    enum Paths { Root = "/", Users = `${Root}users`, Admin = Paths.Users + "/admin" }
+    enum Mixed { A = Status.Deleted * 2, B }
+    const archived = Status.Archived;
+    parse.buildParsers<{
+      Status: Status,
+      Flags: Flags,
+      Paths: Paths,
+      Mixed: Mixed,
+      Inactive: Status.Inactive,
+      ReadWrite: Flags.ReadWrite,
+      Archived: typeof archived,
+    }>();
+  "#;
+        insta::assert_snapshot!(print_types(from), @r#"
+        type Flags = (0 | 1 | 2 | 3 | 7);
+
+        type Mixed = (20 | 21);
+
+        type Paths = ("/" | "/users" | "/users/admin");
+
+        type Status = (0 | 1 | 10 | 11);
+
+        type Flags__ReadWrite = 3;
+
+        type Status__Inactive = 1;
+
+
+        type BuiltParsers = {
+          Archived: 11,
+          Flags: Flags,
+          Inactive: Status__Inactive,
+          Mixed: Mixed,
+          Paths: Paths,
+          ReadWrite: Flags__ReadWrite,
+          Status: Status,
+        }
+        "#);
+    }
+    #[test]
+    fn template_literal_constants_and_nesting() {
+        let from = r#"
+    type Version = `v${1 | 2}`;
+    type Flag = `${true}-${null}-${undefined}`;
+    type Amount = `${bigint}n`;
+    type Inner = `b${string}` | "c";
+    type Outer = `a${Inner}`;
+    type Scaled = `${number}x`;
+    const prefix = "id";
+    const key = `${prefix}_${2}` as const;
+    parse.buildParsers<{ Version: Version, Flag: Flag, Amount: Amount, Outer: Outer, Scaled: Scaled, Key: typeof key }>();
+  "#;
+        insta::assert_snapshot!(print_types(from), @r#"
+        type Amount = `${bigint}n`;
+
+        type Flag = "true-null-undefined";
+
+        type Inner = (`b${string}` | "c");
+
+        type Outer = (`ab${string}` | "ac");
+
+        type Scaled = `${number}x`;
+
+        type Version = `v("1" | "2")`;
+
+
+        type BuiltParsers = {
+          Amount: Amount,
+          Flag: Flag,
+          Key: `${string}_2`,
+          Outer: Outer,
+          Scaled: Scaled,
+          Version: Version,
+        }
+        "#);
+    }
 }
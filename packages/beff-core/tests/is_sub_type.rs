@@ -39,7 +39,7 @@ mod tests {
         RuntypeUUID {
             ty: RuntypeName::Address(TypeAddress {
                 file: BffFileName::new("any_file.bff".into()),
-                name: name,
+                name,
             }),
             type_arguments: vec![],
         }
@@ -153,6 +153,33 @@ mod tests {
         );
         assert!(!res);
     }
+    #[test]
+    fn ref_through_union() {
+        let json = RuntypeUUID {
+            type_arguments: vec![Runtype::string()],
+            ..rt_uuid("Json".into())
+        };
+        let definitions = [NamedSchema {
+            name: json.clone(),
+            schema: Runtype::any_of(vec![
+                Runtype::string(),
+                Runtype::array(Runtype::ref_(json.clone()).into()),
+            ]),
+        }];
+        let definitions = definitions.iter().collect::<Vec<&NamedSchema>>();
+
+        let t1 = Runtype::ref_(json);
+        let t2 = Runtype::any_of(vec![
+            Runtype::string(),
+            Runtype::array(Runtype::array(Runtype::string().into()).into()),
+        ]);
+        let t3 = Runtype::array(Runtype::number().into());
+
+        assert!(rt_is_sub_type(&t2, &t1, &definitions, &definitions));
+        assert!(!rt_is_sub_type(&t1, &t2, &definitions, &definitions));
+        assert!(!rt_is_sub_type(&t3, &t1, &definitions, &definitions));
+        assert!(rt_is_sub_type(&t1, &t1, &definitions, &definitions));
+    }
 
     #[test]
     fn mappings4() {
@@ -209,14 +236,8 @@ mod tests {
         let definitions = vec![];
 
         let t1 = Runtype::object(vec![
-            (
-                "a".into(),
-                Runtype::single_string_const("abc".into()).required(),
-            ),
-            (
-                "b".into(),
-                Runtype::single_string_const("def".into()).required(),
-            ),
+            ("a".into(), Runtype::single_string_const("abc").required()),
+            ("b".into(), Runtype::single_string_const("def").required()),
         ]);
         let t2 = Runtype::object(vec![("a".into(), Runtype::string().required())]);
 
@@ -231,7 +252,7 @@ mod tests {
 
         let t1 = Runtype::object(vec![(
             "a".into(),
-            Runtype::single_string_const("abc".into()).required(),
+            Runtype::single_string_const("abc").required(),
         )]);
         let t2 = Runtype::object(vec![("a".into(), Runtype::string().required())]);
 
@@ -244,7 +265,7 @@ mod tests {
     fn array2() {
         let definitions = vec![];
 
-        let t1 = Runtype::array(Runtype::single_string_const("abc".into()).into());
+        let t1 = Runtype::array(Runtype::single_string_const("abc").into());
         let t2 = Runtype::array(Runtype::string().into());
 
         let res = rt_is_sub_type(&t1, &t2, &definitions, &definitions);
@@ -327,7 +348,7 @@ mod tests {
     fn strings_repr() {
         let definitions = vec![];
 
-        let t1 = Runtype::single_string_const("a".into());
+        let t1 = Runtype::single_string_const("a");
         let t2 = Runtype::tpl_lit_type(TplLitType(vec![TplLitTypeItem::StringConst("a".into())]));
 
         let mut ctx = SemTypeContext::new();
@@ -391,7 +412,7 @@ mod tests {
         let res = rt_is_sub_type(&t1, &t2, &definitions, &definitions);
         assert!(res);
 
-        let t1 = Runtype::single_string_const("abc".into());
+        let t1 = Runtype::single_string_const("abc");
         let t2 = Runtype::string();
         let res = rt_is_sub_type(&t1, &t2, &definitions, &definitions);
         assert!(res);
@@ -466,7 +487,7 @@ mod tests {
     fn string_const_subtyping() {
         let definitions = vec![];
 
-        let const_a = Runtype::single_string_const("a".into());
+        let const_a = Runtype::single_string_const("a");
         let all_strings = Runtype::string();
         let mut ctx = SemTypeContext::new();
 
@@ -861,8 +882,8 @@ mod tests {
         let mut ctx = SemTypeContext::new();
 
         let format_id = Runtype::string_with_format(CustomFormat("id".into(), vec![]));
-        let literal_abc = Runtype::single_string_const("abc".into());
-        let literal_def = Runtype::single_string_const("def".into());
+        let literal_abc = Runtype::single_string_const("abc");
+        let literal_def = Runtype::single_string_const("def");
 
         let format_sem = format_id
             .to_sem_type(&definitions, &mut ctx)
@@ -1113,13 +1134,10 @@ mod tests {
             vs: vec![("a".into(), Runtype::string().required())]
                 .into_iter()
                 .collect(),
-            indexed_properties: Some(
-                IndexedProperty {
-                    key: Runtype::string().into(),
-                    value: Runtype::string().required().into(),
-                }
-                .into(),
-            ),
+            indexed_properties: vec![IndexedProperty {
+                key: Runtype::string(),
+                value: Runtype::string().required(),
+            }],
         });
 
         let expected_st = expected
@@ -1129,6 +1147,79 @@ mod tests {
         assert!(intersection.is_same_type(&expected_st, &mut ctx).unwrap());
     }
 
+    #[test]
+    fn multiple_index_signatures() {
+        let definitions = vec![];
+
+        let data_key = Runtype::tpl_lit_type(TplLitType(vec![
+            TplLitTypeItem::StringConst("data-".into()),
+            TplLitTypeItem::String,
+        ]));
+        let multi = |v: Runtype| {
+            Runtype::new(RuntypeKind::Object {
+                vs: Default::default(),
+                indexed_properties: vec![
+                    IndexedProperty {
+                        key: Runtype::string(),
+                        value: Runtype::any_of(vec![Runtype::string(), Runtype::number()])
+                            .required(),
+                    },
+                    IndexedProperty {
+                        key: data_key.clone(),
+                        value: v.required(),
+                    },
+                ],
+            })
+        };
+        // { [k: string]: string | number; [k: `data-${string}`]: string }
+        let attrs = multi(Runtype::string());
+
+        // data-* keys must satisfy both signatures
+        let data_string = Runtype::object(vec![("data-id".into(), Runtype::string().required())]);
+        let data_number = Runtype::object(vec![("data-id".into(), Runtype::number().required())]);
+        let other_number = Runtype::object(vec![("id".into(), Runtype::number().required())]);
+        assert!(rt_is_sub_type(
+            &data_string,
+            &attrs,
+            &definitions,
+            &definitions
+        ));
+        assert!(!rt_is_sub_type(
+            &data_number,
+            &attrs,
+            &definitions,
+            &definitions
+        ));
+        assert!(rt_is_sub_type(
+            &other_number,
+            &attrs,
+            &definitions,
+            &definitions
+        ));
+
+        let record = Runtype::record(
+            Runtype::string(),
+            Runtype::any_of(vec![Runtype::string(), Runtype::number()]).required(),
+        );
+        assert!(rt_is_sub_type(&attrs, &record, &definitions, &definitions));
+        assert!(!rt_is_sub_type(&record, &attrs, &definitions, &definitions));
+
+        // a narrower data-* value makes a subtype, a wider one does not
+        let attrs_const = multi(Runtype::single_string_const("x"));
+        assert!(rt_is_sub_type(
+            &attrs_const,
+            &attrs,
+            &definitions,
+            &definitions
+        ));
+        assert!(!rt_is_sub_type(
+            &attrs,
+            &attrs_const,
+            &definitions,
+            &definitions
+        ));
+    }
+
     #[test]
     fn object_is_subtype_of_record_string_string() {
         let definitions = vec![];
@@ -1594,7 +1685,7 @@ mod tests {
     fn recursive_types_mutual() {
         // type A = { b: B }
         // type B = { a: A }
-        let definitions = vec![
+        let definitions = [
             NamedSchema {
                 name: rt_uuid("A".into()),
                 schema: Runtype::object(vec![(
@@ -1727,7 +1818,7 @@ mod tests {
         assert!(rt_is_sub_type(&t1, &t2, &definitions, &definitions));
 
         let t3 = Runtype::map(
-            Box::new(Runtype::single_string_const("abc".into())),
+            Box::new(Runtype::single_string_const("abc")),
             Box::new(Runtype::number()),
         );
         // Map<"abc", number> is subtype of Map<string, number>
@@ -1761,7 +1852,7 @@ mod tests {
 
         assert!(rt_is_sub_type(&t1, &t2, &definitions, &definitions));
 
-        let t3 = Runtype::set(Box::new(Runtype::single_string_const("abc".into())));
+        let t3 = Runtype::set(Box::new(Runtype::single_string_const("abc")));
         // Set<"abc"> is subtype of Set<string>
         assert!(rt_is_sub_type(&t3, &t1, &definitions, &definitions));
         assert!(!rt_is_sub_type(&t1, &t3, &definitions, &definitions));
@@ -1771,4 +1862,81 @@ mod tests {
         assert!(!rt_is_sub_type(&t1, &arr, &definitions, &definitions));
         assert!(!rt_is_sub_type(&arr, &t1, &definitions, &definitions));
     }
+
+    #[test]
+    fn tuples_with_optional_elements() {
+        let definitions = vec![];
+        let sub = |a: &Runtype, b: &Runtype| rt_is_sub_type(a, b, &definitions, &definitions);
+
+        // [number, string?]
+        let t = Runtype::variadic_tuple(
+            vec![Runtype::number()],
+            vec![Runtype::string()],
+            None,
+            vec![],
+        );
+        let one = Runtype::tuple(vec![Runtype::number()], None);
+        let two = Runtype::tuple(vec![Runtype::number(), Runtype::string()], None);
+        let rest = Runtype::tuple(vec![Runtype::number()], Some(Runtype::string().into()));
+
+        assert!(sub(&one, &t));
+        assert!(sub(&two, &t));
+        assert!(sub(&t, &rest));
+        assert!(!sub(&t, &two));
+        assert!(!sub(&rest, &t));
+    }
+
+    #[test]
+    fn tuples_with_elements_after_rest() {
+        let definitions = vec![];
+        let sub = |a: &Runtype, b: &Runtype| rt_is_sub_type(a, b, &definitions, &definitions);
+        let numbers = || Some(Box::new(Runtype::number()));
+
+        // [string, ...number[], boolean]
+        let t = Runtype::variadic_tuple(
+            vec![Runtype::string()],
+            vec![],
+            numbers(),
+            vec![Runtype::boolean()],
+        );
+        let shortest = Runtype::tuple(vec![Runtype::string(), Runtype::boolean()], None);
+        let longer = Runtype::tuple(
+            vec![
+                Runtype::string(),
+                Runtype::number(),
+                Runtype::number(),
+                Runtype::boolean(),
+            ],
+            None,
+        );
+        let missing_last = Runtype::tuple(vec![Runtype::string()], numbers());
+
+        assert!(sub(&shortest, &t));
+        assert!(sub(&longer, &t));
+        assert!(!sub(&missing_last, &t));
+        assert!(!sub(&t, &missing_last));
+        assert!(sub(
+            &t,
+            &Runtype::tuple(
+                vec![Runtype::string()],
+                Some(Runtype::any_of(vec![Runtype::number(), Runtype::boolean()]).into())
+            )
+        ));
+        assert!(sub(
+            &t,
+            &Runtype::variadic_tuple(
+                vec![],
+                vec![],
+                Some(Runtype::any().into()),
+                vec![Runtype::boolean()]
+            )
+        ));
+
+        // [...number[], number] and [number, ...number[]] are the same type
+        let last = Runtype::variadic_tuple(vec![], vec![], numbers(), vec![Runtype::number()]);
+        let first = Runtype::tuple(vec![Runtype::number()], numbers());
+        assert!(sub(&last, &first));
+        assert!(sub(&first, &last));
+        assert!(!sub(&Runtype::array(Box::new(Runtype::number())), &last));
+    }
 }
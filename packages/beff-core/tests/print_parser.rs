@@ -2,7 +2,9 @@
 mod tests {
 
     use beff_core::test_tools::{
-        failure, print_cgen, print_cgen_multifile, print_types, print_types_multifile,
+        failure, print_aot, print_aot_error, print_cgen, print_cgen_multifile, print_compiled_dts,
+        print_dts, print_json_schema, print_json_schema_error, print_openapi,
+        print_source_map_multifile, print_types, print_types_multifile,
     };
 
     #[test]
@@ -370,6 +372,68 @@ mod tests {
         "#);
     }
     #[test]
+    fn ok_object_utilities_distribute_over_unions() {
+        let from = r#"
+        type Circle = { kind: "circle", radius: number, label?: string };
+        type Square = { kind: "square", size: number, label: string };
+        type Shape = Circle | Square;
+        type Loose = { id?: string };
+        type Dict = { id: string, [key: string]: string };
+        type PartialShape = Partial<Shape>;
+        type RequiredShape = Required<Shape>;
+        type Labels = Pick<Shape, "kind" | "label">;
+        type WithoutKind = Omit<Shape, "kind">;
+        type StillLoose = Partial<Loose | Circle>;
+        type DictWithoutId = Omit<Dict, "id">;
+        type RequiredDict = Required<Partial<Dict>>;
+        parse.buildParsers<{
+            PartialShape: PartialShape,
+            RequiredShape: RequiredShape,
+            Labels: Labels,
+            WithoutKind: WithoutKind,
+            StillLoose: StillLoose,
+            DictWithoutId: DictWithoutId,
+            RequiredDict: RequiredDict,
+        }>();
+      "#;
+        insta::assert_snapshot!(print_types(from), @r#"
+        type Circle = { "kind": "circle", "label"?: string, "radius": number };
+
+        type Dict = { "id": string, [key: string]: string };
+
+        type DictWithoutId = { [key: string]: string };
+
+        type Labels = { "kind": ("circle" | "square"), "label"?: string };
+
+        type Loose = { "id"?: string };
+
+        type PartialShape = ({ "kind"?: "circle", "label"?: string, "radius"?: number } | { "kind"?: "square", "label"?: string, "size"?: number });
+
+        type RequiredDict = { "id": string, [key: string]: string };
+
+        type RequiredShape = ({ "kind": "circle", "label": string, "radius": number } | Square);
+
+        type Shape = (Circle | Square);
+
+        type Square = { "kind": "square", "label": string, "size": number };
+
+        type StillLoose = ({ "kind"?: "circle", "label"?: string, "radius"?: number } | Loose);
+
+        type WithoutKind = { "label"?: string };
+
+
+        type BuiltParsers = {
+          DictWithoutId: DictWithoutId,
+          Labels: Labels,
+          PartialShape: PartialShape,
+          RequiredDict: RequiredDict,
+          RequiredShape: RequiredShape,
+          StillLoose: StillLoose,
+          WithoutKind: WithoutKind,
+        }
+        "#);
+    }
+    #[test]
     fn fail_partial_on_non_object() {
         let from = r#"
         type P = Partial<string>;
@@ -554,6 +618,50 @@ mod tests {
         "#);
     }
     #[test]
+    fn ok_interface_extends_qualified_and_overridden() {
+        insta::assert_snapshot!(print_types(
+            r#"
+        namespace Models {
+            export interface User {
+                id: string,
+                name: string,
+                role: string,
+            }
+        }
+        interface Box<T> {
+            value: T,
+        }
+        interface Admin extends Models.User {
+            role: "admin",
+        }
+        interface Named extends Pick<Models.User, "id"> {
+            label: string,
+        }
+        interface Wrapped<T> extends Box<T[]> {
+            size: number,
+        }
+        parse.buildParsers<{ Admin: Admin, Named: Named, Wrapped: Wrapped<string> }>();
+      "#
+        ), @r#"
+        type Admin = { "id": string, "name": string, "role": "admin" };
+
+        type Box__Array_string___ = { "value": Array<string> };
+
+        type Named = { "id": string, "label": string };
+
+        type Wrapped__string__ = { "size": number, "value": Array<string> };
+
+        type User = { "id": string, "name": string, "role": string };
+
+
+        type BuiltParsers = {
+          Admin: Admin,
+          Named: Named,
+          Wrapped: Wrapped__string__,
+        }
+        "#);
+    }
+    #[test]
     fn ok_repro() {
         insta::assert_snapshot!(print_types(
             r#"
@@ -863,6 +971,7 @@ mod tests {
             direct_hoist_5,
             direct_hoist_8
         ]);
+        const RequiredClasses = [];
         const namedRuntypes = {
             "DiscriminatedUnion4": direct_hoist_9
         };
@@ -980,6 +1089,7 @@ mod tests {
             "updatedAt": direct_hoist_1,
             "workflowID": direct_hoist_1
         }, []);
+        const RequiredClasses = [];
         const namedRuntypes = {
             "CronWorkflowSource": direct_hoist_7,
             "EventWorkflowSource": direct_hoist_10,
@@ -1040,6 +1150,195 @@ mod tests {
         "#);
     }
     #[test]
+    fn ok_extract() {
+        insta::assert_snapshot!(print_types(
+            r#"
+        type Click = { type: "click"; x: number; y: number };
+        type Key = { type: "key"; key: string };
+        type Scroll = { type: "scroll"; delta: number };
+        type Event = Click | Key | Scroll;
+
+        type A = Extract<Event, { type: "click" }>;
+        type B = Extract<Event, { type: "click" | "key" }>;
+        type C = Extract<"a" | "b" | 1, string>;
+        type D = Extract<string, "a">;
+        parse.buildParsers<{ A: A, B: B, C: C, D: D }>();
+      "#
+        ), @r#"
+        type A = Click;
+
+        type B = (Click | Key);
+
+        type C = ("a" | "b");
+
+        type Click = { "type": "click", "x": number, "y": number };
+
+        type D = "a";
+
+        type Event = (Click | Key | Scroll);
+
+        type Key = { "key": string, "type": "key" };
+
+        type Scroll = { "delta": number, "type": "scroll" };
+
+
+        type BuiltParsers = {
+          A: A,
+          B: B,
+          C: C,
+          D: D,
+        }
+        "#);
+    }
+    #[test]
+    fn ok_non_nullable() {
+        insta::assert_snapshot!(print_types(
+            r#"
+        type Manager = { name: string };
+        type User = { name: string, manager: Manager | null | undefined };
+        type A = NonNullable<User["manager"]>;
+        type B = NonNullable<string | null>;
+        type C = NonNullable<null>;
+        parse.buildParsers<{ A: A, B: B, C: C }>();
+      "#
+        ), @r#"
+        type A = Manager;
+
+        type B = string;
+
+        type C = never;
+
+        type Manager = { "name": string };
+
+        type User = { "manager": (null | undefined | Manager), "name": string };
+
+
+        type BuiltParsers = {
+          A: A,
+          B: B,
+          C: C,
+        }
+        "#);
+    }
+    #[test]
+    fn ok_function_utilities() {
+        insta::assert_snapshot!(print_types(
+            r#"
+        type User = { id: string; name: string };
+        type Handler = (req: { userId: string }, verbose?: boolean) => User;
+        type Ctor = new (name: string, ...rest: number[]) => User;
+
+        type A = Parameters<Handler>;
+        type B = Parameters<Handler>[0];
+        type C = ReturnType<Handler>;
+        type D = ConstructorParameters<Ctor>;
+        type E = Awaited<Promise<Promise<User>>>;
+        type F = Awaited<string | Promise<number>>;
+        parse.buildParsers<{ A: A, B: B, C: C, D: D, E: E, F: F }>();
+      "#
+        ), @r#"
+        type A = [{ "userId": string }, (undefined | boolean)?];
+
+        type B = { "userId": string };
+
+        type C = User;
+
+        type Ctor = (new (...args: [string, ...number]) => User);
+
+        type D = [string, ...number];
+
+        type E = User;
+
+        type F = (string | number);
+
+        type Handler = ((...args: [{ "userId": string }, (undefined | boolean)?]) => User);
+
+        type User = { "id": string, "name": string };
+
+
+        type BuiltParsers = {
+          A: A,
+          B: B,
+          C: C,
+          D: D,
+          E: E,
+          F: F,
+        }
+        "#);
+    }
+    #[test]
+    fn ok_typeof_function() {
+        insta::assert_snapshot!(print_types(
+            r#"
+        type User = { id: string; name: string };
+        const getUser = async (id: string, opts: { cache: boolean } = { cache: true }): Promise<User> => {
+            return { id, name: "" };
+        };
+        function createUser(name: string, ...tags: string[]): User {
+            return { id: "", name };
+        }
+        export function assertUser(it: unknown): asserts it is User {}
+
+        type A = Parameters<typeof getUser>;
+        type B = Awaited<ReturnType<typeof getUser>>;
+        type C = Parameters<typeof createUser>;
+        type D = ReturnType<typeof createUser>;
+        type E = ReturnType<typeof assertUser>;
+        parse.buildParsers<{ A: A, B: B, C: C, D: D, E: E }>();
+      "#
+        ), @r#"
+        type A = [string, (undefined | { "cache": boolean })?];
+
+        type B = User;
+
+        type C = [string, ...string];
+
+        type D = User;
+
+        type E = void;
+
+        type User = { "id": string, "name": string };
+
+
+        type BuiltParsers = {
+          A: A,
+          B: B,
+          C: C,
+          D: D,
+          E: E,
+        }
+        "#);
+    }
+    #[test]
+    fn ok_multiple_index_signatures() {
+        insta::assert_snapshot!(print_types(
+            r#"
+        type Attributes = {
+            id: string;
+            [k: string]: string | number | boolean;
+            [k: `data-${string}`]: string;
+            [k: `aria-${string}`]: string | boolean;
+        };
+        type Headers = { [k: string]: string; [k: `x-${string}`]: string };
+        type A = Attributes["data-id"];
+        parse.buildParsers<{ Attributes: Attributes, Headers: Headers, A: A }>();
+      "#
+        ), @r#"
+        type A = string;
+
+        type Attributes = { "id": string, [key: string]: (boolean | string | number), [key: `data-${string}`]: string, [key: `aria-${string}`]: (boolean | string) };
+
+        type Headers = { [key: string]: string, [key: `x-${string}`]: string };
+
+
+        type BuiltParsers = {
+          A: A,
+          Attributes: Attributes,
+          Headers: Headers,
+        }
+        "#);
+    }
+    #[test]
     fn ok_repro_3() {
         insta::assert_snapshot!(print_types(
             r#"
@@ -1150,6 +1449,98 @@ mod tests {
         "#);
     }
     #[test]
+    fn ok_string_intrinsics() {
+        insta::assert_snapshot!(print_types(
+            r#"
+        type Method = "get" | "post";
+        type A = Uppercase<Method>;
+        type B = Capitalize<`on${Method}`>;
+        type C = Lowercase<"X-Request-Id">;
+        type D = Uncapitalize<"UserId">;
+        type E = `on${Capitalize<string>}`;
+        type F = Uppercase<`x-${string}`>;
+        parse.buildParsers<{ A: A, B: B, C: C, D: D, E: E, F: F }>();
+      "#
+        ), @r#"
+        type A = ("GET" | "POST");
+
+        type B = `On("get" | "post")`;
+
+        type C = "x-request-id";
+
+        type D = "userId";
+
+        type E = `on${Capitalize<string>}`;
+
+        type F = `X-${Uppercase<string>}`;
+
+        type Method = ("get" | "post");
+
+
+        type BuiltParsers = {
+          A: A,
+          B: B,
+          C: C,
+          D: D,
+          E: E,
+          F: F,
+        }
+        "#);
+    }
+    #[test]
+    fn ok_string_intrinsics_of_intersections() {
+        insta::assert_snapshot!(print_types(
+            r#"
+        type User = { name: string; age: number };
+        type Getters<T> = { [K in keyof T as `get${Capitalize<K & string>}`]: () => T[K] };
+        type Setters<T> = { [K in keyof T as `set${Capitalize<string & K>}`]: (value: T[K]) => void };
+        type A = Getters<User>;
+        type B = Setters<User>;
+        parse.buildParsers<{ A: A, B: B }>();
+      "#
+        ), @r#"
+        type A = Getters__User__;
+
+        type B = Setters__User__;
+
+        type Getters__User__ = { "getAge": ((...args: []) => number), "getName": ((...args: []) => string) };
+
+        type Setters__User__ = { "setAge": ((...args: [number]) => void), "setName": ((...args: [string]) => void) };
+
+        type User = { "age": number, "name": string };
+
+
+        type BuiltParsers = {
+          A: A,
+          B: B,
+        }
+        "#);
+    }
+    #[test]
+    fn ok_string_intrinsics_regex() {
+        insta::assert_snapshot!(print_cgen(
+            r#"
+        type Header = Lowercase<`x-${string}`>;
+        type EventName = `on${Capitalize<string>}`;
+        parse.buildParsers<{ Header: Header, EventName: EventName }>();
+      "#
+        ), @r#"
+        const direct_hoist_0 = new RefRuntype(undefined, "Header");
+        const direct_hoist_1 = new RefRuntype(undefined, "EventName");
+        const direct_hoist_2 = new RegexRuntype(undefined, /^(on)([^a-z].*)?$/, "`on${Capitalize<string>}`");
+        const direct_hoist_3 = new RegexRuntype(undefined, /^(x-)([^A-Z]*)$/, "`x-${Lowercase<string>}`");
+        const RequiredClasses = [];
+        const namedRuntypes = {
+            "EventName": direct_hoist_2,
+            "Header": direct_hoist_3
+        };
+        const buildParsersInput = {
+            "Header": direct_hoist_0,
+            "EventName": direct_hoist_1
+        };
+        "#);
+    }
+    #[test]
     fn ok_repro4() {
         insta::assert_snapshot!(print_types(
             r#"
@@ -1465,6 +1856,7 @@ mod tests {
         ), @r#"
         const direct_hoist_0 = new RefRuntype(undefined, "Alias");
         const direct_hoist_1 = new TypeofRuntype(undefined, "string");
+        const RequiredClasses = [];
         const namedRuntypes = {
             "Alias": direct_hoist_1
         };
@@ -1485,6 +1877,7 @@ mod tests {
         const direct_hoist_0 = new RefRuntype(undefined, "Alias");
         const direct_hoist_1 = new TypeofRuntype(undefined, "string");
         const direct_hoist_2 = new ArrayRuntype(undefined, direct_hoist_1);
+        const RequiredClasses = [];
         const namedRuntypes = {
             "Alias": direct_hoist_2
         };
@@ -1528,6 +1921,7 @@ mod tests {
             "BAZ_VALUES": direct_hoist_3,
             "FOO_VALUE": direct_hoist_2
         }, []);
+        const RequiredClasses = [];
         const namedRuntypes = {
             "KnownConstants": direct_hoist_4
         };
@@ -1549,6 +1943,7 @@ mod tests {
         const direct_hoist_1 = new StringWithFormatRuntype(undefined, [
             "password"
         ]);
+        const RequiredClasses = [];
         const namedRuntypes = {
             "Alias": direct_hoist_1
         };
@@ -1578,6 +1973,7 @@ mod tests {
                 "value": direct_hoist_1
             }
         ]);
+        const RequiredClasses = [];
         const namedRuntypes = {
             "PassLenghts": direct_hoist_3,
             "Password": direct_hoist_2
@@ -1609,6 +2005,7 @@ mod tests {
                 "value": direct_hoist_1
             }
         ]);
+        const RequiredClasses = [];
         const namedRuntypes = {
             "PassLenghts": direct_hoist_3,
             "PassLengthGet": direct_hoist_1,
@@ -1638,6 +2035,7 @@ mod tests {
                 "value": direct_hoist_1
             }
         ]);
+        const RequiredClasses = [];
         const namedRuntypes = {
             "NumberRec": direct_hoist_3,
             "NumberRecGet": direct_hoist_1
@@ -1673,6 +2071,7 @@ mod tests {
             "ReadAuthorizedUser",
             "WriteAuthorizedUser"
         ]);
+        const RequiredClasses = [];
         const namedRuntypes = {
             "ReadAuthorizedUser": direct_hoist_3,
             "User": direct_hoist_4,
@@ -1711,6 +2110,7 @@ mod tests {
             "NonNegativeNumber",
             "Rate"
         ]);
+        const RequiredClasses = [];
         const namedRuntypes = {
             "NonInfiniteNumber": direct_hoist_3,
             "NonNegativeNumber": direct_hoist_4,
@@ -1759,6 +2159,7 @@ mod tests {
         ), @r#"
         const direct_hoist_0 = new RefRuntype(undefined, "Alias");
         const direct_hoist_1 = new ConstRuntype(undefined, "some_string_const");
+        const RequiredClasses = [];
         const namedRuntypes = {
             "Alias": direct_hoist_1
         };
@@ -1777,6 +2178,7 @@ mod tests {
         ), @r#"
         const direct_hoist_0 = new RefRuntype(undefined, "Alias");
         const direct_hoist_1 = new DateRuntype(undefined);
+        const RequiredClasses = [];
         const namedRuntypes = {
             "Alias": direct_hoist_1
         };
@@ -1794,7 +2196,8 @@ mod tests {
       "#
         ), @r#"
         const direct_hoist_0 = new RefRuntype(undefined, "Alias");
-        const direct_hoist_1 = new RegexRuntype(undefined, /(\d+(\.\d+)?)(__)(\d+(\.\d+)?)/, "`${number}__${number}`");
+        const direct_hoist_1 = new RegexRuntype(undefined, /^([+-]?(\d+(\.\d*)?|\.\d+)([eE][+-]?\d+)?|[+-]?Infinity|0[xX][0-9a-fA-F]+|0[oO][0-7]+|0[bB][01]+)(__)([+-]?(\d+(\.\d*)?|\.\d+)([eE][+-]?\d+)?|[+-]?Infinity|0[xX][0-9a-fA-F]+|0[oO][0-7]+|0[bB][01]+)$/, "`${number}__${number}`");
+        const RequiredClasses = [];
         const namedRuntypes = {
             "Alias": direct_hoist_1
         };
@@ -1804,6 +2207,32 @@ mod tests {
         "#);
     }
     #[test]
+    fn ok_template_lit_bigint_decoder() {
+        insta::assert_snapshot!(print_cgen(
+            r#"
+        export type Alias = `${bigint}n` | `v${"a" | `b${boolean}`}`
+        parse.buildParsers<{ Dec: Alias }>();
+      "#
+        ), @r#"
+        const direct_hoist_0 = new RefRuntype(undefined, "Alias");
+        const direct_hoist_1 = new RegexRuntype(undefined, /^(-?\d+|0[xX][0-9a-fA-F]+|0[oO][0-7]+|0[bB][01]+)(n)$/, "`${bigint}n`");
+        const direct_hoist_2 = new ConstRuntype(undefined, "va");
+        const direct_hoist_3 = new RegexRuntype(undefined, /^(vb)(true|false)$/, "`vb${boolean}`");
+        const direct_hoist_4 = new AnyOfRuntype(undefined, [
+            direct_hoist_1,
+            direct_hoist_2,
+            direct_hoist_3
+        ]);
+        const RequiredClasses = [];
+        const namedRuntypes = {
+            "Alias": direct_hoist_4
+        };
+        const buildParsersInput = {
+            "Dec": direct_hoist_0
+        };
+        "#);
+    }
+    #[test]
     fn ok_tuple_decoder() {
         insta::assert_snapshot!(print_cgen(
             r#"
@@ -1817,6 +2246,7 @@ mod tests {
             direct_hoist_1,
             direct_hoist_1
         ], null);
+        const RequiredClasses = [];
         const namedRuntypes = {
             "Alias": direct_hoist_2
         };
@@ -1838,6 +2268,7 @@ mod tests {
         const direct_hoist_2 = new ObjectRuntype(undefined, {
             "a": direct_hoist_1
         }, []);
+        const RequiredClasses = [];
         const namedRuntypes = {
             "Alias": direct_hoist_2
         };
@@ -1867,6 +2298,7 @@ mod tests {
         }, {
             "id": direct_hoist_1
         }, []);
+        const RequiredClasses = [];
         const namedRuntypes = {
             "Alias": direct_hoist_2
         };
@@ -1889,6 +2321,7 @@ mod tests {
         const direct_hoist_1 = new TypeofRuntype({
             "description": "New payload."
         }, "string");
+        const RequiredClasses = [];
         const namedRuntypes = {
             "Alias": direct_hoist_1
         };
@@ -1909,6 +2342,7 @@ mod tests {
         ), @r#"
         const direct_hoist_0 = new RefRuntype(undefined, "Alias");
         const direct_hoist_1 = new TypeofRuntype(undefined, "string");
+        const RequiredClasses = [];
         const namedRuntypes = {
             "Alias": direct_hoist_1
         };
@@ -1929,6 +2363,7 @@ mod tests {
         ), @r#"
         const direct_hoist_0 = new RefRuntype(undefined, "Alias");
         const direct_hoist_1 = new TypeofRuntype(undefined, "string");
+        const RequiredClasses = [];
         const namedRuntypes = {
             "Alias": direct_hoist_1
         };
@@ -1962,6 +2397,7 @@ mod tests {
         }, {
             "value": direct_hoist_2
         }, []);
+        const RequiredClasses = [];
         const namedRuntypes = {
             "Alias": direct_hoist_1,
             "Wrapper": direct_hoist_3
@@ -1989,6 +2425,7 @@ mod tests {
         const direct_hoist_2 = new ObjectRuntype(undefined, {
             "quoted-key": direct_hoist_1
         }, []);
+        const RequiredClasses = [];
         const namedRuntypes = {
             "Alias": direct_hoist_2
         };
@@ -2012,6 +2449,7 @@ mod tests {
             direct_hoist_1,
             direct_hoist_2
         ]);
+        const RequiredClasses = [];
         const namedRuntypes = {
             "Alias": direct_hoist_3
         };
@@ -2035,6 +2473,7 @@ mod tests {
             "a": direct_hoist_1,
             "b": direct_hoist_2
         }, []);
+        const RequiredClasses = [];
         const namedRuntypes = {
             "Alias": direct_hoist_3
         };
@@ -2051,6 +2490,7 @@ mod tests {
       "#
         ), @r#"
         const direct_hoist_0 = new TypeofRuntype(undefined, "string");
+        const RequiredClasses = [];
         const namedRuntypes = {};
         const buildParsersInput = {
             "Dec": direct_hoist_0
@@ -2068,6 +2508,7 @@ mod tests {
         ), @r#"
         const direct_hoist_0 = new RefRuntype(undefined, "Alias");
         const direct_hoist_1 = new TypeofRuntype(undefined, "string");
+        const RequiredClasses = [];
         const namedRuntypes = {
             "Alias": direct_hoist_1
         };
@@ -2105,79 +2546,312 @@ mod tests {
         "#);
     }
     #[test]
-    fn ok_conditional_date_subtypes() {
+    fn ok_conditional_template_literal_subtypes() {
         insta::assert_snapshot!(print_types(
             r#"
-        type A = Date;
-        type B = string;
-        type C = A extends B ? true : false;
-        type D = B extends A ? true : false;
-        // expect C to be false and D to be false
-        parse.buildParsers<{ C: C, D: D }>();
+        type UserKey = `user_${string}`;
+        type Pair = `${string}_${string}`;
+        type C = UserKey extends Pair ? true : false;
+        type D = Pair extends UserKey ? true : false;
+        type E = "user_42" extends `user_${number}` ? true : false;
+        type F = `v${number}.${number}` extends `v${string}` ? true : false;
+        type Keys = Exclude<"user_1" | "admin_1" | "other", UserKey>;
+        type Picked = Extract<"user_1" | `user_${number}` | "x", `${string}_1`>;
+        parse.buildParsers<{ C: C, D: D, E: E, F: F, Keys: Keys, Picked: Picked }>();
       "#
-        ), @r"
-        type A = Date;
+        ), @r#"
+        type C = true;
 
-        type B = string;
+        type D = false;
 
-        type C = false;
+        type E = true;
 
-        type D = false;
+        type F = true;
+
+        type Keys = ("admin_1" | "other");
+
+        type Pair = `${string}_${string}`;
+
+        type Picked = ("user_1" | (`${string}_1` & `user_${number}`));
+
+        type UserKey = `user_${string}`;
 
 
         type BuiltParsers = {
           C: C,
           D: D,
+          E: E,
+          F: F,
+          Keys: Keys,
+          Picked: Picked,
         }
-        ");
+        "#);
     }
     #[test]
-    fn ok_conditional_bigint_subtypes() {
+    fn ok_number_range_subtypes() {
         insta::assert_snapshot!(print_types(
             r#"
-        type A = bigint;
-        type B = string;
-        type C = A extends B ? true : false;
-        type D = B extends A ? true : false;
-        // expect C to be false and D to be false
-        parse.buildParsers<{ C: C, D: D }>();
+        type Small = Range<0, 10>;
+        type Percent = Range<0, 100>;
+        type A = Small extends Percent ? true : false;
+        type B = Percent extends Small ? true : false;
+        type C = 5 extends Small ? true : false;
+        type D = 1.5 extends Int ? true : false;
+        type E = Int extends NonNegative ? true : false;
+        type NonZero = Exclude<Int, 0>;
+        type Positive = Exclude<NonNegative, 0>;
+        type Digit = Int & Range<-0.5, 9.5>;
+        type Outside = Exclude<Percent, Small>;
+        parse.buildParsers<{ A: A, B: B, C: C, D: D, E: E, NonZero: NonZero, Positive: Positive, Digit: Digit, Outside: Outside }>();
       "#
         ), @r"
-        type A = bigint;
+        type A = true;
 
-        type B = string;
+        type B = false;
 
-        type C = false;
+        type C = true;
 
         type D = false;
 
+        type Digit = Int & Range<-0.5, 9.5>;
+
+        type E = false;
+
+        type NonZero = (Int & Range<number, -1> | Int & Range<1, number>);
+
+        type Outside = Exclude<Range<10, 100>, 10>;
+
+        type Percent = Range<0, 100>;
+
+        type Positive = Exclude<NonNegative, 0>;
+
+        type Small = Range<0, 10>;
+
 
         type BuiltParsers = {
+          A: A,
+          B: B,
           C: C,
           D: D,
+          Digit: Digit,
+          E: E,
+          NonZero: NonZero,
+          Outside: Outside,
+          Positive: Positive,
         }
         ");
     }
-
     #[test]
-    fn ok_record_extends() {
-        insta::assert_snapshot!(print_types(
+    fn ok_number_range_decoder() {
+        insta::assert_snapshot!(print_cgen(
             r#"
-        export type Obj1 = {a:string}
-        export type Obj2 = {a:string, b:string}
-        export type Obj3 = Obj1 extends Obj2 ? true : false
-        export type Obj4 = Obj2 extends Obj1 ? true : false
-        // expect Obj3 to be false and Obj4 to be true
-        parse.buildParsers<{ Obj3: Obj3, Obj4: Obj4 }>();
+        export type Age = Int & NonNegative;
+        export type Score = Range<-1, 1.5>;
+        export type Floor = Range<10, number>;
+        parse.buildParsers<{ Age: Age, Score: Score, Floor: Floor, Id: Int }>();
       "#
         ), @r#"
-        type Obj1 = { "a": string };
-
-        type Obj2 = { "a": string, "b": string };
-
-        type Obj3 = false;
-
-        type Obj4 = true;
+        const direct_hoist_0 = new RefRuntype(undefined, "Age");
+        const direct_hoist_1 = new RefRuntype(undefined, "Score");
+        const direct_hoist_2 = new RefRuntype(undefined, "Floor");
+        const direct_hoist_3 = new NumberRangeRuntype(undefined, {
+            "multipleOf": 1
+        });
+        const direct_hoist_4 = new NumberRangeRuntype(undefined, {
+            "minimum": 0,
+            "multipleOf": 1
+        });
+        const direct_hoist_5 = new NumberRangeRuntype(undefined, {
+            "minimum": 10
+        });
+        const direct_hoist_6 = new NumberRangeRuntype(undefined, {
+            "maximum": 1.5,
+            "minimum": -1
+        });
+        const RequiredClasses = [];
+        const namedRuntypes = {
+            "Age": direct_hoist_4,
+            "Floor": direct_hoist_5,
+            "Score": direct_hoist_6
+        };
+        const buildParsersInput = {
+            "Age": direct_hoist_0,
+            "Score": direct_hoist_1,
+            "Floor": direct_hoist_2,
+            "Id": direct_hoist_3
+        };
+        "#);
+    }
+    #[test]
+    fn ok_user_declared_range_shadows_builtin() {
+        insta::assert_snapshot!(print_types(
+            r#"
+        type Range<A, B> = { start: A; end: B };
+        type Dates = Range<string, string>;
+        parse.buildParsers<{ Dates: Dates }>();
+      "#
+        ), @r#"
+        type Dates = Range__string_string__;
+
+        type Range__string_string__ = { "end": string, "start": string };
+
+
+        type BuiltParsers = {
+          Dates: Dates,
+        }
+        "#);
+    }
+    #[test]
+    fn ok_user_declared_range_without_type_arguments() {
+        insta::assert_snapshot!(print_types(
+            r#"
+        type Range = { start: number; end: number };
+        type Int = { value: number };
+        parse.buildParsers<{ Range: Range, Int: Int }>();
+      "#
+        ), @r#"
+        type Int = { "value": number };
+
+        type Range = { "end": number, "start": number };
+
+
+        type BuiltParsers = {
+          Int: Int,
+          Range: Range,
+        }
+        "#);
+    }
+    #[test]
+    fn ok_number_range_imported_from_client() {
+        insta::assert_snapshot!(print_types(
+            r#"
+        import { Int, Range } from "@beff/client";
+        type Percent = Int & Range<0, 100>;
+        parse.buildParsers<{ Percent: Percent }>();
+      "#
+        ), @r"
+        type Percent = Int & Range<0, 100>;
+
+
+        type BuiltParsers = {
+          Percent: Percent,
+        }
+        ");
+    }
+    #[test]
+    fn ok_overloaded_constructor_parameters() {
+        insta::assert_snapshot!(print_types(
+            r#"
+        class C {
+          constructor(a: string);
+          constructor(a: number, b: boolean);
+          constructor(a: any, b?: any) {}
+        }
+        type P = ConstructorParameters<typeof C>;
+        parse.buildParsers<{ P: P }>();
+      "#
+        ), @r"
+        type P = [number, boolean];
+
+
+        type BuiltParsers = {
+          P: P,
+        }
+        ");
+    }
+    #[test]
+    fn ok_user_declared_instance_of_shadows_builtin() {
+        insta::assert_snapshot!(print_types(
+            r#"
+        type InstanceOf<T> = { value: T };
+        type Boxed = InstanceOf<string>;
+        parse.buildParsers<{ Boxed: Boxed }>();
+      "#
+        ), @r#"
+        type Boxed = InstanceOf__string__;
+
+        type InstanceOf__string__ = { "value": string };
+
+
+        type BuiltParsers = {
+          Boxed: Boxed,
+        }
+        "#);
+    }
+    #[test]
+    fn ok_conditional_date_subtypes() {
+        insta::assert_snapshot!(print_types(
+            r#"
+        type A = Date;
+        type B = string;
+        type C = A extends B ? true : false;
+        type D = B extends A ? true : false;
+        // expect C to be false and D to be false
+        parse.buildParsers<{ C: C, D: D }>();
+      "#
+        ), @r"
+        type A = Date;
+
+        type B = string;
+
+        type C = false;
+
+        type D = false;
+
+
+        type BuiltParsers = {
+          C: C,
+          D: D,
+        }
+        ");
+    }
+    #[test]
+    fn ok_conditional_bigint_subtypes() {
+        insta::assert_snapshot!(print_types(
+            r#"
+        type A = bigint;
+        type B = string;
+        type C = A extends B ? true : false;
+        type D = B extends A ? true : false;
+        // expect C to be false and D to be false
+        parse.buildParsers<{ C: C, D: D }>();
+      "#
+        ), @r"
+        type A = bigint;
+
+        type B = string;
+
+        type C = false;
+
+        type D = false;
+
+
+        type BuiltParsers = {
+          C: C,
+          D: D,
+        }
+        ");
+    }
+
+    #[test]
+    fn ok_record_extends() {
+        insta::assert_snapshot!(print_types(
+            r#"
+        export type Obj1 = {a:string}
+        export type Obj2 = {a:string, b:string}
+        export type Obj3 = Obj1 extends Obj2 ? true : false
+        export type Obj4 = Obj2 extends Obj1 ? true : false
+        // expect Obj3 to be false and Obj4 to be true
+        parse.buildParsers<{ Obj3: Obj3, Obj4: Obj4 }>();
+      "#
+        ), @r#"
+        type Obj1 = { "a": string };
+
+        type Obj2 = { "a": string, "b": string };
+
+        type Obj3 = false;
+
+        type Obj4 = true;
 
 
         type BuiltParsers = {
@@ -2187,6 +2861,353 @@ mod tests {
         "#);
     }
     #[test]
+    fn ok_conditional_distributive() {
+        insta::assert_snapshot!(print_types(
+            r#"
+        type OnlyStrings<T> = T extends string ? T : never;
+        type Boxed<T> = T extends any ? { value: T } : never;
+        type A = OnlyStrings<"a" | 1 | "b" | true>;
+        type B = Boxed<string | number>;
+        type C = OnlyStrings<never>;
+        type IsTrue<T> = T extends true ? "yes" : "no";
+        type D = IsTrue<boolean>;
+        parse.buildParsers<{ A: A, B: B, C: C, D: D }>();
+      "#
+        ), @r#"
+        type A = OnlyStrings____a_____b____true___1___;
+
+        type B = Boxed___string___number___;
+
+        type Boxed___string___number___ = ({ "value": string } | { "value": number });
+
+        type C = OnlyStrings__never__;
+
+        type D = IsTrue__boolean__;
+
+        type IsTrue__boolean__ = ("no" | "yes");
+
+        type OnlyStrings____a_____b____true___1___ = ("a" | "b");
+
+        type OnlyStrings__never__ = never;
+
+
+        type BuiltParsers = {
+          A: A,
+          B: B,
+          C: C,
+          D: D,
+        }
+        "#);
+    }
+    #[test]
+    fn ok_conditional_non_distributive() {
+        insta::assert_snapshot!(print_types(
+            r#"
+        type OnlyStrings<T> = [T] extends [string] ? T : never;
+        type A = OnlyStrings<"a" | 1>;
+        type B = OnlyStrings<"a" | "b">;
+        parse.buildParsers<{ A: A, B: B }>();
+      "#
+        ), @r#"
+        type A = OnlyStrings____a____1___;
+
+        type B = OnlyStrings____a_____b____;
+
+        type OnlyStrings____a_____b____ = ("a" | "b");
+
+        type OnlyStrings____a____1___ = never;
+
+
+        type BuiltParsers = {
+          A: A,
+          B: B,
+        }
+        "#);
+    }
+    #[test]
+    fn ok_conditional_infer() {
+        insta::assert_snapshot!(print_types(
+            r#"
+        type KindOf<T> = T extends { kind: infer K } ? K : never;
+        type ElementOf<T> = T extends Array<infer E> ? E : never;
+        type First<T> = T extends [infer F, ...any[]] ? F : never;
+        type ValueOf<T> = T extends Record<string, infer V> ? V : never;
+        type StringKind<T> = T extends { kind: infer K extends string } ? K : "not-string";
+        type Shape = { kind: "circle", radius: number } | { kind: "square", size: number };
+        type A = KindOf<Shape>;
+        type B = ElementOf<string[]>;
+        type C = First<[number, string]>;
+        type D = KindOf<{ other: string }>;
+        type E = ValueOf<{ a: string, b: number }>;
+        type F = StringKind<{ kind: 1 }>;
+        parse.buildParsers<{ A: A, B: B, C: C, D: D, E: E, F: F }>();
+      "#
+        ), @r#"
+        type A = KindOf__Shape__;
+
+        type B = ElementOf__Array_string___;
+
+        type C = First___number__string___;
+
+        type D = KindOf_____other___string____;
+
+        type E = ValueOf_____a___string___b___number____;
+
+        type ElementOf__Array_string___ = string;
+
+        type F = StringKind_____kind___1____;
+
+        type First___number__string___ = number;
+
+        type KindOf_____other___string____ = never;
+
+        type KindOf__Shape__ = ("circle" | "square");
+
+        type Shape = ({ "kind": "circle", "radius": number } | { "kind": "square", "size": number });
+
+        type StringKind_____kind___1____ = "not-string";
+
+        type ValueOf_____a___string___b___number____ = (string | number);
+
+
+        type BuiltParsers = {
+          A: A,
+          B: B,
+          C: C,
+          D: D,
+          E: E,
+          F: F,
+        }
+        "#);
+    }
+    #[test]
+    fn ok_conditional_infer_function() {
+        insta::assert_snapshot!(print_types(
+            r#"
+        type Params<T> = T extends (...args: infer P) => any ? P : never;
+        type Ret<T> = T extends (...args: any[]) => infer R ? R : never;
+        type FirstArg<T> = T extends (first: infer A, ...rest: infer R) => any ? [A, R] : never;
+        type CtorArgs<T> = T extends new (...args: infer P) => any ? P : never;
+        type Fn = (name: string, age: number) => boolean;
+        type A = Params<Fn>;
+        type B = Ret<Fn>;
+        type C = FirstArg<Fn>;
+        type D = Params<string>;
+        type E = CtorArgs<new (id: string) => object>;
+        type F = CtorArgs<Fn>;
+        parse.buildParsers<{ A: A, B: B, C: C, D: D, E: E, F: F }>();
+      "#
+        ), @r"
+        type A = Params__Fn__;
+
+        type B = Ret__Fn__;
+
+        type C = FirstArg__Fn__;
+
+        type CtorArgs__Fn__ = never;
+
+        type CtorArgs___new_____args___string_________key___string___number____any_____ = [string];
+
+        type D = Params__string__;
+
+        type E = CtorArgs___new_____args___string_________key___string___number____any_____;
+
+        type F = CtorArgs__Fn__;
+
+        type FirstArg__Fn__ = [string, [number]];
+
+        type Fn = ((...args: [string, number]) => boolean);
+
+        type Params__string__ = never;
+
+        type Params__Fn__ = [string, number];
+
+        type Ret__Fn__ = boolean;
+
+
+        type BuiltParsers = {
+          A: A,
+          B: B,
+          C: C,
+          D: D,
+          E: E,
+          F: F,
+        }
+        ");
+    }
+    #[test]
+    fn ok_mapped_type_key_remapping_of_intersection() {
+        insta::assert_snapshot!(print_types(
+            r#"
+        type User = { id: string, age?: number };
+        type Getters<T> = { [K in keyof T as `get_${K & string}`]: T[K] };
+        type A = Getters<User>;
+        parse.buildParsers<{ A: A }>();
+      "#
+        ), @r#"
+        type A = Getters__User__;
+
+        type Getters__User__ = { "get_age"?: (null | number), "get_id": string };
+
+        type User = { "age"?: number, "id": string };
+
+
+        type BuiltParsers = {
+          A: A,
+        }
+        "#);
+    }
+    #[test]
+    fn ok_mapped_type_key_remapping() {
+        insta::assert_snapshot!(print_types(
+            r#"
+        type User = { id: string, name: string, age?: number };
+        type Getters<T> = { [K in keyof T as `get_${K}`]: T[K] };
+        type WithoutId<T> = { [K in keyof T as Exclude<K, "id">]: T[K] };
+        type Flags<T> = { [K in keyof T as `is_${K}` | `has_${K}`]: boolean };
+        type A = Getters<User>;
+        type B = WithoutId<User>;
+        type C = Flags<{ a: string }>;
+        parse.buildParsers<{ A: A, B: B, C: C }>();
+      "#
+        ), @r#"
+        type A = Getters__User__;
+
+        type B = WithoutId__User__;
+
+        type C = Flags_____a___string____;
+
+        type Flags_____a___string____ = { "has_a": boolean, "is_a": boolean };
+
+        type Getters__User__ = { "get_age"?: (null | number), "get_id": string, "get_name": string };
+
+        type User = { "age"?: number, "id": string, "name": string };
+
+        type WithoutId__User__ = { "age"?: (null | number), "name": string };
+
+
+        type BuiltParsers = {
+          A: A,
+          B: B,
+          C: C,
+        }
+        "#);
+    }
+    #[test]
+    fn ok_mapped_type_key_remapping_expansion_cap() {
+        insta::assert_snapshot!(print_types(
+            r#"
+        type D = "0" | "1" | "2" | "3" | "4" | "5" | "6" | "7" | "8" | "9";
+        type Versions<T> = { [K in keyof T as `${K}_v${1 | 2}`]: T[K] };
+        type A = Versions<{ a: string }>;
+        type C = `${D}${D}${D}${D}${D}`;
+        parse.buildParsers<{ A: A, C: C }>();
+      "#
+        ), @r#"
+        type A = Versions_____a___string____;
+
+        type C = `("0" | "1" | "2" | "3" | "4" | "5" | "6" | "7" | "8" | "9")("0" | "1" | "2" | "3" | "4" | "5" | "6" | "7" | "8" | "9")("0" | "1" | "2" | "3" | "4" | "5" | "6" | "7" | "8" | "9")("0" | "1" | "2" | "3" | "4" | "5" | "6" | "7" | "8" | "9")("0" | "1" | "2" | "3" | "4" | "5" | "6" | "7" | "8" | "9")`;
+
+        type D = ("0" | "1" | "2" | "3" | "4" | "5" | "6" | "7" | "8" | "9");
+
+        type Versions_____a___string____ = { "a_v1": string, "a_v2": string };
+
+
+        type BuiltParsers = {
+          A: A,
+          C: C,
+        }
+        "#);
+    }
+    #[test]
+    fn ok_mapped_type_modifiers() {
+        insta::assert_snapshot!(print_types(
+            r#"
+        type User = { id: string, name?: string, age?: number | undefined };
+        type Concrete<T> = { -readonly [K in keyof T]-?: T[K] };
+        type Loose<T> = { +readonly [K in keyof T]+?: T[K] };
+        type Same<T> = { [K in keyof T]: T[K] };
+        type A = Concrete<User>;
+        type B = Loose<User>;
+        type C = Same<User>;
+        parse.buildParsers<{ A: A, B: B, C: C }>();
+      "#
+        ), @r#"
+        type A = Concrete__User__;
+
+        type B = Loose__User__;
+
+        type C = Same__User__;
+
+        type Concrete__User__ = { "age": number, "id": string, "name": string };
+
+        type Loose__User__ = { "age"?: (null | undefined | number), "id"?: string, "name"?: (null | string) };
+
+        type Same__User__ = { "age"?: (null | undefined | number), "id": string, "name"?: (null | string) };
+
+        type User = { "age"?: (undefined | number), "id": string, "name"?: string };
+
+
+        type BuiltParsers = {
+          A: A,
+          B: B,
+          C: C,
+        }
+        "#);
+    }
+    #[test]
+    fn ok_mapped_type_minus_optional_keeps_null() {
+        insta::assert_snapshot!(print_types(
+            r#"
+        type User = { a?: string | null, b?: string, c: string | null };
+        type Concrete<T> = { [K in keyof T]-?: T[K] };
+        type A = Concrete<User>;
+        type B = Required<User>;
+        parse.buildParsers<{ A: A, B: B }>();
+      "#
+        ), @r#"
+        type A = Concrete__User__;
+
+        type B = { "a": (null | string), "b": string, "c": (null | string) };
+
+        type Concrete__User__ = { "a": (null | string), "b": string, "c": (null | string) };
+
+        type User = { "a"?: (null | string), "b"?: string, "c": (null | string) };
+
+
+        type BuiltParsers = {
+          A: A,
+          B: B,
+        }
+        "#);
+    }
+    #[test]
+    fn ok_mapped_type_minus_optional_keeps_required_undefined() {
+        insta::assert_snapshot!(print_types(
+            r#"
+        type User = { age: number | undefined, name?: string };
+        type Concrete<T> = { [K in keyof T]-?: T[K] };
+        type A = Concrete<User>;
+        type B = Required<User>;
+        parse.buildParsers<{ A: A, B: B }>();
+      "#
+        ), @r#"
+        type A = Concrete__User__;
+
+        type B = { "age": (undefined | number), "name": string };
+
+        type Concrete__User__ = { "age": (undefined | number), "name": string };
+
+        type User = { "age": (undefined | number), "name"?: string };
+
+
+        type BuiltParsers = {
+          A: A,
+          B: B,
+        }
+        "#);
+    }
+    #[test]
     fn ok_readonly_array() {
         insta::assert_snapshot!(print_types(
             r#"
@@ -2333,13 +3354,174 @@ mod tests {
             "wrappedNumber": direct_hoist_8,
             "wrappedString": direct_hoist_2
         }, []);
+        const RequiredClasses = [];
+        const namedRuntypes = {
+            "GenericWrapper_string": direct_hoist_7,
+            "GenericWrapper_number": direct_hoist_12,
+            "UsesGenericWrapper": direct_hoist_13
+        };
+        const buildParsersInput = {
+            "UsesGenericWrapper": direct_hoist_0
+        };
+        "#);
+    }
+
+    #[test]
+    fn ok_recursive_generic_alias() {
+        insta::assert_snapshot!(print_types(
+            r#"
+        type Tree<T> = { value: T; children: Tree<T>[] };
+        type Json<T> = T | Json<T>[] | { [k: string]: Json<T> };
+        type Forest<U> = Tree<U>[];
+        type A = Exclude<Json<string>, string>;
+        type B = Tree<number>["children"];
+        type LinkedList<T> = { head: T; tail: LinkedList<T> } | null;
+        type C = NonNullable<LinkedList<string>>;
+        parse.buildParsers<{ T: Tree<string>, J: Json<number>, F: Forest<boolean>, A: A, B: B, C: C }>();
+      "#
+        ), @r#"
+        type A = ({ [key: string]: Json__string__ } | Array<Json__string__>);
+
+        type B = Array<Tree__number__>;
+
+        type C = { "head": string, "tail": LinkedList__string__ };
+
+        type Forest__boolean__ = Array<Tree__boolean__>;
+
+        type Json__string__ = (string | { [key: string]: Json__string__ } | Array<Json__string__>);
+
+        type Json__number__ = (number | { [key: string]: Json__number__ } | Array<Json__number__>);
+
+        type LinkedList__string__ = (null | { "head": string, "tail": LinkedList__string__ });
+
+        type Tree__boolean__ = { "children": Array<Tree__boolean__>, "value": boolean };
+
+        type Tree__string__ = { "children": Array<Tree__string__>, "value": string };
+
+        type Tree__number__ = { "children": Array<Tree__number__>, "value": number };
+
+
+        type BuiltParsers = {
+          A: A,
+          B: B,
+          C: C,
+          F: Forest__boolean__,
+          J: Json__number__,
+          T: Tree__string__,
+        }
+        "#);
+    }
+
+    #[test]
+    fn ok_instance_of_class() {
+        insta::assert_snapshot!(print_cgen(
+            r#"
+        class CreateUserDto {
+          name!: string;
+        }
+        type Input = { dto: InstanceOf<CreateUserDto>; at: Date };
+        parse.buildParsers<{ Input: Input }>();
+      "#
+        ), @r#"
+        const direct_hoist_0 = new RefRuntype(undefined, "Input");
+        const direct_hoist_1 = new TypeofRuntype(undefined, "string");
+        const direct_hoist_2 = new ObjectRuntype(undefined, {
+            "name": direct_hoist_1
+        }, []);
+        const direct_hoist_3 = new DateRuntype(undefined);
+        const direct_hoist_4 = new InstanceOfRuntype(undefined, "CreateUserDto");
+        const direct_hoist_5 = new ObjectRuntype(undefined, {
+            "at": direct_hoist_3,
+            "dto": direct_hoist_4
+        }, []);
+        const RequiredClasses = [
+            "CreateUserDto"
+        ];
+        const namedRuntypes = {
+            "CreateUserDto": direct_hoist_2,
+            "Input": direct_hoist_5
+        };
+        const buildParsersInput = {
+            "Input": direct_hoist_0
+        };
+        "#);
+    }
+
+    #[test]
+    fn ok_generic_parser_factory() {
+        insta::assert_snapshot!(print_cgen(
+            r#"
+        type User = { name: string };
+        type Paginated<T> = { items: T[]; next: Paginated<T> | null; total: number };
+        type ApiResponse<T> = { ok: true; data: T } | { ok: false; error: string };
+        parse.buildParsers<{
+          User: User;
+          Paginated<T>(item: BeffParser<T>): BeffParser<Paginated<T>>;
+          ApiResponse: <T>(data: BeffParser<T>) => BeffParser<ApiResponse<Paginated<T>>>;
+        }>();
+      "#
+        ), @r#"
+        const direct_hoist_0 = new RefRuntype(undefined, "User");
+        const direct_hoist_1 = new NullishRuntype(undefined, "null");
+        const direct_hoist_2 = new TypeofRuntype(undefined, "number");
+        const direct_hoist_3 = new ConstRuntype(undefined, true);
+        const direct_hoist_4 = new TypeofRuntype(undefined, "string");
+        const direct_hoist_5 = new ConstRuntype(undefined, false);
+        const direct_hoist_6 = new ObjectRuntype(undefined, {
+            "error": direct_hoist_4,
+            "ok": direct_hoist_5
+        }, []);
+        const direct_hoist_7 = new ObjectRuntype(undefined, {
+            "name": direct_hoist_4
+        }, []);
+        const RequiredClasses = [];
         const namedRuntypes = {
-            "GenericWrapper_string": direct_hoist_7,
-            "GenericWrapper_number": direct_hoist_12,
-            "UsesGenericWrapper": direct_hoist_13
+            "User": direct_hoist_7
         };
         const buildParsersInput = {
-            "UsesGenericWrapper": direct_hoist_0
+            "User": direct_hoist_0,
+            "Paginated": (T)=>{
+                const scopedRuntypes = {};
+                const factory_hoist_0 = new ScopedRefRuntype(undefined, "Paginated_instance_0", scopedRuntypes);
+                const factory_hoist_1 = new ArrayRuntype(undefined, T);
+                const factory_hoist_2 = new AnyOfRuntype(undefined, [
+                    direct_hoist_1,
+                    factory_hoist_0
+                ]);
+                const factory_hoist_3 = new ObjectRuntype(undefined, {
+                    "items": factory_hoist_1,
+                    "next": factory_hoist_2,
+                    "total": direct_hoist_2
+                }, []);
+                scopedRuntypes["Paginated_instance_0"] = factory_hoist_3;
+                return factory_hoist_0;
+            },
+            "ApiResponse": (T)=>{
+                const scopedRuntypes = {};
+                const factory_hoist_0 = new ScopedRefRuntype(undefined, "ApiResponse_Paginated__T__", scopedRuntypes);
+                const factory_hoist_1 = new ScopedRefRuntype(undefined, "Paginated_instance_0", scopedRuntypes);
+                const factory_hoist_2 = new ObjectRuntype(undefined, {
+                    "data": factory_hoist_1,
+                    "ok": direct_hoist_3
+                }, []);
+                const factory_hoist_3 = new AnyOfRuntype(undefined, [
+                    factory_hoist_2,
+                    direct_hoist_6
+                ]);
+                const factory_hoist_4 = new ArrayRuntype(undefined, T);
+                const factory_hoist_5 = new AnyOfRuntype(undefined, [
+                    direct_hoist_1,
+                    factory_hoist_1
+                ]);
+                const factory_hoist_6 = new ObjectRuntype(undefined, {
+                    "items": factory_hoist_4,
+                    "next": factory_hoist_5,
+                    "total": direct_hoist_2
+                }, []);
+                scopedRuntypes["ApiResponse_Paginated__T__"] = factory_hoist_3;
+                scopedRuntypes["Paginated_instance_0"] = factory_hoist_6;
+                return factory_hoist_0;
+            }
         };
         "#);
     }
@@ -2391,6 +3573,7 @@ mod tests {
             "wrappedNumber": direct_hoist_6,
             "wrappedString": direct_hoist_1
         }, []);
+        const RequiredClasses = [];
         const namedRuntypes = {
             "GenericWrapper_string": direct_hoist_5,
             "GenericWrapper_number": direct_hoist_9,
@@ -2434,6 +3617,7 @@ mod tests {
             "wrappedNumber": direct_hoist_5,
             "wrappedString": direct_hoist_6
         }, []);
+        const RequiredClasses = [];
         const namedRuntypes = {
             "GenericWrapper_string": direct_hoist_2,
             "GenericWrapper_number": direct_hoist_4,
@@ -2522,6 +3706,7 @@ mod tests {
             "OR"
         ]);
         const direct_hoist_16 = new RefRuntype(undefined, "EditableLogicalConditionsAst_string");
+        const RequiredClasses = [];
         const namedRuntypes = {
             "EditableLogicalConditionsAst_string": direct_hoist_4,
             "EditableLogicalConditionsAstCustom_string": direct_hoist_7,
@@ -2535,4 +3720,1083 @@ mod tests {
         };
         "#);
     }
+
+    #[test]
+    fn ok_jsdoc_schema_tags_decoder() {
+        insta::assert_snapshot!(print_cgen(
+            r#"
+        /**
+         * A user handle.
+         * @minLength 3
+         * @maxLength 20
+         * @pattern ^[a-z_]+$
+         * @example "jane_doe"
+         */
+        export type Handle = string;
+        export type User = {
+            handle: Handle,
+            /**
+             * @minimum 0
+             * @maximum 150
+             * @default 18
+             */
+            age: number,
+            /**
+             * @minItems 1
+             * @maxItems 5
+             * @deprecated use handle
+             */
+            tags: string[],
+            /** @format email */
+            email: string,
+            /** @minItems 1 @maxItems 3 */
+            ids: number[],
+        };
+        parse.buildParsers<{ User: User }>();
+      "#
+        ), @r#"
+        const direct_hoist_0 = new RefRuntype(undefined, "User");
+        const direct_hoist_1 = new ConstrainedRuntype(new TypeofRuntype({
+            "description": "A user handle.",
+            "examples": [
+                "jane_doe"
+            ]
+        }, "string"), {
+            "maxLength": 20,
+            "minLength": 3,
+            "pattern": "^[a-z_]+$"
+        });
+        const direct_hoist_2 = new ConstrainedRuntype(new TypeofRuntype({
+            "default": 18
+        }, "number"), {
+            "maximum": 150,
+            "minimum": 0
+        });
+        const direct_hoist_3 = new TypeofRuntype({
+            "format": "email"
+        }, "string");
+        const direct_hoist_4 = new RefRuntype(undefined, "Handle");
+        const direct_hoist_5 = new TypeofRuntype(undefined, "number");
+        const direct_hoist_6 = new ConstrainedRuntype(new ArrayRuntype(undefined, direct_hoist_5), {
+            "maxItems": 3,
+            "minItems": 1
+        });
+        const direct_hoist_7 = new TypeofRuntype(undefined, "string");
+        const direct_hoist_8 = new ConstrainedRuntype(new ArrayRuntype({
+            "deprecated": true
+        }, direct_hoist_7), {
+            "maxItems": 5,
+            "minItems": 1
+        });
+        const direct_hoist_9 = new ObjectRuntype(undefined, {
+            "age": direct_hoist_2,
+            "email": direct_hoist_3,
+            "handle": direct_hoist_4,
+            "ids": direct_hoist_6,
+            "tags": direct_hoist_8
+        }, []);
+        const RequiredClasses = [];
+        const namedRuntypes = {
+            "Handle": direct_hoist_1,
+            "User": direct_hoist_9
+        };
+        const buildParsersInput = {
+            "User": direct_hoist_0
+        };
+        "#);
+    }
+
+    #[test]
+    fn ok_optional_and_named_tuple_elements() {
+        let from = r#"
+        type Point = [x: number, y: number, z?: number];
+        type Args = [string, ...number[], boolean];
+        type Spread = [first: string, ...rest: [number, boolean?]];
+        type A = [1, 2] extends Point ? true : false;
+        type B = [1, 2, 3, 4] extends Point ? true : false;
+        type C = ["a", true] extends Args ? true : false;
+        type D = ["a", 1, 2] extends Args ? true : false;
+        type E = Args extends [string, ...(number | boolean)[]] ? true : false;
+        type Last = Args[3];
+        parse.buildParsers<{ Point: Point, Args: Args, Spread: Spread, A: A, B: B, C: C, D: D, E: E, Last: Last }>();
+      "#;
+        insta::assert_snapshot!(print_types(from), @r"
+        type A = true;
+
+        type Args = [string, ...number, boolean];
+
+        type B = false;
+
+        type C = true;
+
+        type D = false;
+
+        type E = true;
+
+        type Last = (boolean | number);
+
+        type Point = [x: number, y: number, z?: (undefined | number)];
+
+        type Spread = [first: string, number, (undefined | boolean)?];
+
+
+        type BuiltParsers = {
+          A: A,
+          Args: Args,
+          B: B,
+          C: C,
+          D: D,
+          E: E,
+          Last: Last,
+          Point: Point,
+          Spread: Spread,
+        }
+        ");
+    }
+
+    #[test]
+    fn ok_optional_and_named_tuple_elements_decoder() {
+        insta::assert_snapshot!(print_cgen(
+            r#"
+        export type Point = [x: number, y: number, z?: number];
+        export type Args = [string, ...number[], boolean];
+        parse.buildParsers<{ Point: Point, Args: Args }>();
+      "#
+        ), @r#"
+        const direct_hoist_0 = new RefRuntype(undefined, "Point");
+        const direct_hoist_1 = new RefRuntype(undefined, "Args");
+        const direct_hoist_2 = new TypeofRuntype(undefined, "string");
+        const direct_hoist_3 = new TypeofRuntype(undefined, "boolean");
+        const direct_hoist_4 = new TypeofRuntype(undefined, "number");
+        const direct_hoist_5 = new TupleRuntype(undefined, [
+            direct_hoist_2
+        ], direct_hoist_4, [], [
+            direct_hoist_3
+        ]);
+        const direct_hoist_6 = new NullishRuntype(undefined, "undefined");
+        const direct_hoist_7 = new AnyOfRuntype(undefined, [
+            direct_hoist_6,
+            direct_hoist_4
+        ]);
+        const direct_hoist_8 = new TupleRuntype(undefined, [
+            direct_hoist_4,
+            direct_hoist_4
+        ], null, [
+            new OptionalFieldRuntype(direct_hoist_7)
+        ], []);
+        const RequiredClasses = [];
+        const namedRuntypes = {
+            "Args": direct_hoist_5,
+            "Point": direct_hoist_8
+        };
+        const buildParsersInput = {
+            "Point": direct_hoist_0,
+            "Args": direct_hoist_1
+        };
+        "#);
+    }
+
+    #[test]
+    fn aot_object() {
+        insta::assert_snapshot!(print_aot(
+            r#"
+        type User = { name: string; age?: number; tags: string[] };
+        parse.buildParsers<{ User: User }>();
+      "#
+        ), @r#"
+        const RequiredClasses = [];
+        function validate_0(ctx, input) {
+            if (typeof input !== "object" || input === null || Array.isArray(input)) {
+                return false;
+            }
+            if (!(input.age == null || typeof input.age === "number")) {
+                return false;
+            }
+            if (!(typeof input.name === "string")) {
+                return false;
+            }
+            if (!validate_1(ctx, input.tags)) {
+                return false;
+            }
+            if (ctx.disallowExtraProperties) {
+                for (const k of Object.keys(input)){
+                    switch(k){
+                        case "age":
+                        case "name":
+                        case "tags":
+                            continue;
+                    }
+                    return false;
+                }
+            }
+            return true;
+        }
+        function parse_0(ctx, input) {
+            const acc = {};
+            if (ctx.objectKeyOrder === "input") {
+                for (const k of Object.keys(input)){
+                    switch(k){
+                        case "age":
+                            acc.age = input.age;
+                            continue;
+                        case "name":
+                            acc.name = input.name;
+                            continue;
+                        case "tags":
+                            acc.tags = parse_1(ctx, input.tags);
+                            continue;
+                    }
+                }
+            } else {
+                if (hasOwn(input, "age")) {
+                    acc.age = input.age;
+                }
+                if (hasOwn(input, "name")) {
+                    acc.name = input.name;
+                }
+                if (hasOwn(input, "tags")) {
+                    acc.tags = parse_1(ctx, input.tags);
+                }
+            }
+            return acc;
+        }
+        function report_0(ctx, input) {
+            if (typeof input !== "object" || Array.isArray(input) || input === null) {
+                return buildError(ctx, "expected object", input);
+            }
+            const acc = [];
+            if (!(input.age == null || typeof input.age === "number")) {
+                ctx.path.push("age");
+                acc.push(...buildError(ctx, "expected number", input.age));
+                ctx.path.pop();
+            }
+            if (!(typeof input.name === "string")) {
+                ctx.path.push("name");
+                acc.push(...buildError(ctx, "expected string", input.name));
+                ctx.path.pop();
+            }
+            if (!validate_1(ctx, input.tags)) {
+                ctx.path.push("tags");
+                acc.push(...report_1(ctx, input.tags));
+                ctx.path.pop();
+            }
+            if (ctx.disallowExtraProperties) {
+                const extra = [];
+                for (const k of Object.keys(input)){
+                    switch(k){
+                        case "age":
+                        case "name":
+                        case "tags":
+                            continue;
+                    }
+                    ctx.path.push(k);
+                    extra.push(...buildError(ctx, "extra property", input[k]));
+                    ctx.path.pop();
+                }
+                if (extra.length > 0) {
+                    return extra;
+                }
+            }
+            return acc;
+        }
+        function validate_1(ctx, input) {
+            if (!Array.isArray(input)) {
+                return false;
+            }
+            for(let i = 0; i < input.length; i++){
+                if (!(typeof input[i] === "string")) {
+                    return false;
+                }
+            }
+            return true;
+        }
+        function parse_1(ctx, input) {
+            return input.slice();
+        }
+        function report_1(ctx, input) {
+            if (!Array.isArray(input)) {
+                return buildError(ctx, "expected array", input);
+            }
+            const acc = [];
+            for(let i = 0; i < input.length; i++){
+                if (!(typeof input[i] === "string")) {
+                    ctx.path.push(`[${i}]`);
+                    acc.push(...buildError(ctx, "expected string", input[i]));
+                    ctx.path.pop();
+                }
+            }
+            return acc;
+        }
+        const buildParsersInput = {
+            "User": {
+                validate: validate_0,
+                parse: parse_0,
+                report: report_0
+            }
+        };
+        "#);
+    }
+
+    #[test]
+    fn aot_discriminated_union() {
+        insta::assert_snapshot!(print_aot(
+            r#"
+        type Shape = { kind: "circle"; radius: number } | { kind: "square"; side: number };
+        parse.buildParsers<{ Shape: Shape }>();
+      "#
+        ), @r#"
+        const RequiredClasses = [];
+        function validate_0(ctx, input) {
+            if (typeof input !== "object" || input === null) {
+                return false;
+            }
+            switch(input.kind){
+                case "circle":
+                    return validate_1(ctx, input);
+                case "square":
+                    return validate_2(ctx, input);
+                default:
+                    return false;
+            }
+        }
+        function parse_0(ctx, input) {
+            switch(input.kind){
+                case "circle":
+                    return {
+                        ...parse_1(ctx, input),
+                        kind: input.kind
+                    };
+                case "square":
+                    return {
+                        ...parse_2(ctx, input),
+                        kind: input.kind
+                    };
+                default:
+                    throw new Error("INTERNAL ERROR: Missing parser for discriminator " + JSON.stringify(input.kind));
+            }
+        }
+        function report_0(ctx, input) {
+            if (input == null || typeof input !== "object") {
+                return buildError(ctx, "expected object", input);
+            }
+            switch(input.kind){
+                case "circle":
+                    return report_1(ctx, input);
+                case "square":
+                    return report_2(ctx, input);
+            }
+            if (input.kind == null) {
+                return buildError(ctx, 'expected discriminator key "kind"', input);
+            }
+            ctx.path.push("kind");
+            const errors = buildError(ctx, 'expected one of "circle", "square"', input.kind);
+            ctx.path.pop();
+            return errors;
+        }
+        function validate_1(ctx, input) {
+            if (typeof input !== "object" || input === null || Array.isArray(input)) {
+                return false;
+            }
+            if (!(input.kind === "circle")) {
+                return false;
+            }
+            if (!(typeof input.radius === "number")) {
+                return false;
+            }
+            if (ctx.disallowExtraProperties) {
+                for (const k of Object.keys(input)){
+                    switch(k){
+                        case "kind":
+                        case "radius":
+                            continue;
+                    }
+                    return false;
+                }
+            }
+            return true;
+        }
+        function parse_1(ctx, input) {
+            const acc = {};
+            if (ctx.objectKeyOrder === "input") {
+                for (const k of Object.keys(input)){
+                    switch(k){
+                        case "kind":
+                            acc.kind = input.kind;
+                            continue;
+                        case "radius":
+                            acc.radius = input.radius;
+                            continue;
+                    }
+                }
+            } else {
+                if (hasOwn(input, "kind")) {
+                    acc.kind = input.kind;
+                }
+                if (hasOwn(input, "radius")) {
+                    acc.radius = input.radius;
+                }
+            }
+            return acc;
+        }
+        function report_1(ctx, input) {
+            if (typeof input !== "object" || Array.isArray(input) || input === null) {
+                return buildError(ctx, "expected object", input);
+            }
+            const acc = [];
+            if (!(input.kind === "circle")) {
+                ctx.path.push("kind");
+                acc.push(...buildError(ctx, 'expected "circle"', input.kind));
+                ctx.path.pop();
+            }
+            if (!(typeof input.radius === "number")) {
+                ctx.path.push("radius");
+                acc.push(...buildError(ctx, "expected number", input.radius));
+                ctx.path.pop();
+            }
+            if (ctx.disallowExtraProperties) {
+                const extra = [];
+                for (const k of Object.keys(input)){
+                    switch(k){
+                        case "kind":
+                        case "radius":
+                            continue;
+                    }
+                    ctx.path.push(k);
+                    extra.push(...buildError(ctx, "extra property", input[k]));
+                    ctx.path.pop();
+                }
+                if (extra.length > 0) {
+                    return extra;
+                }
+            }
+            return acc;
+        }
+        function validate_2(ctx, input) {
+            if (typeof input !== "object" || input === null || Array.isArray(input)) {
+                return false;
+            }
+            if (!(input.kind === "square")) {
+                return false;
+            }
+            if (!(typeof input.side === "number")) {
+                return false;
+            }
+            if (ctx.disallowExtraProperties) {
+                for (const k of Object.keys(input)){
+                    switch(k){
+                        case "kind":
+                        case "side":
+                            continue;
+                    }
+                    return false;
+                }
+            }
+            return true;
+        }
+        function parse_2(ctx, input) {
+            const acc = {};
+            if (ctx.objectKeyOrder === "input") {
+                for (const k of Object.keys(input)){
+                    switch(k){
+                        case "kind":
+                            acc.kind = input.kind;
+                            continue;
+                        case "side":
+                            acc.side = input.side;
+                            continue;
+                    }
+                }
+            } else {
+                if (hasOwn(input, "kind")) {
+                    acc.kind = input.kind;
+                }
+                if (hasOwn(input, "side")) {
+                    acc.side = input.side;
+                }
+            }
+            return acc;
+        }
+        function report_2(ctx, input) {
+            if (typeof input !== "object" || Array.isArray(input) || input === null) {
+                return buildError(ctx, "expected object", input);
+            }
+            const acc = [];
+            if (!(input.kind === "square")) {
+                ctx.path.push("kind");
+                acc.push(...buildError(ctx, 'expected "square"', input.kind));
+                ctx.path.pop();
+            }
+            if (!(typeof input.side === "number")) {
+                ctx.path.push("side");
+                acc.push(...buildError(ctx, "expected number", input.side));
+                ctx.path.pop();
+            }
+            if (ctx.disallowExtraProperties) {
+                const extra = [];
+                for (const k of Object.keys(input)){
+                    switch(k){
+                        case "kind":
+                        case "side":
+                            continue;
+                    }
+                    ctx.path.push(k);
+                    extra.push(...buildError(ctx, "extra property", input[k]));
+                    ctx.path.pop();
+                }
+                if (extra.length > 0) {
+                    return extra;
+                }
+            }
+            return acc;
+        }
+        const buildParsersInput = {
+            "Shape": {
+                validate: validate_0,
+                parse: parse_0,
+                report: report_0
+            }
+        };
+        "#);
+    }
+
+    #[test]
+    fn aot_generic_factory() {
+        insta::assert_snapshot!(print_aot(
+            r#"
+        type Box<T> = { value: T };
+        parse.buildParsers<{ Box<T>(value: BeffParser<T>): BeffParser<Box<T>> }>();
+      "#
+        ), @r#"
+        const RequiredClasses = [];
+        const buildParsersInput = {
+            "Box": (T)=>{
+                function factory_validate_0(ctx, input) {
+                    if (typeof input !== "object" || input === null || Array.isArray(input)) {
+                        return false;
+                    }
+                    if (!T.validate(ctx, input.value)) {
+                        return false;
+                    }
+                    if (ctx.disallowExtraProperties) {
+                        for (const k of Object.keys(input)){
+                            switch(k){
+                                case "value":
+                                    continue;
+                            }
+                            return false;
+                        }
+                    }
+                    return true;
+                }
+                function factory_parse_0(ctx, input) {
+                    const acc = {};
+                    if (ctx.objectKeyOrder === "input") {
+                        for (const k of Object.keys(input)){
+                            switch(k){
+                                case "value":
+                                    acc.value = T.parse(ctx, input.value);
+                                    continue;
+                            }
+                        }
+                    } else {
+                        if (hasOwn(input, "value")) {
+                            acc.value = T.parse(ctx, input.value);
+                        }
+                    }
+                    return acc;
+                }
+                function factory_report_0(ctx, input) {
+                    if (typeof input !== "object" || Array.isArray(input) || input === null) {
+                        return buildError(ctx, "expected object", input);
+                    }
+                    const acc = [];
+                    if (!T.validate(ctx, input.value)) {
+                        ctx.path.push("value");
+                        acc.push(...T.report(ctx, input.value));
+                        ctx.path.pop();
+                    }
+                    if (ctx.disallowExtraProperties) {
+                        const extra = [];
+                        for (const k of Object.keys(input)){
+                            switch(k){
+                                case "value":
+                                    continue;
+                            }
+                            ctx.path.push(k);
+                            extra.push(...buildError(ctx, "extra property", input[k]));
+                            ctx.path.pop();
+                        }
+                        if (extra.length > 0) {
+                            return extra;
+                        }
+                    }
+                    return acc;
+                }
+                return {
+                    validate: factory_validate_0,
+                    parse: factory_parse_0,
+                    report: factory_report_0
+                };
+            }
+        };
+        "#);
+    }
+
+    #[test]
+    fn aot_negated_type_is_an_error() {
+        insta::assert_snapshot!(print_aot_error(
+            r#"
+        type A = Exclude<string, "a">;
+        parse.buildParsers<{ A: A }>();
+      "#
+        ), @r#"Cannot compile validators for a negated type, like `Exclude<string, "a">`"#);
+    }
+
+    #[test]
+    fn dts_named_types() {
+        insta::assert_snapshot!(print_dts(
+            r#"
+        type User = { name: string; "e-mail"?: StringFormat<"password">; age: Int };
+        type Shape = { kind: "circle"; radius: number } | { kind: "square"; side: number };
+        type Pair = [first: string, second?: number, ...rest: boolean[]];
+        type Key = `id_${string}`;
+        type Dict = { [k: Key]: User };
+        parse.buildParsers<{ User: User; Shape: Shape; Pair: Pair; Dict: Dict }>();
+      "#
+        ), @r#"
+        import type * as beff from "@beff/client";
+
+        export type Dict = { [K in Key]: User };
+        export type Key = `id_${string}`;
+        export type Pair = [first: string, second?: (undefined | number), ...rest: Array<boolean>];
+        export type Shape = { kind: "circle"; radius: number } | { kind: "square"; side: number };
+        export type User = { age: beff.Int; "e-mail"?: beff.StringFormat<"password">; name: string };
+
+        export type BuiltParsers = {
+          User: beff.BeffParser<User>;
+          Shape: beff.BeffParser<Shape>;
+          Pair: beff.BeffParser<Pair>;
+          Dict: beff.BeffParser<Dict>;
+        };
+        "#);
+    }
+
+    #[test]
+    fn dts_generic_factory() {
+        insta::assert_snapshot!(print_dts(
+            r#"
+        type Paginated<T> = { items: T[]; next: Paginated<T> | null };
+        parse.buildParsers<{ Paginated<T>(item: BeffParser<T>): BeffParser<Paginated<T>> }>();
+      "#
+        ), @r#"
+        import type * as beff from "@beff/client";
+
+        export type Paginated<T> = { items: Array<T>; next: null | Paginated<T> };
+
+        export type BuiltParsers = {
+          Paginated: <T>(T: beff.BeffParser<T>) => beff.BeffParser<Paginated<T>>;
+        };
+        "#);
+    }
+
+    #[test]
+    fn dts_classes() {
+        insta::assert_snapshot!(print_dts(
+            r#"
+        export class Point {
+          constructor(public x: number, public y: number) {}
+        }
+        class Hidden {
+          secret: string = "";
+        }
+        parse.buildParsers<{ Point: InstanceOf<Point>; Hidden: InstanceOf<Hidden> }>();
+      "#
+        ), @r#"
+        import type * as beff from "@beff/client";
+
+        export type Hidden = { secret: string };
+        export type Point = { x: number; y: number };
+
+        export type BuiltParsers = {
+          Point: beff.BeffParser<import("../entry").Point>;
+          Hidden: beff.BeffParser<object>;
+        };
+        "#);
+    }
+
+    #[test]
+    fn dts_compiled_backend() {
+        insta::assert_snapshot!(print_compiled_dts(
+            r#"
+        type Paginated<T> = { items: T[]; total: number };
+        parse.buildParsers<{
+          Names: string[];
+          Paginated<T>(item: CompiledParser<T>): CompiledParser<Paginated<T>>;
+        }>();
+      "#
+        ), @r#"
+        import type * as beff from "@beff/client";
+
+        export type Paginated<T> = { items: Array<T>; total: number };
+
+        export type BuiltParsers = {
+          Names: beff.CompiledParser<Array<string>>;
+          Paginated: <T>(T: beff.CompiledParser<T>) => beff.CompiledParser<Paginated<T>>;
+        };
+        "#);
+    }
+
+    #[test]
+    fn dts_user_type_named_built_parsers() {
+        insta::assert_snapshot!(print_dts(
+            r#"
+        type BuiltParsers = { names: string[] };
+        parse.buildParsers<{ BuiltParsers: BuiltParsers }>();
+      "#
+        ), @r#"
+        import type * as beff from "@beff/client";
+
+        export type BuiltParsers = { names: Array<string> };
+
+        export type BuiltParsers_1 = {
+          BuiltParsers: beff.BeffParser<BuiltParsers>;
+        };
+        "#);
+    }
+
+    #[test]
+    fn json_schema_recursive_object() {
+        insta::assert_snapshot!(print_json_schema(
+            r#"
+        /** A node of the tree */
+        type Node = { id: `node-${number}`; label?: string; children: Node[] };
+        parse.buildParsers<{ Node: Node }>();
+      "#
+        ), @r##"
+        {
+          "Node": {
+            "$defs": {
+              "Node": {
+                "additionalProperties": false,
+                "description": "A node of the tree",
+                "properties": {
+                  "children": {
+                    "items": {
+                      "$ref": "#/$defs/Node"
+                    },
+                    "type": "array"
+                  },
+                  "id": {
+                    "pattern": "^(node-)([+-]?(\\d+(\\.\\d*)?|\\.\\d+)([eE][+-]?\\d+)?|[+-]?Infinity|0[xX][0-9a-fA-F]+|0[oO][0-7]+|0[bB][01]+)$",
+                    "type": "string"
+                  },
+                  "label": {
+                    "type": "string"
+                  }
+                },
+                "required": [
+                  "children",
+                  "id"
+                ],
+                "type": "object"
+              }
+            },
+            "$ref": "#/$defs/Node",
+            "$schema": "https://json-schema.org/draft/2020-12/schema"
+          }
+        }
+        "##);
+    }
+
+    #[test]
+    fn json_schema_tuple_and_union() {
+        insta::assert_snapshot!(print_json_schema(
+            r#"
+        type Status = "active" | "disabled";
+        type Row = [id: string, status: Status, ...tags: string[]];
+        parse.buildParsers<{ Row: Row; Rows: Row[] }>();
+      "#
+        ), @r##"
+        {
+          "Row": {
+            "$defs": {
+              "Row": {
+                "items": {
+                  "type": "string"
+                },
+                "minItems": 2,
+                "prefixItems": [
+                  {
+                    "type": "string"
+                  },
+                  {
+                    "$ref": "#/$defs/Status"
+                  }
+                ],
+                "type": "array"
+              },
+              "Status": {
+                "enum": [
+                  "active",
+                  "disabled"
+                ],
+                "type": "string"
+              }
+            },
+            "$ref": "#/$defs/Row",
+            "$schema": "https://json-schema.org/draft/2020-12/schema"
+          },
+          "Rows": {
+            "$defs": {
+              "Row": {
+                "items": {
+                  "type": "string"
+                },
+                "minItems": 2,
+                "prefixItems": [
+                  {
+                    "type": "string"
+                  },
+                  {
+                    "$ref": "#/$defs/Status"
+                  }
+                ],
+                "type": "array"
+              },
+              "Status": {
+                "enum": [
+                  "active",
+                  "disabled"
+                ],
+                "type": "string"
+              }
+            },
+            "$schema": "https://json-schema.org/draft/2020-12/schema",
+            "items": {
+              "$ref": "#/$defs/Row"
+            },
+            "type": "array"
+          }
+        }
+        "##);
+    }
+
+    #[test]
+    fn json_schema_of_negated_type_is_an_error() {
+        insta::assert_snapshot!(print_json_schema_error(
+            r#"
+        type A = Exclude<string, "a">;
+        parse.buildParsers<{ A: A }>();
+      "#
+        ), @r"Failed to print schema. At A: Cannot generate JSON Schema for a negated type");
+    }
+
+    #[test]
+    fn openapi_routes() {
+        insta::assert_snapshot!(print_openapi(
+            r#"
+        type User = { id: string; name: string };
+        type UserPatch = { name?: string };
+        type Routes = {
+          "GET /users": { query: { limit?: number; cursor?: string }; response: User[] };
+          "GET /users/{id}": { params: { id: string }; response: User };
+          "PATCH /users/{id}": { params: { id: string }; body: UserPatch; response: User };
+          "DELETE /users/{id}": { params: { id: string } };
+        };
+        parse.buildRoutes<Routes>({ title: "Users", version: "1.2.0" });
+      "#
+        ), @r##"
+        {
+          "components": {
+            "schemas": {
+              "User": {
+                "additionalProperties": false,
+                "properties": {
+                  "id": {
+                    "type": "string"
+                  },
+                  "name": {
+                    "type": "string"
+                  }
+                },
+                "required": [
+                  "id",
+                  "name"
+                ],
+                "type": "object"
+              },
+              "UserPatch": {
+                "additionalProperties": false,
+                "properties": {
+                  "name": {
+                    "type": "string"
+                  }
+                },
+                "type": "object"
+              }
+            }
+          },
+          "info": {
+            "title": "Users",
+            "version": "1.2.0"
+          },
+          "openapi": "3.1.0",
+          "paths": {
+            "/users": {
+              "get": {
+                "parameters": [
+                  {
+                    "in": "query",
+                    "name": "cursor",
+                    "required": false,
+                    "schema": {
+                      "type": "string"
+                    }
+                  },
+                  {
+                    "in": "query",
+                    "name": "limit",
+                    "required": false,
+                    "schema": {
+                      "type": "number"
+                    }
+                  }
+                ],
+                "responses": {
+                  "200": {
+                    "content": {
+                      "application/json": {
+                        "schema": {
+                          "items": {
+                            "$ref": "#/components/schemas/User"
+                          },
+                          "type": "array"
+                        }
+                      }
+                    },
+                    "description": "OK"
+                  }
+                }
+              }
+            },
+            "/users/{id}": {
+              "delete": {
+                "parameters": [
+                  {
+                    "in": "path",
+                    "name": "id",
+                    "required": true,
+                    "schema": {
+                      "type": "string"
+                    }
+                  }
+                ],
+                "responses": {
+                  "204": {
+                    "description": "No Content"
+                  }
+                }
+              },
+              "get": {
+                "parameters": [
+                  {
+                    "in": "path",
+                    "name": "id",
+                    "required": true,
+                    "schema": {
+                      "type": "string"
+                    }
+                  }
+                ],
+                "responses": {
+                  "200": {
+                    "content": {
+                      "application/json": {
+                        "schema": {
+                          "$ref": "#/components/schemas/User"
+                        }
+                      }
+                    },
+                    "description": "OK"
+                  }
+                }
+              },
+              "patch": {
+                "parameters": [
+                  {
+                    "in": "path",
+                    "name": "id",
+                    "required": true,
+                    "schema": {
+                      "type": "string"
+                    }
+                  }
+                ],
+                "requestBody": {
+                  "content": {
+                    "application/json": {
+                      "schema": {
+                        "$ref": "#/components/schemas/UserPatch"
+                      }
+                    }
+                  },
+                  "required": true
+                },
+                "responses": {
+                  "200": {
+                    "content": {
+                      "application/json": {
+                        "schema": {
+                          "$ref": "#/components/schemas/User"
+                        }
+                      }
+                    },
+                    "description": "OK"
+                  }
+                }
+              }
+            }
+          }
+        }
+        "##);
+    }
+
+    #[test]
+    fn source_map_points_to_declarations() {
+        insta::assert_snapshot!(print_source_map_multifile(&[
+            (
+                "types.ts",
+                r#"
+export type User = { name: string; age: number };
+
+export interface Post {
+  title: string;
+  author: User;
+}
+"#,
+            ),
+            (
+                "entry.ts",
+                r#"
+import { User, Post } from "./types";
+parse.buildParsers<{
+  User: User;
+  Posts: Post[];
+}>();
+"#,
+            ),
+        ]), @r#"
+        1:7 direct_hoist_0 -> entry.ts:4:3 User: User;
+        2:7 direct_hoist_1 -> entry.ts:5:3 Posts: Post[];
+        3:7 direct_hoist_2 -> entry.ts:5:3 Posts: Post[];
+        4:7 direct_hoist_3 -> types.ts:4:8 export interface Post {
+        5:7 direct_hoist_4 -> types.ts:4:8 export interface Post {
+        9:7 direct_hoist_5 -> types.ts:2:8 export type User = { name: string; age: number };
+        10:7 direct_hoist_6 -> types.ts:2:8 export type User = { name: string; age: number };
+        16:5 "Post" -> types.ts:4:8 export interface Post {
+        17:5 "User" -> types.ts:2:8 export type User = { name: string; age: number };
+        20:5 "User" -> entry.ts:4:3 User: User;
+        21:5 "Posts" -> entry.ts:5:3 Posts: Post[];
+        "#);
+    }
 }
@@ -0,0 +1,129 @@
+"use strict";
+
+// the generated code has no runtime classes, but it shares the merging and error reporting of the
+// runtime backend, so both behave the same
+import { deepmerge, unionDecodeError, printErrors } from "@beff/client/codegen-v2";
+
+const stringFormatters = {};
+const numberFormatters = {};
+
+const hasOwn = (obj, key) => Object.prototype.hasOwnProperty.call(obj, key);
+
+function registeredClass(ctx, name) {
+  const ctor = ctx.classes[name];
+  if (ctor == null) {
+    throw new Error(`Class ${name} was not provided to buildParsers`);
+  }
+  return ctor;
+}
+
+function validateStringFormat(name, input) {
+  const validator = stringFormatters[name]?.validator;
+  return validator != null && validator(input);
+}
+
+function validateNumberFormat(name, input) {
+  const validator = numberFormatters[name]?.validator;
+  return validator != null && validator(input);
+}
+
+function formatErrorMessage(formatters, typeName, formats, input) {
+  if (typeof input === typeName) {
+    for (const f of [...formats].reverse()) {
+      const errorMessage = formatters[f]?.errorMessage;
+      if (errorMessage != null) {
+        return errorMessage(input);
+      }
+    }
+  }
+  return `expected ${typeName} with format "${formats.join(" and ")}"`;
+}
+
+const stringFormatError = (formats, input) => formatErrorMessage(stringFormatters, "string", formats, input);
+const numberFormatError = (formats, input) => formatErrorMessage(numberFormatters, "number", formats, input);
+
+function buildError(ctx, message, received) {
+  return [{ message, path: [...ctx.path], received }];
+}
+
+class CompiledParser {
+  constructor(codec, name, classes) {
+    this._codec = codec;
+    this.name = name;
+    this._classes = classes;
+  }
+  validate(input, options) {
+    const disallowExtraProperties = options?.disallowExtraProperties ?? false;
+    return this._codec.validate({ disallowExtraProperties, classes: this._classes }, input);
+  }
+  safeParse(input, options) {
+    const disallowExtraProperties = options?.disallowExtraProperties ?? false;
+    const objectKeyOrder = options?.objectKeyOrder ?? "input";
+    const classes = this._classes;
+    if (this._codec.validate({ disallowExtraProperties, classes }, input)) {
+      return {
+        success: true,
+        data: this._codec.parse({ disallowExtraProperties, objectKeyOrder, classes }, input),
+      };
+    }
+    return {
+      success: false,
+      errors: this._codec.report({ path: [], disallowExtraProperties, classes }, input).slice(0, 10),
+    };
+  }
+  parse(input, options) {
+    const safe = this.safeParse(input, options);
+    if (safe.success) {
+      return safe.data;
+    }
+    throw new Error(`Failed to parse ${this.name} - ${printErrors(safe.errors)}`);
+  }
+}
+
+// the arguments of a parser factory can be any parser
+function codecOfParser(parser) {
+  if (parser instanceof CompiledParser) {
+    return parser._codec;
+  }
+  return {
+    validate: (ctx, input) => parser.validate(input, ctx),
+    parse: (ctx, input) => parser.parse(input, ctx),
+    report: (ctx, input) => {
+      const res = parser.safeParse(input, ctx);
+      return res.success ? [] : res.errors.map((err) => ({ ...err, path: [...ctx.path, ...err.path] }));
+    },
+  };
+}
+
+function registerFormats(formatters, required, provided) {
+  for (const k of required) {
+    if (provided[k] == null) {
+      throw new Error(`Missing custom format ${k}`);
+    }
+  }
+  for (const k of Object.keys(provided)) {
+    const v = provided[k];
+    formatters[k] = typeof v === "function" ? { validator: v } : v;
+  }
+}
+
+const buildParsers = (args) => {
+  registerFormats(stringFormatters, RequiredStringFormats, args?.stringFormats ?? {});
+  registerFormats(numberFormatters, RequiredNumberFormats, args?.numberFormats ?? {});
+  const classes = args?.classes ?? {};
+  for (const k of RequiredClasses) {
+    if (classes[k] == null) {
+      throw new Error(`Missing class ${k}`);
+    }
+  }
+  let acc = {};
+  for (const k of Object.keys(buildParsersInput)) {
+    const input = buildParsersInput[k];
+    if (typeof input === "function") {
+      acc[k] = (...parsers) => new CompiledParser(input(...parsers.map(codecOfParser)), k, classes);
+      continue;
+    }
+    acc[k] = new CompiledParser(input, k, classes);
+  }
+  return acc;
+};
@@ -0,0 +1,7 @@
+import { BuildCompiledParserFunction } from "@beff/client";
+
+declare const _exports: {
+  buildParsers: BuildCompiledParserFunction;
+};
+
+export default _exports;
@@ -0,0 +1,8 @@
+import { BuildParserFunction, BuildRoutesFunction } from "@beff/client";
+
+declare const _exports: {
+  buildParsers: BuildParserFunction;
+  buildRoutes: BuildRoutesFunction;
+};
+
+export default _exports;
@@ -11,6 +11,7 @@ use beff_core::BffFileName;
 use beff_core::EntryPoints;
 use beff_core::FileManager;
 use beff_core::ParsedModule;
+use beff_core::ast::json::Json;
 use beff_core::diag::DiagnosticInformation;
 use beff_core::parser_extractor::ParserExtractResult;
 use beff_core::swc_tools::bind_exports::FsModuleResolver;
@@ -18,6 +19,7 @@ use beff_core::swc_tools::bind_exports::parse_and_bind;
 use beff_core::wasm_diag::WasmDiagnostic;
 use log::Level;
 use module_resolver::WasmModuleResolver;
+use serde::Deserialize;
 use std::rc::Rc;
 use std::{cell::RefCell, collections::HashMap};
 use swc_common::{GLOBALS, Globals};
@@ -66,8 +68,10 @@ extern "C" {
 }
 
 #[wasm_bindgen]
-pub fn bundle_to_string_v2(parser_entry_point: &str, settings: &str) -> JsValue {
-    match bundle_to_string_inner(parse_entrypoints(parser_entry_point, settings)) {
+pub fn bundle_to_artifacts(parser_entry_point: &str, settings: &str, options: &str) -> JsValue {
+    let options: ArtifactOptions =
+        serde_json::from_str(options).expect("should be able to parse artifact options");
+    match bundle_to_artifacts_inner(parse_entrypoints(parser_entry_point, settings), options) {
         Ok(s) => JsValue::from_str(&s),
         Err(_) => JsValue::undefined(),
     }
@@ -150,13 +154,58 @@ fn print_errors(errors: &[DiagnosticInformation]) {
     emit_diagnostic(v)
 }
 
-fn bundle_to_string_inner(entry: EntryPoints) -> Result<String> {
+#[derive(Deserialize)]
+struct ArtifactOptions {
+    compiled: bool,
+    output_dir: String,
+    source_map: bool,
+    dts: bool,
+    json_schema: bool,
+}
+
+// `{ code, map, dts, schemas, openapi }` from a single extraction, the optional artifacts are
+// `null` when not requested, `openapi` is `null` when `buildRoutes` is not called
+fn bundle_to_artifacts_inner(entry: EntryPoints, options: ArtifactOptions) -> Result<String> {
     let res = run_extraction(entry);
-    if res.errors.is_empty() {
-        return res.emit_code();
+    if !res.errors.is_empty() {
+        print_errors(&res.errors);
+        return Err(anyhow!("Failed to bundle"));
     }
-    print_errors(&res.errors);
-    Err(anyhow!("Failed to bundle"))
+    let dts = if options.dts {
+        Json::String(res.emit_dts(&options.output_dir, options.compiled)?)
+    } else {
+        Json::Null
+    };
+    let schemas = if options.json_schema {
+        let schemas = res
+            .emit_json_schemas()
+            .inspect_err(|e| log::error!("{}", e))?;
+        Json::Object(schemas.into_iter().collect())
+    } else {
+        Json::Null
+    };
+    let openapi = res
+        .emit_openapi()
+        .inspect_err(|e| log::error!("{}", e))?
+        .unwrap_or(Json::Null);
+    let (code, map) = if options.compiled {
+        (
+            res.emit_aot_code().inspect_err(|e| log::error!("{}", e))?,
+            Json::Null,
+        )
+    } else if options.source_map {
+        res.emit_code_with_source_map()?
+    } else {
+        (res.emit_code()?, Json::Null)
+    };
+    let out = Json::object(vec![
+        ("code".to_string(), Json::String(code)),
+        ("map".to_string(), map),
+        ("dts".to_string(), dts),
+        ("schemas".to_string(), schemas),
+        ("openapi".to_string(), openapi),
+    ]);
+    Ok(serde_json::to_string(&out.to_serde())?)
 }
 
 fn bundle_to_diagnostics_inner(entry: EntryPoints) -> WasmDiagnostic {
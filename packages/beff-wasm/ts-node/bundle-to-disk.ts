@@ -1,7 +1,7 @@
 import * as fs from "fs";
 import * as path from "path";
-import { Bundler } from "./bundler";
-import { ProjectJson, ProjectModule } from "./project";
+import { Bundler, SourceMapV3 } from "./bundler";
+import { ProjectBackend, ProjectJson, ProjectModule } from "./project";
 import gen from "./generated/bundle";
 
 const esmTag = (mod: ProjectModule) => {
@@ -22,30 +22,38 @@ const finalizeParserV2File = (
   mod: ProjectModule,
   stringFormats: string[],
   numberFormats: string[],
+  backend: ProjectBackend,
+  openapi: Record<string, unknown> | null,
 ) => {
-  const exportedItems = ["buildParsers"].join(", ");
+  const exportedItems = (openapi == null ? ["buildParsers"] : ["buildParsers", "buildRoutes"]).join(", ");
   const exports = [exportCode(mod), `{ ${exportedItems} };`].join(" ");
 
   const stringFormatsCode = `const RequiredStringFormats = ${JSON.stringify(stringFormats)};`;
   const numberFormatsCode = `const RequiredNumberFormats = ${JSON.stringify(numberFormats)};`;
+  // the document is generated at build time, the info argument is read from the source
+  const buildRoutesCode = openapi == null ? [] : [`const buildRoutes = () => (${JSON.stringify(openapi)});`];
 
-  let genV2 = gen["codegen-v2.js"];
+  let genV2 = backend === "compiled" ? gen["codegen-aot.js"] : gen["codegen-v2.js"];
   if (mod === "cjs") {
     genV2 = genV2
       .replace("import {", "const {")
       .replace('} from "@beff/client/codegen-v2";', '} = require("@beff/client/codegen-v2");');
   }
 
-  return [
+  const beforeWasmCode = [
     //
     "//@ts-nocheck",
     esmTag(mod),
     genV2,
     stringFormatsCode,
     numberFormatsCode,
-    wasmCode,
-    exports,
   ].join("\n");
+
+  return {
+    code: [beforeWasmCode, wasmCode, ...buildRoutesCode, exports].join("\n"),
+    // zero-based, the source map of the validators starts there
+    wasmCodeLine: beforeWasmCode.split("\n").length,
+  };
 };
 
 const writeIfChanged = (filePath: string, content: string) => {
@@ -71,6 +79,7 @@ export const execProject = (
   verbose: boolean,
 ): "ok" | "failed" => {
   const mod = projectJson.module ?? "esm";
+  const backend = projectJson.backend ?? "runtime";
 
   const parserEntryPoint = projectJson.parser
     ? path.join(path.dirname(projectPath), projectJson.parser)
@@ -84,25 +93,66 @@ export const execProject = (
     fs.mkdirSync(outputDir);
   }
 
+  if (projectJson.sourceMap && backend === "compiled") {
+    console.error("Source maps are only generated for the runtime backend");
+    return "failed";
+  }
+
   const bundleStart = Date.now();
-  const outResult = bundler.bundle_v2(parserEntryPoint, projectJson.settings);
+  const outResult = bundler.bundle(parserEntryPoint, projectJson.settings, {
+    compiled: backend === "compiled",
+    outputDir,
+    sourceMap: projectJson.sourceMap ?? false,
+    dts: projectJson.dts ?? false,
+    jsonSchema: projectJson.jsonSchema ?? false,
+  });
   logTiming(verbose, "WASM extraction/codegen", bundleStart);
   if (outResult == null) {
     return "failed";
   }
   const finalizeStart = Date.now();
-  const parserJs = finalizeParserV2File(
-    outResult,
+  const finalized = finalizeParserV2File(
+    outResult.code,
     mod,
     projectJson.settings.stringFormats.map((it) => it.name) ?? [],
     projectJson.settings.numberFormats.map((it) => it.name) ?? [],
+    backend,
+    outResult.openapi,
   );
-  const parserDts = gen["parser.d.ts"];
+  let parserJs = finalized.code;
+  let parserJsMap: SourceMapV3 | undefined;
+  const sourceMap = outResult.map;
+  if (sourceMap != null) {
+    parserJs += "\n//# sourceMappingURL=parser.js.map";
+    parserJsMap = {
+      ...sourceMap,
+      file: "parser.js",
+      sources: sourceMap.sources.map((it) => path.relative(outputDir, it)),
+      mappings: ";".repeat(finalized.wasmCodeLine) + sourceMap.mappings,
+    };
+  }
+  // compiled parsers are declared without the methods they do not have
+  const dtsPrefix = backend === "compiled" ? "parser-compiled" : "parser";
+  const parserDts =
+    outResult.openapi == null ? gen[`${dtsPrefix}.d.ts`] : gen[`${dtsPrefix}-with-routes.d.ts`];
   logTiming(verbose, "output finalization", finalizeStart);
 
   const writeStart = Date.now();
   writeIfChanged(path.join(outputDir, "parser.js"), parserJs);
   writeIfChanged(path.join(outputDir, "parser.d.ts"), parserDts);
+  // the resolved types of the parsers, importable without evaluating `buildParsers`
+  if (outResult.dts != null) {
+    writeIfChanged(path.join(outputDir, "types.d.ts"), outResult.dts);
+  }
+  if (parserJsMap != null) {
+    writeIfChanged(path.join(outputDir, "parser.js.map"), JSON.stringify(parserJsMap) + "\n");
+  }
+  if (projectJson.openapi && outResult.openapi != null) {
+    writeIfChanged(path.join(outputDir, "openapi.json"), JSON.stringify(outResult.openapi, null, 2) + "\n");
+  }
+  for (const [name, schema] of Object.entries(outResult.schemas ?? {})) {
+    writeIfChanged(path.join(outputDir, `${name}.schema.json`), JSON.stringify(schema, null, 2) + "\n");
+  }
   logTiming(verbose, "disk writes", writeStart);
   return "ok";
 };
@@ -175,6 +175,31 @@ type WasmDiagnostic = {
   diagnostics: WasmDiagnosticInformation[];
 };
 
+export type SourceMapV3 = {
+  version: 3;
+  file?: string;
+  sources: string[];
+  names: string[];
+  mappings: string;
+};
+
+export type BundleOptions = {
+  compiled: boolean;
+  outputDir: string;
+  sourceMap: boolean;
+  dts: boolean;
+  jsonSchema: boolean;
+};
+
+// the artifacts that were not requested are null, `openapi` is null when `buildRoutes` is not called
+export type BundleArtifacts = {
+  code: string;
+  map: SourceMapV3 | null;
+  dts: string | null;
+  schemas: Record<string, unknown> | null;
+  openapi: Record<string, unknown> | null;
+};
+
 export class Bundler {
   cbs: ((path: string) => void)[];
   constructor(verbose: boolean) {
@@ -203,8 +228,23 @@ export class Bundler {
     this.cbs.push(cb);
   }
 
-  public bundle_v2(parser_entrypoint: string | undefined, settings: BeffUserSettings): string | undefined {
-    return wasm.bundle_to_string_v2(parser_entrypoint ?? "", JSON.stringify(serializeSettings(settings)));
+  public bundle(
+    parser_entrypoint: string | undefined,
+    settings: BeffUserSettings,
+    options: BundleOptions,
+  ): BundleArtifacts | undefined {
+    const out = wasm.bundle_to_artifacts(
+      parser_entrypoint ?? "",
+      JSON.stringify(serializeSettings(settings)),
+      JSON.stringify({
+        compiled: options.compiled,
+        output_dir: options.outputDir,
+        source_map: options.sourceMap,
+        dts: options.dts,
+        json_schema: options.jsonSchema,
+      }),
+    );
+    return out == null ? undefined : JSON.parse(out);
   }
 
   public diagnostics(
@@ -38,6 +38,11 @@ const readProjectJson = (projectPath: string): ProjectJson => {
     parser: projectJson.parser == null ? projectJson.parser : String(projectJson.parser),
     outputDir: String(projectJson.outputDir),
     module: projectJson.module,
+    backend: projectJson.backend,
+    jsonSchema: projectJson.jsonSchema,
+    dts: projectJson.dts,
+    openapi: projectJson.openapi,
+    sourceMap: projectJson.sourceMap,
     settings: parseUserSettings(projectJson),
   };
 };
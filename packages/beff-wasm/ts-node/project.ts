@@ -10,11 +10,18 @@ export type ProjectJson = {
   parser?: string;
   outputDir: string;
   module: ProjectModule | undefined;
+  backend: ProjectBackend | undefined;
+  jsonSchema: boolean | undefined;
+  dts: boolean | undefined;
+  openapi: boolean | undefined;
+  sourceMap: boolean | undefined;
   settings: BeffUserSettings;
 };
 
 export type ProjectModule = "cjs" | "esm";
 
+export type ProjectBackend = "runtime" | "compiled";
+
 const EMPTY_SETTINGS: BeffUserSettings = {
   stringFormats: [],
   numberFormats: [],